@@ -0,0 +1,507 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for the `check` command. This command is focused on
+//! validating the prerequisites for creating an installer without building
+//! the binaries or executing the WiX Toolset.
+
+use crate::create::{self, merge_workspace_metadata, meta_path, workspace_manifest};
+use crate::init_logging;
+use crate::Error;
+use crate::Result;
+use crate::EXE_FILE_EXTENSION;
+
+use log::LevelFilter;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A builder for creating an execution context to check the prerequisites
+/// for creating an installer.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    banner: Option<&'a str>,
+    bin_path: Option<&'a str>,
+    compiler_name: Option<&'a str>,
+    eula: Option<&'a str>,
+    includes: Option<Vec<&'a str>>,
+    input: Option<&'a str>,
+    linker_name: Option<&'a str>,
+    locale: Option<&'a str>,
+    log_level: Option<LevelFilter>,
+    product_icon: Option<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder {
+            banner: None,
+            bin_path: None,
+            compiler_name: None,
+            eula: None,
+            includes: None,
+            input: None,
+            linker_name: None,
+            locale: None,
+            log_level: None,
+            product_icon: None,
+        }
+    }
+
+    /// Sets the path to a bitmap (`.bmp`) file for the banner that should
+    /// exist.
+    pub fn banner(&mut self, b: Option<&'a str>) -> &mut Self {
+        self.banner = b;
+        self
+    }
+
+    /// Sets the path to the WiX Toolset's `bin` folder.
+    ///
+    /// The WiX Toolset is located in the same way as the _create_ command,
+    /// i.e. this path, the `WIX` system environment variable, and then the
+    /// `PATH` system environment variable.
+    pub fn bin_path(&mut self, b: Option<&'a str>) -> &mut Self {
+        self.bin_path = b;
+        self
+    }
+
+    /// Sets the name of the compiler application. The default is `candle`.
+    pub fn compiler_name(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.compiler_name = c;
+        self
+    }
+
+    /// Sets the path to a custom EULA that should exist.
+    ///
+    /// The default is to check the path in the `eula` field of the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), if it exists.
+    pub fn eula(&mut self, e: Option<&'a str>) -> &mut Self {
+        self.eula = e;
+        self
+    }
+
+    /// Adds multiple WiX Source (wxs) files that should exist.
+    pub fn includes(&mut self, i: Option<Vec<&'a str>>) -> &mut Self {
+        self.includes = i;
+        self
+    }
+
+    /// Sets the path to a package's manifest (Cargo.toml) to be checked.
+    ///
+    /// The default is to use the current working directory if a Cargo.toml file
+    /// is found.
+    pub fn input(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.input = i;
+        self
+    }
+
+    /// Sets the name of the linker application. The default is `light`.
+    pub fn linker_name(&mut self, l: Option<&'a str>) -> &mut Self {
+        self.linker_name = l;
+        self
+    }
+
+    /// Sets the path to a WiX localization file, `.wxl`, that should exist.
+    ///
+    /// The default is to check the path in the `locale` field of the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), if it exists.
+    pub fn locale(&mut self, l: Option<&'a str>) -> &mut Self {
+        self.locale = l;
+        self
+    }
+
+    /// Sets the maximum level for the logging statements.
+    ///
+    /// See the [`create::Builder::log_level`] method for details.
+    ///
+    /// [`create::Builder::log_level`]: ../create/struct.Builder.html#method.log_level
+    pub fn log_level(&mut self, l: LevelFilter) -> &mut Self {
+        self.log_level = Some(l);
+        self
+    }
+
+    /// Sets the path to an image file (`.ico`) for the product icon that
+    /// should exist.
+    pub fn product_icon(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.product_icon = p;
+        self
+    }
+
+    /// Builds an execution context to check the prerequisites for creating an
+    /// installer.
+    pub fn build(&mut self) -> Execution {
+        Execution {
+            banner: self.banner.map(PathBuf::from),
+            create: create::Builder::new()
+                .bin_path(self.bin_path)
+                .compiler_name(self.compiler_name)
+                .includes(self.includes.clone())
+                .input(self.input)
+                .linker_name(self.linker_name)
+                .locale(self.locale)
+                .build(),
+            eula: self.eula.map(PathBuf::from),
+            input: self.input.map(PathBuf::from),
+            log_level: self.log_level,
+            product_icon: self.product_icon.map(PathBuf::from),
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for checking the prerequisites for creating an installer.
+#[derive(Debug)]
+pub struct Execution {
+    banner: Option<PathBuf>,
+    create: create::Execution,
+    eula: Option<PathBuf>,
+    input: Option<PathBuf>,
+    log_level: Option<LevelFilter>,
+    product_icon: Option<PathBuf>,
+}
+
+impl Execution {
+    /// Checks the prerequisites for creating an installer.
+    ///
+    /// This verifies the WiX Toolset's compiler and linker can be found, the
+    /// package's manifest has a name and version, the WiX Source (wxs) files
+    /// exist, and any referenced files exist. The binaries are not built and
+    /// the WiX Toolset is not executed. A single problem is reported with its
+    /// own error, so the exit code is the same as the default _create_
+    /// subcommand for the same problem, i.e. a missing WiX Toolset. Multiple
+    /// problems are reported together in a single error.
+    pub fn run(self) -> Result<()> {
+        if let Some(level) = self.log_level {
+            init_logging(level);
+        }
+        debug!("banner = {:?}", self.banner);
+        debug!("create = {:?}", self.create);
+        debug!("eula = {:?}", self.eula);
+        debug!("input = {:?}", self.input);
+        debug!("log_level = {:?}", self.log_level);
+        debug!("product_icon = {:?}", self.product_icon);
+        let mut problems = self.problems();
+        debug!("problems = {:?}", problems);
+        if problems.is_empty() {
+            info!("Everything is ready to create the installer");
+            Ok(())
+        } else if problems.len() == 1 {
+            Err(problems.remove(0))
+        } else {
+            Err(Error::Generic(format!(
+                "Found {} problem(s) with creating the installer:\n{}",
+                problems.len(),
+                problems
+                    .iter()
+                    .map(|p| format!("  - {}", p))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )))
+        }
+    }
+
+    fn problems(&self) -> Vec<Error> {
+        let mut problems = Vec::new();
+        trace!("Checking the WiX Toolset");
+        // The linker is only checked if the compiler is found, since both are
+        // missing if the WiX Toolset is not installed.
+        if let Err(err) = self
+            .create
+            .compiler()
+            .and_then(|c| Self::tool(&c))
+            .and_then(|_| self.create.linker())
+            .and_then(|l| Self::tool(&l))
+        {
+            problems.push(err);
+        }
+        trace!("Checking the referenced files");
+        for (kind, path) in [
+            ("banner", &self.banner),
+            ("EULA", &self.eula),
+            ("product icon", &self.product_icon),
+        ] {
+            if let Some(path) = path {
+                Self::file(kind, path, &mut problems);
+            }
+        }
+        trace!("Checking the package's manifest");
+        let mut manifest = match crate::manifest(self.input.as_ref()) {
+            Ok(m) => m,
+            Err(err) => {
+                problems.push(err);
+                return problems;
+            }
+        };
+        match crate::cargo_toml_file(self.input.as_ref())
+            .and_then(|p| workspace_manifest(&p, &manifest))
+        {
+            Ok(Some(workspace)) => merge_workspace_metadata(&mut manifest, &workspace),
+            Ok(None) => {}
+            Err(err) => problems.push(err),
+        }
+        if let Err(err) = self.create.name(&manifest) {
+            problems.push(err);
+        }
+        if let Err(err) = self.create.version(&manifest) {
+            problems.push(err);
+        }
+        trace!("Checking the WiX Source (wxs) files");
+        if let Err(err) = self.create.wxs_sources(&manifest) {
+            problems.push(err);
+        }
+        match self.create.locale(&manifest) {
+            Ok(Some(locale)) => Self::file("WiX localization", &locale, &mut problems),
+            Ok(None) => {}
+            Err(err) => problems.push(err),
+        }
+        if self.eula.is_none() {
+            match meta_path(&manifest, "eula") {
+                Ok(Some(eula)) => Self::file("EULA", &eula, &mut problems),
+                Ok(None) => {}
+                Err(err) => problems.push(err),
+            }
+        }
+        problems
+    }
+
+    fn file(kind: &str, path: &Path, problems: &mut Vec<Error>) {
+        if path.is_file() {
+            trace!("The '{}' {} file exists", path.display(), kind);
+        } else {
+            problems.push(Error::Generic(format!(
+                "The '{}' {} file does not exist",
+                path.display(),
+                kind
+            )));
+        }
+    }
+
+    /// Checks that the program for a WiX Toolset application exists.
+    ///
+    /// The path to the program has already been checked if it was obtained
+    /// from the `bin_path` or the `WIX` system environment variable, so only
+    /// a bare program name is searched for in the `PATH` system environment
+    /// variable.
+    fn tool(command: &Command) -> Result<()> {
+        let program = Path::new(command.get_program());
+        if program.components().count() > 1 {
+            return Ok(());
+        }
+        let found = env::var_os("PATH")
+            .map(|paths| {
+                env::split_paths(&paths).any(|dir| {
+                    dir.join(program).is_file()
+                        || dir
+                            .join(program)
+                            .with_extension(EXE_FILE_EXTENSION)
+                            .is_file()
+                })
+            })
+            .unwrap_or(false);
+        if found {
+            trace!("Found the '{}' application in the PATH", program.display());
+            Ok(())
+        } else {
+            Err(Error::WixNotFound {
+                tool: program.to_string_lossy().into_owned().into(),
+            })
+        }
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn defaults_are_correct() {
+            let actual = Builder::new();
+            assert!(actual.banner.is_none());
+            assert!(actual.bin_path.is_none());
+            assert!(actual.compiler_name.is_none());
+            assert!(actual.eula.is_none());
+            assert!(actual.includes.is_none());
+            assert!(actual.input.is_none());
+            assert!(actual.linker_name.is_none());
+            assert!(actual.locale.is_none());
+            assert!(actual.log_level.is_none());
+            assert!(actual.product_icon.is_none());
+        }
+
+        #[test]
+        fn bin_path_works() {
+            const EXPECTED: &str = "C:\\WiX Toolset\\bin";
+            let mut actual = Builder::new();
+            actual.bin_path(Some(EXPECTED));
+            assert_eq!(actual.bin_path, Some(EXPECTED));
+        }
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\Cargo.toml";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn eula_works() {
+            const EXPECTED: &str = "Example_Eula.rtf";
+            let mut actual = Builder::new();
+            actual.eula(Some(EXPECTED));
+            assert_eq!(actual.eula, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        extern crate assert_fs;
+
+        use super::*;
+        use crate::WIX_PATH_KEY;
+        use std::fs;
+
+        const MIN_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+        "#;
+
+        const NO_VERSION_MANIFEST: &str = r#"[package]
+            name = "Example"
+        "#;
+
+        /// Creates a package with fake WiX Toolset applications and a WiX
+        /// Source (wxs) file, so only the manifest can have problems.
+        fn setup(manifest: &str) -> assert_fs::TempDir {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            fs::write(temp_dir.path().join("Cargo.toml"), manifest).unwrap();
+            fs::create_dir(temp_dir.path().join("wix")).unwrap();
+            fs::write(temp_dir.path().join("wix").join("main.wxs"), "").unwrap();
+            fs::write(temp_dir.path().join("candle.exe"), "").unwrap();
+            fs::write(temp_dir.path().join("light.exe"), "").unwrap();
+            temp_dir
+        }
+
+        #[test]
+        fn run_works() {
+            let temp_dir = setup(MIN_MANIFEST);
+            let input = temp_dir.path().join("Cargo.toml");
+            let result = Builder::new()
+                .bin_path(temp_dir.path().to_str())
+                .input(input.to_str())
+                .build()
+                .run();
+            assert!(result.is_ok(), "{:?}", result);
+        }
+
+        #[test]
+        fn run_with_missing_version_fails() {
+            let temp_dir = setup(NO_VERSION_MANIFEST);
+            let input = temp_dir.path().join("Cargo.toml");
+            let result = Builder::new()
+                .bin_path(temp_dir.path().to_str())
+                .input(input.to_str())
+                .build()
+                .run();
+            match result {
+                Err(err @ Error::Manifest("version")) => assert_eq!(err.exit_code(), 3),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_multiple_problems_reports_all() {
+            let temp_dir = setup(NO_VERSION_MANIFEST);
+            fs::remove_file(temp_dir.path().join("light.exe")).unwrap();
+            let input = temp_dir.path().join("Cargo.toml");
+            let eula = temp_dir.path().join("Eula.rtf");
+            let result = Builder::new()
+                .bin_path(temp_dir.path().to_str())
+                .eula(eula.to_str())
+                .input(input.to_str())
+                .build()
+                .run();
+            match result {
+                Err(Error::Generic(msg)) => {
+                    assert!(msg.starts_with("Found 3 problem(s)"));
+                    assert!(msg.contains("linker application ('light')"));
+                    assert!(msg.contains("Eula.rtf' EULA file does not exist"));
+                    assert!(msg.contains(&Error::Manifest("version").to_string()));
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn tool_with_path_works() {
+            let command = Command::new(Path::new("C:\\WiX Toolset\\bin").join("candle.exe"));
+            assert!(Execution::tool(&command).is_ok());
+        }
+
+        #[test]
+        fn run_with_missing_compiler_and_linker_fails() {
+            const COMPILER_NAME: &str = "cargo-wix-missing-candle";
+            if env::var_os(WIX_PATH_KEY).is_some() {
+                // The compiler and linker are not searched for in the PATH.
+                return;
+            }
+            let temp_dir = setup(MIN_MANIFEST);
+            let input = temp_dir.path().join("Cargo.toml");
+            let result = Builder::new()
+                .compiler_name(Some(COMPILER_NAME))
+                .input(input.to_str())
+                .linker_name(Some("cargo-wix-missing-light"))
+                .build()
+                .run();
+            match result {
+                Err(err @ Error::WixNotFound { .. }) => {
+                    assert_eq!(err.exit_code(), 2);
+                    match err {
+                        Error::WixNotFound { tool } => assert_eq!(tool, COMPILER_NAME),
+                        _ => unreachable!(),
+                    }
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn tool_with_missing_program_fails() {
+            let command = Command::new("cargo-wix-nonexistent-candle");
+            match Execution::tool(&command) {
+                Err(Error::WixNotFound { tool }) => {
+                    assert_eq!(tool, "cargo-wix-nonexistent-candle")
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+    }
+}
@@ -21,15 +21,31 @@
 //! (`light.exe`). By default, it looks for a `wix\main.wxs` file relative to
 //! the root of the package's manifest (Cargo.toml). A different WiX Source file
 //! can be set with the `input` method using the `Builder` struct.
+//!
+//! The package's manifest is not required if the build is skipped and the name
+//! and version of the installer are set with the `no_build`, `name`, and
+//! `version` methods, respectively.
 
+use crate::manifest_input;
+use crate::sign;
+use crate::wait;
+use crate::wait_with_output;
 use crate::Cultures;
 use crate::Error;
 use crate::Platform;
 use crate::Result;
 use crate::BINARY_FOLDER_NAME;
 use crate::CARGO;
+use crate::CARGO_KEY;
+use crate::CARGO_MANIFEST_FILE;
+use crate::CARGO_TARGET_DIR_KEY;
+use crate::CARGO_WIX_MSI_KEY;
+use crate::CARGO_WIX_PRODUCT_NAME_KEY;
+use crate::CARGO_WIX_VERSION_KEY;
 use crate::EXE_FILE_EXTENSION;
+use crate::GIT;
 use crate::MSI_FILE_EXTENSION;
+use crate::PROFILE_VARIABLE;
 use crate::TARGET_FOLDER_NAME;
 use crate::WIX;
 use crate::WIX_COMPILER;
@@ -38,58 +54,184 @@ use crate::WIX_OBJECT_FILE_EXTENSION;
 use crate::WIX_PATH_KEY;
 use crate::WIX_SOURCE_FILE_EXTENSION;
 
+use log::LevelFilter;
+
 use semver::Version;
 
+use serde::Serialize;
+
+use sha2::{Digest, Sha256, Sha512};
+
 use std::convert::TryFrom;
 use std::env;
+use std::ffi::OsString;
 use std::fmt;
-use std::io::{ErrorKind, Read};
+use std::fs;
+use std::io::{self, ErrorKind, Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::str::FromStr;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
+use toml::value::{Array, Table};
 use toml::Value;
 
+/// The file name for the hash of the compiler's defines, arguments, and sources,
+/// which is stored with the WiX object files (wixobj) to detect changes between
+/// builds.
+const DEFINES_HASH_FILE_NAME: &str = "defines.hash";
+
+/// The default number of times to retry signing a binary when the timestamp
+/// server fails.
+const DEFAULT_SIGN_RETRIES: u32 = 3;
+
+/// The number of lines at the end of the builder's (cargo) standard error
+/// (stderr) that are reported when the build fails.
+const BUILD_ERROR_LINES: usize = 20;
+
+/// The number of hexadecimal characters of the installer's SHA-256 digest
+/// that are added to its file name.
+const HASH_IN_NAME_LENGTH: usize = 8;
+
+/// The name of the command that is run after the installer is created.
+const POST_BUILD: &str = "post-build";
+
+/// The name of the command that is run before the binaries are built.
+const PRE_BUILD: &str = "pre-build";
+
+/// The architectures accepted by the `-arch` option of the compiler (candle).
+pub const CANDLE_ARCHITECTURES: [&str; 5] = ["x86", "x64", "ia64", "arm", "arm64"];
+
 /// A builder for running the `cargo wix` subcommand.
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
+    all_features: bool,
     bin_path: Option<&'a str>,
+    candle_arch: Option<&'a str>,
     capture_output: bool,
+    checksum: Option<ChecksumKind>,
     compiler_args: Option<Vec<&'a str>>,
+    compiler_name: Option<&'a str>,
     culture: Option<&'a str>,
     debug_build: bool,
     debug_name: bool,
+    dry_run: bool,
+    extensions: Option<Vec<&'a str>>,
+    features: Option<Vec<&'a str>>,
+    force: bool,
+    frozen: bool,
+    hash_in_name: bool,
     includes: Option<Vec<&'a str>>,
     input: Option<&'a str>,
+    jobs: Option<usize>,
+    keep_pdb: bool,
+    libraries: Option<Vec<&'a str>>,
     linker_args: Option<Vec<&'a str>>,
+    linker_name: Option<&'a str>,
     locale: Option<&'a str>,
+    locked: bool,
+    log_level: Option<LevelFilter>,
+    merge_args: bool,
+    message_format: Option<MessageFormat>,
+    min_wix_version: Option<&'a str>,
     name: Option<&'a str>,
+    name_suffix: Option<&'a str>,
     no_build: bool,
+    no_default_features: bool,
+    no_ui: bool,
+    no_util_extension: bool,
+    offline: bool,
     output: Option<&'a str>,
+    pdb_output: Option<&'a str>,
+    post_build: Option<&'a str>,
+    pre_build: Option<&'a str>,
+    profile_var: Option<&'a str>,
+    quiet: bool,
+    require_clean_git: bool,
+    show_logo: bool,
+    sign_binaries: bool,
+    sign_retries: u32,
+    sort_sources: bool,
+    strict_version: bool,
+    suppress_warnings: Vec<u32>,
+    target_dir: Option<&'a str>,
+    timeout: Option<Duration>,
     version: Option<&'a str>,
+    warnings_as_errors: bool,
 }
 
 impl<'a> Builder<'a> {
     /// Creates a new `Builder` instance.
     pub fn new() -> Self {
         Builder {
+            all_features: false,
             bin_path: None,
+            candle_arch: None,
             capture_output: true,
+            checksum: None,
             compiler_args: None,
+            compiler_name: None,
             culture: None,
             debug_build: false,
             debug_name: false,
+            dry_run: false,
+            extensions: None,
+            features: None,
+            force: false,
+            frozen: false,
+            hash_in_name: false,
             includes: None,
             input: None,
+            jobs: None,
+            keep_pdb: false,
+            libraries: None,
             linker_args: None,
+            linker_name: None,
             locale: None,
+            locked: false,
+            log_level: None,
+            merge_args: false,
+            message_format: None,
+            min_wix_version: None,
             name: None,
+            name_suffix: None,
             no_build: false,
+            no_default_features: false,
+            no_ui: false,
+            no_util_extension: false,
+            offline: false,
             output: None,
+            pdb_output: None,
+            post_build: None,
+            pre_build: None,
+            profile_var: None,
+            quiet: false,
+            require_clean_git: false,
+            show_logo: false,
+            sign_binaries: false,
+            sign_retries: DEFAULT_SIGN_RETRIES,
+            sort_sources: true,
+            strict_version: true,
+            suppress_warnings: Vec::new(),
+            target_dir: None,
+            timeout: None,
             version: None,
+            warnings_as_errors: false,
         }
     }
 
+    /// Activates all available features when building the binaries.
+    ///
+    /// If `true`, the `--all-features` flag is passed to the builder
+    /// (`cargo`). This is ignored if the build is skipped. The default is to
+    /// only activate the default features.
+    pub fn all_features(&mut self, a: bool) -> &mut Self {
+        self.all_features = a;
+        self
+    }
+
     /// Sets the path to the WiX Toolset's `bin` folder.
     ///
     /// The WiX Toolset's `bin` folder should contain the needed `candle.exe`
@@ -101,6 +243,19 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the architecture passed to the compiler (`candle`) with the `-arch`
+    /// option.
+    ///
+    /// The architecture determines the default bitness of the components when
+    /// a WiX Source (wxs) file does not specify the `Win64` attribute. The
+    /// value must be one of `x86`, `x64`, `ia64`, `arm`, or `arm64`. The
+    /// default is to use the platform of the installer, i.e. `x64` for a
+    /// 64-bit installer and `x86` for a 32-bit installer.
+    pub fn candle_arch(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.candle_arch = c;
+        self
+    }
+
     /// Enables or disables capturing of the output from the builder (`cargo`),
     /// compiler (`candle`), linker (`light`), and signer (`signtool`).
     ///
@@ -111,6 +266,19 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Writes a checksum file next to the installer.
+    ///
+    /// If set, the digest of the installer is computed after it is created and
+    /// written to a file with the same name as the installer and the
+    /// `.sha256` or `.sha512` extension appended, i.e. `example.msi.sha256`.
+    /// The file has the same format as the `sha256sum` and `sha512sum`
+    /// utilities, i.e. the hexadecimal digest, two spaces, and the installer's
+    /// file name. The default is to not write a checksum file.
+    pub fn checksum(&mut self, c: Option<ChecksumKind>) -> &mut Self {
+        self.checksum = c;
+        self
+    }
+
     /// Adds an argument to the compiler command.
     ///
     /// This "passes" the argument directly to the WiX compiler (candle.exe).
@@ -121,6 +289,19 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the name of the compiler application.
+    ///
+    /// This is the file name of the compiler without the file extension. The
+    /// compiler is still located using the path to the WiX Toolset's `bin`
+    /// folder, the WIX system environment variable, or the PATH system
+    /// environment variable, and the `.exe` file extension is added when
+    /// using a path. This is useful when the compiler is wrapped by another
+    /// application with a different name. The default is `candle`.
+    pub fn compiler_name(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.compiler_name = c;
+        self
+    }
+
     /// Sets the culture to use with the linker (light.exe) for building a
     /// localized installer.
     ///
@@ -160,12 +341,101 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Prints the builder (`cargo`), compiler (`candle`), and linker (`light`)
+    /// commands instead of executing them.
+    ///
+    /// If `true`, each command is printed to `STDOUT` as a single line that can
+    /// be copied and pasted into a command prompt, and no installer is
+    /// created. The WiX object files (wixobj) do not exist until the compiler
+    /// is executed, so the object files passed to the linker are based on the
+    /// WiX Source (wxs) files and a MSI-based installer is assumed. The default
+    /// is to execute the commands.
+    pub fn dry_run(&mut self, d: bool) -> &mut Self {
+        self.dry_run = d;
+        self
+    }
+
+    /// Sets additional WiX extensions for the compiler (candle) and the linker
+    /// (light).
+    ///
+    /// Each extension is passed to both the compiler and the linker with the
+    /// `-ext` option. A bare name, i.e. `WixFirewallExtension`, is passed
+    /// unchanged, so the WiX Toolset locates the extension. An entry with a
+    /// path separator, either a slash (`/`) or a backslash (`\`), or with the
+    /// `.dll` file extension is a path to a custom extension, which is
+    /// resolved against the folder containing the package's manifest
+    /// (Cargo.toml) if it is relative, and an error occurs if it does not
+    /// exist. The WixUIExtension and WixUtilExtension are added independently.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn extensions(&mut self, e: Option<Vec<&'a str>>) -> &mut Self {
+        self.extensions = e;
+        self
+    }
+
+    /// Sets the features to activate when building the binaries.
+    ///
+    /// The features are passed to the builder (`cargo`) with the `--features`
+    /// option, so installers can be created from feature-gated binaries. This
+    /// is ignored if the build is skipped.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn features(&mut self, f: Option<Vec<&'a str>>) -> &mut Self {
+        self.features = f;
+        self
+    }
+
+    /// Forces compiling the WiX Source (wxs) files.
+    ///
+    /// The compiler (`candle`) is skipped if every WiX object file (wixobj) is
+    /// newer than its WiX Source (wxs) file and the defines and arguments for
+    /// the compiler, such as the version, have not changed since the last
+    /// build. If `true`, the WiX Source (wxs) files are always compiled. The
+    /// default is to skip compiling if the WiX object files are up to date.
+    pub fn force(&mut self, f: bool) -> &mut Self {
+        self.force = f;
+        self
+    }
+
+    /// Requires the `Cargo.lock` file to be up to date and disables network
+    /// access when building the binaries.
+    ///
+    /// If `true`, the `--frozen` flag is passed to the builder (`cargo`),
+    /// which is the same as using both the [`locked`] and [`offline`] methods.
+    /// This is ignored if the build is skipped. The default is `false`.
+    ///
+    /// [`locked`]: #method.locked
+    /// [`offline`]: #method.offline
+    pub fn frozen(&mut self, f: bool) -> &mut Self {
+        self.frozen = f;
+        self
+    }
+
+    /// Includes a hash of the installer in its file name.
+    ///
+    /// If `true`, the installer is renamed after it is created to include the
+    /// first eight hexadecimal characters of its SHA-256 digest before the
+    /// file extension, i.e. `example-0.1.0-x86_64-1a2b3c4d.msi`, for artifact
+    /// stores that require immutable file names. The renamed path is the
+    /// output of the [`Summary`] and the path passed to the post-build
+    /// command. The default is `false`.
+    ///
+    /// [`Summary`]: struct.Summary.html
+    pub fn hash_in_name(&mut self, h: bool) -> &mut Self {
+        self.hash_in_name = h;
+        self
+    }
+
     /// Adds multiple WiX Source (wxs) files to the creation of an installer.
     ///
     /// By default, any `.wxs` file located in the project's `wix` folder will
     /// be included in the creation of an installer for the project. This method
     /// adds, or appends, to the list of `.wxs` files. The value is a relative
-    /// or absolute path.
+    /// or absolute path, where a relative path is resolved against the folder
+    /// containing the package's manifest (Cargo.toml), i.e. the same folder as
+    /// the default `wix` folder, not the current working directory (CWD).
     ///
     /// This value will override any default and skip looking for a value in the
     /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
@@ -179,7 +449,16 @@ impl<'a> Builder<'a> {
     /// A package's manifest is used to create an installer. If no path is
     /// specified, then the current working directory (CWD) is used. An error
     /// will occur if there is no `Cargo.toml` file in the CWD or at the
-    /// specified path. Either an absolute or relative path is valid.
+    /// specified path. Either an absolute or relative path is valid. The path
+    /// can also be to a folder containing the package's manifest, similar to
+    /// the `--manifest-path` option for `cargo`.
+    ///
+    /// A path to a file is used verbatim, so the manifest can have a name
+    /// other than `Cargo.toml`, such as `Alt.toml`. The folder containing the
+    /// file is the package's root for the WiX Source (wxs) files, the target
+    /// directory, and the installer. Cargo requires a manifest named
+    /// `Cargo.toml` to build the package, so the build should be skipped for
+    /// a renamed manifest.
     ///
     /// This value will override any default and skip looking for a value in the
     /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
@@ -188,6 +467,45 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the number of compiler (candle) processes to run in parallel.
+    ///
+    /// If a value is specified, the compiler is executed once for each WiX
+    /// Source (wxs) file instead of once for all of the files, and up to the
+    /// specified number of compiler processes are run at the same time. A
+    /// value of zero (0) uses the available parallelism of the system. The
+    /// default is to execute the compiler once with all of the WiX Source
+    /// (wxs) files.
+    pub fn jobs(&mut self, j: Option<usize>) -> &mut Self {
+        self.jobs = j;
+        self
+    }
+
+    /// Keeps the WiX debug information (wixpdb) file created by the linker.
+    ///
+    /// If `true`, the linker (`light`) writes the `.wixpdb` file next to the
+    /// installer, which is needed for authoring patches. The default is to
+    /// suppress the creation of the `.wixpdb` file.
+    pub fn keep_pdb(&mut self, k: bool) -> &mut Self {
+        self.keep_pdb = k;
+        self
+    }
+
+    /// Sets the WiX libraries (wixlib) to link into the installer.
+    ///
+    /// A WiX library is a pre-built collection of installer components, such as
+    /// components shared between installers, which the linker (light) accepts
+    /// as an input alongside the WiX object files (wixobj). The value is a
+    /// relative or absolute path, where a relative path is resolved against the
+    /// folder containing the package's manifest (Cargo.toml), and an error
+    /// occurs if a library does not exist.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn libraries(&mut self, l: Option<Vec<&'a str>>) -> &mut Self {
+        self.libraries = l;
+        self
+    }
+
     /// Adds an argument to the linker command.
     ///
     /// This "passes" the argument directly to the WiX linker (light.exe). See
@@ -198,6 +516,19 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the name of the linker application.
+    ///
+    /// This is the file name of the linker without the file extension. The
+    /// linker is still located using the path to the WiX Toolset's `bin`
+    /// folder, the WIX system environment variable, or the PATH system
+    /// environment variable, and the `.exe` file extension is added when
+    /// using a path. This is useful when the linker is wrapped by another
+    /// application with a different name. The default is `light`.
+    pub fn linker_name(&mut self, l: Option<&'a str>) -> &mut Self {
+        self.linker_name = l;
+        self
+    }
+
     /// Sets the path to a WiX localization file, `.wxl`, for the linker
     /// (light.exe).
     ///
@@ -213,6 +544,74 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Requires the `Cargo.lock` file to be up to date when building the
+    /// binaries.
+    ///
+    /// If `true`, the `--locked` flag is passed to the builder (`cargo`). This
+    /// is ignored if the build is skipped. The default is `false`.
+    pub fn locked(&mut self, l: bool) -> &mut Self {
+        self.locked = l;
+        self
+    }
+
+    /// Sets the maximum level for the logging statements.
+    ///
+    /// A logger that writes to `STDERR` is installed if the application has not
+    /// already installed one, so the logging statements are available without
+    /// any additional setup. This can be called any number of times. If a
+    /// level is not set, then a logger is not installed and the maximum level
+    /// is not changed, so the application is free to install its own logger.
+    pub fn log_level(&mut self, l: LevelFilter) -> &mut Self {
+        self.log_level = Some(l);
+        self
+    }
+
+    /// Enables or disables merging the compiler and linker arguments from the
+    /// package's manifest (Cargo.toml) with the arguments set with the
+    /// [`compiler_args`] and [`linker_args`] methods.
+    ///
+    /// If `true`, then the `compiler-args` and `linker-args` values in the
+    /// `[package.metadata.wix]` section of the package's manifest are used
+    /// first, followed by the arguments set with this builder. This is useful
+    /// for a base set of arguments in the manifest with one-off additions from
+    /// the command line. The default is `false`, where the arguments in the
+    /// package's manifest replace the arguments set with this builder.
+    ///
+    /// [`compiler_args`]: #method.compiler_args
+    /// [`linker_args`]: #method.linker_args
+    pub fn merge_args(&mut self, m: bool) -> &mut Self {
+        self.merge_args = m;
+        self
+    }
+
+    /// Sets the format of the message that is printed after the installer is
+    /// created.
+    ///
+    /// If `MessageFormat::Json`, then a single line with a JSON object is
+    /// printed to `STDOUT` after the installer is successfully created. The
+    /// object contains the product name, version, platform, culture, path to
+    /// the installer, and elapsed time in seconds, which is useful for
+    /// continuous integration (CI) tools. The logging statements are still
+    /// written to `STDERR`. The default is `MessageFormat::Human`, which does
+    /// not print anything after the installer is created.
+    pub fn message_format(&mut self, m: Option<MessageFormat>) -> &mut Self {
+        self.message_format = m;
+        self
+    }
+
+    /// Sets the minimum version of the WiX Toolset.
+    ///
+    /// The version of the installed WiX Toolset is read from the banner of the
+    /// compiler (`candle -?`) once before the installer is compiled, and an
+    /// error occurs if it is older than this version or it cannot be
+    /// determined. The version can have one to three parts, i.e. `3`, `3.11`,
+    /// or `3.11.2`, where missing parts are zero. The default is to only log
+    /// the installed version.
+    pub fn min_wix_version(&mut self, m: Option<&'a str>) -> &mut Self {
+        self.min_wix_version = m;
+        self
+    }
+
     /// Sets the name.
     ///
     /// The default is to use the `name` field under the `[package]` section of
@@ -237,6 +636,21 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Appends a suffix to the file stem for the installer (msi).
+    ///
+    /// The suffix is appended verbatim to the file stem (string before the dot
+    /// and file extension) for the installer's file name, after the `-debug`
+    /// suffix if the `debug_name` method is also used. For example, a suffix of
+    /// `-nightly` would result in a file name of
+    /// `example-0.1.0-x86_64-nightly.msi`. Only the file name is affected, the
+    /// version of the installer, i.e. the `ProductVersion` property, is not
+    /// changed. The suffix cannot contain a path separator, either a slash
+    /// (`/`) or a backslash (`\`). The default is to _not_ append a suffix.
+    pub fn name_suffix(&mut self, s: Option<&'a str>) -> &mut Self {
+        self.name_suffix = s;
+        self
+    }
+
     /// Skips the building of the project with the release profile.
     ///
     /// If `true`, the project will _not_ be built using the release profile,
@@ -247,11 +661,74 @@ impl<'a> Builder<'a> {
     ///
     /// This value will override any default and skip looking for a value in the
     /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    ///
+    /// If the build is skipped and the [`name`] and [`version`] are set, then
+    /// the package's manifest is not needed, so an installer can be created for
+    /// a binary from an external build system. The WiX Source (wxs) files are
+    /// still read from the `wix` folder of the input folder, or the current
+    /// working directory, and the binaries are the ones referenced in the WiX
+    /// Source files. None of the values from the `[package.metadata.wix]`
+    /// section apply without a manifest.
+    ///
+    /// [`name`]: #method.name
+    /// [`version`]: #method.version
     pub fn no_build(&mut self, n: bool) -> &mut Self {
         self.no_build = n;
         self
     }
 
+    /// Deactivates the default features when building the binaries.
+    ///
+    /// If `true`, the `--no-default-features` flag is passed to the builder
+    /// (`cargo`). This is ignored if the build is skipped. The default is to
+    /// activate the default features.
+    pub fn no_default_features(&mut self, n: bool) -> &mut Self {
+        self.no_default_features = n;
+        self
+    }
+
+    /// Links the installer without the WixUI extension.
+    ///
+    /// If `true`, the `-ext WixUIExtension` and `-cultures:` options are not
+    /// passed to the linker (light), which is useful for a silent installer
+    /// without a user interface. The WiX Source (wxs) files must not reference
+    /// any of the WixUI dialog sets, i.e. use `UiSet::None` when printing the
+    /// WXS template; otherwise, the linker fails with unresolved references.
+    /// The default is to use the `no-ui` field of the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or to include the
+    /// WixUI extension if the field does not exist.
+    pub fn no_ui(&mut self, n: bool) -> &mut Self {
+        self.no_ui = n;
+        self
+    }
+
+    /// Compiles and links the installer without the WixUtil extension.
+    ///
+    /// If `true`, the `-ext WixUtilExtension` option is not passed to the
+    /// compiler (candle) or the linker (light), which avoids conflicts with a
+    /// different version of the extension passed with the [`compiler_args`] and
+    /// [`linker_args`] methods. The WiX Source (wxs) files must not use any
+    /// elements from the extension's schema. The default is `false`, i.e. the
+    /// extension is always included.
+    ///
+    /// [`compiler_args`]: #method.compiler_args
+    /// [`linker_args`]: #method.linker_args
+    pub fn no_util_extension(&mut self, n: bool) -> &mut Self {
+        self.no_util_extension = n;
+        self
+    }
+
+    /// Disables network access when building the binaries.
+    ///
+    /// If `true`, the `--offline` flag is passed to the builder (`cargo`),
+    /// which is useful for continuous integration (CI) builds that must not
+    /// access the network. This is ignored if the build is skipped. The
+    /// default is `false`.
+    pub fn offline(&mut self, o: bool) -> &mut Self {
+        self.offline = o;
+        self
+    }
+
     /// Sets the output file and destination.
     ///
     /// The default is to create a MSI file with the
@@ -265,6 +742,13 @@ impl<'a> Builder<'a> {
     /// file name and path, the `.msi` file is not required. It will be added
     /// automatically.
     ///
+    /// The path can contain the `{name}`, `{version}`, `{arch}`, and
+    /// `{platform}` tokens, which are replaced with the product name, the
+    /// version, the Rust architecture, i.e. `x86_64`, and the WiX Toolset
+    /// platform, i.e. `x64`, respectively. For example, `releases/{version}/`
+    /// puts the installer with the default file name in a folder for each
+    /// version. The folder for the installer is created if it does not exist.
+    ///
     /// This value will override any default and skip looking for a value in the
     /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
     pub fn output(&mut self, o: Option<&'a str>) -> &mut Self {
@@ -272,6 +756,214 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the path for the WiX debug information (wixpdb) file created by the
+    /// linker.
+    ///
+    /// If set, the `-pdbout` option with the path is passed to the linker
+    /// (`light`), so the `.wixpdb` file can be written to a symbols folder
+    /// instead of next to the installer. This implies [`keep_pdb`]. A relative
+    /// path is relative to the package's root. The default is to write the
+    /// `.wixpdb` file next to the installer if it is kept.
+    ///
+    /// [`keep_pdb`]: #method.keep_pdb
+    pub fn pdb_output(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.pdb_output = p;
+        self
+    }
+
+    /// Sets a command to run after the installer is created.
+    ///
+    /// The command is run with the system shell, i.e. `cmd /C` on Windows and
+    /// `sh -c` everywhere else, from the package's root folder after the
+    /// linker (`light`) succeeds. The path to the installer is available to the
+    /// command in the `CARGO_WIX_MSI` environment variable, so the command can
+    /// compute a checksum or copy the installer to a release folder, for
+    /// example. An error occurs if the command fails. The default is to not
+    /// run a command.
+    pub fn post_build(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.post_build = p;
+        self
+    }
+
+    /// Sets a command to run before the binaries are built.
+    ///
+    /// The command is run with the system shell, i.e. `cmd /C` on Windows and
+    /// `sh -c` everywhere else, from the package's root folder before the
+    /// builder (`cargo build`), or before the compiler (`candle`) if the build
+    /// is skipped. The product name and version are available to the command
+    /// in the `CARGO_WIX_PRODUCT_NAME` and `CARGO_WIX_VERSION` environment
+    /// variables, so the command can generate code or fetch assets, for
+    /// example. An error occurs if the command fails. The default is to not
+    /// run a command.
+    pub fn pre_build(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.pre_build = p;
+        self
+    }
+
+    /// Sets the name of the preprocessor variable for the build profile.
+    ///
+    /// The compiler (`candle`) is passed a `-d<name>=debug` or
+    /// `-d<name>=release` define, which is used in the source paths of the
+    /// binaries in a WiX Source (wxs) file. This must match the variable name
+    /// used when the WXS file was printed with the [`profile_var`] method of
+    /// the `print::wxs::Builder`. The default is `Profile`.
+    ///
+    /// [`profile_var`]: ../print/wxs/struct.Builder.html#method.profile_var
+    pub fn profile_var(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.profile_var = p;
+        self
+    }
+
+    /// Enables or disables quiet mode for the compiler (`candle`) and linker
+    /// (`light`).
+    ///
+    /// If `true`, the `-nologo` flag is passed to the compiler and the linker
+    /// regardless of the [`show_logo`] method, and the output of the builder
+    /// (`cargo`), compiler, linker, and signer (`signtool`) is captured
+    /// regardless of the [`capture_output`] method. The default is `false`.
+    ///
+    /// [`capture_output`]: #method.capture_output
+    /// [`show_logo`]: #method.show_logo
+    pub fn quiet(&mut self, q: bool) -> &mut Self {
+        self.quiet = q;
+        self
+    }
+
+    /// Requires a clean git working tree for the package.
+    ///
+    /// The `git status --porcelain` command is run in the package's root
+    /// folder before the installer is created. If `true`, then an error occurs
+    /// if there are uncommitted changes, which catches accidentally creating
+    /// an installer that cannot be reproduced from a commit. If `false`, then
+    /// git is not run, unless the `require-clean-git` field of the
+    /// `[package.metadata.wix]` section is `true`. The check is skipped if the
+    /// package is not within a git repository or git is not installed. The
+    /// default is `false`.
+    pub fn require_clean_git(&mut self, r: bool) -> &mut Self {
+        self.require_clean_git = r;
+        self
+    }
+
+    /// Enables or disables the startup banner, or logo, of the compiler
+    /// (`candle`) and linker (`light`).
+    ///
+    /// By default, the `-nologo` flag is passed to both the compiler and the
+    /// linker, so the copyright banner is not printed for each invocation, and
+    /// it does not need to be added with the [`compiler_args`] and
+    /// [`linker_args`] methods. If `true`, the `-nologo` flag is not passed
+    /// and the banner is printed, unless the [`quiet`] method is also used.
+    /// The default is `false`.
+    ///
+    /// [`compiler_args`]: #method.compiler_args
+    /// [`linker_args`]: #method.linker_args
+    /// [`quiet`]: #method.quiet
+    pub fn show_logo(&mut self, s: bool) -> &mut Self {
+        self.show_logo = s;
+        self
+    }
+
+    /// Enables or disables signing the binaries before creating the installer.
+    ///
+    /// If `true`, each binary included in the installer is signed with the
+    /// signer (`signtool`) application from the Windows SDK after the binaries
+    /// are built and before the installer is compiled. The binaries are
+    /// determined in the same way as the `cargo wix print wxs` subcommand,
+    /// i.e. the `[[bin]]` sections of the package's manifest (Cargo.toml) or
+    /// the package's name. The signer is found using the `SIGNTOOL_PATH`
+    /// environment variable or the `PATH` system environment variable. This
+    /// does not sign the installer, which can still be signed with the `cargo
+    /// wix sign` subcommand. The default is to not sign the binaries.
+    pub fn sign_binaries(&mut self, s: bool) -> &mut Self {
+        self.sign_binaries = s;
+        self
+    }
+
+    /// Sets the number of times to retry signing a binary when the timestamp
+    /// server fails.
+    ///
+    /// This only applies when the binaries are signed with the
+    /// [`sign_binaries`] method. If the signer fails because the timestamp
+    /// server could not be reached or returned an invalid response, then
+    /// signing is retried after waiting, where the wait doubles after each
+    /// attempt. Any other failure is not retried. The default is three (3)
+    /// retries.
+    ///
+    /// [`sign_binaries`]: #method.sign_binaries
+    pub fn sign_retries(&mut self, s: u32) -> &mut Self {
+        self.sign_retries = s;
+        self
+    }
+
+    /// Enables or disables sorting the WiX Source (wxs) files in the `wix`
+    /// folder.
+    ///
+    /// The order of the files in a folder depends on the file system, so the
+    /// WiX Source files are sorted by path before they are passed to the
+    /// compiler (`candle`) to create the same installer on every machine. The
+    /// included files are appended after the sorted files in the order they
+    /// are specified. If `false`, the WiX Source files are passed in the order
+    /// they are read from the folder. The default is `true`.
+    pub fn sort_sources(&mut self, s: bool) -> &mut Self {
+        self.sort_sources = s;
+        self
+    }
+
+    /// Enables or disables failing when the version cannot be represented by
+    /// the Windows Installer.
+    ///
+    /// The Windows Installer limits the major and minor parts of the product
+    /// version to 255 and the build, or patch, part to 65,535. A version that
+    /// exceeds these limits is truncated or rejected by the Windows Installer,
+    /// which can make upgrades behave unexpectedly. If `true`, an error occurs
+    /// for such a version before anything is built, instead of a cryptic error
+    /// from the linker (light). If `false`, a warning is logged and the
+    /// installer is still created. The default is `true`.
+    pub fn strict_version(&mut self, s: bool) -> &mut Self {
+        self.strict_version = s;
+        self
+    }
+
+    /// Sets the WiX Toolset warnings to suppress.
+    ///
+    /// Each warning number is passed as the `-sw<N>` option to both the
+    /// compiler (candle) and the linker (light), which share the same syntax
+    /// for suppressing warnings. This is useful for silencing noisy warnings
+    /// in the logs of continuous integration builds. The default is to use
+    /// the `suppress-warnings` field of the `[package.metadata.wix]` section
+    /// of the package's manifest (Cargo.toml). If the field does not exist,
+    /// then no warnings are suppressed.
+    pub fn suppress_warnings(&mut self, s: Vec<u32>) -> &mut Self {
+        self.suppress_warnings = s;
+        self
+    }
+
+    /// Sets the path to the target directory.
+    ///
+    /// This is forwarded to the builder (Cargo) as the `--target-dir` option,
+    /// and the binaries are read from, and the WiX object files (wixobj) and
+    /// installer are written to, this directory. It takes precedence over the
+    /// `CARGO_TARGET_DIR` environment variable. The default is to use the
+    /// `CARGO_TARGET_DIR` environment variable if it is set; otherwise, the
+    /// `target` folder of the package is used.
+    pub fn target_dir(&mut self, t: Option<&'a str>) -> &mut Self {
+        self.target_dir = t;
+        self
+    }
+
+    /// Sets the maximum time to wait for each application that is run.
+    ///
+    /// The timeout applies separately to each run of the builder (`cargo`),
+    /// compiler (`candle`), linker (`light`), signer (`signtool`), and the
+    /// pre-build and post-build commands. If an application does not exit
+    /// within the timeout, then it is stopped and an error occurs, which
+    /// prevents a stalled application, such as one reading from an
+    /// unresponsive network drive, from blocking forever. The default is to
+    /// wait without a timeout.
+    pub fn timeout(&mut self, t: Option<Duration>) -> &mut Self {
+        self.timeout = t;
+        self
+    }
+
     /// Sets the version.
     ///
     /// This overrides the `version` field of the package's manifest
@@ -284,32 +976,93 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Treats the WiX Toolset warnings as errors.
+    ///
+    /// If `true`, the `-wx` flag is passed to both the compiler (candle) and
+    /// the linker (light), so creating the installer fails if any warning,
+    /// other than a suppressed warning, occurs. The default is to use the
+    /// `warnings-as-errors` field of the `[package.metadata.wix]` section of
+    /// the package's manifest (Cargo.toml). If the field does not exist, then
+    /// warnings are not treated as errors.
+    pub fn warnings_as_errors(&mut self, w: bool) -> &mut Self {
+        self.warnings_as_errors = w;
+        self
+    }
+
     /// Builds a context for creating, or building, the installer.
     pub fn build(&mut self) -> Execution {
         Execution {
+            all_features: self.all_features,
             bin_path: self.bin_path.map(PathBuf::from),
-            capture_output: self.capture_output,
+            candle_arch: self.candle_arch.map(String::from),
+            capture_output: self.capture_output || self.quiet,
+            checksum: self.checksum,
             compiler_args: self
                 .compiler_args
                 .as_ref()
                 .map(|c| c.iter().map(|s| (*s).to_string()).collect()),
+            compiler_name: self.compiler_name.map(String::from),
             culture: self.culture.map(String::from),
             debug_build: self.debug_build,
             debug_name: self.debug_name,
+            dry_run: self.dry_run,
+            extensions: self
+                .extensions
+                .as_ref()
+                .map(|f| f.iter().map(|s| (*s).to_string()).collect()),
+            features: self
+                .features
+                .as_ref()
+                .map(|f| f.iter().map(|s| (*s).to_string()).collect()),
+            force: self.force,
+            frozen: self.frozen,
+            hash_in_name: self.hash_in_name,
             includes: self
                 .includes
                 .as_ref()
                 .map(|v| v.iter().map(&PathBuf::from).collect()),
-            input: self.input.map(PathBuf::from),
+            input: self.input.map(manifest_input),
+            jobs: self.jobs,
+            keep_pdb: self.keep_pdb,
+            libraries: self
+                .libraries
+                .as_ref()
+                .map(|v| v.iter().map(&PathBuf::from).collect()),
             linker_args: self
                 .linker_args
                 .as_ref()
                 .map(|l| l.iter().map(|s| (*s).to_string()).collect()),
+            linker_name: self.linker_name.map(String::from),
             locale: self.locale.map(PathBuf::from),
+            locked: self.locked,
+            log_level: self.log_level,
+            merge_args: self.merge_args,
+            message_format: self.message_format,
+            min_wix_version: self.min_wix_version.map(String::from),
             name: self.name.map(String::from),
+            name_suffix: self.name_suffix.map(String::from),
             no_build: self.no_build,
+            no_default_features: self.no_default_features,
+            no_ui: self.no_ui,
+            no_util_extension: self.no_util_extension,
+            offline: self.offline,
             output: self.output.map(String::from),
+            pdb_output: self.pdb_output.map(PathBuf::from),
+            post_build: self.post_build.map(String::from),
+            pre_build: self.pre_build.map(String::from),
+            profile_var: self.profile_var.map(String::from),
+            quiet: self.quiet,
+            require_clean_git: self.require_clean_git,
+            show_logo: self.show_logo,
+            sign_binaries: self.sign_binaries,
+            sign_retries: self.sign_retries,
+            sort_sources: self.sort_sources,
+            strict_version: self.strict_version,
+            suppress_warnings: self.suppress_warnings.clone(),
+            target_dir: self.target_dir.map(PathBuf::from),
+            timeout: self.timeout,
             version: self.version.map(String::from),
+            warnings_as_errors: self.warnings_as_errors,
         }
     }
 }
@@ -323,47 +1076,234 @@ impl<'a> Default for Builder<'a> {
 /// A context for creating, or building, an installer.
 #[derive(Debug)]
 pub struct Execution {
+    all_features: bool,
     bin_path: Option<PathBuf>,
+    candle_arch: Option<String>,
     capture_output: bool,
+    checksum: Option<ChecksumKind>,
     compiler_args: Option<Vec<String>>,
+    compiler_name: Option<String>,
     culture: Option<String>,
     debug_build: bool,
     debug_name: bool,
+    dry_run: bool,
+    extensions: Option<Vec<String>>,
+    features: Option<Vec<String>>,
+    force: bool,
+    frozen: bool,
+    hash_in_name: bool,
     includes: Option<Vec<PathBuf>>,
     input: Option<PathBuf>,
+    jobs: Option<usize>,
+    keep_pdb: bool,
+    libraries: Option<Vec<PathBuf>>,
     linker_args: Option<Vec<String>>,
+    linker_name: Option<String>,
     locale: Option<PathBuf>,
+    locked: bool,
+    log_level: Option<LevelFilter>,
+    merge_args: bool,
+    message_format: Option<MessageFormat>,
+    min_wix_version: Option<String>,
     name: Option<String>,
+    name_suffix: Option<String>,
     no_build: bool,
+    no_default_features: bool,
+    no_ui: bool,
+    no_util_extension: bool,
+    offline: bool,
     output: Option<String>,
+    pdb_output: Option<PathBuf>,
+    post_build: Option<String>,
+    pre_build: Option<String>,
+    profile_var: Option<String>,
+    quiet: bool,
+    require_clean_git: bool,
+    show_logo: bool,
+    sign_binaries: bool,
+    sign_retries: u32,
+    sort_sources: bool,
+    strict_version: bool,
+    suppress_warnings: Vec<u32>,
+    target_dir: Option<PathBuf>,
+    timeout: Option<Duration>,
     version: Option<String>,
+    warnings_as_errors: bool,
 }
 
 impl Execution {
     /// Creates, or builds, an installer within a built context.
-    #[allow(clippy::cognitive_complexity)]
     pub fn run(self) -> Result<()> {
+        self.run_with_summary().map(|_| ())
+    }
+
+    /// Resolves the configuration for creating an installer without building,
+    /// compiling, or linking it.
+    ///
+    /// Each value is resolved from the builder, the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), and the defaults in the
+    /// same way as the [`run`] method, so the decisions can be inspected
+    /// programmatically. The manifest is used as-is, so any workspace metadata
+    /// must already be merged into it. The path to the installer assumes a
+    /// MSI-based installer because the WiX object files (wixobj) are not read.
+    /// No applications are run, so the WiX Toolset does not need to be
+    /// installed.
+    ///
+    /// [`run`]: #method.run
+    pub fn resolved_config(&self, manifest: &Value) -> Result<ResolvedConfig> {
+        let name = self.name(manifest)?;
+        let version = self.version(manifest)?;
+        let platform = self.platform();
+        let culture = self.culture(manifest)?;
+        let locale = self.locale(manifest)?;
+        let debug_build = self.debug_build(manifest);
+        let target_directory = self.target_directory()?;
+        let output = self.installer_destination(
+            &name,
+            &version,
+            platform,
+            self.debug_name(manifest),
+            &InstallerKind::default(),
+            manifest,
+            &target_directory,
+        )?;
+        let sources = self.wxs_sources(manifest)?;
+        let defines = self.defines(debug_build, &version, platform, &target_directory);
+        let mut extensions = Vec::new();
+        if !self.no_ui(manifest) {
+            extensions.push(String::from("WixUIExtension"));
+        }
+        if !self.no_util_extension {
+            extensions.push(String::from("WixUtilExtension"));
+        }
+        extensions.extend(
+            self.custom_extensions(manifest)?
+                .iter()
+                .map(|e| e.to_string_lossy().into_owned()),
+        );
+        Ok(ResolvedConfig {
+            name,
+            version: version.to_string(),
+            product_version: format!("{}.{}.{}", version.major, version.minor, version.patch),
+            platform: platform.to_string(),
+            culture: culture.to_string(),
+            locale,
+            output,
+            sources,
+            defines,
+            extensions,
+        })
+    }
+
+    /// Creates, or builds, an installer within a built context and returns a
+    /// summary of the created installer.
+    ///
+    /// The summary is `None` for a dry run because an installer is not
+    /// created.
+    #[allow(clippy::cognitive_complexity)]
+    pub fn run_with_summary(mut self) -> Result<Option<Summary>> {
+        if let Some(level) = self.log_level {
+            super::init_logging(level);
+        }
+        let start = Instant::now();
+        debug!("self.all_features = {:?}", self.all_features);
         debug!("self.bin_path = {:?}", self.bin_path);
+        debug!("self.candle_arch = {:?}", self.candle_arch);
         debug!("self.capture_output = {:?}", self.capture_output);
+        debug!("self.checksum = {:?}", self.checksum);
         debug!("self.compiler_args = {:?}", self.compiler_args);
+        debug!("self.compiler_name = {:?}", self.compiler_name);
         debug!("self.culture = {:?}", self.culture);
         debug!("self.debug_build = {:?}", self.debug_build);
         debug!("self.debug_name = {:?}", self.debug_name);
+        debug!("self.dry_run = {:?}", self.dry_run);
+        debug!("self.extensions = {:?}", self.extensions);
+        debug!("self.features = {:?}", self.features);
+        debug!("self.force = {:?}", self.force);
+        debug!("self.frozen = {:?}", self.frozen);
+        debug!("self.hash_in_name = {:?}", self.hash_in_name);
         debug!("self.includes = {:?}", self.includes);
         debug!("self.input = {:?}", self.input);
+        debug!("self.jobs = {:?}", self.jobs);
+        debug!("self.keep_pdb = {:?}", self.keep_pdb);
+        debug!("self.libraries = {:?}", self.libraries);
         debug!("self.linker_args = {:?}", self.linker_args);
+        debug!("self.linker_name = {:?}", self.linker_name);
         debug!("self.locale = {:?}", self.locale);
+        debug!("self.locked = {:?}", self.locked);
+        debug!("self.log_level = {:?}", self.log_level);
+        debug!("self.merge_args = {:?}", self.merge_args);
+        debug!("self.message_format = {:?}", self.message_format);
+        debug!("self.min_wix_version = {:?}", self.min_wix_version);
         debug!("self.name = {:?}", self.name);
+        debug!("self.name_suffix = {:?}", self.name_suffix);
         debug!("self.no_build = {:?}", self.no_build);
+        debug!("self.no_default_features = {:?}", self.no_default_features);
+        debug!("self.no_ui = {:?}", self.no_ui);
+        debug!("self.no_util_extension = {:?}", self.no_util_extension);
+        debug!("self.offline = {:?}", self.offline);
         debug!("self.output = {:?}", self.output);
+        debug!("self.pdb_output = {:?}", self.pdb_output);
+        debug!("self.post_build = {:?}", self.post_build);
+        debug!("self.pre_build = {:?}", self.pre_build);
+        debug!("self.profile_var = {:?}", self.profile_var);
+        debug!("self.quiet = {:?}", self.quiet);
+        debug!("self.require_clean_git = {:?}", self.require_clean_git);
+        debug!("self.show_logo = {:?}", self.show_logo);
+        debug!("self.sign_binaries = {:?}", self.sign_binaries);
+        debug!("self.sign_retries = {:?}", self.sign_retries);
+        debug!("self.sort_sources = {:?}", self.sort_sources);
+        debug!("self.strict_version = {:?}", self.strict_version);
+        debug!("self.suppress_warnings = {:?}", self.suppress_warnings);
+        debug!("self.target_dir = {:?}", self.target_dir);
+        debug!("self.timeout = {:?}", self.timeout);
         debug!("self.version = {:?}", self.version);
-        let manifest_path = super::cargo_toml_file(self.input.as_ref())?;
+        debug!("self.warnings_as_errors = {:?}", self.warnings_as_errors);
+        let manifest_free_path = self.manifest_free_path()?;
+        debug!("manifest_free_path = {:?}", manifest_free_path);
+        let manifest_path = if let Some(ref path) = manifest_free_path {
+            // The folder for the missing manifest is still the package's root
+            // for the WiX Source files, target directory, and linker.
+            self.input = Some(path.to_owned());
+            path.to_owned()
+        } else {
+            super::cargo_toml_file(self.input.as_ref())?
+        };
         debug!("manifest_path = {:?}", manifest_path);
-        let manifest = super::manifest(self.input.as_ref())?;
+        let base_path = manifest_path.parent().ok_or_else(|| {
+            Error::Generic(String::from("The base path for the linker is invalid"))
+        })?;
+        debug!("base_path = {:?}", base_path);
+        let manifest = if manifest_free_path.is_some() {
+            info!(
+                "Using the explicit name and version because the package's manifest \
+                 (Cargo.toml) does not exist"
+            );
+            if self.sign_binaries {
+                return Err(Error::Generic(String::from(
+                    "The binaries cannot be signed without a package's manifest (Cargo.toml). \
+                     Please sign the binaries before creating the installer.",
+                )));
+            }
+            Value::Table(Table::new())
+        } else {
+            let mut manifest = super::manifest(self.input.as_ref())?;
+            if let Some(workspace) = workspace_manifest(&manifest_path, &manifest)? {
+                merge_workspace_metadata(&mut manifest, &workspace);
+            }
+            manifest
+        };
         let name = self.name(&manifest)?;
         debug!("name = {:?}", name);
         let version = self.version(&manifest)?;
         debug!("version = {:?}", version);
+        if let Some(msg) = Self::version_limits(&version) {
+            if self.strict_version {
+                return Err(Error::Generic(msg));
+            } else {
+                warn!("{}", msg);
+            }
+        }
         let compiler_args = self.compiler_args(&manifest);
         debug!("compiler_args = {:?}", compiler_args);
         let culture = self.culture(&manifest)?;
@@ -372,6 +1312,9 @@ impl Execution {
         debug!("linker_args = {:?}", linker_args);
         let locale = self.locale(&manifest)?;
         debug!("locale = {:?}", locale);
+        if let Some(ref l) = locale {
+            Self::validate_locale(&culture, l)?;
+        }
         let platform = self.platform();
         debug!("platform = {:?}", platform);
         let debug_build = self.debug_build(&manifest);
@@ -380,94 +1323,205 @@ impl Execution {
         debug!("debug_name = {:?}", debug_name);
         let wxs_sources = self.wxs_sources(&manifest)?;
         debug!("wxs_sources = {:?}", wxs_sources);
-        let wixobj_destination = self.wixobj_destination()?;
+        let target_directory = self.target_directory()?;
+        debug!("target_directory = {:?}", target_directory);
+        let wixobj_destination = Self::wixobj_destination(&target_directory);
         debug!("wixobj_destination = {:?}", wixobj_destination);
         let no_build = self.no_build(&manifest);
         debug!("no_build = {:?}", no_build);
+        let require_clean_git = self.require_clean_git(&manifest);
+        debug!("require_clean_git = {:?}", require_clean_git);
+        if require_clean_git {
+            Self::check_git_status(self.git_status(base_path).as_deref())?;
+        }
+        if let Some(ref script) = self.pre_build {
+            info!("Running the pre-build command");
+            let hook = self.pre_build_hook(script, &name, &version, base_path);
+            debug!("pre_build = {:?}", hook);
+            if self.dry_run {
+                println!("{}", command_line(&hook));
+            } else {
+                self.run_hook(hook, PRE_BUILD)?;
+            }
+        }
         if no_build {
             warn!("Skipped building the release binary");
+            if self.features.is_some() || self.all_features || self.no_default_features {
+                warn!("The feature options are ignored because the build is skipped");
+            }
+            if self.frozen || self.locked || self.offline {
+                warn!(
+                    "The '--frozen', '--locked', and '--offline' options are ignored because \
+                     the build is skipped"
+                );
+            }
         } else {
             // Build the binary with the release profile. If a release binary
             // has already been built, then this will essentially do nothing.
             info!("Building the release binary");
-            let mut builder = Command::new(CARGO);
-            debug!("builder = {:?}", builder);
-            if self.capture_output {
-                trace!("Capturing the '{}' output", CARGO);
-                builder.stdout(Stdio::null());
-                builder.stderr(Stdio::null());
-            }
-            builder.arg("build");
-            if !debug_build {
-                builder.arg("--release");
-            }
-            builder.arg("--manifest-path").arg(&manifest_path);
+            let mut builder = self.builder(&manifest, &manifest_path, debug_build);
             debug!("command = {:?}", builder);
-            let status = builder.status()?;
-            if !status.success() {
-                return Err(Error::Command(
-                    CARGO,
-                    status.code().unwrap_or(100),
-                    self.capture_output,
-                ));
+            if self.dry_run {
+                println!("{}", command_line(&builder));
+            } else if self.capture_output {
+                builder.stdout(Stdio::piped()).stderr(Stdio::piped());
+                let output = wait_with_output(builder.spawn()?, CARGO, self.timeout)?;
+                if !output.status.success() {
+                    return Err(Error::Build {
+                        code: output.status.code().unwrap_or(100),
+                        stderr: last_lines(
+                            &String::from_utf8_lossy(&output.stderr),
+                            BUILD_ERROR_LINES,
+                        ),
+                    });
+                }
+            } else {
+                let status = wait(builder.spawn()?, CARGO, self.timeout)?;
+                if !status.success() {
+                    return Err(Error::Command(
+                        CARGO.into(),
+                        status.code().unwrap_or(100),
+                        self.capture_output,
+                    ));
+                }
+            }
+        }
+        if self.sign_binaries {
+            info!("Signing the binaries");
+            let signer = self.signer(&name);
+            for (binary, command) in
+                self.binary_signers(&signer, &manifest, &target_directory, debug_build)?
+            {
+                if self.dry_run {
+                    println!("{}", command_line(&sign::redact(&command)));
+                } else if binary.exists() {
+                    signer.sign(command)?;
+                } else {
+                    return Err(Error::Generic(format!(
+                        "The '{}' binary to sign does not exist",
+                        binary.display()
+                    )));
+                }
             }
         }
         // Compile the installer
         info!("Compiling the installer");
-        let mut compiler = self.compiler()?;
-        debug!("compiler = {:?}", compiler);
-        if self.capture_output {
-            trace!("Capturing the '{}' output", WIX_COMPILER);
-            compiler.stdout(Stdio::null());
-            compiler.stderr(Stdio::null());
-        }
-        if debug_build {
-            compiler.arg("-dProfile=debug");
-        } else {
-            compiler.arg("-dProfile=release");
+        let min_wix_version = self.min_wix_version()?;
+        debug!("min_wix_version = {:?}", min_wix_version);
+        if !self.dry_run {
+            match self.wix_version() {
+                Ok(wix_version) => {
+                    debug!("wix_version = {:?}", wix_version);
+                    if let Some(ref minimum) = min_wix_version {
+                        Self::check_wix_version(wix_version.as_ref(), minimum)?;
+                    }
+                }
+                Err(err) if min_wix_version.is_some() => return Err(err),
+                Err(err) => debug!("The WiX Toolset version is unknown: {}", err),
+            }
         }
-        compiler
-            .arg(format!("-dVersion={}", version))
-            .arg(format!("-dPlatform={}", platform))
-            .arg("-ext")
-            .arg("WixUtilExtension")
-            .arg("-o")
-            .arg(&wixobj_destination);
-        if let Some(args) = &compiler_args {
-            trace!("Appending compiler arguments");
-            compiler.args(args);
-        }
-        compiler.args(&wxs_sources);
-        debug!("command = {:?}", compiler);
-        let status = compiler.status().map_err(|err| {
-            if err.kind() == ErrorKind::NotFound {
-                Error::Generic(format!(
-                    "The compiler application ({}) could not be found in the PATH environment \
-                    variable. Please check the WiX Toolset (http://wixtoolset.org/) is \
-                    installed and check the WiX Toolset's '{}' folder has been added to the PATH \
-                    system environment variable, the {} system environment variable exists, or use \
-                    the '-b,--bin-path' command line argument.",
-                    WIX_COMPILER, BINARY_FOLDER_NAME, WIX_PATH_KEY
-                ))
+        let wix_start = Instant::now();
+        let jobs = self.jobs();
+        debug!("jobs = {:?}", jobs);
+        let compiler_command = |sources: &[PathBuf]| -> Result<Command> {
+            let mut compiler = self.compiler()?;
+            debug!("compiler = {:?}", compiler);
+            self.logo_arg(&mut compiler);
+            if self.capture_output {
+                trace!("Capturing the '{}' output", self.compiler_name());
+                compiler.stdout(Stdio::null());
+                compiler.stderr(Stdio::null());
+            }
+            for define in self.defines(debug_build, &version, platform, &target_directory) {
+                compiler.arg(format!("-d{}", define));
+            }
+            self.arch_arg(&mut compiler, platform)?;
+            self.util_extension_arg(&mut compiler);
+            self.custom_extension_args(&mut compiler, &manifest)?;
+            compiler.arg("-o").arg(&wixobj_destination);
+            self.warning_args(&mut compiler, &manifest)?;
+            if let Some(args) = &compiler_args {
+                trace!("Appending compiler arguments");
+                compiler.args(args);
+            }
+            compiler.args(sources);
+            debug!("command = {:?}", compiler);
+            Ok(compiler)
+        };
+        let (wixobj_sources, installer_kind) = if self.dry_run {
+            if jobs.is_some() {
+                for source in &wxs_sources {
+                    println!(
+                        "{}",
+                        command_line(&compiler_command(std::slice::from_ref(source))?)
+                    );
+                }
             } else {
-                err.into()
+                println!("{}", command_line(&compiler_command(&wxs_sources)?));
             }
-        })?;
-        if !status.success() {
-            return Err(Error::Command(
-                WIX_COMPILER,
-                status.code().unwrap_or(100),
-                self.capture_output,
-            ));
-        }
-        let wixobj_sources = self.wixobj_sources(&wixobj_destination)?;
+            trace!("Assuming a MSI-based installer because the WiX object files are not created");
+            (
+                Self::sorted_wixobj_sources(
+                    Self::expected_wixobj_sources(&wixobj_destination, &wxs_sources).into_iter(),
+                ),
+                InstallerKind::default(),
+            )
+        } else {
+            let defines_hash = Self::defines_hash(&compiler_command(&[])?, &wxs_sources);
+            debug!("defines_hash = {:?}", defines_hash);
+            if !self.force && Self::is_compiled(&wxs_sources, &wixobj_destination, &defines_hash) {
+                info!(
+                    "Skipped compiling the installer because the WiX object files are up to date"
+                );
+            } else {
+                // The folder does not exist after a `cargo clean`, and the
+                // compiler does not reliably create it for the destination
+                // with a trailing backslash, which would also be part of the
+                // folder's name on other platforms.
+                Self::create_folder(&target_directory.join(WIX))?;
+                if let Some(jobs) = jobs {
+                    trace!("Compiling each WiX Source (wxs) file in parallel");
+                    let compilers = wxs_sources
+                        .iter()
+                        .map(|s| {
+                            compiler_command(std::slice::from_ref(s)).map(|c| (s.as_path(), c))
+                        })
+                        .collect::<Result<Vec<(&Path, Command)>>>()?;
+                    self.compile_in_parallel(compilers, jobs)?;
+                } else {
+                    let compiler = compiler_command(&wxs_sources)?.spawn().map_err(|err| {
+                        if err.kind() == ErrorKind::NotFound {
+                            Error::WixNotFound {
+                                tool: self.compiler_name().to_owned().into(),
+                            }
+                        } else {
+                            err.into()
+                        }
+                    })?;
+                    let status = wait(compiler, self.compiler_name(), self.timeout)?;
+                    if !status.success() {
+                        return Err(Error::Command(
+                            self.compiler_name().to_owned().into(),
+                            status.code().unwrap_or(100),
+                            self.capture_output,
+                        ));
+                    }
+                }
+                fs::write(
+                    wixobj_destination.join(DEFINES_HASH_FILE_NAME),
+                    &defines_hash,
+                )?;
+            }
+            let wixobj_sources = self.wixobj_sources(&wixobj_destination)?;
+            let installer_kind = InstallerKind::try_from(
+                wixobj_sources
+                    .iter()
+                    .map(WixObjKind::try_from)
+                    .collect::<Result<Vec<WixObjKind>>>()?,
+            )?;
+            (wixobj_sources, installer_kind)
+        };
         debug!("wixobj_sources = {:?}", wixobj_sources);
-        let installer_kind = InstallerKind::try_from(
-            wixobj_sources
-                .iter()
-                .map(WixObjKind::try_from)
-                .collect::<Result<Vec<WixObjKind>>>()?,
-        )?;
         debug!("installer_kind = {:?}", installer_kind);
         let installer_destination = self.installer_destination(
             &name,
@@ -476,28 +1530,23 @@ impl Execution {
             debug_name,
             &installer_kind,
             &manifest,
+            &target_directory,
         )?;
         debug!("installer_destination = {:?}", installer_destination);
         // Link the installer
         info!("Linking the installer");
         let mut linker = self.linker()?;
         debug!("linker = {:?}", linker);
-        let base_path = manifest_path.parent().ok_or_else(|| {
-            Error::Generic(String::from("The base path for the linker is invalid"))
-        })?;
-        debug!("base_path = {:?}", base_path);
         if self.capture_output {
-            trace!("Capturing the '{}' output", WIX_LINKER);
+            trace!("Capturing the '{}' output", self.linker_name());
             linker.stdout(Stdio::null());
             linker.stderr(Stdio::null());
         }
+        self.logo_arg(&mut linker);
+        self.pdb_arg(&mut linker, base_path);
+        self.extension_args(&mut linker, &culture, &manifest);
+        self.custom_extension_args(&mut linker, &manifest)?;
         linker
-            .arg("-spdb")
-            .arg("-ext")
-            .arg("WixUIExtension")
-            .arg("-ext")
-            .arg("WixUtilExtension")
-            .arg(format!("-cultures:{}", culture))
             .arg("-out")
             .arg(&installer_destination)
             .arg("-b")
@@ -510,37 +1559,252 @@ impl Execution {
             trace!("Adding the WixBalExtension for the bundle-based installer");
             linker.arg("-ext").arg("WixBalExtension");
         }
+        self.warning_args(&mut linker, &manifest)?;
         if let Some(args) = &linker_args {
             trace!("Appending linker arguments");
             linker.args(args);
         }
         linker.args(&wixobj_sources);
+        self.library_args(&mut linker, &manifest)?;
         debug!("command = {:?}", linker);
-        let status = linker.status().map_err(|err| {
+        if self.dry_run {
+            println!("{}", command_line(&linker));
+            if let Some(ref script) = self.post_build {
+                let hook = self.post_build_hook(script, &installer_destination, base_path);
+                println!("{}", command_line(&hook));
+            }
+            return Ok(None);
+        }
+        if let Some(parent) = installer_destination.parent() {
+            Self::create_folder(parent)?;
+        }
+        let linker = linker.spawn().map_err(|err| {
             if err.kind() == ErrorKind::NotFound {
-                Error::Generic(format!(
-                    "The linker application ({}) could not be found in the PATH environment \
-                     variable. Please check the WiX Toolset (http://wixtoolset.org/) is \
-                     installed and check the WiX Toolset's '{}' folder has been added to the PATH \
-                     environment variable, the {} system environment variable exists, or use the \
-                     '-b,--bin-path' command line argument.",
-                    WIX_LINKER, BINARY_FOLDER_NAME, WIX_PATH_KEY
-                ))
+                Error::WixNotFound {
+                    tool: self.linker_name().to_owned().into(),
+                }
             } else {
                 err.into()
             }
         })?;
+        let status = wait(linker, self.linker_name(), self.timeout)?;
         if !status.success() {
             return Err(Error::Command(
-                WIX_LINKER,
+                self.linker_name().to_owned().into(),
                 status.code().unwrap_or(100),
                 self.capture_output,
             ));
         }
-        Ok(())
+        let installer_destination = if self.hash_in_name {
+            Self::hash_in_name(&installer_destination)?
+        } else {
+            installer_destination
+        };
+        debug!("installer_destination = {:?}", installer_destination);
+        if let Some(kind) = self.checksum {
+            let checksum = Self::write_checksum(kind, &installer_destination)?;
+            info!("Wrote the '{}' checksum file", checksum.display());
+        }
+        if let Some(ref script) = self.post_build {
+            info!("Running the post-build command");
+            let hook = self.post_build_hook(script, &installer_destination, base_path);
+            self.run_hook(hook, POST_BUILD)?;
+        }
+        let wix_elapsed = wix_start.elapsed();
+        let summary = Summary::new(
+            name,
+            version.to_string(),
+            platform.to_string(),
+            culture.to_string(),
+            installer_destination,
+            start.elapsed(),
+        )?;
+        info!(
+            "Created the '{}' installer ({} bytes) in {:.2} seconds",
+            summary.output().display(),
+            summary.size(),
+            wix_elapsed.as_secs_f64()
+        );
+        if self.message_format.unwrap_or_default() == MessageFormat::Json {
+            println!("{}", summary.to_json()?);
+        }
+        Ok(Some(summary))
+    }
+
+    /// Renames the installer to include the first eight hexadecimal characters
+    /// of its SHA-256 digest before the file extension and returns the new
+    /// path.
+    fn hash_in_name(installer: &Path) -> Result<PathBuf> {
+        let digest = file_digest::<Sha256>(installer)?;
+        let stem = installer.file_stem().ok_or_else(|| {
+            Error::Generic(format!(
+                "The '{}' installer does not have a file name",
+                installer.display()
+            ))
+        })?;
+        let mut file_name = stem.to_os_string();
+        file_name.push("-");
+        file_name.push(&digest[..HASH_IN_NAME_LENGTH]);
+        if let Some(extension) = installer.extension() {
+            file_name.push(".");
+            file_name.push(extension);
+        }
+        let destination = installer.with_file_name(file_name);
+        fs::rename(installer, &destination)?;
+        Ok(destination)
+    }
+
+    /// Writes the digest of the installer to a checksum file next to it and
+    /// returns the path to the checksum file.
+    fn write_checksum(kind: ChecksumKind, installer: &Path) -> Result<PathBuf> {
+        let digest = kind.digest(installer)?;
+        let file_name = installer.file_name().ok_or_else(|| {
+            Error::Generic(format!(
+                "The '{}' installer does not have a file name",
+                installer.display()
+            ))
+        })?;
+        let mut checksum = installer.as_os_str().to_os_string();
+        checksum.push(".");
+        checksum.push(kind.extension());
+        let checksum = PathBuf::from(checksum);
+        fs::write(
+            &checksum,
+            format!("{}  {}\n", digest, file_name.to_string_lossy()),
+        )?;
+        Ok(checksum)
+    }
+
+    fn hook(&self, script: &str, base_path: &Path) -> Command {
+        let mut hook = if cfg!(windows) {
+            let mut hook = Command::new("cmd");
+            hook.arg("/C");
+            hook
+        } else {
+            let mut hook = Command::new("sh");
+            hook.arg("-c");
+            hook
+        };
+        hook.arg(script).current_dir(base_path);
+        if self.capture_output {
+            trace!("Capturing the '{}' output", script);
+            hook.stdout(Stdio::null());
+            hook.stderr(Stdio::null());
+        }
+        hook
+    }
+
+    fn post_build_hook(&self, script: &str, installer: &Path, base_path: &Path) -> Command {
+        let mut hook = self.hook(script, base_path);
+        hook.env(CARGO_WIX_MSI_KEY, installer);
+        hook
+    }
+
+    fn pre_build_hook(
+        &self,
+        script: &str,
+        name: &str,
+        version: &Version,
+        base_path: &Path,
+    ) -> Command {
+        let mut hook = self.hook(script, base_path);
+        hook.env(CARGO_WIX_PRODUCT_NAME_KEY, name)
+            .env(CARGO_WIX_VERSION_KEY, version.to_string());
+        hook
+    }
+
+    fn run_hook(&self, mut hook: Command, name: &'static str) -> Result<()> {
+        debug!("command = {:?}", hook);
+        let status = wait(hook.spawn()?, name, self.timeout)?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::Command(
+                name.into(),
+                status.code().unwrap_or(100),
+                self.capture_output,
+            ))
+        }
+    }
+
+    fn compile_in_parallel(&self, compilers: Vec<(&Path, Command)>, jobs: usize) -> Result<()> {
+        let threads = jobs.min(compilers.len());
+        let queue = Mutex::new(compilers.into_iter());
+        let failure: Mutex<Option<Error>> = Mutex::new(None);
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    // Stop taking new sources from the queue after the first
+                    // failure, but let any running compilers finish.
+                    if failure.lock().expect("Failure lock").is_some() {
+                        break;
+                    }
+                    let next = queue.lock().expect("Queue lock").next();
+                    let (source, mut compiler) = match next {
+                        Some(n) => n,
+                        None => break,
+                    };
+                    trace!("Compiling the '{}' WiX Source (wxs) file", source.display());
+                    let result = compiler
+                        .spawn()
+                        .map_err(|err| {
+                            if err.kind() == ErrorKind::NotFound {
+                                Error::WixNotFound {
+                                    tool: self.compiler_name().to_owned().into(),
+                                }
+                            } else {
+                                err.into()
+                            }
+                        })
+                        .and_then(|compiler| wait(compiler, self.compiler_name(), self.timeout))
+                        .and_then(|status| {
+                            if status.success() {
+                                Ok(())
+                            } else {
+                                Err(Error::Compile {
+                                    source: source.to_owned(),
+                                    code: status.code().unwrap_or(100),
+                                    captured_output: self.capture_output,
+                                })
+                            }
+                        });
+                    if let Err(err) = result {
+                        failure.lock().expect("Failure lock").get_or_insert(err);
+                        break;
+                    }
+                });
+            }
+        });
+        match failure.into_inner().expect("Failure lock") {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    fn compiler_name(&self) -> &str {
+        self.compiler_name.as_deref().unwrap_or(WIX_COMPILER)
+    }
+
+    /// Appends the file name of a WiX Toolset application to the path of its
+    /// folder.
+    ///
+    /// The `.exe` file extension is appended to the name instead of replacing
+    /// any existing extension, so a name with a dot, such as `candle-3.11`,
+    /// is not truncated. A name that already has the extension is unchanged.
+    fn tool_path(folder: &mut PathBuf, name: &str) {
+        let mut file_name = OsString::from(name);
+        if !matches!(
+            Path::new(name).extension(),
+            Some(e) if e.eq_ignore_ascii_case(EXE_FILE_EXTENSION)
+        ) {
+            file_name.push(".");
+            file_name.push(EXE_FILE_EXTENSION);
+        }
+        folder.push(file_name);
     }
 
-    fn compiler(&self) -> Result<Command> {
+    pub(crate) fn compiler(&self) -> Result<Command> {
+        let compiler_name = self.compiler_name();
         if let Some(mut path) = self.bin_path.as_ref().map(|s| {
             let mut p = PathBuf::from(s);
             trace!(
@@ -548,17 +1812,16 @@ impl Execution {
                 p.display(),
                 BINARY_FOLDER_NAME
             );
-            p.push(WIX_COMPILER);
-            p.set_extension(EXE_FILE_EXTENSION);
+            Self::tool_path(&mut p, compiler_name);
             p
         }) {
             if !path.exists() {
-                path.pop(); // Remove the `candle` application from the path
+                path.pop(); // Remove the compiler application from the path
                 Err(Error::Generic(format!(
                     "The compiler application ('{}') does not exist at the '{}' path specified via \
                     the '-b,--bin-path' command line argument. Please check the path is correct and \
                     the compiler application exists at the path.",
-                    WIX_COMPILER,
+                    compiler_name,
                     path.display()
                 )))
             } else {
@@ -572,17 +1835,16 @@ impl Execution {
                 BINARY_FOLDER_NAME
             );
             p.push(BINARY_FOLDER_NAME);
-            p.push(WIX_COMPILER);
-            p.set_extension(EXE_FILE_EXTENSION);
+            Self::tool_path(&mut p, compiler_name);
             p
         }) {
             if !path.exists() {
-                path.pop(); // Remove the `candle` application from the path
+                path.pop(); // Remove the compiler application from the path
                 Err(Error::Generic(format!(
                     "The compiler application ('{}') does not exist at the '{}' path specified \
                      via the {} environment variable. Please check the path is correct and the \
                      compiler application exists at the path.",
-                    WIX_COMPILER,
+                    compiler_name,
                     path.display(),
                     WIX_PATH_KEY
                 )))
@@ -590,123 +1852,249 @@ impl Execution {
                 Ok(Command::new(path))
             }
         } else {
-            Ok(Command::new(WIX_COMPILER))
+            Ok(Command::new(compiler_name))
         }
     }
 
     fn debug_build(&self, manifest: &Value) -> bool {
-        if self.debug_build {
-            true
-        } else if let Some(pkg_meta_wix_debug_build) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("dbg-build"))
-            .and_then(|c| c.as_bool())
-        {
-            pkg_meta_wix_debug_build
-        } else {
-            false
-        }
+        self.debug_build || meta_bool(manifest, "dbg-build").unwrap_or_default()
     }
 
     fn debug_name(&self, manifest: &Value) -> bool {
-        if self.debug_name {
-            true
-        } else if let Some(pkg_meta_wix_debug_name) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("dbg-name"))
-            .and_then(|c| c.as_bool())
-        {
-            pkg_meta_wix_debug_name
+        self.debug_name || meta_bool(manifest, "dbg-name").unwrap_or_default()
+    }
+
+    /// Gets the path to the package's manifest (Cargo.toml) when an installer
+    /// can be created without it.
+    ///
+    /// The manifest is not needed if the build is skipped and the name and
+    /// version are set, so an installer can be created for a binary from an
+    /// external build system. `None` is returned if the manifest exists or is
+    /// needed, and then the manifest is read as usual.
+    fn manifest_free_path(&self) -> Result<Option<PathBuf>> {
+        if !self.no_build || self.name.is_none() || self.version.is_none() {
+            return Ok(None);
+        }
+        let path = match self.input {
+            Some(ref i) if i.is_dir() => i.join(CARGO_MANIFEST_FILE),
+            Some(ref i) => i.to_owned(),
+            None => env::current_dir()?.join(CARGO_MANIFEST_FILE),
+        };
+        if path.exists() {
+            Ok(None)
         } else {
-            false
+            Ok(Some(path))
+        }
+    }
+
+    fn require_clean_git(&self, manifest: &Value) -> bool {
+        self.require_clean_git || meta_bool(manifest, "require-clean-git").unwrap_or(false)
+    }
+
+    /// Gets the short status of the git working tree in the folder.
+    ///
+    /// This is `None` if git is not installed, the folder is not within a git
+    /// repository, or the status cannot be obtained.
+    fn git_status(&self, folder: &Path) -> Option<String> {
+        let mut git = Command::new(GIT);
+        git.arg("status")
+            .arg("--porcelain")
+            .current_dir(folder)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        debug!("command = {:?}", git);
+        let output = git
+            .spawn()
+            .map_err(Error::from)
+            .and_then(|child| wait_with_output(child, GIT, self.timeout));
+        match output {
+            Ok(output) if output.status.success() => {
+                Some(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            Ok(output) => {
+                debug!(
+                    "The git status is unknown, likely because the package is not \
+                     within a git repository: {}",
+                    output.status
+                );
+                None
+            }
+            Err(err) => {
+                debug!("The git status is unknown: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Checks the short status of the git working tree for uncommitted
+    /// changes.
+    ///
+    /// An error is returned if there are uncommitted changes.
+    fn check_git_status(status: Option<&str>) -> Result<()> {
+        let entries = match status {
+            Some(status) => status
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect::<Vec<String>>(),
+            None => {
+                trace!("Skipped checking for uncommitted changes");
+                return Ok(());
+            }
+        };
+        if entries.is_empty() {
+            trace!("The git working tree is clean");
+            return Ok(());
         }
+        Err(Error::DirtyGit { entries })
     }
 
     fn no_build(&self, manifest: &Value) -> bool {
-        if self.no_build {
-            true
-        } else if let Some(pkg_meta_wix_no_build) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("no-build"))
-            .and_then(|c| c.as_bool())
-        {
-            pkg_meta_wix_no_build
-        } else {
-            false
+        self.no_build || meta_bool(manifest, "no-build").unwrap_or_default()
+    }
+
+    /// Creates the context for signing the binaries.
+    ///
+    /// The product name used for the description of the signed binaries is
+    /// the name of the installer.
+    fn signer(&self, name: &str) -> sign::Execution {
+        sign::Builder::new()
+            .capture_output(self.capture_output)
+            .product_name(Some(name))
+            .retries(self.sign_retries)
+            .timeout(self.timeout)
+            .build()
+    }
+
+    /// Creates the signer command for each binary included in the installer.
+    ///
+    /// The binaries are resolved in the same way as the `cargo wix print wxs`
+    /// subcommand, where each binary is in the profile folder of the target
+    /// directory.
+    fn binary_signers(
+        &self,
+        signer: &sign::Execution,
+        manifest: &Value,
+        target_directory: &Path,
+        debug_build: bool,
+    ) -> Result<Vec<(PathBuf, Command)>> {
+        let profile = if debug_build { "debug" } else { "release" };
+        let binaries = super::binaries(None, manifest, |name| {
+            let mut path = target_directory.join(profile).join(name);
+            path.set_extension(EXE_FILE_EXTENSION);
+            path
+        })?;
+        debug!("binaries = {:?}", binaries);
+        let manifest_dir = self.manifest_dir()?;
+        binaries
+            .into_iter()
+            .map(|binary| {
+                signer
+                    .command(manifest, &manifest_dir, &binary.source)
+                    .map(|command| (binary.source, command))
+            })
+            .collect()
+    }
+
+    fn builder(&self, manifest: &Value, manifest_path: &Path, debug_build: bool) -> Command {
+        let mut builder =
+            Command::new(env::var_os(CARGO_KEY).unwrap_or_else(|| OsString::from(CARGO)));
+        debug!("builder = {:?}", builder);
+        if self.capture_output {
+            trace!("Capturing the '{}' output", CARGO);
+            builder.stdout(Stdio::null());
+            // The standard error is kept, instead of discarded, to report why
+            // the build failed.
+            builder.stderr(Stdio::piped());
+        }
+        builder.arg("build");
+        if !debug_build {
+            builder.arg("--release");
+        }
+        builder.arg("--manifest-path").arg(manifest_path);
+        if let Some(target_dir) = &self.target_dir {
+            builder.arg("--target-dir").arg(target_dir);
+        }
+        if let Some(features) = self.features(manifest) {
+            builder.arg("--features").arg(features.join(","));
+        }
+        if self.all_features {
+            builder.arg("--all-features");
+        }
+        if self.no_default_features {
+            builder.arg("--no-default-features");
+        }
+        if self.frozen {
+            builder.arg("--frozen");
         }
+        if self.locked {
+            builder.arg("--locked");
+        }
+        if self.offline {
+            builder.arg("--offline");
+        }
+        builder
     }
 
-    fn compiler_args(&self, manifest: &Value) -> Option<Vec<String>> {
-        manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("compiler-args"))
-            .and_then(|i| i.as_array())
-            .map(|a| {
+    fn features(&self, manifest: &Value) -> Option<Vec<String>> {
+        self.features.to_owned().or_else(|| {
+            meta_array(manifest, "features").map(|a| {
                 a.iter()
-                    .map(|s| s.as_str().map(String::from).unwrap())
+                    .filter_map(|s| s.as_str().map(String::from))
                     .collect::<Vec<String>>()
             })
-            .or_else(|| self.compiler_args.to_owned())
+        })
+    }
+
+    fn compiler_args(&self, manifest: &Value) -> Option<Vec<String>> {
+        self.args(manifest, "compiler-args", &self.compiler_args)
     }
 
     fn linker_args(&self, manifest: &Value) -> Option<Vec<String>> {
-        manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("linker-args"))
-            .and_then(|i| i.as_array())
-            .map(|a| {
-                a.iter()
-                    .map(|s| s.as_str().map(String::from).unwrap())
-                    .collect::<Vec<String>>()
-            })
-            .or_else(|| self.linker_args.to_owned())
+        self.args(manifest, "linker-args", &self.linker_args)
+    }
+
+    fn args(&self, manifest: &Value, key: &str, args: &Option<Vec<String>>) -> Option<Vec<String>> {
+        let pkg_meta_wix_args = meta_array(manifest, key).map(|a| {
+            a.iter()
+                .map(|s| s.as_str().map(String::from).unwrap())
+                .collect::<Vec<String>>()
+        });
+        match (pkg_meta_wix_args, args) {
+            (Some(mut m), Some(a)) if self.merge_args => {
+                trace!("Merging the '{}' metadata with the arguments", key);
+                m.extend(a.iter().cloned());
+                Some(m)
+            }
+            (Some(m), _) => Some(m),
+            (None, a) => a.to_owned(),
+        }
+    }
+
+    fn jobs(&self) -> Option<usize> {
+        self.jobs.map(|j| {
+            if j == 0 {
+                thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1)
+            } else {
+                j
+            }
+        })
     }
 
     fn culture(&self, manifest: &Value) -> Result<Cultures> {
         if let Some(culture) = &self.culture {
             Cultures::from_str(culture)
-        } else if let Some(pkg_meta_wix_culture) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("culture"))
-            .and_then(|c| c.as_str())
-        {
+        } else if let Some(pkg_meta_wix_culture) = meta_str(manifest, "culture") {
             Cultures::from_str(pkg_meta_wix_culture)
         } else {
             Ok(Cultures::EnUs)
         }
     }
 
-    fn locale(&self, manifest: &Value) -> Result<Option<PathBuf>> {
+    pub(crate) fn locale(&self, manifest: &Value) -> Result<Option<PathBuf>> {
         if let Some(locale) = self.locale.as_ref().map(PathBuf::from) {
             if locale.exists() {
                 Ok(Some(locale))
@@ -717,24 +2105,257 @@ impl Execution {
                     locale.display()
                 )))
             }
-        } else if let Some(pkg_meta_wix_locale) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("locale"))
-            .and_then(|l| l.as_str())
-            .map(PathBuf::from)
-        {
+        } else if let Some(pkg_meta_wix_locale) = meta_path(manifest, "locale")? {
             Ok(Some(pkg_meta_wix_locale))
         } else {
             Ok(None)
         }
     }
 
-    fn linker(&self) -> Result<Command> {
+    fn locale_culture(locale: &Path) -> Result<Option<String>> {
+        let file = fs::File::open(locale)?;
+        let mut decoder = encoding_rs_io::DecodeReaderBytes::new(file);
+        let mut content = String::new();
+        decoder.read_to_string(&mut content)?;
+        let package = sxd_document::parser::parse(&content)?;
+        let document = package.as_document();
+        let mut context = sxd_xpath::Context::new();
+        context.set_namespace("wix", "http://schemas.microsoft.com/wix/2006/localization");
+        // The assumption is that the following cannot fail because the path is known to be valid at
+        // compile-time.
+        let xpath = sxd_xpath::Factory::new()
+            .build("/wix:WixLocalization/@Culture")
+            .unwrap()
+            .unwrap();
+        let value = xpath.evaluate(&context, document.root())?.string();
+        if value.trim().is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(value.trim().to_owned()))
+        }
+    }
+
+    fn validate_locale(culture: &Cultures, locale: &Path) -> Result<()> {
+        if let Some(locale_culture) = Self::locale_culture(locale)? {
+            debug!("locale_culture = {:?}", locale_culture);
+            if !locale_culture.eq_ignore_ascii_case(&culture.to_string()) {
+                return Err(Error::Generic(format!(
+                    "The '{}' culture does not match the '{}' culture declared in the '{}' WiX \
+                     localization file. Please use a culture that matches the localization \
+                     file, or a localization file for the culture.",
+                    culture,
+                    locale_culture,
+                    locale.display()
+                )));
+            }
+        } else {
+            trace!("The WiX localization file does not declare a culture");
+        }
+        Ok(())
+    }
+
+    fn suppress_warnings(&self, manifest: &Value) -> Result<Vec<u32>> {
+        if !self.suppress_warnings.is_empty() {
+            Ok(self.suppress_warnings.clone())
+        } else if let Some(pkg_meta_wix_suppress_warnings) =
+            meta_array(manifest, "suppress-warnings")
+        {
+            pkg_meta_wix_suppress_warnings
+                .iter()
+                .map(|w| {
+                    w.as_integer()
+                        .and_then(|i| u32::try_from(i).ok())
+                        .ok_or_else(|| {
+                            Error::Generic(format!(
+                                "The '{}' value in the 'suppress-warnings' field of the \
+                                 package's manifest (Cargo.toml) is not a valid warning \
+                                 number. Please use a non-negative integer.",
+                                w
+                            ))
+                        })
+                })
+                .collect()
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    fn warnings_as_errors(&self, manifest: &Value) -> bool {
+        self.warnings_as_errors || meta_bool(manifest, "warnings-as-errors").unwrap_or(false)
+    }
+
+    /// Adds the arguments for suppressing warnings and treating warnings as
+    /// errors, which are the same for the compiler (candle) and the linker
+    /// (light).
+    fn warning_args(&self, command: &mut Command, manifest: &Value) -> Result<()> {
+        for warning in self.suppress_warnings(manifest)? {
+            command.arg(format!("-sw{}", warning));
+        }
+        if self.warnings_as_errors(manifest) {
+            trace!("Treating warnings as errors");
+            command.arg("-wx");
+        }
+        Ok(())
+    }
+
+    /// Adds the WiX libraries (wixlib) as inputs for the linker (light).
+    fn library_args(&self, linker: &mut Command, manifest: &Value) -> Result<()> {
+        let manifest_dir = self.manifest_dir()?;
+        let libraries = if let Some(paths) = self.libraries.as_ref() {
+            paths.iter().map(|p| manifest_dir.join(p)).collect()
+        } else if let Some(pkg_meta_wix_libraries) = meta_array(manifest, "libraries") {
+            pkg_meta_wix_libraries
+                .iter()
+                .map(|s| {
+                    s.as_str()
+                        .map(|s| manifest_dir.join(s))
+                        .ok_or(Error::InvalidManifestField(
+                            "package.metadata.wix.libraries",
+                        ))
+                })
+                .collect::<Result<Vec<PathBuf>>>()?
+        } else {
+            Vec::new()
+        };
+        for library in libraries {
+            if !library.exists() {
+                return Err(Error::Generic(format!(
+                    "The '{}' WiX library (wixlib) does not exist. Please check the path.",
+                    library.display()
+                )));
+            } else if library.is_dir() {
+                return Err(Error::Generic(format!(
+                    "The '{}' path is not a file. Please check the path and ensure it is to \
+                     a WiX library (wixlib) file.",
+                    library.display()
+                )));
+            }
+            trace!("Using the '{}' WiX library", library.display());
+            linker.arg(library);
+        }
+        Ok(())
+    }
+
+    fn no_ui(&self, manifest: &Value) -> bool {
+        self.no_ui || meta_bool(manifest, "no-ui").unwrap_or(false)
+    }
+
+    /// Adds the extensions and the cultures for the linker (light).
+    ///
+    /// The cultures only select the localized strings of the WixUI extension,
+    /// so both are omitted for an installer without a user interface.
+    fn extension_args(&self, linker: &mut Command, culture: &Cultures, manifest: &Value) {
+        if self.no_ui(manifest) {
+            trace!("Linking without the WixUIExtension");
+            self.util_extension_arg(linker);
+        } else {
+            linker.arg("-ext").arg("WixUIExtension");
+            self.util_extension_arg(linker);
+            linker.arg(format!("-cultures:{}", culture));
+        }
+    }
+
+    /// Adds the additional extensions for the compiler (candle) and the linker
+    /// (light).
+    fn custom_extension_args(&self, command: &mut Command, manifest: &Value) -> Result<()> {
+        for extension in self.custom_extensions(manifest)? {
+            command.arg("-ext").arg(extension);
+        }
+        Ok(())
+    }
+
+    /// Gets the additional extensions for the compiler (candle) and the linker
+    /// (light).
+    ///
+    /// A name is passed unchanged, while a path to a custom extension (dll) is
+    /// resolved and checked for existence.
+    fn custom_extensions(&self, manifest: &Value) -> Result<Vec<OsString>> {
+        let extensions: Vec<String> = if let Some(e) = self.extensions.as_ref() {
+            e.clone()
+        } else if let Some(pkg_meta_wix_extensions) = meta_array(manifest, "extensions") {
+            pkg_meta_wix_extensions
+                .iter()
+                .map(|e| {
+                    e.as_str()
+                        .map(String::from)
+                        .ok_or(Error::InvalidManifestField(
+                            "package.metadata.wix.extensions",
+                        ))
+                })
+                .collect::<Result<Vec<String>>>()?
+        } else {
+            Vec::new()
+        };
+        extensions
+            .into_iter()
+            .map(|extension| {
+                if Self::is_extension_path(&extension) {
+                    let path = self.manifest_dir()?.join(&extension);
+                    if !path.is_file() {
+                        return Err(Error::Generic(format!(
+                            "The '{}' WiX extension does not exist. Please check the \
+                             path or use the name of an extension installed with the \
+                             WiX Toolset.",
+                            path.display()
+                        )));
+                    }
+                    trace!("Using the '{}' custom WiX extension", path.display());
+                    Ok(path.into_os_string())
+                } else {
+                    trace!("Using the '{}' WiX extension", extension);
+                    Ok(OsString::from(extension))
+                }
+            })
+            .collect()
+    }
+
+    /// Determines if an extension is a path to a dynamic link library (dll)
+    /// instead of the name of an extension.
+    fn is_extension_path(extension: &str) -> bool {
+        extension.contains(['/', '\\'])
+            || matches!(
+                Path::new(extension).extension().and_then(|e| e.to_str()),
+                Some(e) if e.eq_ignore_ascii_case("dll")
+            )
+    }
+
+    fn util_extension_arg(&self, command: &mut Command) {
+        if self.no_util_extension {
+            trace!("Omitting the WixUtilExtension");
+        } else {
+            command.arg("-ext").arg("WixUtilExtension");
+        }
+    }
+
+    fn logo_arg(&self, command: &mut Command) {
+        if self.quiet || !self.show_logo {
+            command.arg("-nologo");
+        } else {
+            trace!("Showing the logo");
+        }
+    }
+
+    fn pdb_arg(&self, linker: &mut Command, base_path: &Path) {
+        if let Some(ref pdb_output) = self.pdb_output {
+            trace!("Writing the WiX debug information (wixpdb) file to an explicit path");
+            linker.arg("-pdbout").arg(base_path.join(pdb_output));
+        } else if self.keep_pdb {
+            trace!("Keeping the WiX debug information (wixpdb) file");
+        } else {
+            linker.arg("-spdb");
+        }
+    }
+
+    fn profile_var(&self) -> &str {
+        self.profile_var.as_deref().unwrap_or(PROFILE_VARIABLE)
+    }
+
+    fn linker_name(&self) -> &str {
+        self.linker_name.as_deref().unwrap_or(WIX_LINKER)
+    }
+
+    pub(crate) fn linker(&self) -> Result<Command> {
+        let linker_name = self.linker_name();
         if let Some(mut path) = self.bin_path.as_ref().map(|s| {
             let mut p = PathBuf::from(s);
             trace!(
@@ -742,17 +2363,16 @@ impl Execution {
                 p.display(),
                 BINARY_FOLDER_NAME
             );
-            p.push(WIX_LINKER);
-            p.set_extension(EXE_FILE_EXTENSION);
+            Self::tool_path(&mut p, linker_name);
             p
         }) {
             if !path.exists() {
-                path.pop(); // Remove the 'light' application from the path
+                path.pop(); // Remove the linker application from the path
                 Err(Error::Generic(format!(
                     "The linker application ('{}') does not exist at the '{}' path specified via \
                      the '-b,--bin-path' command line argument. Please check the path is correct \
                      and the linker application exists at the path.",
-                    WIX_LINKER,
+                    linker_name,
                     path.display()
                 )))
             } else {
@@ -766,17 +2386,16 @@ impl Execution {
                 BINARY_FOLDER_NAME
             );
             p.push(BINARY_FOLDER_NAME);
-            p.push(WIX_LINKER);
-            p.set_extension(EXE_FILE_EXTENSION);
+            Self::tool_path(&mut p, linker_name);
             p
         }) {
             if !path.exists() {
-                path.pop(); // Remove the `candle` application from the path
+                path.pop(); // Remove the linker application from the path
                 Err(Error::Generic(format!(
                     "The linker application ('{}') does not exist at the '{}' path specified \
                      via the {} environment variable. Please check the path is correct and the \
                      linker application exists at the path.",
-                    WIX_LINKER,
+                    linker_name,
                     path.display(),
                     WIX_PATH_KEY
                 )))
@@ -784,10 +2403,47 @@ impl Execution {
                 Ok(Command::new(path))
             }
         } else {
-            Ok(Command::new(WIX_LINKER))
+            Ok(Command::new(linker_name))
         }
     }
 
+    fn arch_arg(&self, compiler: &mut Command, platform: Platform) -> Result<()> {
+        let arch = if let Some(ref arch) = self.candle_arch {
+            if CANDLE_ARCHITECTURES.contains(&arch.as_str()) {
+                arch.to_owned()
+            } else {
+                return Err(Error::Generic(format!(
+                    "The '{}' architecture for the compiler is not valid. Please use one of \
+                     the following: {}.",
+                    arch,
+                    CANDLE_ARCHITECTURES.join(", ")
+                )));
+            }
+        } else {
+            platform.to_string()
+        };
+        compiler.arg("-arch").arg(arch);
+        Ok(())
+    }
+
+    /// Gets the preprocessor variables defined for the compiler (candle),
+    /// without the `-d` prefix, i.e. `Version=1.2.3`.
+    fn defines(
+        &self,
+        debug_build: bool,
+        version: &Version,
+        platform: Platform,
+        target_directory: &Path,
+    ) -> Vec<String> {
+        let profile = if debug_build { "debug" } else { "release" };
+        vec![
+            format!("{}={}", self.profile_var(), profile),
+            format!("Version={}", version),
+            format!("Platform={}", platform),
+            format!("CargoTargetDir={}", target_directory.display()),
+        ]
+    }
+
     fn platform(&self) -> Platform {
         if cfg!(target_arch = "x86_64") {
             Platform::X64
@@ -796,20 +2452,10 @@ impl Execution {
         }
     }
 
-    fn name(&self, manifest: &Value) -> Result<String> {
+    pub(crate) fn name(&self, manifest: &Value) -> Result<String> {
         if let Some(ref p) = self.name {
             Ok(p.to_owned())
-        } else if let Some(pkg_meta_wix_name) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("name"))
-            .and_then(|n| n.as_str())
-            .map(String::from)
-        {
+        } else if let Some(pkg_meta_wix_name) = meta_str(manifest, "name").map(String::from) {
             Ok(pkg_meta_wix_name)
         } else {
             manifest
@@ -822,6 +2468,7 @@ impl Execution {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn installer_destination(
         &self,
         name: &str,
@@ -830,80 +2477,92 @@ impl Execution {
         debug_name: bool,
         installer_kind: &InstallerKind,
         manifest: &Value,
+        target_directory: &Path,
     ) -> Result<PathBuf> {
-        let filename = if debug_name {
-            format!(
-                "{}-{}-{}-debug.{}",
-                name,
-                version,
-                platform.arch(),
-                installer_kind
-            )
+        let mut stem = if debug_name {
+            format!("{}-{}-{}-debug", name, version, platform.arch())
         } else {
-            format!(
-                "{}-{}-{}.{}",
-                name,
-                version,
-                platform.arch(),
-                installer_kind
-            )
+            format!("{}-{}-{}", name, version, platform.arch())
         };
+        if let Some(ref suffix) = self.name_suffix {
+            if suffix.contains(['/', '\\']) {
+                return Err(Error::Generic(format!(
+                    "The '{}' name suffix for the installer cannot contain a path \
+                     separator",
+                    suffix
+                )));
+            }
+            stem.push_str(suffix);
+        }
+        let filename = format!("{}.{}", stem, installer_kind);
         if let Some(ref path_str) = self.output {
             trace!("Using the explicitly specified output path for the MSI destination");
-            let path = Path::new(path_str);
-            if path_str.ends_with('/') || path_str.ends_with('\\') || path.is_dir() {
-                Ok(path.join(filename))
-            } else {
-                Ok(path.to_owned())
-            }
-        } else if let Some(pkg_meta_wix_output) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("output"))
-            .and_then(|o| o.as_str())
-        {
+            Ok(Self::output_destination(
+                &expand_output_tokens(path_str, name, version, platform),
+                &filename,
+            ))
+        } else if let Some(pkg_meta_wix_output) = meta_path(manifest, "output")? {
             trace!("Using the output path in the package's metadata for the MSI destination");
-            let path = Path::new(pkg_meta_wix_output);
-            if pkg_meta_wix_output.ends_with('/')
-                || pkg_meta_wix_output.ends_with('\\')
-                || path.is_dir()
-            {
-                Ok(path.join(filename))
+            Ok(Self::output_destination(
+                &expand_output_tokens(
+                    &pkg_meta_wix_output.to_string_lossy(),
+                    name,
+                    version,
+                    platform,
+                ),
+                &filename,
+            ))
+        } else {
+            trace!("Using the target directory to specify the MSI destination");
+            Ok(target_directory.join(WIX).join(filename))
+        }
+    }
+
+    /// Determines the installer's path from an output path.
+    ///
+    /// A trailing separator, either a slash (`/`) or a backslash (`\`),
+    /// indicates a folder even if it does not exist yet, and the separators are
+    /// removed before the file name is appended, so mixed separators and UNC
+    /// paths, i.e. `\\server\share\out\`, are not misclassified. Without a
+    /// trailing separator, the file name is only appended if the path is an
+    /// existing folder.
+    fn output_destination(output: &str, filename: &str) -> PathBuf {
+        let trimmed = output.trim_end_matches(['/', '\\']);
+        if trimmed.len() < output.len() {
+            // Removing the separator from a root, i.e. `C:\` or `/`, would
+            // change its meaning, so a root is joined as-is.
+            if trimmed.is_empty() || trimmed.ends_with(':') {
+                Path::new(output).join(filename)
             } else {
-                Ok(path.to_owned())
+                Path::new(trimmed).join(filename)
             }
-        } else if let Some(manifest_path) = &self.input {
-            trace!("Using the package's manifest (Cargo.toml) file path to specify the MSI destination");
-            // Remove the `Cargo.toml` file from the path
-            manifest_path
-                .parent()
-                .ok_or_else(|| {
-                    Error::Generic(format!(
-                        "The '{}' path for the package's manifest file is invalid",
-                        manifest_path.display()
-                    ))
-                })
-                .map(|d| {
-                    PathBuf::from(d)
-                        .join(TARGET_FOLDER_NAME)
-                        .join(WIX)
-                        .join(filename)
-                })
+        } else if Path::new(output).is_dir() {
+            Path::new(output).join(filename)
         } else {
-            trace!("Using the current working directory (CWD) to build the WiX object files destination");
-            Ok(PathBuf::from(TARGET_FOLDER_NAME).join(WIX).join(filename))
+            PathBuf::from(output)
         }
     }
 
-    fn wixobj_destination(&self) -> Result<PathBuf> {
-        let mut dst = if let Some(manifest_path) = &self.input {
+    fn target_directory(&self) -> Result<PathBuf> {
+        self.target_directory_with(env::var_os(CARGO_TARGET_DIR_KEY))
+    }
+
+    /// Determines the target directory using the value of the
+    /// `CARGO_TARGET_DIR` environment variable, if it is set.
+    fn target_directory_with(&self, cargo_target_dir: Option<OsString>) -> Result<PathBuf> {
+        if let Some(target_dir) = &self.target_dir {
+            trace!("Using the explicitly specified target directory");
+            Ok(target_dir.to_owned())
+        } else if let Some(target_dir) = cargo_target_dir {
+            trace!(
+                "Using the '{}' environment variable for the target directory",
+                CARGO_TARGET_DIR_KEY
+            );
+            Ok(PathBuf::from(target_dir))
+        } else if let Some(manifest_path) = &self.input {
             trace!(
                 "Using the package's manifest (Cargo.toml) file path to build \
-                the Wix object files destination"
+                the target directory"
             );
             // Remove the `Cargo.toml` file from the path
             manifest_path
@@ -916,9 +2575,26 @@ impl Execution {
                 })
                 .map(|d| PathBuf::from(d).join(TARGET_FOLDER_NAME))
         } else {
-            trace!("Using the current working directory (CWD) to build the WiX object files destination");
+            trace!("Using the current working directory (CWD) to build the target directory");
             Ok(PathBuf::from(TARGET_FOLDER_NAME))
-        }?;
+        }
+    }
+
+    /// Creates a folder and all of its parents if it does not exist.
+    ///
+    /// The compiler (candle) and linker (light) fail with an obscure error if
+    /// the folder for their output does not exist.
+    fn create_folder(folder: &Path) -> Result<()> {
+        if folder.as_os_str().is_empty() || folder.exists() {
+            Ok(())
+        } else {
+            info!("Creating the '{}' folder", folder.display());
+            fs::create_dir_all(folder).map_err(Error::from)
+        }
+    }
+
+    fn wixobj_destination(target_directory: &Path) -> PathBuf {
+        let mut dst = target_directory.to_owned();
         // A trailing slash is needed; otherwise, candle tries to dump the
         // object files to a `target\wix` file instead of dumping the object
         // files in the `target\wix\` folder for the `-out` option. The trailing
@@ -930,15 +2606,15 @@ impl Execution {
         // not sure how to add a trailing slash in a cross-platform way with
         // PathBuf, not that cargo-wix needs to be cross-platform.
         dst.push(format!("{}\\", WIX));
-        Ok(dst)
+        dst
     }
 
     fn wixobj_sources(&self, wixobj_dst: &Path) -> Result<Vec<PathBuf>> {
-        let wixobj_sources: Vec<PathBuf> = std::fs::read_dir(wixobj_dst)?
-            .filter(|r| r.is_ok())
-            .map(|r| r.unwrap().path())
-            .filter(|p| p.extension().and_then(|s| s.to_str()) == Some(WIX_OBJECT_FILE_EXTENSION))
-            .collect();
+        let wixobj_sources = Self::sorted_wixobj_sources(
+            std::fs::read_dir(wixobj_dst)?
+                .filter(|r| r.is_ok())
+                .map(|r| r.unwrap().path()),
+        );
         if wixobj_sources.is_empty() {
             Err(Error::Generic(String::from("No WiX object files found.")))
         } else {
@@ -946,37 +2622,162 @@ impl Execution {
         }
     }
 
-    fn wxs_sources(&self, manifest: &Value) -> Result<Vec<PathBuf>> {
-        let project_wix_dir = if let Some(manifest_path) = &self.input {
+    /// Keeps only the WiX object (wixobj) files and sorts them by path.
+    ///
+    /// The order of the files in a folder depends on the file system, and the
+    /// order of the linker's (`light`) inputs can change the created installer,
+    /// so the files are always sorted for a reproducible installer.
+    fn sorted_wixobj_sources<I: Iterator<Item = PathBuf>>(paths: I) -> Vec<PathBuf> {
+        let mut wixobj_sources: Vec<PathBuf> = paths
+            .filter(|p| p.extension().and_then(|s| s.to_str()) == Some(WIX_OBJECT_FILE_EXTENSION))
+            .collect();
+        wixobj_sources.sort();
+        wixobj_sources
+    }
+
+    /// Computes a SHA-256 digest of the compiler's arguments and the WiX
+    /// Source (wxs) files, so the digest is stable across Rust releases and
+    /// changes when a source is added or removed.
+    fn defines_hash(compiler: &Command, wxs_sources: &[PathBuf]) -> String {
+        let mut hasher = Sha256::new();
+        for arg in compiler.get_args() {
+            hasher.update(arg.to_string_lossy().as_bytes());
+            hasher.update([0]);
+        }
+        for source in wxs_sources {
+            hasher.update(source.to_string_lossy().as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Determines if every WiX object (wixobj) file is newer than its WiX
+    /// Source (wxs) file and the files it includes with the `<?include?>`
+    /// preprocessor directive, and the compiler's defines have not changed.
+    ///
+    /// The files are always compiled if an included file cannot be found, see
+    /// the [`included_files`] function.
+    ///
+    /// [`included_files`]: #method.included_files
+    fn is_compiled(wxs_sources: &[PathBuf], wixobj_dst: &Path, defines_hash: &str) -> bool {
+        let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+        let previous_hash = fs::read_to_string(wixobj_dst.join(DEFINES_HASH_FILE_NAME)).ok();
+        if previous_hash.as_deref() != Some(defines_hash) {
+            trace!("The defines or sources for the compiler have changed");
+            return false;
+        }
+        wxs_sources
+            .iter()
+            .zip(Self::expected_wixobj_sources(wixobj_dst, wxs_sources))
+            .all(|(wxs, wixobj)| {
+                let wixobj_modified = match modified(&wixobj) {
+                    Some(m) => m,
+                    None => return false,
+                };
+                let included = match Self::included_files(wxs) {
+                    Some(i) => i,
+                    None => {
+                        trace!(
+                            "The files included by the '{}' WiX Source (wxs) file could not \
+                             be found",
+                            wxs.display()
+                        );
+                        return false;
+                    }
+                };
+                std::iter::once(wxs)
+                    .chain(included.iter())
+                    .all(|p| matches!(modified(p), Some(m) if wixobj_modified > m))
+            })
+    }
+
+    /// Gets the files included by a WiX Source (wxs) file with the
+    /// `<?include?>` preprocessor directive, including the files included by
+    /// those files.
+    ///
+    /// A relative path is resolved against the folder of the including file,
+    /// and the paths are canonicalized, so a file is only included once.
+    /// `None` is returned if an included file cannot be found, such as a path
+    /// with a preprocessor variable or a path found with the compiler's
+    /// include search paths, because its changes cannot be detected.
+    fn included_files(source: &Path) -> Option<Vec<PathBuf>> {
+        let source = fs::canonicalize(source).ok()?;
+        let mut included: Vec<PathBuf> = Vec::new();
+        let mut pending = vec![source.clone()];
+        while let Some(file) = pending.pop() {
+            // The files are often encoded with a Windows code page instead of
+            // UTF-8, but the directives are ASCII.
+            let content = String::from_utf8_lossy(&fs::read(&file).ok()?).into_owned();
+            let folder = file.parent().unwrap_or_else(|| Path::new(""));
+            for include in include_directives(&content) {
+                let path = fs::canonicalize(folder.join(include)).ok()?;
+                if path != source && !included.contains(&path) {
+                    included.push(path.clone());
+                    pending.push(path);
+                }
+            }
+        }
+        Some(included)
+    }
+
+    fn expected_wixobj_sources(wixobj_dst: &Path, wxs_sources: &[PathBuf]) -> Vec<PathBuf> {
+        wxs_sources
+            .iter()
+            .filter_map(|s| s.file_stem())
+            .map(|s| {
+                let mut p = wixobj_dst.join(s);
+                p.set_extension(WIX_OBJECT_FILE_EXTENSION);
+                p
+            })
+            .collect()
+    }
+
+    /// Gets the folder containing the package's manifest (Cargo.toml).
+    ///
+    /// The default `wix` folder and relative paths to included WiX Source
+    /// (wxs) files are resolved against this folder. Without an input, the
+    /// folder is the current working directory (CWD), i.e. an empty path.
+    fn manifest_dir(&self) -> Result<PathBuf> {
+        if let Some(manifest_path) = &self.input {
             trace!("Using the package's manifest (Cargo.toml) file path to obtain all WXS files");
-            manifest_path
-                .parent()
-                .ok_or_else(|| {
-                    Error::Generic(format!(
-                        "The '{}' path for the package's manifest file is invalid",
-                        manifest_path.display()
-                    ))
-                })
-                .map(|d| PathBuf::from(d).join(WIX))
+            manifest_path.parent().map(PathBuf::from).ok_or_else(|| {
+                Error::Generic(format!(
+                    "The '{}' path for the package's manifest file is invalid",
+                    manifest_path.display()
+                ))
+            })
         } else {
             trace!("Using the current working directory (CWD) to obtain all WXS files");
-            Ok(PathBuf::from(WIX))
-        }?;
+            Ok(PathBuf::new())
+        }
+    }
+
+    pub(crate) fn wxs_sources(&self, manifest: &Value) -> Result<Vec<PathBuf>> {
+        let manifest_dir = self.manifest_dir()?;
+        // Absolute paths are used as-is by `join`.
+        let resolve = |p: &PathBuf| manifest_dir.join(p);
+        let project_wix_dir = manifest_dir.join(WIX);
         let mut wix_sources = {
             if project_wix_dir.exists() {
-                std::fs::read_dir(project_wix_dir)?
+                let mut sources: Vec<PathBuf> = std::fs::read_dir(project_wix_dir)?
                     .filter(|r| r.is_ok())
                     .map(|r| r.unwrap().path())
                     .filter(|p| {
                         p.extension().and_then(|s| s.to_str()) == Some(WIX_SOURCE_FILE_EXTENSION)
                     })
-                    .collect()
+                    .collect();
+                if self.sort_sources {
+                    trace!("Sorting the WiX Source (wxs) files by path");
+                    sources.sort();
+                }
+                sources
             } else {
                 Vec::new()
             }
         };
         if let Some(paths) = self.includes.as_ref() {
-            for p in paths {
+            let paths: Vec<PathBuf> = paths.iter().map(resolve).collect();
+            for p in &paths {
                 if p.exists() {
                     if p.is_dir() {
                         return Err(Error::Generic(format!(
@@ -988,28 +2789,21 @@ impl Execution {
                         trace!("Using the '{}' WiX source file", p.display());
                     }
                 } else {
-                    return Err(Error::Generic(format!(
-                        "The '{0}' file does not exist. Consider using the 'cargo \
-                        wix print WXS > {0}' command to create it.",
-                        p.display()
-                    )));
+                    return Err(Error::MissingFile(p.clone()));
                 }
             }
-            wix_sources.extend(paths.clone());
-        } else if let Some(pkg_meta_wix_sources) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("include"))
-            .and_then(|i| i.as_array())
+            wix_sources.extend(paths);
+        } else if let Some(pkg_meta_wix_sources) = meta_array(manifest, "include")
             .map(|a| {
                 a.iter()
-                    .map(|s| s.as_str().map(PathBuf::from).unwrap())
-                    .collect::<Vec<PathBuf>>()
+                    .map(|s| {
+                        s.as_str()
+                            .map(|s| resolve(&PathBuf::from(s)))
+                            .ok_or(Error::InvalidManifestField("package.metadata.wix.include"))
+                    })
+                    .collect::<Result<Vec<PathBuf>>>()
             })
+            .transpose()?
         {
             for pkg_meta_wix_source in &pkg_meta_wix_sources {
                 if pkg_meta_wix_source.exists() {
@@ -1029,44 +2823,132 @@ impl Execution {
                         );
                     }
                 } else {
-                    return Err(Error::Generic(format!(
-                        "The '{0}' file does not exist. \
-                        Consider using the 'cargo wix print WXS > {0} command to create \
-                        it.",
-                        pkg_meta_wix_source.display()
-                    )));
+                    return Err(Error::MissingFile(pkg_meta_wix_source.clone()));
                 }
             }
             wix_sources.extend(pkg_meta_wix_sources);
         }
         if wix_sources.is_empty() {
-            Err(Error::Generic(String::from(
-                "There are no WXS files to create an installer",
-            )))
+            Err(Error::NoSources)
         } else {
             Ok(wix_sources)
         }
     }
 
-    fn version(&self, manifest: &Value) -> Result<Version> {
+    fn min_wix_version(&self) -> Result<Option<Version>> {
+        self.min_wix_version
+            .as_ref()
+            .map(|v| {
+                Self::lenient_version(v).ok_or_else(|| {
+                    Error::Generic(format!(
+                        "The '{}' minimum WiX Toolset version is not valid. Please use a \
+                         version with one to three numeric parts, i.e. '3.11'.",
+                        v
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Gets the version of the WiX Toolset from the banner of the compiler
+    /// (candle).
+    fn wix_version(&self) -> Result<Option<Version>> {
+        let compiler = self
+            .compiler()?
+            .arg("-?")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| {
+                if err.kind() == ErrorKind::NotFound {
+                    Error::WixNotFound {
+                        tool: self.compiler_name().to_owned().into(),
+                    }
+                } else {
+                    err.into()
+                }
+            })?;
+        let output = wait_with_output(compiler, WIX_COMPILER, self.timeout)?;
+        Ok(Self::parse_wix_version(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Parses the version from a banner of a WiX Toolset application, i.e.
+    /// `Windows Installer XML Toolset Compiler version 3.11.2.4516`.
+    fn parse_wix_version(banner: &str) -> Option<Version> {
+        banner
+            .split_whitespace()
+            .skip_while(|w| !w.eq_ignore_ascii_case("version"))
+            .nth(1)
+            .and_then(Self::lenient_version)
+    }
+
+    /// Parses a version with one to three numeric parts, where missing parts
+    /// are zero. Any parts after the third, i.e. the revision of a WiX Toolset
+    /// version, are ignored.
+    fn lenient_version(version: &str) -> Option<Version> {
+        let mut parts = version.trim().split('.').map(|p| p.parse::<u64>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        Some(Version::new(major, minor, patch))
+    }
+
+    fn check_wix_version(installed: Option<&Version>, minimum: &Version) -> Result<()> {
+        match installed {
+            Some(installed) if installed >= minimum => Ok(()),
+            Some(installed) => Err(Error::Generic(format!(
+                "The installed WiX Toolset version ({}) is older than the minimum version ({}). \
+                 Please install a newer version of the WiX Toolset.",
+                installed, minimum
+            ))),
+            None => Err(Error::Generic(format!(
+                "The version of the installed WiX Toolset could not be determined from the \
+                 output of the compiler ({}), so the minimum version ({}) cannot be verified.",
+                WIX_COMPILER, minimum
+            ))),
+        }
+    }
+
+    fn version_limits(version: &Version) -> Option<String> {
+        if version.major > 255 || version.minor > 255 || version.patch > 65535 {
+            Some(format!(
+                "The '{}' version exceeds the 255.255.65535 limits of the Windows Installer \
+                 for the major, minor, and build parts of the 'ProductVersion' property. The \
+                 version will be truncated or rejected, which can prevent upgrades from \
+                 working as expected. Please use a version within the limits with the \
+                 '--install-version' option or the 'version' field of the \
+                 '[package.metadata.wix]' section, or use the '--no-strict-version' flag to \
+                 only display a warning.",
+                version
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn version(&self, manifest: &Value) -> Result<Version> {
         if let Some(ref v) = self.version {
             Version::parse(v).map_err(Error::from)
-        } else if let Some(pkg_meta_wix_version) = manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| t.get("metadata"))
-            .and_then(|m| m.as_table())
-            .and_then(|t| t.get("wix"))
-            .and_then(|w| w.as_table())
-            .and_then(|t| t.get("version"))
-            .and_then(|v| v.as_str())
-        {
+        } else if let Some(pkg_meta_wix_version) = meta_str(manifest, "version") {
             Version::parse(pkg_meta_wix_version).map_err(Error::from)
         } else {
-            manifest
+            let pkg_version = manifest
                 .get("package")
                 .and_then(|p| p.as_table())
-                .and_then(|t| t.get("version"))
+                .and_then(|t| t.get("version"));
+            if matches!(pkg_version, Some(v) if is_inherited(v)) {
+                // The inherited version is replaced when the manifest is read,
+                // so the workspace or its version could not be found.
+                return Err(Error::Generic(String::from(
+                    "The version is inherited from the workspace, but the 'version' field \
+                     of the '[workspace.package]' section in the workspace's manifest \
+                     (Cargo.toml) could not be found. Please add the field to the \
+                     workspace's manifest or use the '--install-version' option.",
+                )));
+            }
+            pkg_version
                 .and_then(|v| v.as_str())
                 .ok_or(Error::Manifest("version"))
                 .and_then(|s| Version::parse(s).map_err(Error::from))
@@ -1274,6 +3156,540 @@ impl Default for InstallerKind {
     }
 }
 
+/// The digests for the checksum file written next to a created installer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChecksumKind {
+    /// The SHA-256 digest in a `.sha256` file.
+    Sha256,
+    /// The SHA-512 digest in a `.sha512` file.
+    Sha512,
+}
+
+impl ChecksumKind {
+    /// Gets the possible string representations of each variant.
+    pub fn possible_values() -> Vec<&'static str> {
+        vec!["sha256", "Sha256", "SHA256", "sha512", "Sha512", "SHA512"]
+    }
+
+    /// Gets the file extension of the checksum file.
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+        }
+    }
+
+    fn digest(&self, path: &Path) -> Result<String> {
+        match *self {
+            Self::Sha256 => file_digest::<Sha256>(path),
+            Self::Sha512 => file_digest::<Sha512>(path),
+        }
+    }
+}
+
+/// Computes the digest of a file as lowercase hexadecimal text.
+fn file_digest<D: Digest + Write>(path: &Path) -> Result<String> {
+    let mut digest = D::new();
+    io::copy(&mut fs::File::open(path)?, &mut digest)?;
+    Ok(digest
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+impl FromStr for ChecksumKind {
+    type Err = crate::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match &*value.to_lowercase() {
+            "sha256" => Ok(Self::Sha256),
+            "sha512" => Ok(Self::Sha512),
+            _ => Err(Self::Err::Generic(format!("Unknown '{}' checksum", value))),
+        }
+    }
+}
+
+/// The formats for the message printed after an installer is created.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum MessageFormat {
+    /// Nothing is printed, and only the logging statements are written.
+    #[default]
+    Human,
+    /// A single line with a JSON object summarizing the installer.
+    Json,
+}
+
+impl MessageFormat {
+    /// Gets the possible string representations of each variant.
+    pub fn possible_values() -> Vec<&'static str> {
+        vec!["human", "Human", "json", "Json", "JSON"]
+    }
+}
+
+impl FromStr for MessageFormat {
+    type Err = crate::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match &*value.to_lowercase() {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(Self::Err::Generic(format!(
+                "Unknown '{}' message format",
+                value
+            ))),
+        }
+    }
+}
+
+/// A summary of a created installer.
+///
+/// This is returned by the [`run_with_summary`] method and printed for the
+/// JSON message format.
+///
+/// [`run_with_summary`]: struct.Execution.html#method.run_with_summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    name: String,
+    version: String,
+    platform: String,
+    culture: String,
+    output: PathBuf,
+    size: u64,
+    elapsed_seconds: f64,
+}
+
+impl Summary {
+    fn new(
+        name: String,
+        version: String,
+        platform: String,
+        culture: String,
+        output: PathBuf,
+        elapsed: Duration,
+    ) -> Result<Self> {
+        let size = fs::metadata(&output)?.len();
+        Ok(Summary {
+            name,
+            version,
+            platform,
+            culture,
+            output,
+            size,
+            elapsed_seconds: elapsed.as_secs_f64(),
+        })
+    }
+
+    /// Gets the product name of the installer.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the version of the installer.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Gets the platform, or architecture, of the installer.
+    pub fn platform(&self) -> &str {
+        &self.platform
+    }
+
+    /// Gets the culture of the installer.
+    pub fn culture(&self) -> &str {
+        &self.culture
+    }
+
+    /// Gets the path to the created installer.
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    /// Gets the size of the created installer in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Gets the time taken to create the installer in seconds.
+    pub fn elapsed_seconds(&self) -> f64 {
+        self.elapsed_seconds
+    }
+
+    fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|err| {
+            Error::Generic(format!(
+                "The summary of the installer could not be written as JSON: {}",
+                err
+            ))
+        })
+    }
+}
+
+/// The resolved configuration for creating an installer.
+///
+/// This is returned by the [`resolved_config`] method.
+///
+/// [`resolved_config`]: struct.Execution.html#method.resolved_config
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResolvedConfig {
+    name: String,
+    version: String,
+    product_version: String,
+    platform: String,
+    culture: String,
+    locale: Option<PathBuf>,
+    output: PathBuf,
+    sources: Vec<PathBuf>,
+    defines: Vec<String>,
+    extensions: Vec<String>,
+}
+
+impl ResolvedConfig {
+    /// Gets the product name of the installer.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the version of the installer.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Gets the ProductVersion of the Windows Installer, which is the version
+    /// of the installer in the "Major.Minor.Patch" notation, i.e. without any
+    /// pre-release or build metadata.
+    ///
+    /// The compiler (candle) is passed the complete version with the
+    /// `Version` variable, see the [`defines`] method.
+    ///
+    /// [`defines`]: #method.defines
+    pub fn product_version(&self) -> &str {
+        &self.product_version
+    }
+
+    /// Gets the platform, or architecture, of the installer.
+    pub fn platform(&self) -> &str {
+        &self.platform
+    }
+
+    /// Gets the culture of the installer.
+    pub fn culture(&self) -> &str {
+        &self.culture
+    }
+
+    /// Gets the path to the WiX localization file (wxl), if any.
+    pub fn locale(&self) -> Option<&Path> {
+        self.locale.as_deref()
+    }
+
+    /// Gets the path to the installer.
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    /// Gets the WiX Source (wxs) files for the compiler (candle).
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+
+    /// Gets the preprocessor variables defined for the compiler (candle),
+    /// without the `-d` prefix, i.e. `Version=1.2.3`.
+    pub fn defines(&self) -> &[String] {
+        &self.defines
+    }
+
+    /// Gets the extensions for the linker (light), where a custom extension
+    /// is a path to a dynamic link library (dll).
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+}
+
+/// Gets the last lines of the text, without any trailing blank lines.
+fn last_lines(text: &str, count: usize) -> String {
+    let lines = text.trim_end().lines().collect::<Vec<&str>>();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+/// Creates a single line for a command that can be copied and pasted into a
+/// command prompt.
+///
+/// The program and any argument that contains whitespace or a double quote is
+/// enclosed in double quotes, and any double quotes within are escaped.
+fn command_line(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|a| {
+            let a = a.to_string_lossy();
+            if a.is_empty() || a.contains(|c: char| c.is_whitespace() || c == '"') {
+                format!("\"{}\"", a.replace('"', "\\\""))
+            } else {
+                a.into_owned()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Gets the `[package.metadata.wix]` section of the package's manifest
+/// (Cargo.toml).
+fn wix_metadata(manifest: &Value) -> Option<&Table> {
+    manifest
+        .get("package")
+        .and_then(|p| p.as_table())
+        .and_then(|t| t.get("metadata"))
+        .and_then(|m| m.as_table())
+        .and_then(|t| t.get("wix"))
+        .and_then(|w| w.as_table())
+}
+
+/// Finds the manifest (Cargo.toml) at the root of the workspace for a package.
+///
+/// The package's manifest is the workspace root if it has a `[workspace]`
+/// section. Otherwise, the `workspace` field of the `[package]` section is
+/// used if it exists, and the parent folders are searched for a manifest with
+/// a `[workspace]` section if it does not. `None` is returned if the package is
+/// not part of a workspace.
+pub(crate) fn workspace_manifest(manifest_path: &Path, manifest: &Value) -> Result<Option<Value>> {
+    if manifest.get("workspace").is_some() {
+        trace!("The package's manifest is the workspace root");
+        return Ok(Some(manifest.clone()));
+    }
+    let manifest_path = if manifest_path.is_absolute() {
+        manifest_path.to_owned()
+    } else {
+        env::current_dir()?.join(manifest_path)
+    };
+    let package_root = manifest_path.parent().unwrap_or_else(|| Path::new(""));
+    if let Some(workspace) = manifest
+        .get("package")
+        .and_then(|p| p.as_table())
+        .and_then(|t| t.get("workspace"))
+        .and_then(|w| w.as_str())
+    {
+        trace!("Using the 'workspace' field of the package's manifest for the workspace root");
+        let workspace_path = package_root.join(workspace).join(CARGO_MANIFEST_FILE);
+        return super::manifest(Some(&workspace_path)).map(Some);
+    }
+    for folder in package_root.ancestors().skip(1) {
+        let path = folder.join(CARGO_MANIFEST_FILE);
+        if path.is_file() {
+            let parent_manifest = super::manifest(Some(&path))?;
+            if parent_manifest.get("workspace").is_some() {
+                debug!("workspace_manifest_path = {:?}", path);
+                return Ok(Some(parent_manifest));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Adds the fields of the `[workspace.metadata.wix]` section of a workspace's
+/// manifest to the `[package.metadata.wix]` section of a package's manifest.
+///
+/// The package's fields take precedence, so a field from the workspace is only
+/// added if the package does not already have it.
+pub(crate) fn merge_workspace_metadata(manifest: &mut Value, workspace: &Value) {
+    let workspace_wix = match workspace
+        .get("workspace")
+        .and_then(|w| w.as_table())
+        .and_then(|t| t.get("metadata"))
+        .and_then(|m| m.as_table())
+        .and_then(|t| t.get("wix"))
+        .and_then(|w| w.as_table())
+    {
+        Some(w) => w,
+        None => return,
+    };
+    let package_wix = ["package", "metadata", "wix"]
+        .iter()
+        .try_fold(manifest, |value, key| {
+            value
+                .as_table_mut()
+                .map(|t| t.entry(*key).or_insert(Value::Table(Table::new())))
+        })
+        .and_then(|w| w.as_table_mut());
+    if let Some(package_wix) = package_wix {
+        for (key, value) in workspace_wix {
+            if !package_wix.contains_key(key) {
+                trace!("Using the '{}' field from the workspace's metadata", key);
+                package_wix.insert(key.to_owned(), value.clone());
+            }
+        }
+    }
+}
+
+/// Determines if any field of the `[package]` section of a package's manifest
+/// is inherited from the workspace, i.e. `version.workspace = true`.
+pub(crate) fn has_inherited_fields(manifest: &Value) -> bool {
+    matches!(
+        manifest.get("package").and_then(|p| p.as_table()),
+        Some(t) if t.values().any(is_inherited)
+    )
+}
+
+fn is_inherited(value: &Value) -> bool {
+    value
+        .get("workspace")
+        .and_then(|w| w.as_bool())
+        .unwrap_or(false)
+}
+
+/// Replaces the fields of the `[package]` section of a package's manifest that
+/// are inherited from the workspace, i.e. `authors.workspace = true`, with the
+/// values from the `[workspace.package]` section of a workspace's manifest.
+///
+/// A field that does not exist in the workspace is left as-is, so it is
+/// treated the same as a missing field.
+pub(crate) fn inherit_workspace_package(manifest: &mut Value, workspace: &Value) {
+    let workspace_package = match workspace
+        .get("workspace")
+        .and_then(|w| w.as_table())
+        .and_then(|t| t.get("package"))
+        .and_then(|p| p.as_table())
+    {
+        Some(p) => p,
+        None => return,
+    };
+    if let Some(package) = manifest.get_mut("package").and_then(|p| p.as_table_mut()) {
+        for (key, value) in package.iter_mut() {
+            if is_inherited(value) {
+                if let Some(inherited) = workspace_package.get(key) {
+                    trace!("Using the '{}' field from the workspace's package", key);
+                    *value = inherited.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Gets a value from the `[package.metadata.wix]` section.
+///
+/// A key with dots is a path to a value within a nested table, i.e. the
+/// `sign.pfx` key is the `pfx` field of the `[package.metadata.wix.sign]`
+/// section.
+pub(crate) fn meta_value<'a>(manifest: &'a Value, key: &str) -> Option<&'a Value> {
+    let mut keys = key.split('.');
+    let first = wix_metadata(manifest).and_then(|t| t.get(keys.next()?));
+    keys.fold(first, |v, k| v.and_then(|v| v.get(k)))
+}
+
+/// Gets a string value from the `[package.metadata.wix]` section.
+pub(crate) fn meta_str<'a>(manifest: &'a Value, key: &str) -> Option<&'a str> {
+    meta_value(manifest, key).and_then(|v| v.as_str())
+}
+
+/// Gets a path from the `[package.metadata.wix]` section with any references
+/// to environment variables expanded.
+///
+/// This is used for the fields that are paths, such as the `output`, `locale`,
+/// and `eula` fields, so a path can be relative to a location that differs
+/// between machines, i.e. `output = "$OUT/installers/"`. See the
+/// [`expand_env`] function for the supported references.
+///
+/// [`expand_env`]: fn.expand_env.html
+pub(crate) fn meta_path(manifest: &Value, key: &str) -> Result<Option<PathBuf>> {
+    meta_str(manifest, key)
+        .map(|p| expand_env(p).map(PathBuf::from))
+        .transpose()
+}
+
+/// Expands the references to environment variables in a value.
+///
+/// The `$VAR`, `${VAR}`, and `%VAR%` forms are replaced with the value of the
+/// `VAR` environment variable. Any other use of the `$` and `%` characters is
+/// left as-is, such as the `$` in a `\\server\c$\` administrative share. An
+/// error occurs if a referenced environment variable is not set.
+fn expand_env(value: &str) -> Result<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find(['$', '%']) {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some((name, length)) = env_reference(rest) {
+            let var = env::var(name).map_err(|_| {
+                Error::Generic(format!(
+                    "The '{}' environment variable referenced in the '{}' value is not set",
+                    name, value
+                ))
+            })?;
+            expanded.push_str(&var);
+            rest = &rest[length..];
+        } else {
+            // The `$` and `%` characters are a single byte in UTF-8.
+            expanded.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Gets the name of the environment variable and the length of the reference
+/// at the start of the text, if the text starts with a reference.
+fn env_reference(text: &str) -> Option<(&str, usize)> {
+    let is_name = |name: &str, extra: &[char]| {
+        matches!(name.chars().next(), Some(c) if !c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || extra.contains(&c))
+    };
+    if let Some(braced) = text.strip_prefix("${") {
+        let name = &braced[..braced.find('}')?];
+        Some((name, name.len() + 3)).filter(|(n, _)| is_name(n, &[]))
+    } else if let Some(unbraced) = text.strip_prefix('$') {
+        let end = unbraced
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(unbraced.len());
+        let name = &unbraced[..end];
+        Some((name, name.len() + 1)).filter(|(n, _)| is_name(n, &[]))
+    } else if let Some(percent) = text.strip_prefix('%') {
+        // Windows environment variables can contain parentheses, such as
+        // `ProgramFiles(x86)`.
+        let name = &percent[..percent.find('%')?];
+        Some((name, name.len() + 2)).filter(|(n, _)| is_name(n, &['(', ')']))
+    } else {
+        None
+    }
+}
+
+/// Replaces the `{name}`, `{version}`, `{arch}`, and `{platform}` tokens in an
+/// output path.
+///
+/// Any other text within braces is left as-is, since braces are valid in
+/// file and folder names.
+fn expand_output_tokens(output: &str, name: &str, version: &Version, platform: Platform) -> String {
+    output
+        .replace("{name}", name)
+        .replace("{version}", &version.to_string())
+        .replace("{arch}", platform.arch())
+        .replace("{platform}", &platform.to_string())
+}
+
+/// Gets the paths of the `<?include?>` preprocessor directives in the content
+/// of a WiX Source (wxs) or WiX Include (wxi) file.
+fn include_directives(content: &str) -> Vec<&str> {
+    const DIRECTIVE: &str = "<?include";
+    content
+        .match_indices(DIRECTIVE)
+        .filter_map(|(index, _)| {
+            let rest = &content[index + DIRECTIVE.len()..];
+            let path = rest[..rest.find("?>")?].trim().trim_matches('"');
+            Some(path).filter(|p| !p.is_empty())
+        })
+        .collect()
+}
+
+/// Gets a boolean value from the `[package.metadata.wix]` section.
+fn meta_bool(manifest: &Value, key: &str) -> Option<bool> {
+    meta_value(manifest, key).and_then(|v| v.as_bool())
+}
+
+/// Gets an array value from the `[package.metadata.wix]` section.
+pub(crate) fn meta_array<'a>(manifest: &'a Value, key: &str) -> Option<&'a Array> {
+    meta_value(manifest, key).and_then(|v| v.as_array())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1284,20 +3700,59 @@ mod tests {
         #[test]
         fn defaults_are_correct() {
             let actual = Builder::new();
+            assert!(!actual.all_features);
             assert!(actual.bin_path.is_none());
+            assert!(actual.candle_arch.is_none());
             assert!(actual.capture_output);
+            assert!(actual.checksum.is_none());
             assert!(actual.compiler_args.is_none());
+            assert!(actual.compiler_name.is_none());
             assert!(actual.culture.is_none());
             assert!(!actual.debug_build);
             assert!(!actual.debug_name);
+            assert!(!actual.dry_run);
+            assert!(actual.extensions.is_none());
+            assert!(actual.features.is_none());
+            assert!(!actual.force);
+            assert!(!actual.frozen);
+            assert!(!actual.hash_in_name);
             assert!(actual.includes.is_none());
             assert!(actual.input.is_none());
+            assert!(actual.jobs.is_none());
+            assert!(!actual.keep_pdb);
+            assert!(actual.libraries.is_none());
             assert!(actual.linker_args.is_none());
+            assert!(actual.linker_name.is_none());
             assert!(actual.locale.is_none());
+            assert!(!actual.locked);
+            assert!(actual.log_level.is_none());
+            assert!(!actual.merge_args);
+            assert!(actual.message_format.is_none());
+            assert!(actual.min_wix_version.is_none());
             assert!(actual.name.is_none());
+            assert!(actual.name_suffix.is_none());
             assert!(!actual.no_build);
+            assert!(!actual.no_default_features);
+            assert!(!actual.no_ui);
+            assert!(!actual.no_util_extension);
+            assert!(!actual.offline);
             assert!(actual.output.is_none());
+            assert!(actual.pdb_output.is_none());
+            assert!(actual.post_build.is_none());
+            assert!(actual.pre_build.is_none());
+            assert!(actual.profile_var.is_none());
+            assert!(!actual.quiet);
+            assert!(!actual.require_clean_git);
+            assert!(!actual.show_logo);
+            assert!(!actual.sign_binaries);
+            assert_eq!(actual.sign_retries, DEFAULT_SIGN_RETRIES);
+            assert!(actual.sort_sources);
+            assert!(actual.strict_version);
+            assert!(actual.suppress_warnings.is_empty());
+            assert!(actual.target_dir.is_none());
+            assert!(actual.timeout.is_none());
             assert!(actual.version.is_none());
+            assert!(!actual.warnings_as_errors);
         }
 
         #[test]
@@ -1309,15 +3764,46 @@ mod tests {
         }
 
         #[test]
-        fn capture_output_works() {
+        fn compiler_name_works() {
+            const EXPECTED: &str = "wix-candle";
             let mut actual = Builder::new();
-            actual.capture_output(false);
-            assert!(!actual.capture_output);
+            actual.compiler_name(Some(EXPECTED));
+            assert_eq!(actual.compiler_name, Some(EXPECTED));
         }
 
         #[test]
-        fn compiler_args_with_single_value_works() {
-            const EXPECTED: &str = "-nologo";
+        fn linker_name_works() {
+            const EXPECTED: &str = "wix-light";
+            let mut actual = Builder::new();
+            actual.linker_name(Some(EXPECTED));
+            assert_eq!(actual.linker_name, Some(EXPECTED));
+        }
+
+        #[test]
+        fn candle_arch_works() {
+            const EXPECTED: &str = "arm64";
+            let mut actual = Builder::new();
+            actual.candle_arch(Some(EXPECTED));
+            assert_eq!(actual.candle_arch, Some(EXPECTED));
+        }
+
+        #[test]
+        fn capture_output_works() {
+            let mut actual = Builder::new();
+            actual.capture_output(false);
+            assert!(!actual.capture_output);
+        }
+
+        #[test]
+        fn checksum_works() {
+            let mut actual = Builder::new();
+            actual.checksum(Some(ChecksumKind::Sha256));
+            assert_eq!(actual.checksum, Some(ChecksumKind::Sha256));
+        }
+
+        #[test]
+        fn compiler_args_with_single_value_works() {
+            const EXPECTED: &str = "-nologo";
             let mut actual = Builder::new();
             actual.compiler_args(Some(vec![EXPECTED]));
             assert_eq!(actual.compiler_args, Some(vec![EXPECTED]));
@@ -1353,6 +3839,93 @@ mod tests {
             assert!(actual.debug_name);
         }
 
+        #[test]
+        fn all_features_works() {
+            let mut actual = Builder::new();
+            actual.all_features(true);
+            assert!(actual.all_features);
+        }
+
+        #[test]
+        fn extensions_works() {
+            let mut actual = Builder::new();
+            actual.extensions(Some(vec!["WixFirewallExtension", "Custom.dll"]));
+            assert_eq!(
+                actual.extensions,
+                Some(vec!["WixFirewallExtension", "Custom.dll"])
+            );
+        }
+
+        #[test]
+        fn features_works() {
+            let mut actual = Builder::new();
+            actual.features(Some(vec!["gui", "cli"]));
+            assert_eq!(actual.features, Some(vec!["gui", "cli"]));
+        }
+
+        #[test]
+        fn frozen_works() {
+            let mut actual = Builder::new();
+            actual.frozen(true);
+            assert!(actual.frozen);
+        }
+
+        #[test]
+        fn hash_in_name_works() {
+            let mut actual = Builder::new();
+            actual.hash_in_name(true);
+            assert!(actual.hash_in_name);
+        }
+
+        #[test]
+        fn locked_works() {
+            let mut actual = Builder::new();
+            actual.locked(true);
+            assert!(actual.locked);
+        }
+
+        #[test]
+        fn offline_works() {
+            let mut actual = Builder::new();
+            actual.offline(true);
+            assert!(actual.offline);
+        }
+
+        #[test]
+        fn no_default_features_works() {
+            let mut actual = Builder::new();
+            actual.no_default_features(true);
+            assert!(actual.no_default_features);
+        }
+
+        #[test]
+        fn no_ui_works() {
+            let mut actual = Builder::new();
+            actual.no_ui(true);
+            assert!(actual.no_ui);
+        }
+
+        #[test]
+        fn no_util_extension_works() {
+            let mut actual = Builder::new();
+            actual.no_util_extension(true);
+            assert!(actual.no_util_extension);
+        }
+
+        #[test]
+        fn dry_run_works() {
+            let mut actual = Builder::new();
+            actual.dry_run(true);
+            assert!(actual.dry_run);
+        }
+
+        #[test]
+        fn force_works() {
+            let mut actual = Builder::new();
+            actual.force(true);
+            assert!(actual.force);
+        }
+
         #[test]
         fn includes_works() {
             const EXPECTED: &str = "C:\\tmp\\hello_world\\wix\\main.wxs";
@@ -1361,6 +3934,14 @@ mod tests {
             assert_eq!(actual.includes, Some(vec![EXPECTED]));
         }
 
+        #[test]
+        fn libraries_works() {
+            const EXPECTED: &str = "C:\\tmp\\shared\\components.wixlib";
+            let mut actual = Builder::new();
+            actual.libraries(Some(vec![EXPECTED]));
+            assert_eq!(actual.libraries, Some(vec![EXPECTED]));
+        }
+
         #[test]
         fn input_works() {
             const EXPECTED: &str = "C:\\tmp\\hello_world\\Cargo.toml";
@@ -1369,6 +3950,20 @@ mod tests {
             assert_eq!(actual.input, Some(EXPECTED));
         }
 
+        #[test]
+        fn jobs_works() {
+            let mut actual = Builder::new();
+            actual.jobs(Some(4));
+            assert_eq!(actual.jobs, Some(4));
+        }
+
+        #[test]
+        fn keep_pdb_works() {
+            let mut actual = Builder::new();
+            actual.keep_pdb(true);
+            assert!(actual.keep_pdb);
+        }
+
         #[test]
         fn linker_args_with_single_value_works() {
             const EXPECTED: &str = "-nologo";
@@ -1393,6 +3988,35 @@ mod tests {
             assert_eq!(actual.locale, Some(EXPECTED));
         }
 
+        #[test]
+        fn merge_args_works() {
+            let mut actual = Builder::new();
+            actual.merge_args(true);
+            assert!(actual.merge_args);
+        }
+
+        #[test]
+        fn message_format_works() {
+            let mut actual = Builder::new();
+            actual.message_format(Some(MessageFormat::Json));
+            assert_eq!(actual.message_format, Some(MessageFormat::Json));
+        }
+
+        #[test]
+        fn min_wix_version_works() {
+            const EXPECTED: &str = "3.11";
+            let mut actual = Builder::new();
+            actual.min_wix_version(Some(EXPECTED));
+            assert_eq!(actual.min_wix_version, Some(EXPECTED));
+        }
+
+        #[test]
+        fn log_level_works() {
+            let mut actual = Builder::new();
+            actual.log_level(LevelFilter::Debug);
+            assert_eq!(actual.log_level, Some(LevelFilter::Debug));
+        }
+
         #[test]
         fn name_works() {
             const EXPECTED: &str = "Name";
@@ -1401,6 +4025,14 @@ mod tests {
             assert_eq!(actual.name, Some(EXPECTED));
         }
 
+        #[test]
+        fn name_suffix_works() {
+            const EXPECTED: &str = "-nightly";
+            let mut actual = Builder::new();
+            actual.name_suffix(Some(EXPECTED));
+            assert_eq!(actual.name_suffix, Some(EXPECTED));
+        }
+
         #[test]
         fn no_build_works() {
             let mut actual = Builder::new();
@@ -1416,6 +4048,112 @@ mod tests {
             assert_eq!(actual.output, Some(EXPECTED));
         }
 
+        #[test]
+        fn pdb_output_works() {
+            const EXPECTED: &str = "C:\\tmp\\symbols\\Example.wixpdb";
+            let mut actual = Builder::new();
+            actual.pdb_output(Some(EXPECTED));
+            assert_eq!(actual.pdb_output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn post_build_works() {
+            const EXPECTED: &str = "copy %CARGO_WIX_MSI% dist";
+            let mut actual = Builder::new();
+            actual.post_build(Some(EXPECTED));
+            assert_eq!(actual.post_build, Some(EXPECTED));
+        }
+
+        #[test]
+        fn pre_build_works() {
+            const EXPECTED: &str = "python generate.py";
+            let mut actual = Builder::new();
+            actual.pre_build(Some(EXPECTED));
+            assert_eq!(actual.pre_build, Some(EXPECTED));
+        }
+
+        #[test]
+        fn profile_var_works() {
+            const EXPECTED: &str = "BuildConfig";
+            let mut actual = Builder::new();
+            actual.profile_var(Some(EXPECTED));
+            assert_eq!(actual.profile_var, Some(EXPECTED));
+        }
+
+        #[test]
+        fn sign_binaries_works() {
+            let mut actual = Builder::new();
+            actual.sign_binaries(true);
+            assert!(actual.sign_binaries);
+        }
+
+        #[test]
+        fn sign_retries_works() {
+            const EXPECTED: u32 = 5;
+            let mut actual = Builder::new();
+            actual.sign_retries(EXPECTED);
+            assert_eq!(actual.sign_retries, EXPECTED);
+        }
+
+        #[test]
+        fn quiet_works() {
+            let mut actual = Builder::new();
+            actual.quiet(true);
+            assert!(actual.quiet);
+        }
+
+        #[test]
+        fn require_clean_git_works() {
+            let mut actual = Builder::new();
+            actual.require_clean_git(true);
+            assert!(actual.require_clean_git);
+        }
+
+        #[test]
+        fn show_logo_works() {
+            let mut actual = Builder::new();
+            actual.show_logo(true);
+            assert!(actual.show_logo);
+        }
+
+        #[test]
+        fn sort_sources_works() {
+            let mut actual = Builder::new();
+            actual.sort_sources(false);
+            assert!(!actual.sort_sources);
+        }
+
+        #[test]
+        fn strict_version_works() {
+            let mut actual = Builder::new();
+            actual.strict_version(false);
+            assert!(!actual.strict_version);
+        }
+
+        #[test]
+        fn suppress_warnings_works() {
+            let expected = vec![1076, 1105];
+            let mut actual = Builder::new();
+            actual.suppress_warnings(expected.clone());
+            assert_eq!(actual.suppress_warnings, expected);
+        }
+
+        #[test]
+        fn target_dir_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\target";
+            let mut actual = Builder::new();
+            actual.target_dir(Some(EXPECTED));
+            assert_eq!(actual.target_dir, Some(EXPECTED));
+        }
+
+        #[test]
+        fn timeout_works() {
+            const EXPECTED: Duration = Duration::from_secs(600);
+            let mut actual = Builder::new();
+            actual.timeout(Some(EXPECTED));
+            assert_eq!(actual.timeout, Some(EXPECTED));
+        }
+
         #[test]
         fn version_works() {
             const EXPECTED: &str = "1.2.3";
@@ -1424,85 +4162,266 @@ mod tests {
             assert_eq!(actual.version, Some(EXPECTED));
         }
 
+        #[test]
+        fn warnings_as_errors_works() {
+            let mut actual = Builder::new();
+            actual.warnings_as_errors(true);
+            assert!(actual.warnings_as_errors);
+        }
+
         #[test]
         fn build_with_defaults_works() {
             let mut b = Builder::new();
             let default_execution = b.build();
+            assert!(!default_execution.all_features);
             assert!(default_execution.bin_path.is_none());
+            assert!(default_execution.candle_arch.is_none());
             assert!(default_execution.capture_output);
+            assert!(default_execution.checksum.is_none());
             assert!(default_execution.compiler_args.is_none());
+            assert!(default_execution.compiler_name.is_none());
             assert!(default_execution.culture.is_none());
             assert!(!default_execution.debug_build);
             assert!(!default_execution.debug_name);
+            assert!(!default_execution.dry_run);
+            assert!(default_execution.extensions.is_none());
+            assert!(default_execution.features.is_none());
+            assert!(!default_execution.force);
+            assert!(!default_execution.frozen);
+            assert!(!default_execution.hash_in_name);
             assert!(default_execution.includes.is_none());
             assert!(default_execution.input.is_none());
+            assert!(default_execution.jobs.is_none());
+            assert!(!default_execution.keep_pdb);
+            assert!(default_execution.libraries.is_none());
             assert!(default_execution.linker_args.is_none());
+            assert!(default_execution.linker_name.is_none());
             assert!(default_execution.locale.is_none());
+            assert!(!default_execution.locked);
+            assert!(default_execution.log_level.is_none());
+            assert!(!default_execution.merge_args);
+            assert!(default_execution.message_format.is_none());
+            assert!(default_execution.min_wix_version.is_none());
             assert!(default_execution.name.is_none());
+            assert!(default_execution.name_suffix.is_none());
             assert!(!default_execution.no_build);
+            assert!(!default_execution.no_default_features);
+            assert!(!default_execution.no_ui);
+            assert!(!default_execution.no_util_extension);
+            assert!(!default_execution.offline);
             assert!(default_execution.output.is_none());
+            assert!(default_execution.pdb_output.is_none());
+            assert!(default_execution.post_build.is_none());
+            assert!(default_execution.pre_build.is_none());
+            assert!(default_execution.profile_var.is_none());
+            assert!(!default_execution.quiet);
+            assert!(!default_execution.require_clean_git);
+            assert!(!default_execution.show_logo);
+            assert!(!default_execution.sign_binaries);
+            assert_eq!(default_execution.sign_retries, DEFAULT_SIGN_RETRIES);
+            assert!(default_execution.sort_sources);
+            assert!(default_execution.strict_version);
+            assert!(default_execution.suppress_warnings.is_empty());
+            assert!(default_execution.target_dir.is_none());
+            assert!(default_execution.timeout.is_none());
             assert!(default_execution.version.is_none());
+            assert!(!default_execution.warnings_as_errors);
         }
 
         #[test]
         fn build_with_all_works() {
             const EXPECTED_BIN_PATH: &str = "C:\\Wix Toolset\\bin";
+            const EXPECTED_CANDLE_ARCH: &str = "arm64";
             const EXPECTED_CULTURE: &str = "FrFr";
             const EXPECTED_COMPILER_ARGS: &str = "-nologo";
+            const EXPECTED_COMPILER_NAME: &str = "wix-candle";
+            const EXPECTED_EXTENSIONS: &str = "WixFirewallExtension";
+            const EXPECTED_FEATURES: &str = "gui";
             const EXPECTED_INCLUDES: &str = "C:\\tmp\\hello_world\\wix\\main.wxs";
             const EXPECTED_INPUT: &str = "C:\\tmp\\hello_world\\Cargo.toml";
+            const EXPECTED_LIBRARIES: &str = "C:\\tmp\\shared\\components.wixlib";
             const EXPECTED_LINKER_ARGS: &str = "-nologo";
+            const EXPECTED_LINKER_NAME: &str = "wix-light";
             const EXPECTED_LOCALE: &str = "C:\\tmp\\hello_world\\wix\\main.wxl";
+            const EXPECTED_MIN_WIX_VERSION: &str = "3.11";
             const EXPECTED_NAME: &str = "Name";
+            const EXPECTED_NAME_SUFFIX: &str = "-nightly";
             const EXPECTED_OUTPUT: &str = "C:\\tmp\\hello_world\\output";
+            const EXPECTED_PDB_OUTPUT: &str = "C:\\tmp\\symbols\\Example.wixpdb";
+            const EXPECTED_POST_BUILD: &str = "copy %CARGO_WIX_MSI% dist";
+            const EXPECTED_PRE_BUILD: &str = "python generate.py";
+            const EXPECTED_PROFILE_VAR: &str = "BuildConfig";
+            const EXPECTED_SIGN_RETRIES: u32 = 5;
+            const EXPECTED_SUPPRESS_WARNINGS: u32 = 1076;
+            const EXPECTED_TARGET_DIR: &str = "C:\\tmp\\hello_world\\target";
+            const EXPECTED_TIMEOUT: Duration = Duration::from_secs(600);
             const EXPECTED_VERSION: &str = "1.2.3";
             let mut b = Builder::new();
+            b.all_features(true);
             b.bin_path(Some(EXPECTED_BIN_PATH));
+            b.candle_arch(Some(EXPECTED_CANDLE_ARCH));
             b.capture_output(false);
+            b.checksum(Some(ChecksumKind::Sha512));
             b.culture(Some(EXPECTED_CULTURE));
             b.compiler_args(Some(vec![EXPECTED_COMPILER_ARGS]));
+            b.compiler_name(Some(EXPECTED_COMPILER_NAME));
             b.debug_build(true);
             b.debug_name(true);
+            b.dry_run(true);
+            b.extensions(Some(vec![EXPECTED_EXTENSIONS]));
+            b.features(Some(vec![EXPECTED_FEATURES]));
+            b.force(true);
+            b.frozen(true);
+            b.hash_in_name(true);
             b.includes(Some(vec![EXPECTED_INCLUDES]));
             b.input(Some(EXPECTED_INPUT));
+            b.jobs(Some(4));
+            b.keep_pdb(true);
+            b.libraries(Some(vec![EXPECTED_LIBRARIES]));
             b.linker_args(Some(vec![EXPECTED_LINKER_ARGS]));
+            b.linker_name(Some(EXPECTED_LINKER_NAME));
             b.locale(Some(EXPECTED_LOCALE));
+            b.locked(true);
+            b.log_level(LevelFilter::Trace);
+            b.merge_args(true);
+            b.message_format(Some(MessageFormat::Json));
+            b.min_wix_version(Some(EXPECTED_MIN_WIX_VERSION));
             b.name(Some(EXPECTED_NAME));
+            b.name_suffix(Some(EXPECTED_NAME_SUFFIX));
             b.no_build(true);
+            b.no_default_features(true);
+            b.no_ui(true);
+            b.no_util_extension(true);
+            b.offline(true);
             b.output(Some(EXPECTED_OUTPUT));
+            b.pdb_output(Some(EXPECTED_PDB_OUTPUT));
+            b.post_build(Some(EXPECTED_POST_BUILD));
+            b.pre_build(Some(EXPECTED_PRE_BUILD));
+            b.profile_var(Some(EXPECTED_PROFILE_VAR));
+            b.require_clean_git(true);
+            b.show_logo(true);
+            b.sign_binaries(true);
+            b.sign_retries(EXPECTED_SIGN_RETRIES);
+            b.sort_sources(false);
+            b.strict_version(true);
+            b.suppress_warnings(vec![EXPECTED_SUPPRESS_WARNINGS]);
+            b.target_dir(Some(EXPECTED_TARGET_DIR));
+            b.timeout(Some(EXPECTED_TIMEOUT));
             b.version(Some(EXPECTED_VERSION));
+            b.warnings_as_errors(true);
             let execution = b.build();
+            assert!(execution.all_features);
             assert_eq!(
                 execution.bin_path,
                 Some(EXPECTED_BIN_PATH).map(PathBuf::from)
             );
+            assert_eq!(
+                execution.candle_arch,
+                Some(EXPECTED_CANDLE_ARCH).map(String::from)
+            );
             assert!(!execution.capture_output);
+            assert_eq!(execution.checksum, Some(ChecksumKind::Sha512));
             assert_eq!(
                 execution.compiler_args,
                 Some(vec![String::from(EXPECTED_COMPILER_ARGS)])
             );
+            assert_eq!(
+                execution.compiler_name,
+                Some(EXPECTED_COMPILER_NAME).map(String::from)
+            );
             assert_eq!(execution.culture, Some(EXPECTED_CULTURE).map(String::from));
             assert!(execution.debug_build);
             assert!(execution.debug_name);
+            assert!(execution.dry_run);
+            assert_eq!(
+                execution.extensions,
+                Some(vec![String::from(EXPECTED_EXTENSIONS)])
+            );
+            assert_eq!(
+                execution.features,
+                Some(vec![String::from(EXPECTED_FEATURES)])
+            );
+            assert!(execution.force);
+            assert!(execution.frozen);
+            assert!(execution.hash_in_name);
             assert_eq!(
                 execution.includes,
                 Some(vec![PathBuf::from(EXPECTED_INCLUDES)])
             );
             assert_eq!(execution.input, Some(PathBuf::from(EXPECTED_INPUT)));
+            assert_eq!(execution.jobs, Some(4));
+            assert!(execution.keep_pdb);
+            assert_eq!(
+                execution.libraries,
+                Some(vec![PathBuf::from(EXPECTED_LIBRARIES)])
+            );
             assert_eq!(
                 execution.linker_args,
                 Some(vec![String::from(EXPECTED_LINKER_ARGS)])
             );
+            assert_eq!(
+                execution.linker_name,
+                Some(EXPECTED_LINKER_NAME).map(String::from)
+            );
             assert_eq!(execution.locale, Some(EXPECTED_LOCALE).map(PathBuf::from));
+            assert!(execution.locked);
+            assert_eq!(execution.log_level, Some(LevelFilter::Trace));
+            assert!(execution.merge_args);
+            assert_eq!(execution.message_format, Some(MessageFormat::Json));
+            assert_eq!(
+                execution.min_wix_version,
+                Some(EXPECTED_MIN_WIX_VERSION).map(String::from)
+            );
             assert_eq!(execution.name, Some(EXPECTED_NAME).map(String::from));
+            assert_eq!(
+                execution.name_suffix,
+                Some(EXPECTED_NAME_SUFFIX).map(String::from)
+            );
             assert!(execution.no_build);
+            assert!(execution.no_default_features);
+            assert!(execution.no_ui);
+            assert!(execution.no_util_extension);
+            assert!(execution.offline);
             assert_eq!(execution.output, Some(EXPECTED_OUTPUT).map(String::from));
+            assert_eq!(
+                execution.pdb_output,
+                Some(PathBuf::from(EXPECTED_PDB_OUTPUT))
+            );
+            assert_eq!(
+                execution.post_build,
+                Some(EXPECTED_POST_BUILD).map(String::from)
+            );
+            assert_eq!(
+                execution.pre_build,
+                Some(EXPECTED_PRE_BUILD).map(String::from)
+            );
+            assert_eq!(
+                execution.profile_var,
+                Some(EXPECTED_PROFILE_VAR).map(String::from)
+            );
+            assert!(execution.require_clean_git);
+            assert!(execution.show_logo);
+            assert!(execution.sign_binaries);
+            assert_eq!(execution.sign_retries, EXPECTED_SIGN_RETRIES);
+            assert!(!execution.sort_sources);
+            assert!(execution.strict_version);
+            assert_eq!(
+                execution.suppress_warnings,
+                vec![EXPECTED_SUPPRESS_WARNINGS]
+            );
+            assert_eq!(
+                execution.target_dir,
+                Some(EXPECTED_TARGET_DIR).map(PathBuf::from)
+            );
+            assert_eq!(execution.timeout, Some(EXPECTED_TIMEOUT));
             assert_eq!(execution.version, Some(EXPECTED_VERSION).map(String::from));
+            assert!(execution.warnings_as_errors);
         }
     }
 
     mod execution {
         use super::*;
+        use std::ffi::OsStr;
 
         #[test]
         fn debug_build_metadata_works() {
@@ -1610,11 +4529,170 @@ mod tests {
                     false,
                     &InstallerKind::default(),
                     &PKG_META_WIX.parse::<Value>().unwrap(),
+                    Path::new(TARGET_FOLDER_NAME),
                 )
                 .unwrap();
             assert_eq!(output, PathBuf::from("target/wix/test.msi"));
         }
 
+        #[test]
+        fn input_with_directory_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join(CARGO_MANIFEST_FILE);
+            fs::write(&manifest, "[package]\nname = \"Example\"\n").unwrap();
+            let target_dir = temp_dir.path().join("custom-target");
+            let execution = Builder::new()
+                .input(temp_dir.path().to_str())
+                .target_dir(target_dir.to_str())
+                .build();
+            assert_eq!(execution.input, Some(manifest.clone()));
+            assert_eq!(
+                crate::cargo_toml_file(execution.input.as_ref()).unwrap(),
+                manifest
+            );
+            assert_eq!(execution.target_directory().unwrap(), target_dir);
+            let execution = Builder::new().input(temp_dir.path().to_str()).build();
+            assert_eq!(
+                execution.target_directory_with(None).unwrap(),
+                temp_dir.path().join(TARGET_FOLDER_NAME)
+            );
+        }
+
+        #[test]
+        fn input_with_directory_without_manifest_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let execution = Builder::new().input(temp_dir.path().to_str()).build();
+            match crate::cargo_toml_file(execution.input.as_ref()) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("does not contain")),
+                e => panic!("Unexpected error: {:?}", e),
+            }
+        }
+
+        #[test]
+        fn input_with_alternate_manifest_name_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Alt.toml");
+            fs::write(
+                &manifest,
+                "[package]\nname = \"Example\"\nversion = \"0.1.0\"\n",
+            )
+            .unwrap();
+            let wxs_source = temp_dir.path().join(WIX).join("main.wxs");
+            fs::create_dir(temp_dir.path().join(WIX)).unwrap();
+            fs::write(&wxs_source, "").unwrap();
+            let target_dir = temp_dir.path().join(TARGET_FOLDER_NAME);
+            let execution = Builder::new()
+                .input(manifest.to_str())
+                .target_dir(target_dir.to_str())
+                .build();
+            assert_eq!(execution.input, Some(manifest.clone()));
+            assert_eq!(
+                crate::cargo_toml_file(execution.input.as_ref()).unwrap(),
+                manifest
+            );
+            let parsed = crate::manifest(execution.input.as_ref()).unwrap();
+            assert_eq!(execution.name(&parsed).unwrap(), "Example");
+            assert_eq!(execution.wxs_sources(&parsed).unwrap(), vec![wxs_source]);
+            let target_directory = execution.target_directory().unwrap();
+            assert_eq!(target_directory, target_dir);
+            assert_eq!(
+                execution
+                    .installer_destination(
+                        "Example",
+                        &"0.1.0".parse::<Version>().unwrap(),
+                        Platform::X64,
+                        false,
+                        &InstallerKind::default(),
+                        &parsed,
+                        &target_directory,
+                    )
+                    .unwrap(),
+                target_directory.join(WIX).join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn cargo_toml_file_with_directory_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join(CARGO_MANIFEST_FILE);
+            fs::write(&manifest, "[package]\nname = \"Example\"\n").unwrap();
+            assert_eq!(
+                crate::cargo_toml_file(Some(&temp_dir.path().to_path_buf())).unwrap(),
+                manifest
+            );
+        }
+
+        #[test]
+        fn cargo_toml_file_with_directory_without_manifest_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let execution = Builder::new().input(temp_dir.path().to_str()).build();
+            assert_eq!(execution.input, Some(temp_dir.path().to_path_buf()));
+            match crate::cargo_toml_file(execution.input.as_ref()) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("does not contain")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn output_metadata_with_environment_variable_works() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                output = "$CARGO_WIX_TEST_OUTPUT_DIR/installers/"
+            "#;
+            env::set_var("CARGO_WIX_TEST_OUTPUT_DIR", "out");
+            let output = Execution::default().installer_destination(
+                "Example",
+                &"0.1.0".parse::<Version>().unwrap(),
+                Platform::X64,
+                false,
+                &InstallerKind::default(),
+                &PKG_META_WIX.parse::<Value>().unwrap(),
+                Path::new(TARGET_FOLDER_NAME),
+            );
+            env::remove_var("CARGO_WIX_TEST_OUTPUT_DIR");
+            assert_eq!(
+                output.unwrap(),
+                Path::new("out")
+                    .join("installers")
+                    .join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn locale_metadata_with_environment_variable_works() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                locale = "${CARGO_WIX_TEST_LOCALE_DIR}/French.wxl"
+            "#;
+            env::set_var("CARGO_WIX_TEST_LOCALE_DIR", "wix");
+            let locale = Execution::default().locale(&PKG_META_WIX.parse::<Value>().unwrap());
+            env::remove_var("CARGO_WIX_TEST_LOCALE_DIR");
+            assert_eq!(locale.unwrap(), Some(PathBuf::from("wix/French.wxl")));
+        }
+
+        #[test]
+        fn output_metadata_with_unset_environment_variable_fails() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                output = "%CARGO_WIX_TEST_UNSET_OUTPUT_DIR%\\installers\\"
+            "#;
+            let result = Execution::default().installer_destination(
+                "Example",
+                &"0.1.0".parse::<Version>().unwrap(),
+                Platform::X64,
+                false,
+                &InstallerKind::default(),
+                &PKG_META_WIX.parse::<Value>().unwrap(),
+                Path::new(TARGET_FOLDER_NAME),
+            );
+            match result {
+                Err(Error::Generic(msg)) => {
+                    assert!(msg.contains("CARGO_WIX_TEST_UNSET_OUTPUT_DIR"))
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
         #[test]
         fn include_metadata_works() {
             const PKG_META_WIX: &str = r#"
@@ -1629,33 +4707,706 @@ mod tests {
         }
 
         #[test]
-        fn compiler_args_metadata_works() {
+        fn include_metadata_with_missing_file_fails() {
             const PKG_META_WIX: &str = r#"
                 [package.metadata.wix]
-                compiler-args = ["-nologo", "-ws"]
+                include = ["Missing.wxs"]
             "#;
             let execution = Execution::default();
-            let args = execution.compiler_args(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert_eq!(
-                args,
-                Some(vec![String::from("-nologo"), String::from("-ws")])
-            );
+            let result = execution.wxs_sources(&PKG_META_WIX.parse::<Value>().unwrap());
+            match result {
+                Err(Error::MissingFile(path)) => assert_eq!(path, PathBuf::from("Missing.wxs")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
         }
 
         #[test]
-        fn linker_args_metadata_works() {
+        fn include_metadata_with_invalid_value_fails() {
             const PKG_META_WIX: &str = r#"
                 [package.metadata.wix]
-                linker-args = ["-nologo", "-ws"]
+                include = [1]
             "#;
             let execution = Execution::default();
-            let args = execution.linker_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            let result = execution.wxs_sources(&PKG_META_WIX.parse::<Value>().unwrap());
+            match result {
+                Err(Error::InvalidManifestField(field)) => {
+                    assert_eq!(field, "package.metadata.wix.include")
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn wxs_sources_are_sorted() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join(CARGO_MANIFEST_FILE);
+            let wix = temp_dir.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            for name in &[
+                "main.wxs",
+                "bundle.wxs",
+                "zebra.wxs",
+                "alpha.wxs",
+                "main.wxl",
+            ] {
+                fs::write(wix.join(name), "").unwrap();
+            }
+            let execution = Builder::default().input(manifest.to_str()).build();
+            let manifest = EMPTY_PKG_META_WIX.parse::<Value>().unwrap();
+            let first = execution.wxs_sources(&manifest).unwrap();
+            let second = execution.wxs_sources(&manifest).unwrap();
+            assert_eq!(first, second);
+            assert_eq!(
+                first,
+                vec![
+                    wix.join("alpha.wxs"),
+                    wix.join("bundle.wxs"),
+                    wix.join("main.wxs"),
+                    wix.join("zebra.wxs"),
+                ]
+            );
+        }
+
+        /// Creates a relative path from the current working directory (CWD) to
+        /// the path, i.e. `../../tmp/other/Cargo.toml`.
+        fn relative_to_cwd(path: &Path) -> PathBuf {
+            let normal = |c: &std::path::Component| matches!(c, std::path::Component::Normal(_));
+            let mut relative: PathBuf = env::current_dir()
+                .unwrap()
+                .components()
+                .filter(normal)
+                .map(|_| std::path::Component::ParentDir)
+                .collect();
+            relative.extend(path.components().filter(normal));
+            relative
+        }
+
+        #[test]
+        fn wxs_sources_with_relative_includes_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let other = temp_dir.path().join("other");
+            fs::create_dir_all(other.join("installer")).unwrap();
+            fs::write(other.join("installer").join("extra.wxs"), "").unwrap();
+            let absolute = temp_dir.path().join("absolute.wxs");
+            fs::write(&absolute, "").unwrap();
+            let manifest = relative_to_cwd(&other.join(CARGO_MANIFEST_FILE));
+            assert!(manifest.starts_with(".."));
+            let execution = Builder::default()
+                .input(manifest.to_str())
+                .includes(Some(vec![
+                    "installer/extra.wxs",
+                    absolute.to_str().unwrap(),
+                ]))
+                .build();
+            let sources = execution
+                .wxs_sources(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                sources,
+                vec![
+                    manifest.parent().unwrap().join("installer/extra.wxs"),
+                    absolute
+                ]
+            );
+            assert!(sources[0].exists());
+        }
+
+        #[test]
+        fn wxs_sources_with_relative_include_metadata_works() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                include = ["installer/extra.wxs"]
+            "#;
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let other = temp_dir.path().join("other");
+            fs::create_dir_all(other.join("installer")).unwrap();
+            fs::write(other.join("installer").join("extra.wxs"), "").unwrap();
+            let manifest = relative_to_cwd(&other.join(CARGO_MANIFEST_FILE));
+            let execution = Builder::default().input(manifest.to_str()).build();
+            let sources = execution
+                .wxs_sources(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                sources,
+                vec![manifest.parent().unwrap().join("installer/extra.wxs")]
+            );
+        }
+
+        #[test]
+        fn wxs_sources_with_no_sources_fails() {
+            let execution = Execution::default();
+            let result = execution.wxs_sources(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            match result {
+                Err(Error::NoSources) => {}
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn builder_works() {
+            let execution = Execution::default();
+            let builder = execution.builder(
+                &EMPTY_PKG_META_WIX.parse::<Value>().unwrap(),
+                Path::new("Cargo.toml"),
+                false,
+            );
+            assert_eq!(
+                builder.get_args().collect::<Vec<&OsStr>>(),
+                vec!["build", "--release", "--manifest-path", "Cargo.toml"]
+            );
+        }
+
+        #[test]
+        fn builder_with_features_works() {
+            let execution = Builder::new()
+                .features(Some(vec!["gui", "cli"]))
+                .all_features(true)
+                .no_default_features(true)
+                .build();
+            let builder = execution.builder(
+                &EMPTY_PKG_META_WIX.parse::<Value>().unwrap(),
+                Path::new("Cargo.toml"),
+                true,
+            );
+            assert_eq!(
+                builder.get_args().collect::<Vec<&OsStr>>(),
+                vec![
+                    "build",
+                    "--manifest-path",
+                    "Cargo.toml",
+                    "--features",
+                    "gui,cli",
+                    "--all-features",
+                    "--no-default-features"
+                ]
+            );
+        }
+
+        #[test]
+        fn binary_signers_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                description = "An example"
+
+                [[bin]]
+                name = "one"
+
+                [[bin]]
+                name = "two"
+            "#;
+            let execution = Builder::new().sign_binaries(true).build();
+            let signer = execution.signer("Product");
+            let manifest = MANIFEST.parse::<Value>().unwrap();
+            let target_directory = Path::new("target");
+            let signers = execution
+                .binary_signers(&signer, &manifest, target_directory, false)
+                .unwrap();
+            assert_eq!(signers.len(), 2);
+            for ((binary, command), name) in signers.iter().zip(&["one", "two"]) {
+                let expected = target_directory
+                    .join("release")
+                    .join(name)
+                    .with_extension(EXE_FILE_EXTENSION);
+                assert_eq!(binary, &expected);
+                assert_eq!(
+                    command.get_args().collect::<Vec<&OsStr>>(),
+                    vec![
+                        OsStr::new("sign"),
+                        OsStr::new("/a"),
+                        OsStr::new("/d"),
+                        OsStr::new("Product - An example"),
+                        expected.as_os_str()
+                    ]
+                );
+            }
+        }
+
+        #[test]
+        fn binary_signers_with_debug_build_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+            "#;
+            let execution = Execution::default();
+            let signer = execution.signer("Example");
+            let signers = execution
+                .binary_signers(
+                    &signer,
+                    &MANIFEST.parse::<Value>().unwrap(),
+                    Path::new("target"),
+                    true,
+                )
+                .unwrap();
+            assert_eq!(signers.len(), 1);
+            let (binary, command) = &signers[0];
+            assert_eq!(
+                binary,
+                &Path::new("target")
+                    .join("debug")
+                    .join("Example")
+                    .with_extension(EXE_FILE_EXTENSION)
+            );
+            assert_eq!(command.get_args().last(), Some(binary.as_os_str()));
+        }
+
+        #[test]
+        fn post_build_hook_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            let script = if cfg!(windows) {
+                "echo %CARGO_WIX_MSI%> msi.txt"
+            } else {
+                "printf '%s' \"$CARGO_WIX_MSI\" > msi.txt"
+            };
+            let execution = Builder::default().post_build(Some(script)).build();
+            let hook = execution.post_build_hook(script, &installer, temp_dir.path());
+            execution.run_hook(hook, POST_BUILD).unwrap();
+            let actual = fs::read_to_string(temp_dir.path().join("msi.txt")).unwrap();
+            assert_eq!(actual.trim(), installer.to_str().unwrap());
+        }
+
+        #[test]
+        fn run_hook_with_timeout_fails() {
+            const SLOW_COMMAND: &str = if cfg!(windows) {
+                "ping -n 11 127.0.0.1 > nul"
+            } else {
+                "sleep 10"
+            };
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let execution = Builder::default()
+                .timeout(Some(Duration::from_millis(200)))
+                .build();
+            let hook = execution.hook(SLOW_COMMAND, temp_dir.path());
+            let start = Instant::now();
+            match execution.run_hook(hook, PRE_BUILD) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("timed out")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+            assert!(start.elapsed() < Duration::from_secs(5));
+        }
+
+        #[test]
+        fn run_hook_within_timeout_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let execution = Builder::default()
+                .timeout(Some(Duration::from_secs(60)))
+                .build();
+            let hook = execution.hook("exit 0", temp_dir.path());
+            assert!(execution.run_hook(hook, PRE_BUILD).is_ok());
+        }
+
+        #[test]
+        fn post_build_hook_with_failure_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            let execution = Builder::default().post_build(Some("exit 3")).build();
+            let hook = execution.post_build_hook("exit 3", &installer, temp_dir.path());
+            match execution.run_hook(hook, POST_BUILD) {
+                Err(Error::Command(ref name, 3, true)) if name == POST_BUILD => {}
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn pre_build_hook_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let script = if cfg!(windows) {
+                "echo %CARGO_WIX_PRODUCT_NAME% %CARGO_WIX_VERSION%> product.txt"
+            } else {
+                "echo \"$CARGO_WIX_PRODUCT_NAME $CARGO_WIX_VERSION\" > product.txt"
+            };
+            let execution = Builder::default().pre_build(Some(script)).build();
+            let hook = execution.pre_build_hook(
+                script,
+                "Example",
+                &Version::parse("1.2.3").unwrap(),
+                temp_dir.path(),
+            );
+            execution.run_hook(hook, PRE_BUILD).unwrap();
+            let actual = fs::read_to_string(temp_dir.path().join("product.txt")).unwrap();
+            assert_eq!(actual.trim(), "Example 1.2.3");
+        }
+
+        #[test]
+        fn run_with_pre_build_runs_before_cargo() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(
+                &manifest,
+                r#"[package]
+                name = "example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                "#,
+            )
+            .unwrap();
+            fs::create_dir(temp_dir.path().join(WIX)).unwrap();
+            fs::write(temp_dir.path().join(WIX).join("main.wxs"), "").unwrap();
+            // The marker is the only source file, so the build fails unless
+            // the hook creates it before cargo runs.
+            let script = if cfg!(windows) {
+                "mkdir src && echo fn main() {} > src\\main.rs"
+            } else {
+                "mkdir src && echo 'fn main() {}' > src/main.rs"
+            };
+            let target_dir = temp_dir.path().join("target");
+            let result = Builder::default()
+                .bin_path(temp_dir.path().to_str())
+                .input(manifest.to_str())
+                .pre_build(Some(script))
+                .target_dir(target_dir.to_str())
+                .build()
+                .run();
+            // The build succeeds, but the compiler does not exist in the
+            // folder from the bin path.
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains(WIX_COMPILER)),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+            let mut binary = target_dir.join("release").join("example");
+            binary.set_extension(std::env::consts::EXE_EXTENSION);
+            assert!(temp_dir.path().join("src").join("main.rs").exists());
+            assert!(binary.exists());
+        }
+
+        #[test]
+        fn run_with_failed_pre_build_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(
+                &manifest,
+                r#"[package]
+                name = "example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                "#,
+            )
+            .unwrap();
+            fs::create_dir(temp_dir.path().join(WIX)).unwrap();
+            fs::write(temp_dir.path().join(WIX).join("main.wxs"), "").unwrap();
+            let result = Builder::default()
+                .input(manifest.to_str())
+                .pre_build(Some("exit 2"))
+                .target_dir(temp_dir.path().join("target").to_str())
+                .build()
+                .run();
+            match result {
+                Err(Error::Command(ref name, 2, true)) if name == PRE_BUILD => {}
+                r => panic!("Unexpected result: {:?}", r),
+            }
+            assert!(!temp_dir.path().join("target").exists());
+        }
+
+        fn fake_wix_bin(temp_dir: &Path) -> PathBuf {
+            let bin = temp_dir.join(BINARY_FOLDER_NAME);
+            fs::create_dir(&bin).unwrap();
+            for tool in &[WIX_COMPILER, WIX_LINKER] {
+                let mut path = bin.join(tool);
+                path.set_extension(EXE_FILE_EXTENSION);
+                fs::write(path, "").unwrap();
+            }
+            bin
+        }
+
+        #[test]
+        fn run_without_manifest_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            fs::create_dir(temp_dir.path().join(WIX)).unwrap();
+            fs::write(temp_dir.path().join(WIX).join("main.wxs"), "").unwrap();
+            let bin = fake_wix_bin(temp_dir.path());
+            let result = Builder::default()
+                .bin_path(bin.to_str())
+                .dry_run(true)
+                .input(temp_dir.path().to_str())
+                .name(Some("Example"))
+                .no_build(true)
+                .version(Some("1.2.3"))
+                .build()
+                .run_with_summary();
+            assert!(result.is_ok(), "Unexpected result: {:?}", result);
+        }
+
+        #[test]
+        fn run_without_manifest_and_version_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            fs::create_dir(temp_dir.path().join(WIX)).unwrap();
+            fs::write(temp_dir.path().join(WIX).join("main.wxs"), "").unwrap();
+            let bin = fake_wix_bin(temp_dir.path());
+            let result = Builder::default()
+                .bin_path(bin.to_str())
+                .dry_run(true)
+                .input(temp_dir.path().to_str())
+                .name(Some("Example"))
+                .no_build(true)
+                .build()
+                .run_with_summary();
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains("does not contain")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn manifest_free_path_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let actual = Builder::default()
+                .input(temp_dir.path().to_str())
+                .name(Some("Example"))
+                .no_build(true)
+                .version(Some("1.2.3"))
+                .build()
+                .manifest_free_path()
+                .unwrap();
+            assert_eq!(actual, Some(temp_dir.path().join(CARGO_MANIFEST_FILE)));
+        }
+
+        #[test]
+        fn manifest_free_path_with_manifest_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            fs::write(temp_dir.path().join(CARGO_MANIFEST_FILE), "").unwrap();
+            let actual = Builder::default()
+                .input(temp_dir.path().to_str())
+                .name(Some("Example"))
+                .no_build(true)
+                .version(Some("1.2.3"))
+                .build()
+                .manifest_free_path()
+                .unwrap();
+            assert_eq!(actual, None);
+        }
+
+        #[test]
+        fn manifest_free_path_with_build_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let actual = Builder::default()
+                .input(temp_dir.path().to_str())
+                .name(Some("Example"))
+                .version(Some("1.2.3"))
+                .build()
+                .manifest_free_path()
+                .unwrap();
+            assert_eq!(actual, None);
+        }
+
+        #[test]
+        fn hash_in_name_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            fs::write(&installer, "abc").unwrap();
+            let actual = Execution::hash_in_name(&installer).unwrap();
+            assert_eq!(
+                actual,
+                temp_dir.path().join("Example-0.1.0-x86_64-ba7816bf.msi")
+            );
+            let hash = actual
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.rsplit('-').next())
+                .unwrap();
+            assert_eq!(hash.len(), 8);
+            assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+            assert!(actual.exists());
+            assert!(!installer.exists());
+        }
+
+        #[test]
+        fn write_checksum_with_sha256_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            fs::write(&installer, "abc").unwrap();
+            let actual = Execution::write_checksum(ChecksumKind::Sha256, &installer).unwrap();
+            assert_eq!(
+                actual,
+                temp_dir.path().join("Example-0.1.0-x86_64.msi.sha256")
+            );
+            assert_eq!(
+                fs::read_to_string(&actual).unwrap(),
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  \
+                 Example-0.1.0-x86_64.msi\n"
+            );
+        }
+
+        #[test]
+        fn write_checksum_with_sha512_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            fs::write(&installer, "abc").unwrap();
+            let actual = Execution::write_checksum(ChecksumKind::Sha512, &installer).unwrap();
+            assert_eq!(
+                actual,
+                temp_dir.path().join("Example-0.1.0-x86_64.msi.sha512")
+            );
+            assert_eq!(
+                fs::read_to_string(&actual).unwrap(),
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                 2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f  \
+                 Example-0.1.0-x86_64.msi\n"
+            );
+        }
+
+        #[test]
+        fn builder_with_frozen_locked_and_offline_works() {
+            let execution = Builder::new()
+                .frozen(true)
+                .locked(true)
+                .offline(true)
+                .build();
+            let builder = execution.builder(
+                &EMPTY_PKG_META_WIX.parse::<Value>().unwrap(),
+                Path::new("Cargo.toml"),
+                false,
+            );
+            assert_eq!(
+                builder.get_args().collect::<Vec<&OsStr>>(),
+                vec![
+                    "build",
+                    "--release",
+                    "--manifest-path",
+                    "Cargo.toml",
+                    "--frozen",
+                    "--locked",
+                    "--offline"
+                ]
+            );
+        }
+
+        #[test]
+        fn builder_with_metadata_features_works() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                features = ["gui", "cli"]
+            "#;
+            let execution = Execution::default();
+            let builder = execution.builder(
+                &PKG_META_WIX.parse::<Value>().unwrap(),
+                Path::new("Cargo.toml"),
+                false,
+            );
+            assert_eq!(
+                builder.get_args().collect::<Vec<&OsStr>>(),
+                vec![
+                    "build",
+                    "--release",
+                    "--manifest-path",
+                    "Cargo.toml",
+                    "--features",
+                    "gui,cli"
+                ]
+            );
+        }
+
+        #[test]
+        fn features_with_metadata_is_overridden_works() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                features = ["gui"]
+            "#;
+            let execution = Builder::new().features(Some(vec!["cli"])).build();
+            let features = execution.features(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(features, Some(vec![String::from("cli")]));
+        }
+
+        #[test]
+        fn compiler_args_metadata_works() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                compiler-args = ["-nologo", "-ws"]
+            "#;
+            let execution = Execution::default();
+            let args = execution.compiler_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![String::from("-nologo"), String::from("-ws")])
+            );
+        }
+
+        #[test]
+        fn linker_args_metadata_works() {
+            const PKG_META_WIX: &str = r#"
+                [package.metadata.wix]
+                linker-args = ["-nologo", "-ws"]
+            "#;
+            let execution = Execution::default();
+            let args = execution.linker_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![String::from("-nologo"), String::from("-ws")])
+            );
+        }
+
+        const ARGS_PKG_META_WIX: &str = r#"
+            [package.metadata.wix]
+            compiler-args = ["-nologo", "-ws"]
+            linker-args = ["-nologo", "-sval"]
+        "#;
+
+        #[test]
+        fn compiler_args_with_metadata_replaces_args() {
+            let execution = Builder::new()
+                .compiler_args(Some(vec!["-pedantic"]))
+                .build();
+            let args = execution.compiler_args(&ARGS_PKG_META_WIX.parse::<Value>().unwrap());
             assert_eq!(
                 args,
                 Some(vec![String::from("-nologo"), String::from("-ws")])
             );
         }
 
+        #[test]
+        fn compiler_args_with_merge_args_works() {
+            let execution = Builder::new()
+                .compiler_args(Some(vec!["-pedantic", "-v"]))
+                .merge_args(true)
+                .build();
+            let args = execution.compiler_args(&ARGS_PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![
+                    String::from("-nologo"),
+                    String::from("-ws"),
+                    String::from("-pedantic"),
+                    String::from("-v")
+                ])
+            );
+        }
+
+        #[test]
+        fn linker_args_with_metadata_replaces_args() {
+            let execution = Builder::new().linker_args(Some(vec!["-pedantic"])).build();
+            let args = execution.linker_args(&ARGS_PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![String::from("-nologo"), String::from("-sval")])
+            );
+        }
+
+        #[test]
+        fn linker_args_with_merge_args_works() {
+            let execution = Builder::new()
+                .linker_args(Some(vec!["-pedantic", "-v"]))
+                .merge_args(true)
+                .build();
+            let args = execution.linker_args(&ARGS_PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![
+                    String::from("-nologo"),
+                    String::from("-sval"),
+                    String::from("-pedantic"),
+                    String::from("-v")
+                ])
+            );
+        }
+
+        #[test]
+        fn linker_args_with_merge_args_and_no_metadata_works() {
+            let execution = Builder::new()
+                .linker_args(Some(vec!["-pedantic"]))
+                .merge_args(true)
+                .build();
+            let args = execution.linker_args(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(args, Some(vec![String::from("-pedantic")]));
+        }
+
         const EMPTY_PKG_META_WIX: &str = r#"[package.metadata.wix]"#;
 
         #[test]
@@ -1702,12 +5453,2057 @@ mod tests {
         }
 
         #[test]
-        fn wixobj_destination_works() {
-            let execution = Execution::default();
+        fn wix_version_with_missing_compiler_name_fails() {
+            const NAME: &str = "cargo-wix-missing-candle";
+            if env::var_os(WIX_PATH_KEY).is_some() {
+                // The compiler is not searched for in the PATH.
+                return;
+            }
+            let err = Builder::default()
+                .compiler_name(Some(NAME))
+                .build()
+                .wix_version()
+                .unwrap_err();
+            assert!(err.to_string().contains(&format!("({})", NAME)));
+            match err {
+                Error::WixNotFound { tool } => assert_eq!(tool, NAME),
+                e => panic!("Unexpected error: {:?}", e),
+            }
+        }
+
+        #[test]
+        fn compiler_with_compiler_name_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let mut expected = temp_dir.path().join("wix-candle");
+            expected.set_extension(EXE_FILE_EXTENSION);
+            fs::write(&expected, "").unwrap();
+            let actual = Builder::default()
+                .bin_path(temp_dir.path().to_str())
+                .compiler_name(Some("wix-candle"))
+                .build()
+                .compiler()
+                .unwrap();
+            assert_eq!(actual.get_program(), expected.as_os_str());
+        }
+
+        #[test]
+        fn compiler_with_missing_compiler_name_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let actual = Builder::default()
+                .bin_path(temp_dir.path().to_str())
+                .compiler_name(Some("wix-candle"))
+                .build()
+                .compiler();
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn linker_with_linker_name_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let mut expected = temp_dir.path().join("wix-light");
+            expected.set_extension(EXE_FILE_EXTENSION);
+            fs::write(&expected, "").unwrap();
+            let actual = Builder::default()
+                .bin_path(temp_dir.path().to_str())
+                .linker_name(Some("wix-light"))
+                .build()
+                .linker()
+                .unwrap();
+            assert_eq!(actual.get_program(), expected.as_os_str());
+        }
+
+        #[test]
+        fn compiler_and_linker_with_spaces_and_unicode_in_bin_path_work() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let bin = temp_dir
+                .path()
+                .join("WiX Toolset v3.11 ünïcödé")
+                .join("bin");
+            fs::create_dir_all(&bin).unwrap();
+            let compiler = bin.join("candle.exe");
+            let linker = bin.join("light.exe");
+            fs::write(&compiler, "").unwrap();
+            fs::write(&linker, "").unwrap();
+            let execution = Builder::default().bin_path(bin.to_str()).build();
             assert_eq!(
-                execution.wixobj_destination().unwrap(),
-                PathBuf::from("target\\wix\\")
-            )
+                execution.compiler().unwrap().get_program(),
+                compiler.as_os_str()
+            );
+            assert_eq!(
+                execution.linker().unwrap().get_program(),
+                linker.as_os_str()
+            );
+        }
+
+        #[test]
+        fn compiler_and_linker_with_dots_in_names_work() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let compiler = temp_dir.path().join("candle-3.11.exe");
+            let linker = temp_dir.path().join("light-3.11.exe");
+            fs::write(&compiler, "").unwrap();
+            fs::write(&linker, "").unwrap();
+            let execution = Builder::default()
+                .bin_path(temp_dir.path().to_str())
+                .compiler_name(Some("candle-3.11"))
+                .linker_name(Some("light-3.11.exe"))
+                .build();
+            assert_eq!(
+                execution.compiler().unwrap().get_program(),
+                compiler.as_os_str()
+            );
+            assert_eq!(
+                execution.linker().unwrap().get_program(),
+                linker.as_os_str()
+            );
+        }
+
+        #[test]
+        fn compiler_with_spaces_and_unicode_in_missing_bin_path_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let bin = temp_dir.path().join("WiX Toolset ünïcödé");
+            let actual = Builder::default().bin_path(bin.to_str()).build().compiler();
+            match actual {
+                Err(Error::Generic(msg)) => {
+                    assert!(msg.contains(&format!("'{}'", bin.display())))
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn parse_wix_version_works() {
+            const BANNER: &str = "Windows Installer XML Toolset Compiler version 3.11.2.4516\r\n\
+                                  Copyright (c) .NET Foundation and contributors. All rights \
+                                  reserved.\r\n";
+            let actual = Execution::parse_wix_version(BANNER).unwrap();
+            assert_eq!(actual, Version::new(3, 11, 2));
+            assert!(actual >= Version::new(3, 11, 0));
+            assert!(actual < Version::new(3, 14, 0));
+        }
+
+        #[test]
+        fn parse_wix_version_without_version_fails() {
+            assert!(Execution::parse_wix_version("usage: candle.exe [-?]").is_none());
+        }
+
+        #[test]
+        fn lenient_version_works() {
+            assert_eq!(Execution::lenient_version("3"), Some(Version::new(3, 0, 0)));
+            assert_eq!(
+                Execution::lenient_version("3.11"),
+                Some(Version::new(3, 11, 0))
+            );
+            assert_eq!(
+                Execution::lenient_version("3.11.2.4516"),
+                Some(Version::new(3, 11, 2))
+            );
+            assert!(Execution::lenient_version("3.x").is_none());
+            assert!(Execution::lenient_version("").is_none());
+        }
+
+        #[test]
+        fn min_wix_version_with_invalid_version_fails() {
+            let execution = Builder::default().min_wix_version(Some("three")).build();
+            match execution.min_wix_version() {
+                Err(Error::Generic(msg)) => assert!(msg.contains("three")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn check_wix_version_works() {
+            let minimum = Version::new(3, 11, 0);
+            assert!(Execution::check_wix_version(Some(&Version::new(3, 11, 0)), &minimum).is_ok());
+            assert!(Execution::check_wix_version(Some(&Version::new(3, 14, 1)), &minimum).is_ok());
+        }
+
+        #[test]
+        fn check_wix_version_with_older_version_fails() {
+            let minimum = Version::new(3, 11, 0);
+            match Execution::check_wix_version(Some(&Version::new(3, 10, 4)), &minimum) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("3.10.4")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn check_wix_version_with_unknown_version_fails() {
+            assert!(Execution::check_wix_version(None, &Version::new(3, 11, 0)).is_err());
+        }
+
+        #[test]
+        fn version_limits_works() {
+            let version = Version::parse("255.255.65535").unwrap();
+            assert!(Execution::version_limits(&version).is_none());
+        }
+
+        #[test]
+        fn version_limits_with_major_over_255_works() {
+            let version = Version::parse("256.0.0").unwrap();
+            assert!(Execution::version_limits(&version).is_some());
+        }
+
+        #[test]
+        fn version_limits_with_minor_over_255_works() {
+            let version = Version::parse("1.256.0").unwrap();
+            assert!(Execution::version_limits(&version).is_some());
+        }
+
+        #[test]
+        fn version_limits_with_patch_over_65535_works() {
+            let version = Version::parse("1.0.65536").unwrap();
+            assert!(Execution::version_limits(&version).is_some());
+        }
+
+        fn run_with_version(version: &str, strict_version: bool) -> Result<()> {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(
+                &manifest,
+                format!("[package]\nname = \"Example\"\nversion = \"{}\"\n", version),
+            )
+            .unwrap();
+            fs::create_dir(temp_dir.path().join(WIX)).unwrap();
+            fs::write(temp_dir.path().join(WIX).join("main.wxs"), "").unwrap();
+            Builder::default()
+                .input(manifest.to_str())
+                .dry_run(true)
+                .no_build(true)
+                .strict_version(strict_version)
+                .build()
+                .run()
+        }
+
+        #[test]
+        fn run_with_maximum_version_works() {
+            assert!(run_with_version("255.255.65535", true).is_ok());
+        }
+
+        #[test]
+        fn run_with_major_over_255_fails() {
+            match run_with_version("256.0.0", true) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("ProductVersion")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_minor_over_255_fails() {
+            match run_with_version("1.256.0", true) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("1.256.0")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_patch_over_65535_fails() {
+            match run_with_version("1.0.65536", true) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("1.0.65536")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_without_strict_version_works() {
+            assert!(run_with_version("256.256.65536", false).is_ok());
+        }
+
+        #[test]
+        fn run_with_strict_version_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(
+                &manifest,
+                r#"[package]
+                name = "Example"
+                version = "256.0.0"
+                authors = ["First Last <first.last@example.com>"]
+                "#,
+            )
+            .unwrap();
+            let result = Builder::default()
+                .input(manifest.to_str())
+                .strict_version(true)
+                .build()
+                .run();
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains("256.0.0")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn create_folder_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir
+                .path()
+                .join(TARGET_FOLDER_NAME)
+                .join(WIX)
+                .join("custom")
+                .join("sub")
+                .join("Example-0.1.0-x86_64.msi");
+            Execution::create_folder(installer.parent().unwrap()).unwrap();
+            assert!(installer.parent().unwrap().is_dir());
+            // An existing folder is not an error.
+            Execution::create_folder(installer.parent().unwrap()).unwrap();
+        }
+
+        #[test]
+        fn run_without_wix_folder_in_target_directory_creates_it() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wix = temp_dir.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            fs::write(wix.join("main.wxs"), "").unwrap();
+            let target = temp_dir.path().join(TARGET_FOLDER_NAME);
+            assert!(!target.exists());
+            // The compiler cannot be found, so the run fails after the folder
+            // for the object files is created and before the compiler runs.
+            let result = Builder::default()
+                .input(temp_dir.path().join(CARGO_MANIFEST_FILE).to_str())
+                .name(Some("Example"))
+                .version(Some("1.2.3"))
+                .no_build(true)
+                .compiler_name(Some("cargo-wix-missing-compiler"))
+                .target_dir(target.to_str())
+                .build()
+                .run();
+            assert!(result.is_err());
+            assert!(target.join(WIX).is_dir());
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn run_with_failing_compiler_name_reports_name() {
+            use std::os::unix::fs::PermissionsExt;
+
+            const NAME: &str = "cargo-wix-failing-candle";
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wix = temp_dir.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            fs::write(wix.join("main.wxs"), "").unwrap();
+            let mut compiler = temp_dir.path().join(NAME);
+            compiler.set_extension(EXE_FILE_EXTENSION);
+            fs::write(&compiler, "#!/bin/sh\nexit 3\n").unwrap();
+            fs::set_permissions(&compiler, fs::Permissions::from_mode(0o755)).unwrap();
+            let result = Builder::default()
+                .bin_path(temp_dir.path().to_str())
+                .input(temp_dir.path().join(CARGO_MANIFEST_FILE).to_str())
+                .name(Some("Example"))
+                .version(Some("1.2.3"))
+                .no_build(true)
+                .compiler_name(Some(NAME))
+                .target_dir(temp_dir.path().join(TARGET_FOLDER_NAME).to_str())
+                .build()
+                .run();
+            match result {
+                Err(err @ Error::Command(..)) => {
+                    assert!(err.to_string().contains(&format!("'{}'", NAME)));
+                    match err {
+                        Error::Command(command, code, _) => {
+                            assert_eq!(command, NAME);
+                            assert_eq!(code, 3);
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn require_clean_git_metadata_works() {
+            const PKG_META_WIX: &str = r#"[package.metadata.wix]
+                require-clean-git = true
+            "#;
+            let manifest = PKG_META_WIX.parse::<Value>().unwrap();
+            assert!(Execution::default().require_clean_git(&manifest));
+        }
+
+        #[test]
+        fn check_git_status_with_dirty_tree_and_require_clean_fails() {
+            const STATUS: &str = " M src/main.rs\n?? wix/extra.wxs\n";
+            match Execution::check_git_status(Some(STATUS)) {
+                Err(err @ Error::DirtyGit { .. }) => {
+                    let msg = err.to_string();
+                    assert!(msg.contains("2 uncommitted change(s)"));
+                    assert!(msg.contains("M src/main.rs"));
+                    assert!(msg.contains("?? wix/extra.wxs"));
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn check_git_status_with_many_changes_fails() {
+            let status = (0..8)
+                .map(|i| format!(" M src/file{}.rs\n", i))
+                .collect::<String>();
+            match Execution::check_git_status(Some(&status)) {
+                Err(err @ Error::DirtyGit { .. }) => {
+                    let msg = err.to_string();
+                    assert!(msg.contains("8 uncommitted change(s)"));
+                    assert!(msg.contains("src/file4.rs"));
+                    assert!(!msg.contains("src/file5.rs"));
+                    assert!(msg.contains("..."));
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn check_git_status_with_clean_tree_works() {
+            assert!(Execution::check_git_status(Some("")).is_ok());
+        }
+
+        #[test]
+        fn check_git_status_without_repository_works() {
+            assert!(Execution::check_git_status(None).is_ok());
+        }
+
+        #[test]
+        fn git_status_without_repository_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let status = Execution::default().git_status(temp_dir.path());
+            assert!(status.is_none());
+        }
+
+        #[test]
+        fn create_folder_with_empty_path_works() {
+            assert!(Execution::create_folder(Path::new("")).is_ok());
+        }
+
+        #[test]
+        fn wixobj_destination_works() {
+            assert_eq!(
+                Execution::wixobj_destination(Path::new(TARGET_FOLDER_NAME)),
+                PathBuf::from("target\\wix\\")
+            )
+        }
+
+        #[test]
+        fn target_directory_with_cargo_target_dir_works() {
+            const EXPECTED: &str = "C:\\Cargo\\target";
+            let target_directory = Execution::default()
+                .target_directory_with(Some(OsString::from(EXPECTED)))
+                .unwrap();
+            assert_eq!(target_directory, PathBuf::from(EXPECTED));
+            assert_eq!(
+                Execution::wixobj_destination(&target_directory),
+                PathBuf::from("C:\\Cargo\\target").join("wix\\")
+            );
+        }
+
+        #[test]
+        fn target_directory_with_target_dir_works() {
+            const EXPECTED: &str = "C:\\Shared\\target";
+            let actual = Builder::default()
+                .target_dir(Some(EXPECTED))
+                .build()
+                .target_directory()
+                .unwrap();
+            assert_eq!(actual, PathBuf::from(EXPECTED));
+        }
+
+        #[test]
+        fn target_directory_with_target_dir_and_cargo_target_dir_works() {
+            const EXPECTED: &str = "C:\\Shared\\target";
+            let actual = Builder::default()
+                .target_dir(Some(EXPECTED))
+                .build()
+                .target_directory_with(Some(OsString::from("C:\\Cargo\\target")))
+                .unwrap();
+            assert_eq!(actual, PathBuf::from(EXPECTED));
+        }
+
+        #[test]
+        fn target_directory_without_cargo_target_dir_works() {
+            let actual = Execution::default().target_directory_with(None).unwrap();
+            assert_eq!(actual, PathBuf::from(TARGET_FOLDER_NAME));
+        }
+
+        #[test]
+        fn installer_destination_with_target_directory_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+            "#;
+            let target_directory = PathBuf::from("C:\\Cargo\\target");
+            let actual = Execution::default()
+                .installer_destination(
+                    "Example",
+                    &"0.1.0".parse::<Version>().unwrap(),
+                    Platform::X64,
+                    false,
+                    &InstallerKind::default(),
+                    &MANIFEST.parse::<Value>().unwrap(),
+                    &target_directory,
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                target_directory.join(WIX).join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_name_suffix_works() {
+            let target_directory = PathBuf::from("C:\\Cargo\\target");
+            let actual = Builder::default()
+                .name_suffix(Some("-nightly"))
+                .build()
+                .installer_destination(
+                    "Example",
+                    &"1.2.3".parse::<Version>().unwrap(),
+                    Platform::X64,
+                    false,
+                    &InstallerKind::default(),
+                    &Value::Table(Table::new()),
+                    &target_directory,
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                target_directory
+                    .join(WIX)
+                    .join("Example-1.2.3-x86_64-nightly.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_name_suffix_and_debug_name_works() {
+            let target_directory = PathBuf::from("C:\\Cargo\\target");
+            let actual = Builder::default()
+                .name_suffix(Some("-20261016"))
+                .build()
+                .installer_destination(
+                    "Example",
+                    &"1.2.3".parse::<Version>().unwrap(),
+                    Platform::X64,
+                    true,
+                    &InstallerKind::default(),
+                    &Value::Table(Table::new()),
+                    &target_directory,
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                target_directory
+                    .join(WIX)
+                    .join("Example-1.2.3-x86_64-debug-20261016.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_name_suffix_and_output_works() {
+            let actual = Builder::default()
+                .name_suffix(Some("-nightly"))
+                .output(Some("releases/"))
+                .build()
+                .installer_destination(
+                    "Example",
+                    &"1.2.3".parse::<Version>().unwrap(),
+                    Platform::X64,
+                    false,
+                    &InstallerKind::default(),
+                    &Value::Table(Table::new()),
+                    Path::new("C:\\Cargo\\target"),
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                Path::new("releases").join("Example-1.2.3-x86_64-nightly.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_name_suffix_containing_separator_fails() {
+            let result = Builder::default()
+                .name_suffix(Some("-nightly/evil"))
+                .build()
+                .installer_destination(
+                    "Example",
+                    &"1.2.3".parse::<Version>().unwrap(),
+                    Platform::X64,
+                    false,
+                    &InstallerKind::default(),
+                    &Value::Table(Table::new()),
+                    Path::new("C:\\Cargo\\target"),
+                );
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains("-nightly/evil")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn installer_destination_with_metadata_output_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+
+                [package.metadata.wix]
+                output = "C:\\Installers\\"
+            "#;
+            let actual = Execution::default()
+                .installer_destination(
+                    "Example",
+                    &"0.1.0".parse::<Version>().unwrap(),
+                    Platform::X64,
+                    false,
+                    &InstallerKind::default(),
+                    &MANIFEST.parse::<Value>().unwrap(),
+                    Path::new("C:\\Cargo\\target"),
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                Path::new("C:\\Installers").join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_version_token_works() {
+            let actual = Builder::default()
+                .output(Some("releases/{version}/"))
+                .build()
+                .installer_destination(
+                    "name",
+                    &"1.2.3".parse::<Version>().unwrap(),
+                    Platform::X64,
+                    false,
+                    &InstallerKind::default(),
+                    &Value::Table(Table::new()),
+                    Path::new("C:\\Cargo\\target"),
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                Path::new("releases/1.2.3").join("name-1.2.3-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_metadata_output_tokens_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+
+                [package.metadata.wix]
+                output = "releases/{platform}/{name}-{version}-{arch}.msi"
+            "#;
+            let actual = Execution::default()
+                .installer_destination(
+                    "Example",
+                    &"0.1.0".parse::<Version>().unwrap(),
+                    Platform::X86,
+                    false,
+                    &InstallerKind::default(),
+                    &MANIFEST.parse::<Value>().unwrap(),
+                    Path::new("C:\\Cargo\\target"),
+                )
+                .unwrap();
+            assert_eq!(actual, PathBuf::from("releases/x86/Example-0.1.0-i686.msi"));
+        }
+
+        #[test]
+        fn expand_output_tokens_with_unknown_token_works() {
+            let actual = expand_output_tokens(
+                "releases/{commit}/{version}/",
+                "Example",
+                &"0.1.0".parse::<Version>().unwrap(),
+                Platform::X64,
+            );
+            assert_eq!(actual, "releases/{commit}/0.1.0/");
+        }
+
+        #[test]
+        fn output_destination_with_file_works() {
+            const EXPECTED: &str = "C:\\Installers\\Example.msi";
+            let actual = Execution::output_destination(EXPECTED, "Example-0.1.0-x86_64.msi");
+            assert_eq!(actual, PathBuf::from(EXPECTED));
+        }
+
+        #[test]
+        fn output_destination_with_trailing_backslash_works() {
+            let actual =
+                Execution::output_destination("C:\\Installers\\", "Example-0.1.0-x86_64.msi");
+            assert_eq!(
+                actual,
+                Path::new("C:\\Installers").join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn output_destination_with_unc_path_works() {
+            let actual = Execution::output_destination(
+                "\\\\server\\share\\out\\",
+                "Example-0.1.0-x86_64.msi",
+            );
+            assert_eq!(
+                actual,
+                Path::new("\\\\server\\share\\out").join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn output_destination_with_mixed_separators_works() {
+            let actual =
+                Execution::output_destination("C:\\Installers/out\\/", "Example-0.1.0-x86_64.msi");
+            assert_eq!(
+                actual,
+                Path::new("C:\\Installers/out").join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn output_destination_with_root_works() {
+            let actual = Execution::output_destination("C:\\", "Example-0.1.0-x86_64.msi");
+            assert_eq!(actual, Path::new("C:\\").join("Example-0.1.0-x86_64.msi"));
+        }
+
+        #[test]
+        fn output_destination_with_existing_folder_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let actual = Execution::output_destination(
+                temp_dir.path().to_str().unwrap(),
+                "Example-0.1.0-x86_64.msi",
+            );
+            assert_eq!(actual, temp_dir.path().join("Example-0.1.0-x86_64.msi"));
+        }
+
+        #[test]
+        fn jobs_with_defaults_works() {
+            assert_eq!(Execution::default().jobs(), None);
+        }
+
+        #[test]
+        fn jobs_with_zero_works() {
+            let actual = Builder::default().jobs(Some(0)).build().jobs();
+            assert!(actual.unwrap() > 0);
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn compile_in_parallel_with_failure_works() {
+            let sources = [
+                PathBuf::from("first.wxs"),
+                PathBuf::from("second.wxs"),
+                PathBuf::from("third.wxs"),
+            ];
+            let compilers = sources
+                .iter()
+                .map(|s| {
+                    let mut c = Command::new(if s.ends_with("second.wxs") {
+                        "false"
+                    } else {
+                        "true"
+                    });
+                    c.arg(s);
+                    (s.as_path(), c)
+                })
+                .collect();
+            match Execution::default().compile_in_parallel(compilers, 2) {
+                Err(Error::Compile { source, .. }) => {
+                    assert_eq!(source, PathBuf::from("second.wxs"))
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn extension_args_with_defaults_works() {
+            let execution = Execution::default();
+            let manifest = Value::Table(Table::new());
+            let mut linker = execution.linker().unwrap();
+            execution.extension_args(&mut linker, &Cultures::FrFr, &manifest);
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![
+                    OsStr::new("-ext"),
+                    OsStr::new("WixUIExtension"),
+                    OsStr::new("-ext"),
+                    OsStr::new("WixUtilExtension"),
+                    OsStr::new("-cultures:fr-FR"),
+                ]
+            );
+        }
+
+        #[test]
+        fn extension_args_with_no_ui_works() {
+            let execution = Builder::default().no_ui(true).build();
+            let manifest = Value::Table(Table::new());
+            let mut linker = execution.linker().unwrap();
+            execution.extension_args(&mut linker, &Cultures::FrFr, &manifest);
+            let args = linker.get_args().collect::<Vec<&OsStr>>();
+            assert!(!args.contains(&OsStr::new("WixUIExtension")));
+            assert!(!args
+                .iter()
+                .any(|a| a.to_string_lossy().starts_with("-cultures:")));
+            assert_eq!(
+                args,
+                vec![OsStr::new("-ext"), OsStr::new("WixUtilExtension")]
+            );
+        }
+
+        #[test]
+        fn extension_args_with_no_ui_metadata_works() {
+            const PKG_META_WIX: &str = r#"[package.metadata.wix]
+                no-ui = true
+            "#;
+            let execution = Execution::default();
+            let manifest = PKG_META_WIX.parse::<Value>().unwrap();
+            let mut linker = execution.linker().unwrap();
+            execution.extension_args(&mut linker, &Cultures::EnUs, &manifest);
+            assert!(!linker.get_args().any(|a| a == OsStr::new("WixUIExtension")));
+        }
+
+        #[test]
+        fn custom_extension_args_with_defaults_works() {
+            let execution = Execution::default();
+            let mut command = execution.compiler().unwrap();
+            execution
+                .custom_extension_args(&mut command, &Value::Table(Table::new()))
+                .unwrap();
+            assert_eq!(command.get_args().count(), 0);
+        }
+
+        #[test]
+        fn custom_extension_args_with_name_works() {
+            let execution = Builder::default()
+                .extensions(Some(vec!["WixFirewallExtension"]))
+                .build();
+            let mut linker = execution.linker().unwrap();
+            execution
+                .custom_extension_args(&mut linker, &Value::Table(Table::new()))
+                .unwrap();
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-ext"), OsStr::new("WixFirewallExtension")]
+            );
+        }
+
+        #[test]
+        fn custom_extension_args_with_names_and_paths_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let extensions = temp_dir.path().join("extensions");
+            fs::create_dir(&extensions).unwrap();
+            fs::write(extensions.join("Custom.dll"), "").unwrap();
+            fs::write(temp_dir.path().join("Other.DLL"), "").unwrap();
+            let manifest = temp_dir.path().join(CARGO_MANIFEST_FILE);
+            let execution = Builder::default()
+                .input(manifest.to_str())
+                .extensions(Some(vec![
+                    "WixFirewallExtension",
+                    "extensions/Custom.dll",
+                    "Other.DLL",
+                ]))
+                .build();
+            let mut compiler = execution.compiler().unwrap();
+            execution
+                .custom_extension_args(&mut compiler, &Value::Table(Table::new()))
+                .unwrap();
+            assert_eq!(
+                compiler.get_args().collect::<Vec<&OsStr>>(),
+                vec![
+                    OsStr::new("-ext"),
+                    OsStr::new("WixFirewallExtension"),
+                    OsStr::new("-ext"),
+                    extensions.join("Custom.dll").as_os_str(),
+                    OsStr::new("-ext"),
+                    temp_dir.path().join("Other.DLL").as_os_str(),
+                ]
+            );
+        }
+
+        #[test]
+        fn custom_extension_args_with_metadata_works() {
+            const PKG_META_WIX: &str = r#"[package.metadata.wix]
+                extensions = ["WixFirewallExtension"]
+            "#;
+            let execution = Execution::default();
+            let mut linker = execution.linker().unwrap();
+            execution
+                .custom_extension_args(&mut linker, &PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-ext"), OsStr::new("WixFirewallExtension")]
+            );
+        }
+
+        #[test]
+        fn custom_extension_args_with_missing_path_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join(CARGO_MANIFEST_FILE);
+            let execution = Builder::default()
+                .input(manifest.to_str())
+                .extensions(Some(vec!["Missing.dll"]))
+                .build();
+            let mut linker = execution.linker().unwrap();
+            match execution.custom_extension_args(&mut linker, &Value::Table(Table::new())) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("Missing.dll")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn is_extension_path_works() {
+            assert!(!Execution::is_extension_path("WixFirewallExtension"));
+            assert!(Execution::is_extension_path("Custom.dll"));
+            assert!(Execution::is_extension_path("Custom.Dll"));
+            assert!(Execution::is_extension_path("extensions/Custom"));
+            assert!(Execution::is_extension_path("C:\\extensions\\Custom"));
+        }
+
+        #[test]
+        fn util_extension_arg_with_defaults_works() {
+            let execution = Execution::default();
+            let mut compiler = execution.compiler().unwrap();
+            execution.util_extension_arg(&mut compiler);
+            assert_eq!(
+                compiler.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-ext"), OsStr::new("WixUtilExtension")]
+            );
+        }
+
+        #[test]
+        fn util_extension_arg_with_no_util_extension_works() {
+            let execution = Builder::default().no_util_extension(true).build();
+            let manifest = Value::Table(Table::new());
+            let mut compiler = execution.compiler().unwrap();
+            execution.util_extension_arg(&mut compiler);
+            assert!(!compiler
+                .get_args()
+                .any(|a| a == OsStr::new("WixUtilExtension")));
+            let mut linker = execution.linker().unwrap();
+            execution.extension_args(&mut linker, &Cultures::EnUs, &manifest);
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![
+                    OsStr::new("-ext"),
+                    OsStr::new("WixUIExtension"),
+                    OsStr::new("-cultures:en-US"),
+                ]
+            );
+        }
+
+        #[test]
+        fn extension_args_with_no_ui_and_no_util_extension_works() {
+            let execution = Builder::default()
+                .no_ui(true)
+                .no_util_extension(true)
+                .build();
+            let manifest = Value::Table(Table::new());
+            let mut linker = execution.linker().unwrap();
+            execution.extension_args(&mut linker, &Cultures::EnUs, &manifest);
+            assert_eq!(linker.get_args().count(), 0);
+        }
+
+        #[test]
+        fn library_args_with_defaults_works() {
+            let execution = Execution::default();
+            let manifest = Value::Table(Table::new());
+            let mut linker = execution.linker().unwrap();
+            execution.library_args(&mut linker, &manifest).unwrap();
+            assert_eq!(linker.get_args().count(), 0);
+        }
+
+        #[test]
+        fn library_args_with_libraries_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let library = temp_dir.path().join("components.wixlib");
+            fs::write(&library, "").unwrap();
+            let wixobj = temp_dir.path().join("main.wixobj");
+            let execution = Builder::default()
+                .libraries(Some(vec![library.to_str().unwrap()]))
+                .build();
+            let manifest = Value::Table(Table::new());
+            let mut linker = execution.linker().unwrap();
+            linker.arg(&wixobj);
+            execution.library_args(&mut linker, &manifest).unwrap();
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![wixobj.as_os_str(), library.as_os_str()]
+            );
+        }
+
+        #[test]
+        fn library_args_with_relative_libraries_metadata_works() {
+            const PKG_META_WIX: &str = r#"[package.metadata.wix]
+                libraries = ["wix/components.wixlib"]
+            "#;
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wix = temp_dir.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            fs::write(wix.join("components.wixlib"), "").unwrap();
+            let manifest = temp_dir.path().join(CARGO_MANIFEST_FILE);
+            let execution = Builder::default().input(manifest.to_str()).build();
+            let mut linker = execution.linker().unwrap();
+            execution
+                .library_args(&mut linker, &PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![wix.join("components.wixlib").as_os_str()]
+            );
+        }
+
+        #[test]
+        fn library_args_with_missing_library_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let library = temp_dir.path().join("missing.wixlib");
+            let execution = Builder::default()
+                .libraries(Some(vec![library.to_str().unwrap()]))
+                .build();
+            let mut linker = execution.linker().unwrap();
+            match execution.library_args(&mut linker, &Value::Table(Table::new())) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("missing.wixlib")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn warning_args_with_defaults_works() {
+            let execution = Execution::default();
+            let manifest = Value::Table(Table::new());
+            let mut compiler = execution.compiler().unwrap();
+            execution.warning_args(&mut compiler, &manifest).unwrap();
+            assert_eq!(compiler.get_args().count(), 0);
+            let mut linker = execution.linker().unwrap();
+            execution.warning_args(&mut linker, &manifest).unwrap();
+            assert_eq!(linker.get_args().count(), 0);
+        }
+
+        #[test]
+        fn warning_args_works() {
+            let execution = Builder::default()
+                .suppress_warnings(vec![1076, 1105])
+                .warnings_as_errors(true)
+                .build();
+            let manifest = Value::Table(Table::new());
+            let expected = vec![
+                OsStr::new("-sw1076"),
+                OsStr::new("-sw1105"),
+                OsStr::new("-wx"),
+            ];
+            let mut compiler = execution.compiler().unwrap();
+            execution.warning_args(&mut compiler, &manifest).unwrap();
+            assert_eq!(compiler.get_args().collect::<Vec<&OsStr>>(), expected);
+            let mut linker = execution.linker().unwrap();
+            execution.warning_args(&mut linker, &manifest).unwrap();
+            assert_eq!(linker.get_args().collect::<Vec<&OsStr>>(), expected);
+        }
+
+        #[test]
+        fn warning_args_metadata_works() {
+            const PKG_META_WIX: &str = r#"[package.metadata.wix]
+                suppress-warnings = [1076]
+                warnings-as-errors = true
+            "#;
+            let execution = Execution::default();
+            let manifest = PKG_META_WIX.parse::<Value>().unwrap();
+            let mut linker = execution.linker().unwrap();
+            execution.warning_args(&mut linker, &manifest).unwrap();
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-sw1076"), OsStr::new("-wx")]
+            );
+        }
+
+        #[test]
+        fn warning_args_with_invalid_metadata_fails() {
+            const PKG_META_WIX: &str = r#"[package.metadata.wix]
+                suppress-warnings = ["1076"]
+            "#;
+            let execution = Execution::default();
+            let manifest = PKG_META_WIX.parse::<Value>().unwrap();
+            let mut compiler = execution.compiler().unwrap();
+            assert!(execution.warning_args(&mut compiler, &manifest).is_err());
+        }
+
+        #[test]
+        fn arch_arg_with_defaults_works() {
+            let execution = Execution::default();
+            let mut compiler = execution.compiler().unwrap();
+            execution.arch_arg(&mut compiler, Platform::X64).unwrap();
+            assert_eq!(
+                compiler.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-arch"), OsStr::new("x64")]
+            );
+            let mut compiler = execution.compiler().unwrap();
+            execution.arch_arg(&mut compiler, Platform::X86).unwrap();
+            assert_eq!(
+                compiler.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-arch"), OsStr::new("x86")]
+            );
+        }
+
+        #[test]
+        fn arch_arg_with_candle_arch_works() {
+            let execution = Builder::default().candle_arch(Some("arm64")).build();
+            let mut compiler = execution.compiler().unwrap();
+            execution.arch_arg(&mut compiler, Platform::X64).unwrap();
+            assert_eq!(
+                compiler.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-arch"), OsStr::new("arm64")]
+            );
+        }
+
+        #[test]
+        fn arch_arg_with_invalid_candle_arch_fails() {
+            let execution = Builder::default().candle_arch(Some("amd64")).build();
+            let mut compiler = execution.compiler().unwrap();
+            assert!(execution.arch_arg(&mut compiler, Platform::X64).is_err());
+        }
+
+        #[test]
+        fn logo_arg_with_defaults_works() {
+            let mut compiler = Command::new(WIX_COMPILER);
+            Execution::default().logo_arg(&mut compiler);
+            assert!(compiler.get_args().any(|a| a == "-nologo"));
+        }
+
+        #[test]
+        fn logo_arg_with_show_logo_works() {
+            let mut linker = Command::new(WIX_LINKER);
+            Builder::default()
+                .show_logo(true)
+                .build()
+                .logo_arg(&mut linker);
+            assert!(!linker.get_args().any(|a| a == "-nologo"));
+        }
+
+        #[test]
+        fn logo_arg_with_quiet_works() {
+            let mut linker = Command::new(WIX_LINKER);
+            let execution = Builder::default()
+                .capture_output(false)
+                .show_logo(true)
+                .quiet(true)
+                .build();
+            execution.logo_arg(&mut linker);
+            assert!(linker.get_args().any(|a| a == "-nologo"));
+            assert!(execution.capture_output);
+        }
+
+        #[test]
+        fn profile_var_with_defaults_works() {
+            assert_eq!(Execution::default().profile_var(), PROFILE_VARIABLE);
+        }
+
+        #[test]
+        fn profile_var_with_profile_var_works() {
+            let execution = Builder::default().profile_var(Some("BuildConfig")).build();
+            assert_eq!(execution.profile_var(), "BuildConfig");
+        }
+
+        #[test]
+        fn pdb_arg_with_defaults_works() {
+            let mut linker = Command::new(WIX_LINKER);
+            Execution::default().pdb_arg(&mut linker, Path::new("C:\\Example"));
+            assert!(linker.get_args().any(|a| a == "-spdb"));
+        }
+
+        #[test]
+        fn pdb_arg_with_keep_pdb_works() {
+            let mut linker = Command::new(WIX_LINKER);
+            Builder::default()
+                .keep_pdb(true)
+                .build()
+                .pdb_arg(&mut linker, Path::new("C:\\Example"));
+            assert!(!linker.get_args().any(|a| a == "-spdb"));
+        }
+
+        #[test]
+        fn pdb_arg_with_pdb_output_works() {
+            let base_path = Path::new("C:\\Example");
+            let mut linker = Command::new(WIX_LINKER);
+            Builder::default()
+                .pdb_output(Some("symbols/Example.wixpdb"))
+                .build()
+                .pdb_arg(&mut linker, base_path);
+            let args: Vec<&OsStr> = linker.get_args().collect();
+            assert!(!args.contains(&OsStr::new("-spdb")));
+            assert_eq!(
+                args,
+                vec![
+                    OsStr::new("-pdbout"),
+                    base_path.join("symbols/Example.wixpdb").as_os_str()
+                ]
+            );
+        }
+
+        #[test]
+        fn pdb_arg_with_absolute_pdb_output_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let expected = temp_dir.path().join("Example.wixpdb");
+            let mut linker = Command::new(WIX_LINKER);
+            Builder::default()
+                .pdb_output(expected.to_str())
+                .build()
+                .pdb_arg(&mut linker, Path::new("C:\\Example"));
+            assert_eq!(
+                linker.get_args().collect::<Vec<&OsStr>>(),
+                vec![OsStr::new("-pdbout"), expected.as_os_str()]
+            );
+        }
+
+        fn locale_file(temp_dir: &Path, culture: &str) -> PathBuf {
+            let locale = temp_dir.join("Example.wxl");
+            fs::write(
+                &locale,
+                format!(
+                    r#"<?xml version="1.0" encoding="utf-8"?>
+<WixLocalization Culture="{}" xmlns="http://schemas.microsoft.com/wix/2006/localization">
+  <String Id="Example">Example</String>
+</WixLocalization>"#,
+                    culture
+                ),
+            )
+            .unwrap();
+            locale
+        }
+
+        #[test]
+        fn locale_culture_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let locale = locale_file(temp_dir.path(), "fr-FR");
+            let actual = Execution::locale_culture(&locale).unwrap();
+            assert_eq!(actual, Some(String::from("fr-FR")));
+        }
+
+        #[test]
+        fn validate_locale_with_matching_culture_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let locale = locale_file(temp_dir.path(), "fr-FR");
+            assert!(Execution::validate_locale(&Cultures::FrFr, &locale).is_ok());
+        }
+
+        #[test]
+        fn validate_locale_with_different_case_culture_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let locale = locale_file(temp_dir.path(), "en-us");
+            assert!(Execution::validate_locale(&Cultures::EnUs, &locale).is_ok());
+        }
+
+        #[test]
+        fn validate_locale_with_mismatched_culture_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let locale = locale_file(temp_dir.path(), "en-US");
+            assert!(Execution::validate_locale(&Cultures::FrFr, &locale).is_err());
+        }
+
+        #[test]
+        fn validate_locale_without_culture_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let locale = temp_dir.path().join("Example.wxl");
+            fs::write(
+                &locale,
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<WixLocalization xmlns="http://schemas.microsoft.com/wix/2006/localization">
+  <String Id="Example">Example</String>
+</WixLocalization>"#,
+            )
+            .unwrap();
+            assert!(Execution::validate_locale(&Cultures::FrFr, &locale).is_ok());
+        }
+
+        #[test]
+        fn defines_hash_works() {
+            let mut release = Command::new("candle");
+            release.arg("-dProfile=release").arg("-dVersion=1.0.0");
+            let mut other_release = Command::new("candle");
+            other_release
+                .arg("-dProfile=release")
+                .arg("-dVersion=1.0.0");
+            let mut other_version = Command::new("candle");
+            other_version
+                .arg("-dProfile=release")
+                .arg("-dVersion=1.0.1");
+            let wxs_sources = vec![PathBuf::from("wix\\main.wxs")];
+            assert_eq!(
+                Execution::defines_hash(&release, &wxs_sources),
+                Execution::defines_hash(&other_release, &wxs_sources)
+            );
+            assert_ne!(
+                Execution::defines_hash(&release, &wxs_sources),
+                Execution::defines_hash(&other_version, &wxs_sources)
+            );
+        }
+
+        #[test]
+        fn defines_hash_with_different_sources_works() {
+            let mut compiler = Command::new("candle");
+            compiler.arg("-dProfile=release");
+            let main = vec![PathBuf::from("wix\\main.wxs")];
+            let main_and_other = vec![
+                PathBuf::from("wix\\main.wxs"),
+                PathBuf::from("wix\\other.wxs"),
+            ];
+            assert_ne!(
+                Execution::defines_hash(&compiler, &main),
+                Execution::defines_hash(&compiler, &main_and_other)
+            );
+        }
+
+        #[test]
+        fn defines_hash_is_stable() {
+            // The SHA-256 digest of an empty input, i.e. no arguments and no
+            // sources.
+            assert_eq!(
+                Execution::defines_hash(&Command::new("candle"), &[]),
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            );
+        }
+
+        #[test]
+        fn is_compiled_works() {
+            const DEFINES_HASH: &str = "0123456789abcdef";
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            let wixobj = temp_dir.path().join("main.wixobj");
+            fs::write(&wxs, "").unwrap();
+            fs::write(&wixobj, "").unwrap();
+            let wxs_modified = fs::metadata(&wxs).unwrap().modified().unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&wixobj)
+                .unwrap()
+                .set_modified(wxs_modified + std::time::Duration::from_secs(1))
+                .unwrap();
+            let wxs_sources = vec![wxs];
+            assert!(!Execution::is_compiled(
+                &wxs_sources,
+                temp_dir.path(),
+                DEFINES_HASH
+            ));
+            fs::write(temp_dir.path().join(DEFINES_HASH_FILE_NAME), DEFINES_HASH).unwrap();
+            assert!(Execution::is_compiled(
+                &wxs_sources,
+                temp_dir.path(),
+                DEFINES_HASH
+            ));
+            assert!(!Execution::is_compiled(
+                &wxs_sources,
+                temp_dir.path(),
+                "fedcba9876543210"
+            ));
+        }
+
+        #[test]
+        fn is_compiled_with_older_wixobj_works() {
+            const DEFINES_HASH: &str = "0123456789abcdef";
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            let wixobj = temp_dir.path().join("main.wixobj");
+            fs::write(&wxs, "").unwrap();
+            fs::write(&wixobj, "").unwrap();
+            fs::write(temp_dir.path().join(DEFINES_HASH_FILE_NAME), DEFINES_HASH).unwrap();
+            let wxs_modified = fs::metadata(&wxs).unwrap().modified().unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&wixobj)
+                .unwrap()
+                .set_modified(wxs_modified - std::time::Duration::from_secs(1))
+                .unwrap();
+            assert!(!Execution::is_compiled(
+                &[wxs],
+                temp_dir.path(),
+                DEFINES_HASH
+            ));
+        }
+
+        #[test]
+        fn is_compiled_with_missing_wixobj_works() {
+            const DEFINES_HASH: &str = "0123456789abcdef";
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            fs::write(&wxs, "").unwrap();
+            fs::write(temp_dir.path().join(DEFINES_HASH_FILE_NAME), DEFINES_HASH).unwrap();
+            assert!(!Execution::is_compiled(
+                &[wxs],
+                temp_dir.path(),
+                DEFINES_HASH
+            ));
+        }
+
+        #[test]
+        fn is_compiled_with_newer_include_works() {
+            const DEFINES_HASH: &str = "0123456789abcdef";
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            let wxi = temp_dir.path().join("variables.wxi");
+            let wixobj = temp_dir.path().join("main.wixobj");
+            fs::write(&wxs, "<Wix>\n  <?include variables.wxi ?>\n</Wix>").unwrap();
+            fs::write(&wxi, "<Include/>").unwrap();
+            fs::write(&wixobj, "").unwrap();
+            fs::write(temp_dir.path().join(DEFINES_HASH_FILE_NAME), DEFINES_HASH).unwrap();
+            let wxs_modified = fs::metadata(&wxs).unwrap().modified().unwrap();
+            let set_modified = |path: &Path, secs: u64| {
+                fs::File::options()
+                    .write(true)
+                    .open(path)
+                    .unwrap()
+                    .set_modified(wxs_modified + std::time::Duration::from_secs(secs))
+                    .unwrap();
+            };
+            set_modified(&wixobj, 1);
+            set_modified(&wxi, 0);
+            let wxs_sources = vec![wxs];
+            assert!(Execution::is_compiled(
+                &wxs_sources,
+                temp_dir.path(),
+                DEFINES_HASH
+            ));
+            set_modified(&wxi, 2);
+            assert!(!Execution::is_compiled(
+                &wxs_sources,
+                temp_dir.path(),
+                DEFINES_HASH
+            ));
+        }
+
+        #[test]
+        fn included_files_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            let include = temp_dir.path().join("include");
+            fs::create_dir(&include).unwrap();
+            fs::write(
+                &wxs,
+                "<?include include/first.wxi ?><?include \"second.wxi\"?>",
+            )
+            .unwrap();
+            fs::write(include.join("first.wxi"), "<?include ../second.wxi ?>").unwrap();
+            fs::write(
+                temp_dir.path().join("second.wxi"),
+                "<?include include/../main.wxs ?>",
+            )
+            .unwrap();
+            assert_eq!(
+                Execution::included_files(&wxs),
+                Some(vec![
+                    fs::canonicalize(include.join("first.wxi")).unwrap(),
+                    fs::canonicalize(temp_dir.path().join("second.wxi")).unwrap(),
+                ])
+            );
+        }
+
+        #[test]
+        fn included_files_with_missing_include_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            fs::write(&wxs, "<?include $(var.Folder)\\variables.wxi ?>").unwrap();
+            assert_eq!(Execution::included_files(&wxs), None);
+        }
+
+        #[test]
+        fn include_directives_works() {
+            assert_eq!(
+                include_directives("<?include a.wxi?>\n<?include  \"b c.wxi\" ?><?include ?>"),
+                vec!["a.wxi", "b c.wxi"]
+            );
+            assert!(include_directives("<?define Version = \"1.0.0\" ?>").is_empty());
+        }
+
+        #[test]
+        fn sorted_wixobj_sources_works() {
+            let wixobj_destination = PathBuf::from("target").join("wix");
+            let listing = vec![
+                wixobj_destination.join("main.wixobj"),
+                wixobj_destination.join(DEFINES_HASH_FILE_NAME),
+                wixobj_destination.join("bundle.wixobj"),
+                wixobj_destination.join("zebra.wixobj"),
+                wixobj_destination.join("alpha.wixobj"),
+            ];
+            assert_eq!(
+                Execution::sorted_wixobj_sources(listing.into_iter()),
+                vec![
+                    wixobj_destination.join("alpha.wixobj"),
+                    wixobj_destination.join("bundle.wixobj"),
+                    wixobj_destination.join("main.wixobj"),
+                    wixobj_destination.join("zebra.wixobj"),
+                ]
+            );
+        }
+
+        #[test]
+        fn expected_wixobj_sources_works() {
+            let wixobj_destination = PathBuf::from("target").join("wix");
+            let wxs_sources = vec![
+                PathBuf::from("wix").join("main.wxs"),
+                PathBuf::from("wix").join("bundle.wxs"),
+            ];
+            assert_eq!(
+                Execution::expected_wixobj_sources(&wixobj_destination, &wxs_sources),
+                vec![
+                    wixobj_destination.join("main.wixobj"),
+                    wixobj_destination.join("bundle.wixobj"),
+                ]
+            );
+        }
+
+        const ALL_PKG_META_WIX: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+
+            [package.metadata.wix]
+            compiler-args = ["-nologo", "-wx"]
+            culture = "Fr-Fr"
+            dbg-build = true
+            dbg-name = true
+            features = ["gui"]
+            linker-args = ["-nologo"]
+            locale = "wix/French.wxl"
+            name = "Different"
+            no-build = true
+            output = "target/wix/test.msi"
+            version = "2.1.0"
+        "#;
+
+        #[test]
+        fn wix_metadata_works() {
+            let manifest = ALL_PKG_META_WIX.parse::<Value>().unwrap();
+            assert_eq!(wix_metadata(&manifest).map(|t| t.len()), Some(11));
+        }
+
+        #[test]
+        fn wix_metadata_without_section_works() {
+            let manifest = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+            "#
+            .parse::<Value>()
+            .unwrap();
+            assert!(wix_metadata(&manifest).is_none());
+            assert!(meta_str(&manifest, "name").is_none());
+            assert!(meta_bool(&manifest, "no-build").is_none());
+            assert!(meta_array(&manifest, "include").is_none());
+        }
+
+        #[test]
+        fn expand_env_works() {
+            env::set_var("CARGO_WIX_TEST_EXPAND", "C:\\Out");
+            let dollar = expand_env("$CARGO_WIX_TEST_EXPAND/installers/");
+            let braced = expand_env("${CARGO_WIX_TEST_EXPAND}installers");
+            let percent = expand_env("%CARGO_WIX_TEST_EXPAND%\\installers");
+            env::remove_var("CARGO_WIX_TEST_EXPAND");
+            assert_eq!(dollar.unwrap(), "C:\\Out/installers/");
+            assert_eq!(braced.unwrap(), "C:\\Outinstallers");
+            assert_eq!(percent.unwrap(), "C:\\Out\\installers");
+        }
+
+        #[test]
+        fn expand_env_without_references_works() {
+            for value in &[
+                "target/wix/",
+                "\\\\server\\c$\\installers",
+                "100% $ %",
+                "$1/installers",
+                "${}",
+                "${CARGO_WIX_TEST_UNCLOSED",
+                "%%",
+                "",
+            ] {
+                assert_eq!(expand_env(value).unwrap(), *value);
+            }
+        }
+
+        #[test]
+        fn expand_env_with_unset_variable_fails() {
+            for value in &[
+                "$CARGO_WIX_TEST_UNSET/installers",
+                "${CARGO_WIX_TEST_UNSET}/installers",
+                "%CARGO_WIX_TEST_UNSET%\\installers",
+            ] {
+                assert!(expand_env(value).is_err());
+            }
+        }
+
+        #[test]
+        fn meta_path_works() {
+            let manifest = r#"[package.metadata.wix]
+                eula = "$CARGO_WIX_TEST_META_PATH/License.rtf"
+            "#
+            .parse::<Value>()
+            .unwrap();
+            env::set_var("CARGO_WIX_TEST_META_PATH", "legal");
+            let eula = meta_path(&manifest, "eula");
+            env::remove_var("CARGO_WIX_TEST_META_PATH");
+            assert_eq!(eula.unwrap(), Some(PathBuf::from("legal/License.rtf")));
+            assert_eq!(meta_path(&manifest, "output").unwrap(), None);
+        }
+
+        #[test]
+        fn meta_accessors_with_wrong_type_work() {
+            let manifest = ALL_PKG_META_WIX.parse::<Value>().unwrap();
+            assert!(meta_str(&manifest, "dbg-build").is_none());
+            assert!(meta_bool(&manifest, "name").is_none());
+            assert!(meta_array(&manifest, "culture").is_none());
+        }
+
+        #[test]
+        fn meta_accessors_with_nested_key_work() {
+            let manifest = r#"[package.metadata.wix.sign]
+                digest = "sha384"
+            "#
+            .parse::<Value>()
+            .unwrap();
+            assert!(meta_value(&manifest, "sign").unwrap().is_table());
+            assert_eq!(meta_str(&manifest, "sign.digest"), Some("sha384"));
+            assert!(meta_str(&manifest, "sign.pfx").is_none());
+            assert!(meta_str(&manifest, "digest").is_none());
+        }
+
+        #[test]
+        fn all_metadata_keys_resolve() {
+            let manifest = ALL_PKG_META_WIX.parse::<Value>().unwrap();
+            let execution = Execution::default();
+            assert_eq!(
+                execution.compiler_args(&manifest),
+                Some(vec![String::from("-nologo"), String::from("-wx")])
+            );
+            assert_eq!(execution.culture(&manifest).unwrap(), Cultures::FrFr);
+            assert!(execution.debug_build(&manifest));
+            assert!(execution.debug_name(&manifest));
+            assert_eq!(
+                execution.features(&manifest),
+                Some(vec![String::from("gui")])
+            );
+            assert_eq!(
+                execution.linker_args(&manifest),
+                Some(vec![String::from("-nologo")])
+            );
+            assert_eq!(
+                execution.locale(&manifest).unwrap(),
+                Some(PathBuf::from("wix/French.wxl"))
+            );
+            assert_eq!(execution.name(&manifest).unwrap(), "Different");
+            assert!(execution.no_build(&manifest));
+            assert_eq!(
+                execution
+                    .installer_destination(
+                        "Different",
+                        &"2.1.0".parse::<Version>().unwrap(),
+                        Platform::X64,
+                        false,
+                        &InstallerKind::default(),
+                        &manifest,
+                        Path::new(TARGET_FOLDER_NAME),
+                    )
+                    .unwrap(),
+                PathBuf::from("target/wix/test.msi")
+            );
+            assert_eq!(
+                execution.version(&manifest).unwrap(),
+                "2.1.0".parse::<Version>().unwrap()
+            );
+        }
+
+        const WORKSPACE_MANIFEST: &str = r#"[workspace]
+            members = ["member"]
+
+            [workspace.metadata.wix]
+            culture = "Fr-Fr"
+            name = "Workspace"
+        "#;
+
+        const MEMBER_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+
+            [package.metadata.wix]
+            name = "Package"
+        "#;
+
+        fn workspace(temp_dir: &Path, member_manifest: &str) -> PathBuf {
+            fs::write(temp_dir.join(CARGO_MANIFEST_FILE), WORKSPACE_MANIFEST).unwrap();
+            let member_dir = temp_dir.join("member");
+            fs::create_dir(&member_dir).unwrap();
+            let member_manifest_path = member_dir.join(CARGO_MANIFEST_FILE);
+            fs::write(&member_manifest_path, member_manifest).unwrap();
+            member_manifest_path
+        }
+
+        #[test]
+        fn workspace_metadata_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = workspace(temp_dir.path(), MEMBER_MANIFEST);
+            let mut manifest = MEMBER_MANIFEST.parse::<Value>().unwrap();
+            let workspace = workspace_manifest(&manifest_path, &manifest)
+                .unwrap()
+                .unwrap();
+            merge_workspace_metadata(&mut manifest, &workspace);
+            let execution = Execution::default();
+            assert_eq!(execution.culture(&manifest).unwrap(), Cultures::FrFr);
+            assert_eq!(execution.name(&manifest).unwrap(), "Package");
+        }
+
+        #[test]
+        fn workspace_metadata_without_package_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+            "#;
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = workspace(temp_dir.path(), MANIFEST);
+            let mut manifest = MANIFEST.parse::<Value>().unwrap();
+            let workspace = workspace_manifest(&manifest_path, &manifest)
+                .unwrap()
+                .unwrap();
+            merge_workspace_metadata(&mut manifest, &workspace);
+            assert_eq!(meta_str(&manifest, "culture"), Some("Fr-Fr"));
+            assert_eq!(meta_str(&manifest, "name"), Some("Workspace"));
+        }
+
+        #[test]
+        fn workspace_manifest_with_workspace_field_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                workspace = "../root"
+            "#;
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let root_dir = temp_dir.path().join("root");
+            fs::create_dir(&root_dir).unwrap();
+            fs::write(root_dir.join(CARGO_MANIFEST_FILE), WORKSPACE_MANIFEST).unwrap();
+            let member_dir = temp_dir.path().join("member");
+            fs::create_dir(&member_dir).unwrap();
+            let manifest_path = member_dir.join(CARGO_MANIFEST_FILE);
+            fs::write(&manifest_path, MANIFEST).unwrap();
+            let workspace =
+                workspace_manifest(&manifest_path, &MANIFEST.parse::<Value>().unwrap()).unwrap();
+            assert_eq!(
+                workspace,
+                Some(WORKSPACE_MANIFEST.parse::<Value>().unwrap())
+            );
+        }
+
+        const INHERITED_WORKSPACE_MANIFEST: &str = r#"[workspace]
+            members = ["member"]
+
+            [workspace.package]
+            authors = ["First Last <first.last@example.com>"]
+            description = "An example description"
+            version = "1.2.3"
+        "#;
+
+        const INHERITED_MEMBER_MANIFEST: &str = r#"[package]
+            name = "Example"
+            authors.workspace = true
+            description = { workspace = true }
+            version.workspace = true
+        "#;
+
+        fn inherited_workspace(temp_dir: &Path) -> PathBuf {
+            fs::write(
+                temp_dir.join(CARGO_MANIFEST_FILE),
+                INHERITED_WORKSPACE_MANIFEST,
+            )
+            .unwrap();
+            let member_dir = temp_dir.join("member");
+            fs::create_dir(&member_dir).unwrap();
+            let member_manifest_path = member_dir.join(CARGO_MANIFEST_FILE);
+            fs::write(&member_manifest_path, INHERITED_MEMBER_MANIFEST).unwrap();
+            member_manifest_path
+        }
+
+        #[test]
+        fn manifest_with_inherited_version_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = inherited_workspace(temp_dir.path());
+            let manifest = crate::manifest(Some(&manifest_path)).unwrap();
+            assert_eq!(
+                Execution::default().version(&manifest).unwrap(),
+                "1.2.3".parse::<Version>().unwrap()
+            );
+        }
+
+        #[test]
+        fn version_with_inherited_version_and_overrides_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version.workspace = true
+
+                [package.metadata.wix]
+                version = "2.0.0"
+            "#;
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = inherited_workspace(temp_dir.path());
+            fs::write(&manifest_path, MANIFEST).unwrap();
+            let manifest = crate::manifest(Some(&manifest_path)).unwrap();
+            assert_eq!(
+                Execution::default().version(&manifest).unwrap(),
+                "2.0.0".parse::<Version>().unwrap()
+            );
+            assert_eq!(
+                Builder::default()
+                    .version(Some("3.0.0"))
+                    .build()
+                    .version(&manifest)
+                    .unwrap(),
+                "3.0.0".parse::<Version>().unwrap()
+            );
+        }
+
+        #[test]
+        fn version_with_unresolved_inherited_version_fails() {
+            let manifest = INHERITED_MEMBER_MANIFEST.parse::<Value>().unwrap();
+            match Execution::default().version(&manifest) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("[workspace.package]")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn manifest_with_inherited_description_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = inherited_workspace(temp_dir.path());
+            let manifest = crate::manifest(Some(&manifest_path)).unwrap();
+            assert_eq!(
+                crate::description(None, &manifest),
+                Some(String::from("An example description"))
+            );
+        }
+
+        #[test]
+        fn inherit_workspace_package_without_workspace_package_works() {
+            let expected = INHERITED_MEMBER_MANIFEST.parse::<Value>().unwrap();
+            let mut actual = expected.clone();
+            inherit_workspace_package(&mut actual, &"[workspace]".parse::<Value>().unwrap());
+            assert_eq!(actual, expected);
+            assert!(has_inherited_fields(&actual));
+        }
+
+        #[test]
+        fn merge_workspace_metadata_without_workspace_metadata_works() {
+            let expected = MEMBER_MANIFEST.parse::<Value>().unwrap();
+            let mut actual = expected.clone();
+            merge_workspace_metadata(&mut actual, &"[workspace]".parse::<Value>().unwrap());
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn message_format_from_str_works() {
+            assert_eq!(
+                MessageFormat::from_str("JSON").unwrap(),
+                MessageFormat::Json
+            );
+            assert_eq!(
+                MessageFormat::from_str("human").unwrap(),
+                MessageFormat::Human
+            );
+            assert!(MessageFormat::from_str("xml").is_err());
+        }
+
+        #[test]
+        fn checksum_kind_from_str_works() {
+            assert_eq!(
+                ChecksumKind::from_str("SHA256").unwrap(),
+                ChecksumKind::Sha256
+            );
+            assert_eq!(
+                ChecksumKind::from_str("sha512").unwrap(),
+                ChecksumKind::Sha512
+            );
+            assert!(ChecksumKind::from_str("md5").is_err());
+        }
+
+        #[test]
+        fn summary_to_json_works() {
+            let summary = Summary {
+                name: String::from("Example"),
+                version: String::from("0.1.0"),
+                platform: Platform::X64.to_string(),
+                culture: Cultures::EnUs.to_string(),
+                output: PathBuf::from("target/wix/Example-0.1.0-x86_64.msi"),
+                size: 4096,
+                elapsed_seconds: 1.5,
+            };
+            let actual = summary.to_json().unwrap();
+            assert!(!actual.contains('\n'));
+            let json: serde_json::Value = serde_json::from_str(&actual).unwrap();
+            assert_eq!(json["name"], "Example");
+            assert_eq!(json["version"], "0.1.0");
+            assert_eq!(json["platform"], "x64");
+            assert_eq!(json["culture"], "en-US");
+            assert_eq!(json["output"], "target/wix/Example-0.1.0-x86_64.msi");
+            assert_eq!(json["size"], 4096);
+            assert_eq!(json["elapsed_seconds"], 1.5);
+        }
+
+        /// Creates a package with a WiX Source (wxs) file and returns the
+        /// path to its manifest (Cargo.toml).
+        fn package_with_wxs(temp_dir: &Path, manifest: &str) -> PathBuf {
+            let wix = temp_dir.join(WIX);
+            fs::create_dir(&wix).unwrap();
+            fs::write(wix.join("main.wxs"), "").unwrap();
+            let manifest_path = temp_dir.join(CARGO_MANIFEST_FILE);
+            fs::write(&manifest_path, manifest).unwrap();
+            manifest_path
+        }
+
+        #[test]
+        fn resolved_config_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "1.2.3"
+
+                [package.metadata.wix]
+                culture = "fr-FR"
+                extensions = ["WixFirewallExtension"]
+            "#;
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = package_with_wxs(temp_dir.path(), MANIFEST);
+            let target = temp_dir.path().join("target");
+            let execution = Builder::default()
+                .input(manifest_path.to_str())
+                .target_dir(target.to_str())
+                .build();
+            let config = execution
+                .resolved_config(&MANIFEST.parse::<Value>().unwrap())
+                .unwrap();
+            let platform = execution.platform();
+            assert_eq!(config.name(), "Example");
+            assert_eq!(config.version(), "1.2.3");
+            assert_eq!(config.product_version(), "1.2.3");
+            assert_eq!(config.platform(), platform.to_string());
+            assert_eq!(config.culture(), "fr-FR");
+            assert!(config.locale().is_none());
+            assert_eq!(
+                config.output(),
+                target
+                    .join(WIX)
+                    .join(format!("Example-1.2.3-{}.msi", platform.arch()))
+            );
+            assert_eq!(
+                config.sources(),
+                &[temp_dir.path().join(WIX).join("main.wxs")]
+            );
+            assert_eq!(
+                config.defines(),
+                &[
+                    String::from("Profile=release"),
+                    String::from("Version=1.2.3"),
+                    format!("Platform={}", platform),
+                    format!("CargoTargetDir={}", target.display()),
+                ]
+            );
+            assert_eq!(
+                config.extensions(),
+                &["WixUIExtension", "WixUtilExtension", "WixFirewallExtension"]
+            );
+        }
+
+        #[test]
+        fn resolved_config_with_builder_values_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "1.2.3"
+            "#;
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = package_with_wxs(temp_dir.path(), MANIFEST);
+            let config = Builder::default()
+                .input(manifest_path.to_str())
+                .name(Some("Other"))
+                .version(Some("2.0.0-beta.1+abc"))
+                .debug_build(true)
+                .profile_var(Some("BuildConfig"))
+                .no_ui(true)
+                .no_util_extension(true)
+                .build()
+                .resolved_config(&MANIFEST.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(config.name(), "Other");
+            assert_eq!(config.version(), "2.0.0-beta.1+abc");
+            assert_eq!(config.product_version(), "2.0.0");
+            assert_eq!(config.culture(), "en-US");
+            assert_eq!(config.defines()[0], "BuildConfig=debug");
+            assert_eq!(config.defines()[1], "Version=2.0.0-beta.1+abc");
+            assert!(config.extensions().is_empty());
+        }
+
+        #[test]
+        fn summary_new_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            fs::write(&installer, vec![0u8; 1024]).unwrap();
+            let summary = Summary::new(
+                String::from("Example"),
+                String::from("0.1.0"),
+                Platform::X64.to_string(),
+                Cultures::EnUs.to_string(),
+                installer.clone(),
+                Duration::from_millis(1500),
+            )
+            .unwrap();
+            assert_eq!(summary.name(), "Example");
+            assert_eq!(summary.output(), installer.as_path());
+            assert_eq!(summary.size(), 1024);
+            assert_eq!(summary.elapsed_seconds(), 1.5);
+        }
+
+        #[test]
+        fn summary_new_with_missing_installer_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            assert!(Summary::new(
+                String::from("Example"),
+                String::from("0.1.0"),
+                Platform::X64.to_string(),
+                Cultures::EnUs.to_string(),
+                temp_dir.path().join("Example-0.1.0-x86_64.msi"),
+                Duration::from_secs(1),
+            )
+            .is_err());
+        }
+
+        #[test]
+        fn init_logging_twice_works() {
+            crate::init_logging(LevelFilter::Debug);
+            assert_eq!(log::max_level(), LevelFilter::Debug);
+            crate::init_logging(LevelFilter::Info);
+            assert_eq!(log::max_level(), LevelFilter::Info);
+        }
+
+        #[test]
+        fn command_line_works() {
+            let mut command = Command::new("light");
+            command
+                .arg("-out")
+                .arg("C:\\Program Files\\Example\\example.msi")
+                .arg("-b")
+                .arg("wix")
+                .arg("say \"hello\"");
+            assert_eq!(
+                command_line(&command),
+                "light -out \"C:\\Program Files\\Example\\example.msi\" -b wix \"say \\\"hello\\\"\""
+            );
+        }
+
+        #[test]
+        fn last_lines_works() {
+            let text = "one\ntwo\nthree\nfour\n\n";
+            assert_eq!(last_lines(text, 2), "three\nfour");
+        }
+
+        #[test]
+        fn last_lines_with_fewer_lines_works() {
+            assert_eq!(last_lines("one\ntwo\n", 20), "one\ntwo");
+            assert_eq!(last_lines("", 20), "");
+        }
+
+        #[test]
+        fn build_error_works() {
+            let err = Error::Build {
+                code: 101,
+                stderr: String::from("error[E0425]: cannot find value `x` in this scope"),
+            };
+            assert_eq!(err.code(), 15);
+            assert_eq!(
+                err.to_string(),
+                "The 'cargo' application failed with exit code = 101:\n\n\
+                 error[E0425]: cannot find value `x` in this scope"
+            );
         }
     }
 
@@ -20,7 +20,6 @@ use crate::RTF_FILE_EXTENSION;
 
 use std::fmt;
 use std::path::PathBuf;
-use std::str::FromStr;
 
 use toml::Value;
 
@@ -41,6 +40,32 @@ impl Eula {
         }
     }
 
+    pub fn from_metadata(path: PathBuf) -> Result<Self> {
+        debug!("path = {:?}", path);
+        if path.extension().and_then(|s| s.to_str()) != Some(RTF_FILE_EXTENSION) {
+            Err(Error::Generic(format!(
+                "The '{}' file to be used for the EULA specified in the package's \
+                 manifest (Cargo.toml) using the 'package.metadata.wix.eula' field does \
+                 not have a RTF file extension.",
+                path.display()
+            )))
+        } else if path.exists() {
+            trace!(
+                "The '{}' path from the 'package.metadata.wix.eula' field of the package's \
+                 manifest (Cargo.toml) exists and has a RTF file extension.",
+                path.display()
+            );
+            Ok(Eula::Manifest(path))
+        } else {
+            Err(Error::Generic(format!(
+                "The '{}' file to be used for the EULA specified in the package's \
+                 manifest (Cargo.toml) using the 'package.metadata.wix.eula' field does \
+                 not exist.",
+                path.display()
+            )))
+        }
+    }
+
     pub fn from_manifest(manifest: &Value) -> Result<Self> {
         if let Some(license_file_path) = manifest
             .get("package")
@@ -88,7 +113,7 @@ impl Eula {
         {
             trace!("The 'license' field is specified in the package's manifest (Cargo.toml)");
             debug!("license_name = {:?}", license_name);
-            if let Ok(template) = Template::from_str(license_name) {
+            if let Some(template) = Template::from_license_expression(license_name) {
                 trace!(
                     "An embedded template for the '{}' license from the package's \
                      manifest (Cargo.toml) exists.",
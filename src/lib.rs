@@ -55,6 +55,38 @@
 //! `Builder` struct generally corresponds to a CLI option or argument found in
 //! the [`cargo wix`] subcommand and binary.
 //!
+//! The methods of a `Builder` take and return a mutable reference, and the
+//! `build` method only borrows the `Builder`, so the methods can be chained
+//! on a temporary `Builder` without binding it to a variable:
+//!
+//! ```rust
+//! use wix::create;
+//!
+//! let execution = create::Builder::new()
+//!     .name(Some("Example"))
+//!     .version(Some("1.2.3"))
+//!     .no_build(true)
+//!     .build();
+//! # let _ = execution;
+//! ```
+//!
+//! A `Builder` bound to a variable can be customized conditionally and used to
+//! build more than one `Execution`:
+//!
+//! ```rust
+//! use wix::create;
+//!
+//! let debug = true;
+//! let mut builder = create::Builder::new();
+//! builder.name(Some("Example")).version(Some("1.2.3"));
+//! if debug {
+//!     builder.debug_build(true).debug_name(true);
+//! }
+//! let debug_execution = builder.build();
+//! let release_execution = builder.debug_build(false).debug_name(false).build();
+//! # let _ = (debug_execution, release_execution);
+//! ```
+//!
 //! [binary]: ../cargo_wix/index.html
 //! [Builder]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
 //! [cargo subcommand]: https://github.com/rust-lang/cargo/wiki/Third-party-cargo-subcommands
@@ -63,13 +95,16 @@
 
 extern crate chrono;
 extern crate encoding_rs_io;
+extern crate env_logger;
+extern crate glob;
 #[macro_use]
 extern crate log;
 #[cfg_attr(test, macro_use)]
 extern crate maplit;
 extern crate mustache;
-extern crate regex;
 extern crate semver;
+extern crate serde;
+extern crate serde_json;
 extern crate sxd_document;
 extern crate sxd_xpath;
 extern crate toml;
@@ -77,6 +112,7 @@ extern crate uuid;
 
 pub use crate::templates::Template;
 
+pub mod check;
 pub mod clean;
 pub mod create;
 mod eula;
@@ -86,15 +122,21 @@ pub mod purge;
 pub mod sign;
 mod templates;
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::default::Default;
 use std::env;
 use std::error::Error as StdError;
-use std::ffi::OsStr;
 use std::fmt;
 use std::fs::File;
 use std::io::{self, ErrorKind, Read};
 use std::path::{Path, PathBuf};
+use std::process::{Child, ExitStatus, Output};
 use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::LevelFilter;
 
 use toml::Value;
 
@@ -107,15 +149,43 @@ pub const CARGO_MANIFEST_FILE: &str = "Cargo.toml";
 /// The name of the builder application for a Rust project.
 pub const CARGO: &str = "cargo";
 
+/// The name of the environment variable used by Cargo to specify the path to
+/// the builder application, such as when Cargo is invoked through a wrapper.
+pub const CARGO_KEY: &str = "CARGO";
+
+/// The name of the environment variable used by Cargo to relocate the `target`
+/// folder.
+pub const CARGO_TARGET_DIR_KEY: &str = "CARGO_TARGET_DIR";
+
+/// The name of the environment variable with the path to the created
+/// installer that is set for the post-build command.
+pub const CARGO_WIX_MSI_KEY: &str = "CARGO_WIX_MSI";
+
+/// The name of the environment variable with the product name that is set
+/// for the pre-build command.
+pub const CARGO_WIX_PRODUCT_NAME_KEY: &str = "CARGO_WIX_PRODUCT_NAME";
+
+/// The name of the environment variable with the product version that is set
+/// for the pre-build command.
+pub const CARGO_WIX_VERSION_KEY: &str = "CARGO_WIX_VERSION";
+
 /// The file extension for an executable.
 pub const EXE_FILE_EXTENSION: &str = "exe";
 
+/// The name of the version control application used to check for
+/// uncommitted changes.
+pub const GIT: &str = "git";
+
 /// The file name without an extension when generating a license.
 pub const LICENSE_FILE_NAME: &str = "License";
 
 /// The file extension for a Windows installer.
 pub const MSI_FILE_EXTENSION: &str = "msi";
 
+/// The default name of the WiX Toolset preprocessor variable for the build
+/// profile, i.e. `$(var.Profile)`, which is either `debug` or `release`.
+pub const PROFILE_VARIABLE: &str = "Profile";
+
 /// The file extension for a Rich Text Format (RTF) file.
 pub const RTF_FILE_EXTENSION: &str = "rtf";
 
@@ -163,16 +233,41 @@ pub const WIX_SOURCE_FILE_NAME: &str = "main";
 /// [`Result`]: https://doc.rust-lang.org/std/result/
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Resolves the path to a package's manifest (Cargo.toml) from an input path.
+///
+/// Similar to the `--manifest-path` option for `cargo`, the input path can be
+/// a path to the manifest or a path to a folder containing the manifest. A
+/// path to a file is used verbatim, regardless of its name, so a renamed
+/// manifest, such as `Alt.toml`, can be used. If the folder does not contain a
+/// manifest, then the input path is used as-is, so the error for the missing
+/// manifest is reported when the manifest is read.
+fn manifest_input<P: AsRef<Path>>(input: P) -> PathBuf {
+    let path = input.as_ref();
+    let manifest = path.join(CARGO_MANIFEST_FILE);
+    if path.is_dir() && manifest.is_file() {
+        manifest
+    } else {
+        path.to_owned()
+    }
+}
+
 fn cargo_toml_file(input: Option<&PathBuf>) -> Result<PathBuf> {
     let i = match input {
-        Some(i) => i.to_owned(),
+        Some(i) => manifest_input(i),
         None => {
             let mut cwd = env::current_dir()?;
             cwd.push(CARGO_MANIFEST_FILE);
             cwd
         }
     };
-    if i.exists() && i.is_file() && i.file_name() == Some(OsStr::new(CARGO_MANIFEST_FILE)) {
+    if i.is_dir() {
+        return Err(Error::Generic(format!(
+            "The '{}' folder does not contain a package's manifest ({})",
+            i.display(),
+            CARGO_MANIFEST_FILE
+        )));
+    }
+    if i.is_file() {
         Ok(i)
     } else {
         Err(Error::not_found(&i))
@@ -187,13 +282,39 @@ fn package_root(input: Option<&PathBuf>) -> Result<PathBuf> {
     })
 }
 
+/// Initializes the logger for the logging statements of this crate and sets
+/// the maximum level for logging statements.
+///
+/// A logger is only installed if one has not already been installed, either by
+/// a previous call or by the application, so this can be called any number of
+/// times. This is only called when a level has been explicitly set, so an
+/// application embedding this crate can install its own logger.
+fn init_logging(level: LevelFilter) {
+    // An error only indicates a logger is already installed, which is kept.
+    let _ = env_logger::Builder::new()
+        .filter(Some("wix"), LevelFilter::Trace)
+        .is_test(cfg!(test))
+        .try_init();
+    log::set_max_level(level);
+}
+
 fn manifest(input: Option<&PathBuf>) -> Result<Value> {
     let cargo_file_path = cargo_toml_file(input)?;
     debug!("cargo_file_path = {:?}", cargo_file_path);
-    let mut cargo_file = File::open(cargo_file_path)?;
+    let mut cargo_file = File::open(&cargo_file_path)?;
     let mut cargo_file_content = String::new();
     cargo_file.read_to_string(&mut cargo_file_content)?;
-    let manifest = cargo_file_content.parse::<Value>()?;
+    let mut manifest = cargo_file_content.parse::<Value>()?;
+    if create::has_inherited_fields(&manifest) {
+        if let Some(workspace) = create::workspace_manifest(&cargo_file_path, &manifest)? {
+            create::inherit_workspace_package(&mut manifest, &workspace);
+        } else {
+            warn!(
+                "The package's manifest (Cargo.toml) inherits fields from a workspace, but the \
+                 workspace's manifest could not be found"
+            );
+        }
+    }
     Ok(manifest)
 }
 
@@ -222,26 +343,272 @@ fn product_name(product_name: Option<&String>, manifest: &Value) -> Result<Strin
     }
 }
 
+/// A binary to include in the installer.
+#[derive(Debug, Clone, PartialEq)]
+struct Binary {
+    /// The name of the binary, which is the file name without the extension.
+    name: String,
+    /// The path to the binary.
+    source: PathBuf,
+}
+
+/// Resolves the binaries to include in the installer.
+///
+/// If paths are specified, then any glob patterns are expanded and the paths
+/// are otherwise used as-is. Otherwise, the binaries are all of the `[[bin]]` sections in the manifest, or the package's name if
+/// there are no `[[bin]]` sections, and the `default_source` function
+/// determines the path to each of these binaries from its name. An error
+/// occurs if two binaries have the same name.
+fn binaries<F>(
+    paths: Option<&Vec<PathBuf>>,
+    manifest: &Value,
+    default_source: F,
+) -> Result<Vec<Binary>>
+where
+    F: Fn(&str) -> PathBuf,
+{
+    let mut binaries = Vec::new();
+    if let Some(binary_paths) = paths {
+        let binary_paths = expand_binary_paths(binary_paths)?;
+        debug!("binary_paths = {:?}", binary_paths);
+        for binary in binary_paths {
+            let name = binary
+                .file_stem()
+                .ok_or_else(|| {
+                    Error::Generic(format!(
+                        "The '{}' binary path does not have a file name",
+                        binary.display()
+                    ))
+                })?
+                .to_string_lossy()
+                .into_owned();
+            binaries.push(Binary {
+                name,
+                source: binary,
+            });
+        }
+    } else if let Some(array) = manifest.get("bin").and_then(|b| b.as_array()) {
+        for binary in array {
+            let table = binary
+                .as_table()
+                .expect("The [[bin]] section to be a table");
+            let name = table.get("name").and_then(|n| n.as_str()).ok_or_else(|| {
+                Error::Generic(String::from(
+                    "Missing the 'name' field for the binary in the project's manifest file \
+                     (Cargo.toml)",
+                ))
+            })?;
+            binaries.push(Binary {
+                name: name.to_owned(),
+                source: default_source(name),
+            });
+        }
+    } else {
+        let name = product_name(None, manifest)?;
+        binaries.push(Binary {
+            source: default_source(&name),
+            name,
+        });
+    }
+    check_duplicate_binaries(&binaries)?;
+    Ok(binaries)
+}
+
+/// Expands any glob patterns in the paths to the binaries.
+///
+/// A path without any glob metacharacters is used as-is, even if it does not
+/// exist, while a pattern is replaced with the matching files sorted by path.
+fn expand_binary_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut expanded = Vec::with_capacity(paths.len());
+    for path in paths {
+        let pattern = path.to_string_lossy();
+        if !pattern.contains(['*', '?', '[']) {
+            expanded.push(path.to_owned());
+            continue;
+        }
+        trace!("Expanding the '{}' binary pattern", pattern);
+        let mut matches = glob::glob(&pattern)
+            .map_err(|err| {
+                Error::Generic(format!(
+                    "The '{}' binary pattern is invalid: {}",
+                    pattern, err
+                ))
+            })?
+            .collect::<std::result::Result<Vec<PathBuf>, glob::GlobError>>()
+            .map_err(|err| {
+                Error::Generic(format!(
+                    "The '{}' binary pattern could not be expanded: {}",
+                    pattern, err
+                ))
+            })?;
+        if matches.is_empty() {
+            return Err(Error::Generic(format!(
+                "The '{}' binary pattern does not match any files",
+                pattern
+            )));
+        }
+        matches.sort();
+        expanded.extend(matches);
+    }
+    Ok(expanded)
+}
+
+/// Checks that each binary has a unique name.
+///
+/// The binary name is used for the file name and the identifiers of the
+/// binary's component in the WXS file, so duplicate names produce WiX errors
+/// when the installer is created.
+fn check_duplicate_binaries(binaries: &[Binary]) -> Result<()> {
+    let mut sources: HashMap<&str, &Path> = HashMap::with_capacity(binaries.len());
+    for binary in binaries {
+        if let Some(other_source) = sources.insert(&binary.name, &binary.source) {
+            return Err(Error::Generic(format!(
+                "The '{}' binary name is used more than once, by the '{}' and '{}' \
+                 binaries. Each binary must have a unique name.",
+                binary.name,
+                other_source.display(),
+                binary.source.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The maximum number of uncommitted changes listed in the message for a dirty
+/// git working tree.
+const DIRTY_GIT_ENTRIES: usize = 5;
+
+/// The interval between checks of a child process that is waited on with a
+/// timeout.
+const WAIT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Waits for a child process to exit.
+///
+/// If a timeout is specified and the process has not exited within it, then
+/// the process is killed and an error occurs. Without a timeout, this blocks
+/// until the process exits.
+fn wait(mut child: Child, name: &str, timeout: Option<Duration>) -> Result<ExitStatus> {
+    let timeout = match timeout {
+        Some(t) => t,
+        None => return child.wait().map_err(Error::from),
+    };
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            // The process might exit on its own between the check and the
+            // kill, so only the waiting matters.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::Generic(format!(
+                "The '{}' application timed out after {:?} and was stopped",
+                name, timeout
+            )));
+        }
+        thread::sleep(WAIT_INTERVAL.min(timeout - elapsed));
+    }
+}
+
+/// Waits for a child process to exit and collects its output.
+///
+/// This is the same as the [`wait`] function, but the piped stdout and stderr
+/// of the process are read while waiting, so the process cannot block on a
+/// full pipe.
+///
+/// [`wait`]: fn.wait.html
+fn wait_with_output(mut child: Child, name: &str, timeout: Option<Duration>) -> Result<Output> {
+    if timeout.is_none() {
+        return child.wait_with_output().map_err(Error::from);
+    }
+    fn read_to_end<R: Read + Send + 'static>(
+        pipe: Option<R>,
+    ) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                pipe.read_to_end(&mut buffer)?;
+            }
+            Ok(buffer)
+        })
+    }
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+    let status = wait(child, name, timeout)?;
+    Ok(Output {
+        status,
+        stdout: stdout.join().expect("Stdout reader")?,
+        stderr: stderr.join().expect("Stderr reader")?,
+    })
+}
+
 /// The error type for wix-related operations and associated traits.
 ///
 /// Errors mostly originate from the dependencies, but custom instances of `Error` can be created
 /// with the `Generic` variant and a message.
 #[derive(Debug)]
 pub enum Error {
+    /// The builder (`cargo`) failed to build the binaries while its output was
+    /// captured.
+    Build {
+        /// The exit code of the builder.
+        code: i32,
+        /// The last lines of the standard error (stderr) of the builder.
+        stderr: String,
+    },
     /// A command operation failed.
-    Command(&'static str, i32, bool),
+    ///
+    /// The name of the application is the overridden name if the default
+    /// name has been changed.
+    Command(Cow<'static, str>, i32, bool),
+    /// The compiler failed to compile a WiX Source (wxs) file.
+    Compile {
+        /// The path to the WiX Source (wxs) file that failed to compile.
+        source: PathBuf,
+        /// The exit code of the compiler.
+        code: i32,
+        /// Indicates if the output from the compiler was captured.
+        captured_output: bool,
+    },
+    /// The git working tree of the package has uncommitted changes, but a
+    /// clean working tree is required to create the installer.
+    DirtyGit {
+        /// The short status of each uncommitted change, i.e. ` M src/main.rs`.
+        entries: Vec<String>,
+    },
     /// A generic or custom error occurred. The message should contain the detailed information.
     Generic(String),
+    /// A field within the `Cargo.toml` manifest is missing or has an invalid value.
+    InvalidManifestField(&'static str),
+    /// A property to add to the installer has an invalid identifier.
+    InvalidProperty {
+        /// The identifier of the property.
+        id: String,
+        /// The reason the identifier is invalid.
+        reason: &'static str,
+    },
     /// An I/O operation failed.
     Io(io::Error),
     /// A needed field within the `Cargo.toml` manifest could not be found.
     Manifest(&'static str),
+    /// A WiX Source (wxs) file to be included in the installer does not exist.
+    MissingFile(PathBuf),
     /// An error occurred with rendering the template using the mustache renderer.
     Mustache(mustache::Error),
+    /// No WiX Source (wxs) files were found to create the installer.
+    NoSources,
     /// Parsing of the `Cargo.toml` manifest failed.
     Toml(toml::de::Error),
     /// Parsing error for a version string or field.
     Version(semver::SemVerError),
+    /// A WiX Toolset application, i.e. the compiler or linker, could not be found.
+    WixNotFound {
+        /// The name of the application without the file extension, which is
+        /// the overridden name if the default name has been changed.
+        tool: Cow<'static, str>,
+    },
     /// Parsing the intermediate WiX Object (wixobj) file, which is XML, failed.
     Xml(sxd_document::parser::Error),
     /// Evaluation of an XPath expression failed.
@@ -274,6 +641,84 @@ impl Error {
             Error::Version(..) => 7,
             Error::Xml(..) => 8,
             Error::XPath(..) => 9,
+            Error::WixNotFound { .. } => 10,
+            Error::NoSources => 11,
+            Error::MissingFile(..) => 12,
+            Error::InvalidManifestField(..) => 13,
+            Error::Compile { .. } => 14,
+            Error::Build { .. } => 15,
+            Error::DirtyGit { .. } => 16,
+            Error::InvalidProperty { .. } => 17,
+        }
+    }
+
+    /// Gets the exit code for a command line application based on the category
+    /// of the error.
+    ///
+    /// Unlike the [`code`] method, which is unique for each variant, the exit
+    /// code only distinguishes the categories of errors that are useful for
+    /// branching in a script or continuous integration (CI) server:
+    ///
+    /// | Code | Category                                                    |
+    /// |------|-------------------------------------------------------------|
+    /// | 1    | All other errors                                            |
+    /// | 2    | The WiX Toolset compiler or linker was not found            |
+    /// | 3    | The package's manifest is invalid or missing a needed field |
+    /// | 4    | The compiler (candle) failed                                |
+    /// | 5    | The linker (light) failed                                   |
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use std::path::PathBuf;
+    /// use wix::{Error, CARGO, SIGNTOOL, WIX_COMPILER, WIX_LINKER};
+    ///
+    /// assert_eq!(Error::WixNotFound { tool: WIX_COMPILER.into() }.exit_code(), 2);
+    /// assert_eq!(Error::WixNotFound { tool: WIX_LINKER.into() }.exit_code(), 2);
+    /// assert_eq!(Error::Manifest("version").exit_code(), 3);
+    /// assert_eq!(Error::InvalidManifestField("package.metadata.wix.include").exit_code(), 3);
+    /// assert_eq!(
+    ///     Error::from("name = ".parse::<toml::Value>().unwrap_err()).exit_code(),
+    ///     3
+    /// );
+    /// assert_eq!(
+    ///     Error::Compile {
+    ///         source: PathBuf::from("main.wxs"),
+    ///         code: 1,
+    ///         captured_output: true,
+    ///     }
+    ///     .exit_code(),
+    ///     4
+    /// );
+    /// assert_eq!(Error::Command(WIX_COMPILER.into(), 1, true).exit_code(), 4);
+    /// assert_eq!(Error::Command(WIX_LINKER.into(), 1, true).exit_code(), 5);
+    /// assert_eq!(Error::Command(CARGO.into(), 101, true).exit_code(), 1);
+    /// assert_eq!(Error::Command(SIGNTOOL.into(), 1, true).exit_code(), 1);
+    /// assert_eq!(
+    ///     Error::Build {
+    ///         code: 101,
+    ///         stderr: String::new(),
+    ///     }
+    ///     .exit_code(),
+    ///     1
+    /// );
+    /// assert_eq!(Error::from("A generic error").exit_code(), 1);
+    /// assert_eq!(Error::from(io::Error::from(io::ErrorKind::Other)).exit_code(), 1);
+    /// assert_eq!(Error::MissingFile(PathBuf::from("main.wxs")).exit_code(), 1);
+    /// assert_eq!(Error::NoSources.exit_code(), 1);
+    /// assert_eq!(Error::DirtyGit { entries: Vec::new() }.exit_code(), 1);
+    /// ```
+    ///
+    /// [`code`]: #method.code
+    pub fn exit_code(&self) -> i32 {
+        match *self {
+            Error::WixNotFound { .. } => 2,
+            Error::Manifest(..) | Error::InvalidManifestField(..) | Error::Toml(..) => 3,
+            Error::Compile { .. } => 4,
+            Error::Command(ref command, ..) if command == WIX_COMPILER => 4,
+            Error::Command(ref command, ..) if command == WIX_LINKER => 5,
+            _ => 1,
         }
     }
 
@@ -336,13 +781,21 @@ impl Error {
     /// message formatting.
     pub fn as_str(&self) -> &str {
         match *self {
+            Error::Build { .. } => "Build",
             Error::Command(..) => "Command",
+            Error::Compile { .. } => "Compile",
+            Error::DirtyGit { .. } => "DirtyGit",
             Error::Generic(..) => "Generic",
+            Error::InvalidManifestField(..) => "InvalidManifestField",
+            Error::InvalidProperty { .. } => "InvalidProperty",
             Error::Io(..) => "Io",
             Error::Manifest(..) => "Manifest",
+            Error::MissingFile(..) => "MissingFile",
             Error::Mustache(..) => "Mustache",
+            Error::NoSources => "NoSources",
             Error::Toml(..) => "TOML",
             Error::Version(..) => "Version",
+            Error::WixNotFound { .. } => "WixNotFound",
             Error::Xml(..) => "XML",
             Error::XPath(..) => "XPath",
         }
@@ -370,6 +823,21 @@ impl StdError for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Error::Build { code, ref stderr } => {
+                if stderr.is_empty() {
+                    write!(
+                        f,
+                        "The '{}' application failed with exit code = {}",
+                        CARGO, code
+                    )
+                } else {
+                    write!(
+                        f,
+                        "The '{}' application failed with exit code = {}:\n\n{}",
+                        CARGO, code, stderr
+                    )
+                }
+            }
             Error::Command(ref command, ref code, captured_output) => {
                 if captured_output {
                     write!(
@@ -386,7 +854,55 @@ impl fmt::Display for Error {
                     )
                 }
             }
+            Error::Compile {
+                ref source,
+                code,
+                captured_output,
+            } => {
+                if captured_output {
+                    write!(
+                        f,
+                        "The '{}' file failed to compile with exit code = {}. Consider using the \
+                         '--nocapture' flag to obtain more information.",
+                        source.display(),
+                        code
+                    )
+                } else {
+                    write!(
+                        f,
+                        "The '{}' file failed to compile with exit code = {}",
+                        source.display(),
+                        code
+                    )
+                }
+            }
+            Error::DirtyGit { ref entries } => {
+                write!(
+                    f,
+                    "The git working tree has {} uncommitted change(s), so the installer may \
+                     not be reproducible from a commit:",
+                    entries.len()
+                )?;
+                for entry in entries.iter().take(DIRTY_GIT_ENTRIES) {
+                    write!(f, "\n  {}", entry)?;
+                }
+                if entries.len() > DIRTY_GIT_ENTRIES {
+                    write!(f, "\n  ...")?;
+                }
+                write!(
+                    f,
+                    "\nPlease commit or stash the changes before creating the installer."
+                )
+            }
             Error::Generic(ref msg) => msg.fmt(f),
+            Error::InvalidManifestField(ref field) => write!(
+                f,
+                "The '{}' field is missing or invalid in the package's manifest (Cargo.toml)",
+                field
+            ),
+            Error::InvalidProperty { ref id, reason } => {
+                write!(f, "The '{}' property {}", id, reason)
+            }
             Error::Io(ref err) => match err.kind() {
                 ErrorKind::AlreadyExists => {
                     if let Some(path) = err.get_ref() {
@@ -409,9 +925,34 @@ impl fmt::Display for Error {
                 "No '{}' field found in the package's manifest (Cargo.toml)",
                 var
             ),
+            Error::MissingFile(ref path) => write!(
+                f,
+                "The '{0}' file does not exist. Consider using the 'cargo wix print WXS > {0}' \
+                 command to create it.",
+                path.display()
+            ),
             Error::Mustache(ref err) => err.fmt(f),
+            Error::NoSources => write!(f, "There are no WXS files to create an installer"),
             Error::Toml(ref err) => err.fmt(f),
             Error::Version(ref err) => err.fmt(f),
+            Error::WixNotFound { ref tool } => write!(
+                f,
+                "The {} application ({}) could not be found in the PATH environment variable. \
+                 Please check the WiX Toolset (http://wixtoolset.org/) is installed and check \
+                 the WiX Toolset's '{}' folder has been added to the PATH system environment \
+                 variable, the {} system environment variable exists, or use the \
+                 '-b,--bin-path' command line argument.",
+                if tool == WIX_LINKER {
+                    "linker"
+                } else if tool == WIX_COMPILER {
+                    "compiler"
+                } else {
+                    "WiX Toolset"
+                },
+                tool,
+                BINARY_FOLDER_NAME,
+                WIX_PATH_KEY
+            ),
             Error::Xml(ref err) => err.fmt(f),
             Error::XPath(ref err) => err.fmt(f),
         }
@@ -513,6 +1054,33 @@ impl fmt::Display for Platform {
     }
 }
 
+/// Parses a platform from either the WiX Toolset value or the architecture
+/// used in Rust toolchains.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use wix::Platform;
+///
+/// assert_eq!(Platform::from_str("x64").unwrap(), Platform::X64);
+/// assert_eq!(Platform::from_str("x86_64").unwrap(), Platform::X64);
+/// assert_eq!(Platform::from_str("x86").unwrap(), Platform::X86);
+/// assert_eq!(Platform::from_str("i686").unwrap(), Platform::X86);
+/// assert!(Platform::from_str("arm").is_err());
+/// ```
+impl FromStr for Platform {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "x86" | "i686" | "i586" => Ok(Platform::X86),
+            "x64" | "x86_64" | "amd64" => Ok(Platform::X64),
+            p => Err(Error::Generic(format!("Unknown '{}' platform", p))),
+        }
+    }
+}
+
 impl Default for Platform {
     fn default() -> Self {
         if cfg!(target_arch = "x86_64") {
@@ -671,6 +1239,67 @@ pub enum Cultures {
 }
 
 impl Cultures {
+    /// All of the supported cultures.
+    ///
+    /// The canonical WiX Toolset culture code for each culture is obtained
+    /// with the `Display` implementation, i.e. `to_string`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use wix::Cultures;
+    ///
+    /// assert_eq!(Cultures::all().len(), 39);
+    /// assert_eq!(Cultures::all()[0].to_string(), "ar-SA");
+    /// for culture in Cultures::all() {
+    ///     assert_eq!(&Cultures::from_str(&culture.to_string()).unwrap(), culture);
+    /// }
+    /// ```
+    pub fn all() -> &'static [Cultures] {
+        &[
+            Cultures::ArSa,
+            Cultures::BgBg,
+            Cultures::CaEs,
+            Cultures::HrHr,
+            Cultures::CsCz,
+            Cultures::DaDk,
+            Cultures::NlNl,
+            Cultures::EnUs,
+            Cultures::EtEe,
+            Cultures::FiFi,
+            Cultures::FrFr,
+            Cultures::DeDe,
+            Cultures::ElGr,
+            Cultures::HeIl,
+            Cultures::HiIn,
+            Cultures::HuHu,
+            Cultures::ItIt,
+            Cultures::JaJp,
+            Cultures::KkKz,
+            Cultures::KoKr,
+            Cultures::LvLv,
+            Cultures::LtLt,
+            Cultures::NbNo,
+            Cultures::PlPl,
+            Cultures::PtBr,
+            Cultures::PtPt,
+            Cultures::RoRo,
+            Cultures::RuRu,
+            Cultures::SrLatnCs,
+            Cultures::ZhCn,
+            Cultures::SkSk,
+            Cultures::SlSi,
+            Cultures::EsEs,
+            Cultures::SvSe,
+            Cultures::ThTh,
+            Cultures::ZhHk,
+            Cultures::ZhTw,
+            Cultures::TrTr,
+            Cultures::UkUa,
+        ]
+    }
+
     /// The language of the culture code.
     ///
     /// # Examples
@@ -852,6 +1481,68 @@ impl Cultures {
             Cultures::UkUa => "Ukraine",
         }
     }
+
+    /// The Windows language code identifier (LCID) of the culture.
+    ///
+    /// This is the value for the `Language` attribute of the `Product` tag
+    /// and the `Languages` attribute of the `Package` tag in a WiX Source
+    /// (wxs) file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::Cultures;
+    ///
+    /// assert_eq!(Cultures::EnUs.lcid(), 1033);
+    /// assert_eq!(Cultures::FrFr.lcid(), 1036);
+    /// assert_eq!(Cultures::DeDe.lcid(), 1031);
+    /// assert_eq!(Cultures::JaJp.lcid(), 1041);
+    /// assert_eq!(Cultures::PtPt.lcid(), 2070);
+    /// assert_eq!(Cultures::ZhCn.lcid(), 2052);
+    /// ```
+    pub fn lcid(&self) -> u16 {
+        match *self {
+            Cultures::ArSa => 1025,
+            Cultures::BgBg => 1026,
+            Cultures::CaEs => 1027,
+            Cultures::HrHr => 1050,
+            Cultures::CsCz => 1029,
+            Cultures::DaDk => 1030,
+            Cultures::NlNl => 1043,
+            Cultures::EnUs => 1033,
+            Cultures::EtEe => 1061,
+            Cultures::FiFi => 1035,
+            Cultures::FrFr => 1036,
+            Cultures::DeDe => 1031,
+            Cultures::ElGr => 1032,
+            Cultures::HeIl => 1037,
+            Cultures::HiIn => 1081,
+            Cultures::HuHu => 1038,
+            Cultures::ItIt => 1040,
+            Cultures::JaJp => 1041,
+            Cultures::KkKz => 1087,
+            Cultures::KoKr => 1042,
+            Cultures::LvLv => 1062,
+            Cultures::LtLt => 1063,
+            Cultures::NbNo => 1044,
+            Cultures::PlPl => 1045,
+            Cultures::PtBr => 1046,
+            Cultures::PtPt => 2070,
+            Cultures::RoRo => 1048,
+            Cultures::RuRu => 1049,
+            Cultures::SrLatnCs => 2074,
+            Cultures::ZhCn => 2052,
+            Cultures::SkSk => 1051,
+            Cultures::SlSi => 1060,
+            Cultures::EsEs => 3082,
+            Cultures::SvSe => 1053,
+            Cultures::ThTh => 1054,
+            Cultures::ZhHk => 3076,
+            Cultures::ZhTw => 1028,
+            Cultures::TrTr => 1055,
+            Cultures::UkUa => 1058,
+        }
+    }
 }
 
 impl fmt::Display for Cultures {
@@ -900,50 +1591,89 @@ impl fmt::Display for Cultures {
     }
 }
 
+/// Parses a culture from its code.
+///
+/// The code is case-insensitive and either a hyphen (`-`) or an underscore
+/// (`_`) can separate the language from the region. A two-letter language code
+/// without a region is also accepted and it uses the most common region for
+/// the language, i.e. `en` is `en-US` and `fr` is `fr-FR`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use wix::Cultures;
+///
+/// let accepted = [
+///     ("en-US", Cultures::EnUs),
+///     ("en-us", Cultures::EnUs),
+///     ("EN-US", Cultures::EnUs),
+///     ("en_US", Cultures::EnUs),
+///     (" en-US ", Cultures::EnUs),
+///     ("en", Cultures::EnUs),
+///     ("Fr-Fr", Cultures::FrFr),
+///     ("fr", Cultures::FrFr),
+///     ("de-DE", Cultures::DeDe),
+///     ("de", Cultures::DeDe),
+///     ("ru_RU", Cultures::RuRu),
+///     ("pt", Cultures::PtPt),
+///     ("pt-BR", Cultures::PtBr),
+///     ("sr-Latn-CS", Cultures::SrLatnCs),
+///     ("sr_latn_cs", Cultures::SrLatnCs),
+///     ("zh", Cultures::ZhCn),
+///     ("zh-TW", Cultures::ZhTw),
+/// ];
+/// for (code, expected) in accepted.iter() {
+///     assert_eq!(&Cultures::from_str(code).unwrap(), expected, "{}", code);
+/// }
+/// assert!(Cultures::from_str("xx").is_err());
+/// assert!(Cultures::from_str("en-GB").is_err());
+/// assert!(Cultures::from_str("").is_err());
+/// ```
 impl FromStr for Cultures {
     type Err = Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s.to_lowercase().trim() {
-            "ar-sa" => Ok(Cultures::ArSa),
-            "bg-bg" => Ok(Cultures::BgBg),
-            "ca-es" => Ok(Cultures::CaEs),
-            "hr-hr" => Ok(Cultures::HrHr),
-            "cs-cz" => Ok(Cultures::CsCz),
-            "da-dk" => Ok(Cultures::DaDk),
-            "nl-nl" => Ok(Cultures::NlNl),
-            "en-us" => Ok(Cultures::EnUs),
-            "et-ee" => Ok(Cultures::EtEe),
-            "fi-fi" => Ok(Cultures::FiFi),
-            "fr-fr" => Ok(Cultures::FrFr),
-            "de-de" => Ok(Cultures::DeDe),
-            "el-gr" => Ok(Cultures::ElGr),
-            "he-il" => Ok(Cultures::HeIl),
-            "hi-in" => Ok(Cultures::HiIn),
-            "hu-hu" => Ok(Cultures::HuHu),
-            "it-it" => Ok(Cultures::ItIt),
-            "ja-jp" => Ok(Cultures::JaJp),
-            "kk-kz" => Ok(Cultures::KkKz),
-            "ko-kr" => Ok(Cultures::KoKr),
-            "lv-lv" => Ok(Cultures::LvLv),
-            "lt-lt" => Ok(Cultures::LtLt),
-            "nb-no" => Ok(Cultures::NbNo),
-            "pl-pl" => Ok(Cultures::PlPl),
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "ar-sa" | "ar" => Ok(Cultures::ArSa),
+            "bg-bg" | "bg" => Ok(Cultures::BgBg),
+            "ca-es" | "ca" => Ok(Cultures::CaEs),
+            "hr-hr" | "hr" => Ok(Cultures::HrHr),
+            "cs-cz" | "cs" => Ok(Cultures::CsCz),
+            "da-dk" | "da" => Ok(Cultures::DaDk),
+            "nl-nl" | "nl" => Ok(Cultures::NlNl),
+            "en-us" | "en" => Ok(Cultures::EnUs),
+            "et-ee" | "et" => Ok(Cultures::EtEe),
+            "fi-fi" | "fi" => Ok(Cultures::FiFi),
+            "fr-fr" | "fr" => Ok(Cultures::FrFr),
+            "de-de" | "de" => Ok(Cultures::DeDe),
+            "el-gr" | "el" => Ok(Cultures::ElGr),
+            "he-il" | "he" => Ok(Cultures::HeIl),
+            "hi-in" | "hi" => Ok(Cultures::HiIn),
+            "hu-hu" | "hu" => Ok(Cultures::HuHu),
+            "it-it" | "it" => Ok(Cultures::ItIt),
+            "ja-jp" | "ja" => Ok(Cultures::JaJp),
+            "kk-kz" | "kk" => Ok(Cultures::KkKz),
+            "ko-kr" | "ko" => Ok(Cultures::KoKr),
+            "lv-lv" | "lv" => Ok(Cultures::LvLv),
+            "lt-lt" | "lt" => Ok(Cultures::LtLt),
+            "nb-no" | "nb" => Ok(Cultures::NbNo),
+            "pl-pl" | "pl" => Ok(Cultures::PlPl),
             "pt-br" => Ok(Cultures::PtBr),
-            "pt-pt" => Ok(Cultures::PtPt),
-            "ro-ro" => Ok(Cultures::RoRo),
-            "ru_ru" => Ok(Cultures::RuRu),
-            "sr-Latn-CS" => Ok(Cultures::SrLatnCs),
-            "zh-CN" => Ok(Cultures::ZhCn),
-            "sk-SK" => Ok(Cultures::SkSk),
-            "sl-SI" => Ok(Cultures::SlSi),
-            "es-ES" => Ok(Cultures::EsEs),
-            "sv-SE" => Ok(Cultures::SvSe),
-            "th-TH" => Ok(Cultures::ThTh),
-            "zh-HK" => Ok(Cultures::ZhHk),
-            "zh-TW" => Ok(Cultures::ZhTw),
-            "tr-TR" => Ok(Cultures::TrTr),
-            "uk-UA" => Ok(Cultures::UkUa),
+            "pt-pt" | "pt" => Ok(Cultures::PtPt),
+            "ro-ro" | "ro" => Ok(Cultures::RoRo),
+            "ru-ru" | "ru" => Ok(Cultures::RuRu),
+            "sr-latn-cs" | "sr" => Ok(Cultures::SrLatnCs),
+            "zh-cn" | "zh" => Ok(Cultures::ZhCn),
+            "sk-sk" | "sk" => Ok(Cultures::SkSk),
+            "sl-si" | "sl" => Ok(Cultures::SlSi),
+            "es-es" | "es" => Ok(Cultures::EsEs),
+            "sv-se" | "sv" => Ok(Cultures::SvSe),
+            "th-th" | "th" => Ok(Cultures::ThTh),
+            "zh-hk" => Ok(Cultures::ZhHk),
+            "zh-tw" => Ok(Cultures::ZhTw),
+            "tr-tr" | "tr" => Ok(Cultures::TrTr),
+            "uk-ua" | "uk" => Ok(Cultures::UkUa),
             e => Err(Error::Generic(format!("Unknown '{}' culture", e))),
         }
     }
@@ -32,6 +32,8 @@
 //!   - [Extensions](#extensions)
 //!   - [Multiple WiX Sources](#multiple-wix-sources)
 //!   - [Bundles](#bundles)
+//!   - [Checking](#checking)
+//!   - [Exit Codes](#exit-codes)
 //! - [Configuration](#configuration)
 //! - [Flags and Options](#flags-and-options)
 //!
@@ -152,6 +154,13 @@
 //! is an artifact of the installer build process and can be ignored and/or
 //! deleted.
 //!
+//! Similar to Cargo, the `CARGO_TARGET_DIR` environment variable can be used to
+//! relocate the `target` folder. If it is set, the binary is read from, and the
+//! installer and `main.wixobj` file are written to, the folder specified by the
+//! environment variable instead of the `target` folder. The `CARGO` environment
+//! variable, which is set by Cargo when running the `cargo wix` subcommand, is
+//! used as the builder application if it exists.
+//!
 //! The installer that is created with the above steps and commands will install
 //! the `example.exe` file to: `C:\Program Files\example\bin\example.exe`. It
 //! will also add the `C:\Program Files\example\bin` path to the `PATH` system
@@ -322,6 +331,14 @@
 //! subcommand or the WiX Toolset's compiler (`candle.exe`) and linker
 //! (`light.exe`) applications.
 //!
+//! A WiX Include (WXI) file of pre-processor variables can be printed with the
+//! `cargo wix print wxi` command for a hand-authored WXS file. The include file
+//! defines the `ProductName`, `Version`, `Manufacturer`, and `UpgradeCode`
+//! variables, along with the `Binary<index>Name` and `Binary<index>Source`
+//! variables for each binary, from the package's manifest (Cargo.toml). The
+//! variables are available to the WXS file after adding the `<?include
+//! variables.wxi ?>` tag, i.e. `$(var.Version)`.
+//!
 //! In addition to the WXS template, there are several license templates which
 //! are used to generate an End User License Agreement (EULA) during the `cargo
 //! wix init` command. Depending on the license ID(s) in the `license` field for
@@ -339,6 +356,9 @@
 //! automatically created during initialization and it will have to be created
 //! manually with a text editor or some other authoring tool.
 //!
+//! The culture codes supported by the `-c,--culture` option are listed, one
+//! per line, with the `cargo wix print cultures` command.
+//!
 //! The `cargo wix init` subcommand uses a combination of the [`license`] and
 //! [`license-file`] fields of the project's manifest (Cargo.toml) to determine
 //! if a [sidecar] license file should be included in the installation folder
@@ -347,9 +367,10 @@
 //! package, while the `license-file` field is used to specify a custom, or
 //! proprietary, license.
 //!
-//! The top three most common licenses for Rust projects are supported from the
-//! `license` field, i.e. MIT, Apache-2.0, and GPLv3. If any of these three
-//! supported open source licenses are used for the `license` field, then a
+//! The most common licenses for Rust projects are supported from the `license`
+//! field, i.e. MIT, Apache-2.0, GPLv3, BSD-2-Clause, BSD-3-Clause, ISC, and
+//! MPL-2.0. If any of these supported open source licenses are used for the
+//! `license` field, then a
 //! `License.rtf` file is generated from an embedded template and placed in the
 //! `wix` folder as part of the `cargo wix init` subcommand. This generated RTF
 //! file will be used as a sidecar file and for the End User License Agreement
@@ -607,6 +628,44 @@
 //! [Issue #74]: https://github.com/volks73/cargo-wix/issues/74
 //! [Issue #98]: https://github.com/volks73/cargo-wix/issues/98
 //!
+//! ### Checking
+//!
+//! The _check_ subcommand, `cargo wix check`, validates the prerequisites for
+//! creating an installer without building the binaries or executing the WiX
+//! Toolset, which is useful as a fast preflight before a long build on a
+//! continuous integration (CI) server:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix check
+//! ```
+//!
+//! The compiler (candle.exe) and linker (light.exe) are located in the same way
+//! as the default _create_ subcommand, the package's manifest (Cargo.toml) must
+//! have a name and version, the WiX Source (wxs) files must exist, and any
+//! referenced files, such as the WiX localization file (wxl), EULA, banner, and
+//! product icon, must exist. All of the problems that are found are reported
+//! together, and the exit code is non-zero if there are any problems. If there
+//! is only one problem, then the exit code is the same as the default _create_
+//! subcommand for the problem, i.e. `2` if the WiX Toolset cannot be found. The
+//! linker is only checked if the compiler is found.
+//!
+//! ### Exit Codes
+//!
+//! The exit code is zero if the subcommand succeeds. Otherwise, the exit code
+//! indicates the category of the error, so a script or continuous integration
+//! (CI) server can branch on the reason for the failure:
+//!
+//! | Code | Category                                                    |
+//! |------|-------------------------------------------------------------|
+//! | 1    | All other errors                                            |
+//! | 2    | The WiX Toolset compiler or linker was not found            |
+//! | 3    | The package's manifest is invalid or missing a needed field |
+//! | 4    | The compiler (candle.exe) failed                            |
+//! | 5    | The linker (light.exe) failed                               |
+//!
+//! The number in the `Error[<number>]` tag of the error message identifies
+//! the specific error and is not the same as the exit code.
+//!
 //! ## Configuration
 //!
 //! The default subcommand, `cargo wix`, which creates a MSI based on the
@@ -626,7 +685,10 @@
 //! culture = "Fr-Fr"
 //! dbg-build = false
 //! dbg-name = false
+//! extensions = ["WixFirewallExtension", "Path\to\Custom\Extension.dll"]
+//! features = ["gui"]
 //! include = ["Path\to\WIX\Source\File\One.wxs", "Path\to\WIX\Source\File\Two.wxs"]
+//! libraries = ["Path\to\WIX\Library\File.wixlib"]
 //! linker-args = ["-nologo"]
 //! locale = "Path\to\WIX\Localization\File.wxl"
 //! name = "example"
@@ -649,6 +711,42 @@
 //! array] instead of a string value. This is the same as passing multiple paths
 //! to the default _create_ subcommand using multiple `-I,--include` options or
 //! including multiple WXS files in the default, `wix`, project source location.
+//! The `libraries` field is also an array, which is the same as using multiple
+//! `--library` options. Relative paths are resolved against the package's root
+//! folder.
+//!
+//! The fields that are paths, i.e. the `eula`, `locale`, and `output` fields,
+//! can reference environment variables using the `$VAR`, `${VAR}`, or `%VAR%`
+//! forms, such as `output = "$OUT/installers/"`. Each reference is replaced
+//! with the value of the environment variable, and an error occurs if the
+//! environment variable is not set.
+//!
+//! The signer (`signtool`) application used by the `--sign-binaries` option of
+//! the default _create_ subcommand and by the _sign_ (`cargo wix sign`)
+//! subcommand can be configured with a `[package.metadata.wix.sign]` section:
+//!
+//! ```toml
+//! [package.metadata.wix.sign]
+//! digest = "sha256"
+//! password-env = "PFX_PASSWORD"
+//! pfx = "Path\to\Certificate.pfx"
+//! thumbprint = "0123456789ABCDEF0123456789ABCDEF01234567"
+//! timestamp-url = "http://timestamp.digicert.com"
+//! ```
+//!
+//! The `thumbprint` field selects a certificate from the certificate store by
+//! its SHA1 hash, and the `pfx` field is a path to a PFX file with the
+//! certificate. Like the other paths, the `pfx` field can reference
+//! environment variables, and a relative path is relative to the package's
+//! root. If neither field is used, then the signer automatically
+//! selects the best certificate. The password for the PFX file is never
+//! written in the manifest. Instead, the `password-env` field is the name of
+//! an environment variable that contains the password, and the password is
+//! redacted from any displayed or logged signer command. The `timestamp-url`
+//! field is the URL of a RFC 3161 timestamp server, which is overridden by the
+//! `-t,--timestamp` option. The `digest` field is the digest algorithm for the
+//! signature and the timestamp, which can be `sha1`, `sha256`, `sha384`, or
+//! `sha512`. The default is `sha256`.
 //!
 //! The only CLI option, or argument, that is not supported in the
 //! `[package.metadata.wix]` section is the `<INPUT>` argument for the default
@@ -657,6 +755,16 @@
 //! be used for the default _create_ subcommand is the same manifest that
 //! contains the `[package.metadata.wix]` section.
 //!
+//! If the package is a member of a workspace, then a `[workspace.metadata.wix]`
+//! section in the workspace's manifest, i.e. the Cargo.toml file with the
+//! `[workspace]` section, is also used. This is useful for sharing settings,
+//! such as the `culture` field, across all members of the workspace. The fields
+//! in the `[package.metadata.wix]` section of the member's manifest take
+//! precedence over the same fields in the `[workspace.metadata.wix]` section.
+//! The workspace's manifest is found using the `workspace` field of the
+//! `[package]` section if it exists; otherwise, the parent folders of the
+//! package are searched.
+//!
 //! ## Flags and Options
 //!
 //! Generally, any value that is obtained from the package's manifest
@@ -674,10 +782,28 @@
 //! this section is a list of all flags and options implemented for all
 //! subcommands.
 //!
+//! ### `--all-features`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Activates all available features of the package when building the binaries,
+//! i.e. the `--all-features` flag is passed to the `cargo build` command. This
+//! is ignored if the `--no-build` flag is used.
+//!
+//! ### `--allow-downgrades`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Allows an older version of the product to replace a newer version when
+//! printing the WXS template, i.e. the `AllowDowngrades` attribute of the
+//! `MajorUpgrade` element is set. The default is to block downgrades and
+//! display the message from the `--downgrade-message` option.
+//!
 //! ### `-b,--banner`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
-//! subcommands.
+//! subcommands. Also available for the _check_ (`cargo wix check`) subcommand,
+//! but only as `--banner` because `-b` is used for the `-b,--bin-path` option.
 //!
 //! Sets the path to a bitmap (.bmp) image file that will be displayed across
 //! the top of each dialog in the installer. The banner image dimensions should
@@ -685,8 +811,8 @@
 //!
 //! ### `-b,--bin-path`
 //!
-//! Available for the default _create_ (`cargo wix`) and _sign_ (`cargo wix
-//! sign`) subcommands.
+//! Available for the default _create_ (`cargo wix`), _check_ (`cargo wix
+//! check`), and _sign_ (`cargo wix sign`) subcommands.
 //!
 //! The `-b,--bin-path` option can be used to specify a path (relative or
 //! absolute) to the WiX Toolset `bin` folder. The `-b,--bin-path` option is
@@ -716,7 +842,55 @@
 //! This option can be used multiple times to define multiple binaries to
 //! include in the installer. The value is a path to a binary file. The file
 //! stem (file name without extension) is used as the binary name within the WXS
-//! file. A relative or absolute path is acceptable.
+//! file. A relative or absolute path is acceptable. Without this option, the
+//! path to each binary is `$(var.CargoTargetDir)\$(var.Profile)\<name>.exe`,
+//! which is relative to the folder containing the package's manifest
+//! (Cargo.toml) if a path to the manifest is specified as the `INPUT`.
+//!
+//! The value can also be a glob pattern, such as `target\release\*.exe`, to
+//! include all of the matching files as binaries. Quote the pattern to prevent
+//! the shell from expanding it. An error occurs if the pattern does not match
+//! any files.
+//!
+//! ### `--binary-display-name`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets a human-friendly name for a binary when printing the WXS template. The
+//! option takes two values: the name of the binary and the display name, i.e.
+//! `cargo wix print wxs --binary-display-name tool "Example Tool"`. The name of
+//! the binary is the `name` field of a `[[bin]]` section or the file stem of a
+//! path from the `-B,--binary` option. The display name is available to the
+//! template as the `binary-display-name` variable, which is useful for labels,
+//! such as Start Menu shortcuts, in a custom template. In the default template,
+//! it is the title of the binary's feature when the `--per-binary-features`
+//! flag is used. The `binary-name` variable is still the file name of the
+//! installed binary. This option can be used multiple times for multiple
+//! binaries. The default is to use the name of the binary.
+//!
+//! ### `--candle-arch`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the architecture passed to the compiler (candle.exe) with the `-arch`
+//! option, which determines the default bitness of the components when a WiX
+//! Source (wxs) file does not specify the `Win64` attribute. Valid values are:
+//! `x86`, `x64`, `ia64`, `arm`, and `arm64`. The default is to use the
+//! platform of the installer, i.e. `x64` for a 64-bit installer and `x86` for
+//! a 32-bit installer.
+//!
+//! ### `--checksum`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Writes a checksum file next to the installer after it is created. The
+//! value is the digest, either `sha256` or `sha512`, and the file has the same
+//! name as the installer with the digest appended as the extension, i.e.
+//! `example-0.1.0-x86_64.msi.sha256`. The contents are the same as the output
+//! from the `sha256sum` and `sha512sum` utilities, i.e. the hexadecimal digest,
+//! two spaces, and the installer's file name, so the installer can be verified
+//! with `sha256sum -c example-0.1.0-x86_64.msi.sha256`. The default is to not
+//! write a checksum file.
 //!
 //! ### `-C,--compiler-arg`
 //!
@@ -729,6 +903,46 @@
 //! as a separate usage of this option. For example, adding an user-defined compiler
 //! extension would require the following command `cargo wix -C -ext -C
 //! UserDefinedExtension` to yield a `candle -ext UserDefinedExtension` invocation.
+//! The `-nologo` flag is passed by default and does not need to be added, see
+//! the `--show-logo` flag.
+//!
+//! ### `--comments`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the `Comments` field of the installer's summary information when
+//! printing the WXS template. The comments are displayed in the properties of
+//! the installer (msi) file. The default is to use the description.
+//!
+//! ### `--company-dir`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the name of a company folder that contains the installation folder
+//! when printing the WXS template, i.e. `C:\Program
+//! Files\<company-dir>\<install-dir>`. The name must be a single folder name
+//! without path separators or characters that are not allowed in file names.
+//! The default is to install directly within the `Program Files` folder.
+//!
+//! ### `--compiler-name`
+//!
+//! Available for the default _create_ (`cargo wix`) and _check_ (`cargo wix
+//! check`) subcommands.
+//!
+//! Sets the name of the WiX compiler application without the file extension.
+//! The compiler is still located using the `-b,--bin-path` option, the
+//! `WIX` system environment variable, or the `PATH` system environment
+//! variable. This is useful when the compiler is wrapped by another
+//! application with a different name. The default is `candle`.
+//!
+//! ### `--compression`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the compression level of the cabinet that is embedded in the installer
+//! when printing the WXS template. The values are: `None`, `Low`, `Medium`,
+//! `High`, and `Mszip`, which are case insensitive. Higher levels create a
+//! smaller installer but take longer. The default is `High`.
 //!
 //! ### `-c,--culture`
 //!
@@ -736,7 +950,10 @@
 //!
 //! Sets the culture for localization. Use with the [`-l,--locale`] option. See
 //! the [WixUI localization documentation] for more information about acceptable
-//! culture codes. The codes are case insensitive.
+//! culture codes. The codes are case insensitive and either a hyphen or an
+//! underscore can separate the language and region, i.e. `en-US` or `en_us`. A
+//! two-letter language code is also accepted and uses the most common region
+//! for the language, i.e. `fr` is the same as `fr-FR`.
 //!
 //! ### `-d,--dbg-build`
 //!
@@ -761,6 +978,16 @@
 //! developer to provide other mechanisms for creating a debugging variant of
 //! his or her application and still use the Release profile.
 //!
+//! ### `--dry-run`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Prints the builder (Cargo), compiler (candle.exe), and linker (light.exe)
+//! commands, one per line, instead of executing them. No installer is created.
+//! Each line can be copied and pasted into a command prompt to execute the
+//! command manually. This is useful for troubleshooting or for integrating with
+//! other build systems.
+//!
 //! ### `-d,--description`
 //!
 //! Available for the _init_ (`cargo wix init`), _print_ (`cargo wix print`),
@@ -769,7 +996,10 @@
 //! The package description is used in multiple places for the installer,
 //! including the text that appears in the blue UAC dialog when using a signed
 //! installer. This can be overridden using the `-d,--description` option with
-//! the `cargo wix init` or `cargo wix sign` subcommands, respectively.
+//! the `cargo wix init` or `cargo wix sign` subcommands, respectively. When
+//! printing the WXS template, whitespace and line breaks in the description are
+//! collapsed into single spaces and a description longer than 255 characters
+//! is truncated.
 //!
 //! ### `-D,--dialog`
 //!
@@ -781,26 +1011,121 @@
 //! should be 493 x 312 pixels. The first dialog is known as the "Welcome"
 //! dialog.
 //!
+//! ### `--downgrade-message`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the message that is displayed when a newer version of the product is
+//! already installed, i.e. the `DowngradeErrorMessage` attribute of the
+//! `MajorUpgrade` element in the WXS template. The message can contain Windows
+//! Installer properties, such as `[ProductName]`. This is ignored if the
+//! `--allow-downgrades` flag is used. The default is "A newer version of
+//! [ProductName] is already installed. Setup will now exit."
+//!
 //! ### `-e,--eula`
 //!
-//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
-//! subcommands.
+//! Available for the _check_ (`cargo wix check`), _init_ (`cargo wix init`),
+//! and _print_ (`cargo wix print`) subcommands.
 //!
 //! Specifies a Rich Text Format (RTF) file to use as the End User License
 //! Agreement (EULA) for the license agreement dialog of the installer. The
-//! default is to disable the license agreement dialog unless one of the
-//! supported licenses (GPL-3.0, Apache-2.0, or MIT) is generated based on the
+//! default is to use the `eula` field of the `[package.metadata.wix]` section
+//! in the package's manifest (Cargo.toml), which must be an existing file with
+//! the `.rtf` extension. Otherwise, the license agreement
+//! dialog is disabled unless one of the supported licenses (GPL-3.0, Apache-2.0, MIT, BSD-2-Clause, BSD-3-Clause,
+//! ISC, or MPL-2.0) is generated based on the
 //! value of the `license` field in the package's manifest (Cargo.toml). An EULA
 //! can be enabled later by directly modifying the WiX Source (WXS) file with a
 //! text editor.
 //!
+//! ### `--eula-from-markdown`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Converts a Markdown file to a Rich Text Format (RTF) file and uses it as the
+//! End User License Agreement (EULA) for the license agreement dialog of the
+//! installer. The RTF file is written next to the WXS file when the template is
+//! printed to a file. Headings, paragraphs, bold and italic text,
+//! inline code, code blocks, links, and lists are converted, and everything
+//! else is kept as plain text. The `-e,--eula` option takes precedence over
+//! this option.
+//!
+//! ### `--extension`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Adds a WiX extension to both the compiler (candle.exe) and the linker
+//! (light.exe) with the `-ext` option. The value is either the name of an
+//! extension installed with the WiX Toolset, i.e. `WixFirewallExtension`, which
+//! is passed unchanged, or a path to a custom extension, which is any value
+//! with a slash (`/`), a backslash (`\`), or the `.dll` file extension. A
+//! relative path is resolved against the package's root folder, and an error
+//! occurs if the extension does not exist. This option can be used multiple
+//! times to add multiple extensions. The `extensions` field of the
+//! `[package.metadata.wix]` section is an array of extensions, i.e.
+//! `extensions = ["WixFirewallExtension", "ext/Custom.dll"]`.
+//!
+//! ### `--features`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Activates the features of the package when building the binaries, i.e. the
+//! features are passed to the `cargo build` command with the `--features`
+//! option, so the installer can be created from feature-gated binaries.
+//! Multiple features can be separated by commas or spaces, or this option can
+//! be used multiple times. This is ignored if the `--no-build` flag is used.
+//! The `features` field of the `[package.metadata.wix]` section is an array of
+//! features, i.e. `features = ["gui", "tray"]`.
+//!
+//! ### `--file-assoc`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Adds a file association for the first binary when printing the WXS
+//! template. The option takes three values: the file extension, the
+//! programmatic identifier (ProgId), and a description of the file type, i.e.
+//! `cargo wix print wxs --file-assoc ext Example.Document "Example
+//! Document"`. Opening a file with the extension from the Windows Explorer runs
+//! the binary with the path to the file as its argument. The default is to not
+//! associate any file types with the binary.
+//!
 //! ### `--force`
 //!
-//! Available for the _init_ (`cargo wix init`) subcommand.
+//! Available for the default _create_ (`cargo wix`) and _init_ (`cargo wix
+//! init`) subcommands.
+//!
+//! For the _create_ subcommand, forces compiling the WiX Source (wxs) files. By
+//! default, the compiler (candle.exe) is skipped if every WiX object file
+//! (wixobj) in the `target\wix` folder is newer than its WiX Source (wxs) file
+//! and the files it includes with the `<?include?>` preprocessor directive, and
+//! the defines and arguments for the compiler, such as the version, have not
+//! changed since the previous build. A relative path to an included file is
+//! resolved against the folder of the including file. The compiler is always
+//! run if an included file cannot be found this way, such as a path with a
+//! preprocessor variable.
 //!
-//! Forces overwriting of generated files from the _init_ subcommand. Use with
+//! For the _init_ subcommand, forces overwriting of generated files. Use with
 //! caution! This cannot be undone.
 //!
+//! ### `--frozen`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Passes the `--frozen` flag to the `cargo build` command, which requires the
+//! Cargo.lock file to be up to date and disables network access. This is the
+//! same as using both the `--locked` and `--offline` flags. This is ignored if
+//! the `--no-build` flag is used.
+//!
+//! ### `--hash-in-name`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Renames the installer after it is created to include the first eight
+//! hexadecimal characters of its SHA-256 digest before the file extension, i.e.
+//! `example-0.1.0-x86_64-1a2b3c4d.msi`, for artifact stores that require
+//! immutable file names. The renamed path is used for the `--post-build`
+//! command and the JSON summary with the `--message-format json` option.
+//!
 //! ### `-h,--help`
 //!
 //! Available for all subcommands.
@@ -815,6 +1140,25 @@
 //!
 //! This will be displayed in the ACL dialog.
 //!
+//! ### `--install-dir`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the name of the installation folder when printing the WXS template.
+//! The name must be a single folder name without path separators or
+//! characters that are not allowed in file names. The default is to use the
+//! product name.
+//!
+//! ### `--install-readme`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the path to a readme file, such as `README.txt`, that is installed in
+//! the installation folder next to the license sidecar file when printing the
+//! WXS template. The file is installed with the same file name. A relative
+//! path is relative to the package's root when the installer is created. By
+//! default, a readme file is not installed.
+//!
 //! ### `-i,--install-version`
 //!
 //! Available for the default _create_ (`cargo wix`) subcommand.
@@ -825,7 +1169,8 @@
 //!
 //! ### `-I,--include`
 //!
-//! Available only for the default _create_ (`cargo wix`) subcommand.
+//! Available for the default _create_ (`cargo wix`) and _check_ (`cargo wix
+//! check`) subcommands.
 //!
 //! This option can be used multiple times to include multiple WiX Source (WXS)
 //! files in the creation of an installer. The option takes a path to a single
@@ -833,7 +1178,66 @@
 //! located within the package's root folder, i.e. same location as the
 //! package's manifest (Cargo.toml) are automatically included and used in the
 //! creation of the installer. This option allows the inclusion of other WXS
-//! files outside of the default `wix` location.
+//! files outside of the default `wix` location. A relative path is resolved
+//! against the package's root folder, not the current working directory
+//! (CWD), so it works the same with the `--manifest-path` option.
+//!
+//! ### `--include-pdb`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Installs the program database (pdb) file of each binary next to the binary
+//! when printing the WXS template, so crash dumps can be symbolicated. The
+//! program database is expected next to the binary in the `target` folder with
+//! the name of the binary, where hyphens are replaced with underscores, which
+//! is where Cargo writes it for the `*-pc-windows-msvc` targets. The file is
+//! only checked when the installer is created, so the _create_ (`cargo wix`)
+//! subcommand fails if it does not exist.
+//!
+//! ### `-j,--jobs`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Executes the compiler (candle.exe) once for each WiX Source (wxs) file and
+//! runs up to the specified number of compiler processes in parallel. A value
+//! of zero (0) uses the available parallelism of the system. This can reduce
+//! the time to create an installer for projects with many WiX Source (wxs)
+//! files. Creation of the installer stops after the first WiX Source (wxs) file
+//! fails to compile. The default is to execute the compiler once with all of the
+//! WiX Source (wxs) files.
+//!
+//! ### `--keep-pdb`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Keeps the WiX debug information (wixpdb) file that is created by the linker
+//! (light.exe) next to the installer. The wixpdb file is needed for authoring
+//! patches. The default is to suppress the creation of the wixpdb file.
+//!
+//! ### `--keyword`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Adds a keyword to the `Keywords` field of the installer's summary
+//! information when printing the WXS template. Use this option repeatedly to
+//! add multiple keywords. The default is to use the `keywords` field of the
+//! `[package.metadata.wix]` section, then the `keywords` field of the
+//! `[package]` section, of the package's manifest (Cargo.toml), or the
+//! `Installer` keyword if neither field exists.
+//!
+//! ### `--language`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the `Language` attribute of the `Product` tag and the `Languages`
+//! attribute of the `Package` tag when printing the WXS template. The value is
+//! a Windows language code identifier (LCID), such as `1033` for English
+//! (United States). The language of the product is stored in the installer's
+//! summary information and can be different than the culture of the
+//! installer's user interface, such as a French user interface for an English
+//! product, but a warning is printed if they differ. The default is to use the
+//! LCID of the culture in the `package.metadata.wix.culture` field of the
+//! package's manifest (Cargo.toml), or `1033` if the field does not exist.
 //!
 //! ### `-l,--license`
 //!
@@ -848,6 +1252,17 @@
 //! PDF, or RTF file, can be added later by directly editing the generated WiX
 //! Source file (WXS) in a text editor.
 //!
+//! ### `--library`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Links a pre-built WiX library (wixlib), such as installer components shared
+//! between installers, into the installer. The library is passed to the WiX
+//! linker (light.exe) alongside the WiX object files (wixobj) compiled from the
+//! WXS files. This option can be used multiple times to link multiple
+//! libraries. A relative path is resolved against the package's root folder,
+//! and an error occurs if a library does not exist.
+//!
 //! ### `-L,--linker-arg`
 //!
 //! Available for the default _create (`cargo wix`) subcommand.
@@ -859,24 +1274,104 @@
 //! passed as a separate usage of this option. For example, adding an
 //! user-defined linker extension would require the following command `cargo wix
 //! -L -ext -L UserDefinedExtension` to yield a `light -ext
-//! UserDefinedExtension` invocation.
+//! UserDefinedExtension` invocation. The `-nologo` flag is passed by default
+//! and does not need to be added, see the `--show-logo` flag.
+//!
+//! ### `--linker-name`
+//!
+//! Available for the default _create_ (`cargo wix`) and _check_ (`cargo wix
+//! check`) subcommands.
+//!
+//! Sets the name of the WiX linker application without the file extension.
+//! The linker is still located using the `-b,--bin-path` option, the `WIX`
+//! system environment variable, or the `PATH` system environment variable.
+//! This is useful when the linker is wrapped by another application with a
+//! different name. The default is `light`.
 //!
 //! ### `-l,--locale`
 //!
-//! Available for the default _create_ (`cargo wix`) subcommand.
+//! Available for the default _create_ (`cargo wix`) and _check_ (`cargo wix
+//! check`) subcommands.
 //!
 //! Sets the path to a WiX localization file (wxl) which contains localized
-//! strings. Use in conjunction with the [`-c,--culture`] option.
+//! strings. Use in conjunction with the [`-c,--culture`] option. An error
+//! occurs before the installer is created if the `Culture` attribute of the
+//! `WixLocalization` element in the file does not match the culture.
+//!
+//! ### `--locked`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Passes the `--locked` flag to the `cargo build` command, which requires the
+//! Cargo.lock file to be up to date. This is ignored if the `--no-build` flag is
+//! used.
 //!
 //! ### `-m,--manufacturer`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
 //! subcommands.
 //!
-//! Overrides the first author in the `authors` field of the package's manifest
-//! (Cargo.toml) as the manufacturer within the installer. The manufacturer can
-//! be changed after initialization by directly modifying the WiX Source file
-//! (WXS) with a text editor.
+//! Overrides the manufacturer within the installer. The default is to use the
+//! `manufacturer` field of the `[package.metadata.wix]` section, the first
+//! author in the `authors` field, or the domain of the `homepage` field of the
+//! package's manifest (Cargo.toml), in that order. The manufacturer can be
+//! changed after initialization by directly modifying the WiX Source file (WXS)
+//! with a text editor.
+//!
+//! ### `--manifest-path`
+//!
+//! Available for the default _create_ (`cargo wix`) and _print_ (`cargo wix
+//! print`) subcommands.
+//!
+//! An alternative to the `<INPUT>` argument for the path to a package's
+//! manifest (Cargo.toml), which is similar to the `--manifest-path` option for
+//! `cargo`. Only one of the `--manifest-path` option or the `<INPUT>` argument
+//! can be used. For both, the path can be to the manifest or to a folder that
+//! contains the manifest, and an error occurs if the folder does not contain a
+//! manifest. A path to a file is used as-is, so a manifest with a name other
+//! than `Cargo.toml` can be used, but Cargo only builds a package with a
+//! `Cargo.toml` manifest, so use the `--no-build` flag with a renamed
+//! manifest. The WiX Source files, target folder, and installer are relative
+//! to the folder containing the file.
+//!
+//! ### `--merge-args`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Merges the `compiler-args` and `linker-args` values in the
+//! `[package.metadata.wix]` section of the package's manifest (Cargo.toml) with
+//! the arguments from the `-C,--compiler-arg` and `-L,--linker-arg` options. The
+//! arguments from the package's manifest are used first, followed by the
+//! arguments from the command line. This is useful for a base set of arguments
+//! in the manifest with one-off additions from the command line. The default
+//! is to use the arguments from the package's manifest instead of the arguments
+//! from the command line when both are specified.
+//!
+//! ### `--message-format`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the format of the message that is printed after the installer is
+//! created. If `json`, then a single line with a JSON object is printed to
+//! stdout, which is useful for continuous integration (CI) tools, i.e.:
+//!
+//! ```json
+//! {"name":"example","version":"0.1.0","platform":"x64","culture":"en-US","output":"target\\wix\\example-0.1.0-x86_64.msi","size":421888,"elapsed_seconds":12.3}
+//! ```
+//!
+//! The logging statements are still written to stderr. The default is `human`,
+//! which does not print a message.
+//!
+//! ### `--min-wix-version`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the minimum version of the WiX Toolset, i.e. `3.11`. The version of the
+//! installed WiX Toolset is read from the banner of the compiler (candle.exe)
+//! before the installer is compiled, and an error occurs if it is older than
+//! the minimum version or it cannot be determined. The version can have one to
+//! three parts, where missing parts are zero. The installed version is always
+//! logged with the `-vv` flag. The default is to accept any version.
 //!
 //! ### `-n,--name`
 //!
@@ -886,12 +1381,44 @@
 //! used in the file name of the installer (msi). This does not change the name
 //! of the executable _within_ the installer.
 //!
+//! ### `--name-suffix`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Appends a suffix to the file stem of the installer (msi), i.e. `--name-suffix
+//! -nightly` creates `example-0.1.0-x86_64-nightly.msi`. The suffix is appended
+//! verbatim after the `-debug` suffix from the `--dbg-name` flag, if present.
+//! Only the file name is changed, the version of the installer is not. The
+//! suffix cannot contain a path separator. The default is to not append a
+//! suffix.
+//!
 //! ### `--no-build`
 //!
 //! Available for the default _create_ (`cargo wix`) subcommand.
 //!
 //! This skips building the Rust package using Cargo for the Release target.
 //!
+//! The package's manifest (Cargo.toml) is not required if this flag is used
+//! with both the `--name` and `--install-version` options, so an installer can
+//! be created for a binary from an external build system, i.e. `cargo wix
+//! --no-build --name Example --install-version 1.2.3`. The WiX Source (wxs)
+//! files are read from the `wix` folder of the current working directory, or
+//! the folder from the `<INPUT>` argument, and they must reference the
+//! binaries to install. None of the `[package.metadata.wix]` values apply
+//! without a manifest, and the `--sign-binaries` flag cannot be used.
+//!
+//! ### `--no-install-license`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Excludes the license sidecar file from the installation folder when
+//! printing the WXS template. The component that installs the license file is
+//! omitted and only the placeholder component for manually adding a license
+//! file is printed. The license is still used for the license agreement
+//! dialog. By default, the license file determined by the `-l,--license`
+//! option, or the `license` and `license-file` fields of the package's manifest
+//! (Cargo.toml), is installed next to the binaries.
+//!
 //! ### `--nocapture`
 //!
 //! Available for the default _create_ (`cargo wix`) and _sign_ (`cargo wix sign`)
@@ -900,6 +1427,66 @@
 //! Displays all output from the builder (Cargo), compiler (candle.exe), linker
 //! (light.exe), and signer (signtool.exe) applications.
 //!
+//! ### `--no-default-features`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Deactivates the default features of the package when building the binaries,
+//! i.e. the `--no-default-features` flag is passed to the `cargo build`
+//! command. This is ignored if the `--no-build` flag is used.
+//!
+//! ### `--no-ui`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Links the installer without the WixUI extension, i.e. the `-ext
+//! WixUIExtension` and `-cultures:` options are not passed to the linker
+//! (light.exe). This is useful for a silent installer for unattended
+//! deployment. The WiX Source (wxs) files must not reference a WixUI dialog
+//! set, so use the `--ui None` option when printing the WXS template. The
+//! default is to use the `no-ui` field of the `[package.metadata.wix]` section
+//! of the package's manifest (Cargo.toml).
+//!
+//! ### `--no-util-extension`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Compiles and links the installer without the WixUtil extension, i.e. the
+//! `-ext WixUtilExtension` option is not passed to the compiler (candle.exe)
+//! or the linker (light.exe). The WiX Source (wxs) files must not use any
+//! elements from the extension. This is useful to pass a different version of
+//! the extension with the `-C` and `-L` options. The default is to always
+//! include the extension.
+//!
+//! ### `--no-sort-sources`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Passes the WiX Source (wxs) files in the `wix` folder to the compiler
+//! (candle.exe) in the order they are read from the folder. The order depends
+//! on the file system, so by default, the files are sorted by path to create
+//! the same installer on every machine. Files from the `--include` option are
+//! always passed after the files in the `wix` folder in the order they are
+//! specified.
+//!
+//! ### `--no-strict-version`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Displays a warning and still creates the installer if the version exceeds
+//! the 255.255.65535 limits of the Windows Installer for the `ProductVersion`
+//! property. By default, an error occurs before the package is built. This
+//! cannot be used with the `--strict-version` flag.
+//!
+//! ### `--offline`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Passes the `--offline` flag to the `cargo build` command, which disables
+//! network access. This is useful for continuous integration (CI) builds that
+//! must not access the network. This is ignored if the `--no-build` flag is
+//! used.
+//!
 //! ### `-o,--output`
 //!
 //! Available for the default _create_ (`cargo wix`), _init_ (`cargo wix init`) and
@@ -913,7 +1500,22 @@
 //! if the path is to an existing directory or the path has a trailing `/` or
 //! `\`, then the MSI will be available after creation at the specified path,
 //! but the MSI file name will be the default file name based on the package
-//! name, version, and platform.
+//! name, version, and platform. The path can contain the `{name}`,
+//! `{version}`, `{arch}`, and `{platform}` tokens, which are replaced with the
+//! product name, version, Rust architecture (`x86_64`), and WiX Toolset
+//! platform (`x64`), respectively. For example, `-o releases/{version}/`
+//! creates the `releases/1.2.3` folder, if it does not exist, for the MSI.
+//!
+//! ### `--output-dir`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets a directory for all of the files generated when printing the WXS
+//! template. The WiX Source file (WXS) is written to `<dir>\wix\main.wxs`, and
+//! a license generated from the `license` field of the package's manifest
+//! (Cargo.toml) is referenced from the same `wix` folder. The folders are
+//! created if they do not exist. The `-o,--output` option takes precedence if
+//! both are used. This option is ignored for all other templates.
 //!
 //! ### `-O,--owner`
 //!
@@ -925,10 +1527,77 @@
 //! (Cargo.toml). This is only used when generating a license based on the value
 //! of the `license` field in the package's manifest.
 //!
+//! ### `--path-guid`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the GUID for the component that adds the installation folder to the
+//! `PATH` system environment variable when printing the WXS template. An error
+//! occurs if the value is not a valid GUID. The default is to generate a new,
+//! random GUID each time the WXS template is printed.
+//!
+//! ### `--pdb-output`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Writes the WiX debug information (wixpdb) file created by the linker
+//! (light.exe) to the path, i.e. the `-pdbout` option is passed to the linker.
+//! This is useful for keeping the `.wixpdb` files in a separate symbols folder
+//! instead of next to the installer. This implies the `--keep-pdb` flag. A
+//! relative path is relative to the package's root.
+//!
+//! ### `--per-binary-features`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Places each binary in its own feature when printing the WXS template, so
+//! the binaries can be individually deselected with the `FeatureTree` or
+//! `Mondo` dialog sets. Each feature is titled with the binary's display name.
+//! A warning is printed if the dialog set does not include feature selection.
+//! The default is to install all binaries with a single feature.
+//!
+//! ### `--platform`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the platform, `x86` or `x64`, of the installer when printing the WXS
+//! template. The Rust toolchain architectures, `i686` and `x86_64`, are also
+//! accepted. When set, the installation folder and components are fixed for
+//! the platform in the printed WXS file, i.e. `x64` installs to the 64-bit
+//! `Program Files` folder. The default is to determine the installation folder
+//! when the installer is created, so the same WXS file can be used for both
+//! 32-bit and 64-bit installers.
+//!
+//! ### `--post-build`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Runs a command after the installer is created, such as computing a
+//! checksum or copying the installer to a release folder. The command is run
+//! with the system shell, i.e. `cmd /C` on Windows and `sh -c` everywhere else,
+//! from the package's root folder after the linker (light.exe) succeeds. The
+//! path to the installer is available to the command in the `CARGO_WIX_MSI`
+//! environment variable, i.e. `cargo wix --post-build "copy %CARGO_WIX_MSI%
+//! dist"`. The command's output is captured unless the `--nocapture` flag is
+//! used, and an error occurs if the command fails.
+//!
+//! ### `--pre-build`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Runs a command before the binaries are built, such as generating code or
+//! fetching assets. The command is run with the system shell, i.e. `cmd /C` on
+//! Windows and `sh -c` everywhere else, from the package's root folder before
+//! the builder (`cargo build`), or before the compiler (candle.exe) if the
+//! `--no-build` flag is used. The product name and version are available to
+//! the command in the `CARGO_WIX_PRODUCT_NAME` and `CARGO_WIX_VERSION`
+//! environment variables. The command's output is captured unless the
+//! `--nocapture` flag is used, and an error occurs if the command fails.
+//!
 //! ### `-p,--product-icon`
 //!
-//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
-//! subcommands.
+//! Available for the _check_ (`cargo wix check`), _init_ (`cargo wix init`),
+//! and _print_ (`cargo wix print`) subcommands.
 //!
 //! Sets the path to an image file that will be display as an icon in the
 //! Add/Remove Programs (ARP) control panel for the installed application.
@@ -943,6 +1612,195 @@
 //! initialization or printing by directly modifying the WiX Source file (wxs)
 //! with a text editor.
 //!
+//! ### `--profile-var`
+//!
+//! Available for the default _create_ (`cargo wix`) and _print_ (`cargo wix
+//! print`) subcommands.
+//!
+//! Sets the name of the WiX preprocessor variable for the build profile. The
+//! _print_ subcommand uses the name in the source paths of the binaries in the
+//! WXS and WXI templates, i.e. `$(var.BuildConfig)\example.exe` instead of
+//! `$(var.Profile)\example.exe`, and the _create_ subcommand passes the
+//! matching `-dBuildConfig=release` define to the compiler (candle.exe). The
+//! same name must be used for both subcommands. The default is `Profile`.
+//!
+//! ### `--property`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Adds a property to the installer when printing the WXS template, so custom
+//! actions and conditions can use values that are set when the WXS file is
+//! created. The option takes two values: the identifier and the value, i.e.
+//! `cargo wix print wxs --property INSTALLMODE per-machine`, which adds a
+//! `<Property Id='INSTALLMODE' Value='per-machine'/>` element. The identifier
+//! must be a public property with only uppercase letters, digits, underscores,
+//! and periods, starting with a letter or an underscore, and it cannot be a
+//! property already defined by the template, such as `ARPPRODUCTICON`. The
+//! option can be used multiple times to add multiple properties. The default is
+//! to not add any properties.
+//!
+//! ### `--quiet`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Passes the `-nologo` flag to the compiler (candle.exe) and the linker
+//! (light.exe), even if the `--show-logo` flag is used, and captures all
+//! output from the builder (Cargo), compiler, linker, and signer
+//! (signtool.exe), even if the `--nocapture` flag is used.
+//!
+//! ### `--registry`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Adds a value that is written to the Windows registry when the installer
+//! runs and removed when the product is uninstalled. The option takes five
+//! values: the root (`HKLM`, `HKCU`, `HKMU`, `HKCR`, or `HKU`), the key, the
+//! name, the type (`string`, `integer`, `expandable`, `multiString`, or
+//! `binary`), and the value, i.e. `cargo wix print wxs --registry HKLM
+//! Software\Example Version string 1.0.0`. An empty name, `""`, sets the
+//! default value of the key. The option can be used multiple times, and all of
+//! the values are added to a single component. The default is to not write any
+//! values to the registry.
+//!
+//! ### `--require-clean-git`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Fails if the git working tree of the package has uncommitted changes, which
+//! catches accidentally creating an installer that cannot be reproduced from a
+//! commit. The `git status --porcelain` command is run in the package's root
+//! folder before the installer is created. Without this flag, or the
+//! `require-clean-git` field of the `[package.metadata.wix]` section, git is
+//! not run. The check is skipped if the package is not within a git repository
+//! or git is not installed.
+//!
+//! ### `--retries`
+//!
+//! Available for the _sign_ (`cargo wix sign`) subcommand.
+//!
+//! The number of times to retry signing when the timestamp server fails.
+//! Public timestamp servers can be unreliable, so if the signer (`signtool`)
+//! reports that the timestamp server could not be reached or returned an
+//! invalid response, then signing is retried after waiting one second, and
+//! the wait doubles for each retry. Any other failure, such as a missing or
+//! invalid certificate, fails immediately. The default is three (3) retries.
+//!
+//! ### `--show-logo`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Shows the startup banner, or logo, of the compiler (candle.exe) and the
+//! linker (light.exe). By default, the `-nologo` flag is passed to both
+//! applications, so it does not need to be added with the `-C,--compiler-arg`
+//! and `-L,--linker-arg` options.
+//!
+//! ### `--sign-binaries`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Signs each binary included in the installer with the signer (`signtool`)
+//! application from the Windows SDK after the binaries are built and before
+//! the installer is compiled. The binaries are the `[[bin]]` sections of the
+//! package's manifest (Cargo.toml), or the package's name if there are no
+//! `[[bin]]` sections, which matches the binaries included by the default WiX
+//! Source (wxs) file. The signer is found using the `SIGNTOOL_PATH`
+//! environment variable or the `PATH` system environment variable. The
+//! installer itself is not signed, but it can be signed afterwards with the
+//! `cargo wix sign` subcommand, so both the binaries and the installer are
+//! signed.
+//!
+//! ### `--sign-retries`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! The number of times to retry signing a binary with the `--sign-binaries`
+//! flag when the timestamp server fails. See the `--retries` option for the
+//! _sign_ (`cargo wix sign`) subcommand for more information. The default is
+//! three (3) retries.
+//!
+//! ### `--skip-license-file`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! When printing the WXS template to a file with the `-o,--output` or
+//! `--output-dir` options, a `License.rtf` file is rendered from the embedded
+//! license template and written next to the WiX Source file (WXS) if the
+//! `license` field of the package's manifest (Cargo.toml) is a supported
+//! license. An existing `License.rtf` file is not overwritten, so any edits to
+//! it are kept; remove the file to render it again. This flag skips writing the
+//! `License.rtf` file, but the WiX Source file (WXS) still references it. This is
+//! useful if the license file is managed separately.
+//!
+//! ### `--stamp`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Appends the current time in UTC and the abbreviated revision of the git
+//! `HEAD` commit to the `Comments` field of the installer's summary
+//! information when printing the WXS template, i.e. `Built
+//! 2024-01-31T12:00:00Z from 1a2b3c4`, for traceability. The stamp is also
+//! available to a template as the `build-stamp` variable. Only the time is
+//! used if git is not installed or the package is not in a git repository.
+//!
+//! ### `--strict-metadata`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Fails when printing the WXS template if a description, help URL, license
+//! file, or End User License Agreement (EULA) cannot be found at the command
+//! line or in the package's manifest (Cargo.toml). This is useful for catching
+//! incomplete installer metadata for a release in continuous integration (CI).
+//! The default is to display a warning and print the template without the
+//! missing metadata.
+//!
+//! ### `--strict-version`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Fails if the version cannot be represented by the Windows Installer. The
+//! Windows Installer limits the major and minor parts of the product version
+//! to 255 and the build part to 65,535, so a version such as `256.0.0` is
+//! truncated or rejected when the installer is used, which can prevent
+//! upgrades from working as expected. This is the default, and the version is
+//! checked before the package is built. The flag is kept for compatibility
+//! with earlier versions. Use the `--no-strict-version` flag to display a
+//! warning and still create the installer.
+//!
+//! ### `--suppress-warning`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Suppresses a WiX Toolset warning by its number. The warning number is
+//! passed as the `-sw<N>` option to both the compiler (candle.exe) and the
+//! linker (light.exe). Use this option repeatedly to suppress multiple
+//! warnings. The default is to use the `suppress-warnings` field of the
+//! `[package.metadata.wix]` section of the package's manifest (Cargo.toml), or
+//! to not suppress any warnings if the field does not exist.
+//!
+//! ### `--target-dir`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the target directory, which is forwarded to the builder (Cargo) as the
+//! `--target-dir` option. The binaries are read from, and the installer is
+//! written to, this directory. This takes precedence over the
+//! `CARGO_TARGET_DIR` environment variable. The default is to use the
+//! `CARGO_TARGET_DIR` environment variable if it is set; otherwise, the `target`
+//! folder is used.
+//!
+//! ### `--timeout`
+//!
+//! Available for the default _create_ (`cargo wix`) and _sign_ (`cargo wix
+//! sign`) subcommands.
+//!
+//! Sets the maximum number of seconds to wait for each application that is
+//! run, i.e. the builder (Cargo), compiler (candle.exe), linker (light.exe),
+//! signer (signtool.exe), and the `--pre-build` and `--post-build` commands.
+//! An application that does not exit in time is stopped, and an error occurs.
+//! This prevents a stalled application, such as one reading from an
+//! unresponsive network drive, from blocking forever. The default is to wait
+//! without a timeout.
+//!
 //! ### `-t,--timestamp`
 //!
 //! Available for the _sign_ (`cargo wix sign`) subcommand.
@@ -951,15 +1809,101 @@
 //! certificate. Valid aliases are: `Comodo` and `Versign`, which are case
 //! insenstive.
 //!
+//! ### `--template-file`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Renders a [mustache] template from a file instead of the embedded WiX Source
+//! (WXS) template. The same values are available to the template in the file
+//! as the embedded template, so a customized template can be maintained and
+//! shared across projects without editing the generated output each time. An
+//! error occurs if the file cannot be read or is not a valid mustache
+//! template. A warning is printed for each variable in the template that is not
+//! available, since mustache renders unknown variables as blank text.
+//!
+//! The available variables are: `allow-downgrades`, `banner`, `binaries`,
+//! `binary-display-name`, `binary-index`, `binary-name`, `binary-source`,
+//! `binary-symbols-name`, `binary-symbols-source`, `build-stamp`, `comments`,
+//! `company-dir`, `compression`, `description`, `dialog`, `downgrade-message`,
+//! `eula`, `file-association`, `file-association-description`,
+//! `file-association-extension`, `file-association-guid`,
+//! `file-association-prog-id`, `fixed-platform`, `help-url`, `install-dir`,
+//! `keywords`, `language`, `license-name`, `license-source`, `manufacturer`,
+//! `path-component-guid`, `per-binary-features`, `product-icon`,
+//! `product-name`, `properties`, `property-id`, `property-value`,
+//! `readme-name`, `readme-source`, `registry`, `registry-guid`, `registry-key`,
+//! `registry-key-path`, `registry-name`, `registry-root`, `registry-type`,
+//! `registry-value`, `registry-values`, `ui`, `ui-install-dir`,
+//! `ui-next-dialog`, `uninstall-shortcut`, `uninstall-shortcut-guid`,
+//! `upgrade-code-guid`, and `win64`. The `binary-*` variables are only
+//! available within the `binaries` section, the `file-association-*` variables
+//! are only available within the `file-association` section, the `property-*`
+//! variables are only available within the `properties` section, and the
+//! `registry-*` variables, other than `registry-guid` and `registry-values`,
+//! are only available within the `registry-values` section. The
+//! `uninstall-shortcut-guid` variable is only available within the
+//! `uninstall-shortcut` section.
+//!
+//! [mustache]: https://mustache.github.io/
+//!
+//! ### `--ui`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the set of dialogs from the WixUI extension for the installer's
+//! graphical user interface (GUI). The values are: `FeatureTree`, `InstallDir`,
+//! `Minimal`, `Mondo`, and `None`, which are case insensitive. The license
+//! agreement dialog is skipped if an End User License Agreement (EULA) is not
+//! available, except for the `Minimal` dialog set, which always displays a
+//! license agreement. The `InstallDir` dialog set lets the user change the
+//! installation folder through the `WIXUI_INSTALLDIR` property. The `None`
+//! value removes the dialogs, and only the progress of the installation is
+//! displayed. The default is `FeatureTree`.
+//!
+//! ### `--ui-license`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the path to a Rich Text Format (RTF) file that is displayed in the
+//! license agreement dialog when printing the WXS template. Only the
+//! `WixUILicenseRtf` variable is changed, and this takes precedence over the
+//! `-e,--eula`, `--eula-from-markdown`, and `-l,--license` options for the
+//! dialog. The license installed with the binaries is not changed, so a custom
+//! EULA can be displayed while a separate license file is installed. The default
+//! is to display the EULA determined from the other options and the package's
+//! manifest (Cargo.toml).
+//!
+//! ### `--uninstall-shortcut`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Adds a folder with the product name to the Start Menu with an "Uninstall"
+//! shortcut when printing the WXS template. The shortcut runs `msiexec /x
+//! [ProductCode]` to uninstall the product, and the folder and shortcut are
+//! removed when the product is uninstalled. The default is to not add a
+//! shortcut.
+//!
+//! ### `--upgrade-guid`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand.
+//!
+//! Sets the upgrade code, a GUID that identifies the product across versions,
+//! when printing the WXS or WXI templates. This is useful for reproducible
+//! installers or when the upgrade code is stored elsewhere, such as a secrets
+//! store. An error occurs if the value is not a valid GUID. The default is to
+//! generate a new, random GUID each time a template is printed.
+//!
 //! ### `-u,--url`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
 //! subcommands.
 //!
 //! Adds a URL to the installer that will be displayed in the Add/Remove
-//! Programs (ARP) control panel for the application. The default is to disable
-//! it unless a URL is specified for either the `homepage`, `documentation`, or
-//! `repository` fields in the package's manifest (Cargo.toml). The help URL can
+//! Programs (ARP) control panel for the application. The default is to use the
+//! `help-url` field of the `[package.metadata.wix]` section in the package's
+//! manifest (Cargo.toml). Otherwise, it is disabled unless a URL is specified
+//! for either the `homepage`, `documentation`, or `repository` fields in the
+//! package's manifest. The help URL can
 //! be enabled after initialization by directly modifying the WiX Source (wxs)
 //! file with a text editor.
 //!
@@ -978,6 +1922,15 @@
 //! will be printed during execution of a subcommand. When combined with the
 //! `--nocapture` flag, this is useful for debugging and testing.
 //!
+//! ### `--warnings-as-errors`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Treats WiX Toolset warnings as errors by passing the `-wx` flag to both the
+//! compiler (candle.exe) and the linker (light.exe). Suppressed warnings are
+//! still ignored. The default is to use the `warnings-as-errors` field of the
+//! `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+//!
 //! ### `-y,--year`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
@@ -1044,9 +1997,11 @@ use env_logger::Builder;
 use log::{Level, LevelFilter};
 
 use std::io::Write;
+use std::time::Duration;
 
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
+use wix::check;
 use wix::clean;
 use wix::create;
 use wix::initialize;
@@ -1055,6 +2010,10 @@ use wix::purge;
 use wix::sign;
 use wix::{Cultures, Template, BINARY_FOLDER_NAME, WIX_PATH_KEY};
 
+// The value for the `TEMPLATE` argument of the `print` subcommand that lists
+// the supported culture codes.
+const CULTURES_TEMPLATE: &str = "cultures";
+
 const SUBCOMMAND_NAME: &str = "wix";
 
 fn main() {
@@ -1081,7 +2040,9 @@ fn main() {
              defined; otherwise, all binaries defined in the package's manifest \
              in each '[[bin]]' section are included. This option overrides any \
              and all binaries defined in the package's manifest. Use this option \
-             repeatedly to include multiple binaries.",
+             repeatedly to include multiple binaries. The path can also be a \
+             glob pattern, i.e. 'target\\release\\*.exe', to include all of \
+             the matching files.",
         )
         .long("binary")
         .multiple(true)
@@ -1110,14 +2071,17 @@ fn main() {
         .long("dialog")
         .short("D")
         .takes_value(true);
-    // The eula option for the `init` and `print` subcommands.
+    // The eula option for the `check`, `init`, and `print` subcommands.
     let eula = Arg::with_name("eula")
         .help("A path to a RTF file (.rtf) for the installer's license agreement dialog")
         .long_help(
             "Specifies a Rich Text Format (RTF) file to use as the End \
              User License Agreement (EULA) for the license agreement dialog of the \
-             installer. The default is to disable the license agreement dialog unless \
-             one of the supported licenses (GPL-3.0, Apache-2.0, or MIT) is generated \
+             installer. The default is to use the 'eula' field of the \
+             [package.metadata.wix] section in the package's manifest (Cargo.toml). \
+             Otherwise, the license agreement dialog is disabled unless \
+             one of the supported licenses (GPL-3.0, Apache-2.0, MIT, BSD-2-Clause, \
+             BSD-3-Clause, ISC, or MPL-2.0) is generated \
              based on the value of the 'license' field in the package's manifest \
              (Cargo.toml). An EULA can be enabled later by directly modifying the WiX \
              Source (wxs) file with a text editor.",
@@ -1146,7 +2110,8 @@ fn main() {
         .long_help(
             "Adds a URL to the installer that will be displayed in the \
              Add/Remove Programs control panel for the application. The default is to \
-             disable it unless a URL is specified for either the 'homepage', \
+             use the 'help-url' field of the [package.metadata.wix] section in the \
+             package's manifest (Cargo.toml). Otherwise, it is disabled unless a URL is specified for either the 'homepage', \
              'documentation', or 'repository' fields in the package's manifest \
              (Cargo.toml). The help URL can be enabled after initialization by \
              directly modifying the WiX Source (wxs) file with a text editor.",
@@ -1158,9 +2123,10 @@ fn main() {
     let manufacturer = Arg::with_name("manufacturer")
         .help("A string for the Add/Remove Programs control panel's Manufacturer")
         .long_help(
-            "Overrides the first author in the 'authors' field of the \
-             package's manifest (Cargo.toml) as the manufacturer within the \
-             installer. The manufacturer can be changed after initialization by \
+            "Overrides the 'manufacturer' field of the [package.metadata.wix] \
+             section, the first author in the 'authors' field, or the domain of \
+             the 'homepage' field of the package's manifest (Cargo.toml) as the \
+             manufacturer within the installer. The manufacturer can be changed after initialization by \
              directly modifying the WiX Source file (wxs) with a text editor.",
         )
         .long("manufacturer")
@@ -1179,7 +2145,7 @@ fn main() {
         .long("owner")
         .short("O")
         .takes_value(true);
-    // The product icon option for the `init` and `print` subcommands
+    // The product icon option for the `check`, `init`, and `print` subcommands
     let product_icon = Arg::with_name("product-icon")
         .help("A path to an image file (.ico) for the Add/Remove Programs control panel")
         .long_help(
@@ -1190,6 +2156,24 @@ fn main() {
         .long("product-icon")
         .short("p")
         .takes_value(true);
+    // The timeout option for the default `create` and the `sign` subcommands
+    let timeout = Arg::with_name("timeout")
+        .help("The maximum number of seconds to wait for each application")
+        .long_help(
+            "Stops an application, such as the builder (cargo), compiler \
+             (candle), linker (light), or signer (signtool), and fails if it does \
+             not exit within the number of seconds. The default is to wait without \
+             a timeout.",
+        )
+        .long("timeout")
+        .takes_value(true)
+        .validator(|t| {
+            t.parse::<u64>()
+                .ok()
+                .filter(|t| *t > 0)
+                .map(|_| ())
+                .ok_or_else(|| String::from("The value must be a positive integer"))
+        });
     // The product name option for the `init`, `print`, and `sign` subcommands
     let product_name = Arg::with_name("product-name")
         .help("A string for the Add/Remove Programs control panel's Name")
@@ -1234,6 +2218,12 @@ fn main() {
             SubCommand::with_name(SUBCOMMAND_NAME)
                 .version(crate_version!())
                 .about(crate_description!())
+                .arg(Arg::with_name("all-features")
+                    .help("Activates all available features when building the binaries")
+                    .long_help("Passes the '--all-features' flag to the 'cargo \
+                        build' command. This is ignored if the '--no-build' flag is \
+                        used.")
+                    .long("all-features"))
                 .arg(Arg::with_name("bin-path")
                      .help(&format!(
                          "A path to the WiX Toolset's '{}' folder",
@@ -1251,6 +2241,51 @@ fn main() {
                      .long("bin-path")
                      .short("b")
                      .takes_value(true))
+                .subcommand(SubCommand::with_name("check")
+                    .version(crate_version!())
+                    .about("Checks the prerequisites for creating an installer")
+                    .long_about("Checks the WiX Toolset can be found, the package's \
+                        manifest (Cargo.toml) has a name and version, the WiX Source \
+                        (wxs) files exist, and any referenced files exist without \
+                        building the binaries or creating the installer. All of the \
+                        problems that are found are reported.")
+                    .arg(Arg::with_name("banner")
+                        .help("A path to an image file (.bmp) for the installer's banner")
+                        .long("banner")
+                        .takes_value(true))
+                    .arg(Arg::with_name("bin-path")
+                        .help(&format!(
+                            "A path to the WiX Toolset's '{}' folder",
+                            BINARY_FOLDER_NAME))
+                        .long("bin-path")
+                        .short("b")
+                        .takes_value(true))
+                    .arg(Arg::with_name("compiler-name")
+                        .help("The name of the WiX compiler application")
+                        .long("compiler-name")
+                        .takes_value(true))
+                    .arg(eula.clone())
+                    .arg(Arg::with_name("include")
+                        .help("Include an additional WiX Source (wxs) file")
+                        .long("include")
+                        .short("I")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .takes_value(true))
+                    .arg(Arg::with_name("INPUT")
+                        .help("A path to a package's manifest (Cargo.toml)")
+                        .index(1))
+                    .arg(Arg::with_name("linker-name")
+                        .help("The name of the WiX linker application")
+                        .long("linker-name")
+                        .takes_value(true))
+                    .arg(Arg::with_name("locale")
+                        .help("A path to a WiX localization file (.wxl)")
+                        .long("locale")
+                        .short("l")
+                        .takes_value(true))
+                    .arg(product_icon.clone())
+                    .arg(verbose.clone()))
                 .subcommand(SubCommand::with_name("clean")
                     .version(crate_version!())
                     .about("Deletes the 'target\\wix' folder")
@@ -1262,12 +2297,37 @@ fn main() {
                             is optional and the default is to use the current \
                             working directory (cwd).")
                          .index(1)))
+                .arg(Arg::with_name("candle-arch")
+                    .help("The architecture passed to the compiler")
+                    .long_help("Passes the value as the '-arch' option to the \
+                        compiler (candle), which determines the default bitness of \
+                        the components when a WiX Source (wxs) file does not \
+                        specify the 'Win64' attribute. The default is to use the \
+                        platform of the installer.")
+                    .possible_values(&create::CANDLE_ARCHITECTURES)
+                    .long("candle-arch")
+                    .takes_value(true))
+                .arg(Arg::with_name("checksum")
+                    .help("Writes a checksum file next to the installer")
+                    .long_help("Computes the digest of the installer after it is \
+                        created and writes it to a file with the same name and the \
+                        '.sha256' or '.sha512' extension appended, i.e. \
+                        'example.msi.sha256'. The file has the same format as the \
+                        'sha256sum' and 'sha512sum' utilities. [values: sha256, \
+                        sha512]")
+                    .hide_possible_values(true)
+                    .possible_values(&create::ChecksumKind::possible_values())
+                    .long("checksum")
+                    .takes_value(true))
                 .arg(Arg::with_name("culture")
                     .help("The culture code for localization")
                     .long_help("Sets the culture for localization. Use with the \
                         '-l,--locale' option. See the WixUI localization \
                         documentation for more information about acceptable culture \
-                        codes. The codes are case insensitive.")
+                        codes. The codes are case insensitive and either a hyphen \
+                        or an underscore can separate the language and region. A \
+                        two-letter language code, such as 'fr', uses the most \
+                        common region for the language.")
                     .long("culture")
                     .short("c")
                     .default_value(&default_culture)
@@ -1284,6 +2344,15 @@ fn main() {
                     .short("C")
                     .takes_value(true)
                     .multiple(true))
+                .arg(Arg::with_name("compiler-name")
+                    .help("The name of the WiX compiler application")
+                    .long_help("Sets the name of the compiler application without \
+                        the file extension. The compiler is still located with the \
+                        '-b,--bin-path' option, the WIX system environment \
+                        variable, or the PATH system environment variable. The \
+                        default is 'candle'.")
+                    .long("compiler-name")
+                    .takes_value(true))
                 .arg(Arg::with_name("debug-build")
                     .help("Builds the package using the Debug profile")
                     .long_help("Uses the Debug profile when building the package \
@@ -1302,16 +2371,99 @@ fn main() {
                         binary with the Debug profile.")
                     .long("dbg-name")
                     .short("D"))
+                .arg(Arg::with_name("dry-run")
+                    .help("Prints the commands instead of executing them")
+                    .long_help("Prints the builder (cargo), compiler (candle), \
+                        and linker (light) commands, one per line, instead of \
+                        executing them. No installer is created.")
+                    .long("dry-run"))
+                .arg(Arg::with_name("extension")
+                    .help("Add a WiX extension by name or by path to a dll")
+                    .long_help("Passes the extension to the compiler (candle) \
+                        and the linker (light) with the '-ext' option. A name, \
+                        i.e. 'WixFirewallExtension', is passed unchanged, while a \
+                        value with a path separator or the '.dll' file extension is \
+                        a path to a custom extension that must exist. A relative \
+                        path is resolved against the folder containing the \
+                        package's manifest. Use this option multiple times to add \
+                        multiple extensions.")
+                    .long("extension")
+                    .multiple(true)
+                    .number_of_values(1)
+                    .takes_value(true))
+                .arg(Arg::with_name("features")
+                    .help("Features to activate when building the binaries")
+                    .long_help("Passes the features to the 'cargo build' command \
+                        with the '--features' option. Multiple features can be \
+                        separated by commas or spaces, or this option can be used \
+                        multiple times. This is ignored if the '--no-build' flag is \
+                        used.")
+                    .long("features")
+                    .multiple(true)
+                    .number_of_values(1)
+                    .takes_value(true))
+                .arg(Arg::with_name("force")
+                    .help("Compiles the WiX Source (wxs) files even if they are unchanged")
+                    .long_help("Always executes the compiler (candle). By \
+                        default, the compiler is skipped if every WiX object \
+                        (wixobj) file is newer than its WiX Source (wxs) file and \
+                        the files it includes, and the defines for the compiler \
+                        have not changed since the previous build.")
+                    .long("force"))
+                .arg(Arg::with_name("frozen")
+                    .help("Requires the Cargo.lock file to be up to date and disables network access")
+                    .long_help("Passes the '--frozen' flag to the 'cargo build' \
+                        command. This is ignored if the '--no-build' flag is used.")
+                    .long("frozen"))
+                .arg(Arg::with_name("hash-in-name")
+                    .help("Includes a hash of the installer in its file name")
+                    .long_help("Renames the installer after it is created to \
+                        include the first eight hexadecimal characters of its \
+                        SHA-256 digest before the file extension, i.e. \
+                        'example-0.1.0-x86_64-1a2b3c4d.msi'.")
+                    .long("hash-in-name"))
                 .arg(Arg::with_name("include")
                     .help("Include an additional WiX Source (wxs) file")
                     .long_help("Includes a WiX source (wxs) file for a project, \
                         where the wxs file is not located in the default location, \
                         i.e. 'wix'. Use this option multiple times to include \
-                        multiple wxs files.")
+                        multiple wxs files. A relative path is resolved against \
+                        the folder containing the package's manifest.")
                     .long("include")
                     .multiple(true)
                     .short("I")
                     .takes_value(true))
+                .arg(Arg::with_name("jobs")
+                    .help("The number of compiler processes to run in parallel")
+                    .long_help("Compiles each WiX Source (wxs) file with a \
+                        separate compiler (candle) process and runs up to this \
+                        number of processes in parallel. A value of zero (0) uses \
+                        the available parallelism of the system. The default is to \
+                        compile all of the WiX Source (wxs) files with a single \
+                        compiler process.")
+                    .long("jobs")
+                    .short("j")
+                    .takes_value(true)
+                    .validator(|j| {
+                        j.parse::<usize>()
+                            .map(|_| ())
+                            .map_err(|_| String::from("The value must be a non-negative integer"))
+                    }))
+                .arg(Arg::with_name("keep-pdb")
+                    .help("Keeps the WiX debug information (wixpdb) file")
+                    .long_help("Keeps the WiX debug information (wixpdb) file \
+                        created by the linker (light) next to the installer. The \
+                        wixpdb file is needed for authoring patches. The default \
+                        is to suppress the creation of the wixpdb file.")
+                    .long("keep-pdb"))
+                .arg(Arg::with_name("pdb-output")
+                    .help("A path for the WiX debug information (wixpdb) file")
+                    .long_help("Writes the WiX debug information (wixpdb) file \
+                        created by the linker (light) to the path instead of next \
+                        to the installer. This implies the '--keep-pdb' flag. A \
+                        relative path is relative to the package's root.")
+                    .long("pdb-output")
+                    .takes_value(true))
                 .subcommand(SubCommand::with_name("init")
                     .version(crate_version!())
                     .about("Generates files from a package's manifest (Cargo.toml) to create an installer")
@@ -1319,7 +2471,8 @@ fn main() {
                         file that can be used immediately without modification to create an \
                         installer for the package. This will also generate an EULA in the Rich \
                         Text Format (RTF) if the 'license' field is specified with a supported \
-                        license (GPL-3.0, Apache-2.0, or MIT). All generated files are placed in \
+                        license (GPL-3.0, Apache-2.0, MIT, BSD-2-Clause, BSD-3-Clause, ISC, or \
+                        MPL-2.0). All generated files are placed in \
                         the 'wix' sub-folder by default.")
                     .arg(banner.clone())
                     .arg(binaries.clone())
@@ -1360,7 +2513,8 @@ fn main() {
                         working directory (CWD) will be used to locate a package's \
                         manifest. An error will occur if a manifest cannot be \
                         found. A relative or absolute path to a package's manifest \
-                        (Cargo.toml) file can be used. Only one manifest is \
+                        (Cargo.toml) file, or a folder containing the manifest, \
+                        can be used. Only one manifest is \
                         allowed. The creation of an installer will be relative to \
                         the specified manifest.")
                      .required(false)
@@ -1373,6 +2527,17 @@ fn main() {
                     .long("install-version")
                     .short("i")
                     .takes_value(true))
+                .arg(Arg::with_name("library")
+                    .help("Link a WiX library (wixlib) into the installer")
+                    .long_help("Passes a pre-built WiX library (wixlib) to the \
+                        linker (light) alongside the WiX object files (wixobj). Use \
+                        this option multiple times to link multiple libraries. A \
+                        relative path is resolved against the folder containing \
+                        the package's manifest.")
+                    .long("library")
+                    .multiple(true)
+                    .number_of_values(1)
+                    .takes_value(true))
                 .arg(Arg::with_name("linker-arg")
                     .help("Send an argument to the WiX linker (light.exe)")
                     .long_help("Appends the argument to the command that is \
@@ -1385,6 +2550,15 @@ fn main() {
                     .short("L")
                     .takes_value(true)
                     .multiple(true))
+                .arg(Arg::with_name("linker-name")
+                    .help("The name of the WiX linker application")
+                    .long_help("Sets the name of the linker application without \
+                        the file extension. The linker is still located with the \
+                        '-b,--bin-path' option, the WIX system environment \
+                        variable, or the PATH system environment variable. The \
+                        default is 'light'.")
+                    .long("linker-name")
+                    .takes_value(true))
                 .arg(Arg::with_name("locale")
                     .help("A path to a WiX localization file (.wxl)")
                     .long_help("Sets the path to a WiX localization file (wxl) \
@@ -1393,6 +2567,49 @@ fn main() {
                     .long("locale")
                     .short("l")
                     .takes_value(true))
+                .arg(Arg::with_name("locked")
+                    .help("Requires the Cargo.lock file to be up to date")
+                    .long_help("Passes the '--locked' flag to the 'cargo build' \
+                        command. This is ignored if the '--no-build' flag is used.")
+                    .long("locked"))
+                .arg(Arg::with_name("manifest-path")
+                    .help("A path to a package's manifest (Cargo.toml)")
+                    .long_help("An alternative to the <INPUT> argument, which is \
+                        similar to the '--manifest-path' option for cargo. The \
+                        path can be to the manifest or to a folder containing the \
+                        manifest.")
+                    .long("manifest-path")
+                    .takes_value(true)
+                    .conflicts_with("INPUT"))
+                .arg(Arg::with_name("merge-args")
+                    .help("Merges the compiler and linker arguments from the manifest with the command line")
+                    .long_help("Uses the 'compiler-args' and 'linker-args' values \
+                        in the package's manifest (Cargo.toml) followed by the \
+                        '-C,--compiler-arg' and '-L,--linker-arg' values. The \
+                        default is to use the values in the package's manifest \
+                        instead of the values from the command line.")
+                    .long("merge-args"))
+                .arg(Arg::with_name("message-format")
+                    .help("The format of the message printed after the installer is created")
+                    .long_help("Prints a single line with a JSON object to stdout \
+                        after the installer is created if 'json'. The object \
+                        contains the product name, version, platform, culture, \
+                        path to the installer, and elapsed time in seconds. The \
+                        default is 'human', which does not print a message. \
+                        [values: human, json]")
+                    .hide_possible_values(true)
+                    .possible_values(&create::MessageFormat::possible_values())
+                    .long("message-format")
+                    .takes_value(true))
+                .arg(Arg::with_name("min-wix-version")
+                    .help("The minimum version of the WiX Toolset")
+                    .long_help("Fails before compiling the installer if the \
+                        version of the installed WiX Toolset, which is read from \
+                        the compiler's (candle) banner, is older than this \
+                        version or cannot be determined. The version can have one \
+                        to three parts, i.e. '3.11'.")
+                    .long("min-wix-version")
+                    .takes_value(true))
                 .arg(Arg::with_name("name")
                     .help("A string for the installer's product name")
                     .long_help("Overrides the 'name' field in the package's \
@@ -1402,11 +2619,51 @@ fn main() {
                     .long("name")
                     .short("n")
                     .takes_value(true))
+                .arg(Arg::with_name("name-suffix")
+                    .help("A suffix for the file name of the installer")
+                    .long_help("Appends the suffix verbatim to the file stem of \
+                        the installer (msi), i.e. '-nightly' creates \
+                        'example-0.1.0-x86_64-nightly.msi'. The suffix is appended \
+                        after the '-debug' suffix from the '--dbg-name' flag. The \
+                        version of the installer is not changed.")
+                    .long("name-suffix")
+                    .allow_hyphen_values(true)
+                    .takes_value(true))
                 .arg(Arg::with_name("no-build")
                     .help("Skips building the release binary")
                     .long_help("The installer is created, but the 'cargo build \
                         --release' is not executed.")
                     .long("no-build"))
+                .arg(Arg::with_name("no-default-features")
+                    .help("Deactivates the default features when building the binaries")
+                    .long_help("Passes the '--no-default-features' flag to the \
+                        'cargo build' command. This is ignored if the '--no-build' \
+                        flag is used.")
+                    .long("no-default-features"))
+                .arg(Arg::with_name("no-ui")
+                    .help("Links the installer without the WixUI extension")
+                    .long_help("Omits the '-ext WixUIExtension' and '-cultures:' \
+                        options for the linker (light) to create an installer \
+                        without a user interface. The WiX Source (wxs) files must \
+                        not reference a WixUI dialog set.")
+                    .long("no-ui"))
+                .arg(Arg::with_name("no-util-extension")
+                    .help("Compiles and links the installer without the WixUtil extension")
+                    .long_help("Omits the '-ext WixUtilExtension' option for the \
+                        compiler (candle) and the linker (light). The WiX Source \
+                        (wxs) files must not use any elements from the extension. \
+                        A different version of the extension can be passed with \
+                        the '--compiler-arg' and '--linker-arg' options.")
+                    .long("no-util-extension"))
+                .arg(Arg::with_name("no-sort-sources")
+                    .help("Skips sorting the WiX Source (wxs) files in the 'wix' folder")
+                    .long_help("By default, the WiX Source (wxs) files in the \
+                        'wix' folder are sorted by path before they are passed to \
+                        the compiler (candle), so the same installer is created on \
+                        every machine. This flag passes the files in the order \
+                        they are read from the folder, which depends on the file \
+                        system.")
+                    .long("no-sort-sources"))
                 .arg(Arg::with_name("no-capture")
                     .help("Displays all output from the builder, compiler, linker, and signer")
                     .long_help("By default, this subcommand captures, or hides, \
@@ -1414,6 +2671,11 @@ fn main() {
                         for the binary and Windows installer, respectively. Use this \
                         flag to show the output.")
                     .long("nocapture"))
+                .arg(Arg::with_name("offline")
+                    .help("Disables network access when building the binaries")
+                    .long_help("Passes the '--offline' flag to the 'cargo build' \
+                        command. This is ignored if the '--no-build' flag is used.")
+                    .long("offline"))
                 .arg(Arg::with_name("output")
                     .help("A path to a destination file or an existing folder")
                     .long_help("Sets the destination file name and path for the \
@@ -1430,6 +2692,132 @@ fn main() {
                     .long("output")
                     .short("o")
                     .takes_value(true))
+                .arg(Arg::with_name("post-build")
+                    .help("A command to run after the installer is created")
+                    .long_help("Runs the command with the system shell, i.e. \
+                        'cmd /C' on Windows, from the package's root folder after \
+                        the installer is linked. The path to the installer is \
+                        available to the command in the 'CARGO_WIX_MSI' \
+                        environment variable. An error occurs if the command \
+                        fails.")
+                    .long("post-build")
+                    .takes_value(true))
+                .arg(Arg::with_name("pre-build")
+                    .help("A command to run before the binaries are built")
+                    .long_help("Runs the command with the system shell, i.e. \
+                        'cmd /C' on Windows, from the package's root folder before \
+                        the binaries are built, or before the installer is \
+                        compiled if the '--no-build' flag is used. The product name \
+                        and version are available to the command in the \
+                        'CARGO_WIX_PRODUCT_NAME' and 'CARGO_WIX_VERSION' \
+                        environment variables. An error occurs if the command \
+                        fails.")
+                    .long("pre-build")
+                    .takes_value(true))
+                .arg(Arg::with_name("profile-var")
+                    .help("The name of the preprocessor variable for the build profile")
+                    .long_help("Sets the name of the preprocessor variable that is \
+                        defined as 'debug' or 'release' for the compiler (candle), \
+                        i.e. '-d<name>=release'. This must match the variable \
+                        used in the source paths of the binaries in the WiX \
+                        Source (wxs) files. The default is 'Profile'.")
+                    .long("profile-var")
+                    .takes_value(true))
+                .arg(Arg::with_name("quiet")
+                    .help("Hides the logo and all output of the WiX Toolset")
+                    .long_help("Passes the '-nologo' flag to the compiler (candle) \
+                        and the linker (light), even if the '--show-logo' flag is \
+                        used, and captures all output from the builder, compiler, \
+                        linker, and signer, even if the '--nocapture' flag is \
+                        used.")
+                    .long("quiet"))
+                .arg(Arg::with_name("require-clean-git")
+                    .help("Fails if the git working tree has uncommitted changes")
+                    .long_help("Runs 'git status --porcelain' in the package's root \
+                        folder and fails if there are uncommitted changes. The \
+                        check is skipped if the package is not within a git \
+                        repository.")
+                    .long("require-clean-git"))
+                .arg(Arg::with_name("show-logo")
+                    .help("Shows the logo of the compiler and linker")
+                    .long_help("By default, the '-nologo' flag is passed to the \
+                        compiler (candle) and the linker (light). Use this flag to \
+                        show the startup banner, or logo, of each application.")
+                    .long("show-logo"))
+                .arg(Arg::with_name("sign-binaries")
+                    .help("Signs the binaries before creating the installer")
+                    .long_help("Each binary included in the installer is signed \
+                        with the signer (signtool) application from the Windows \
+                        SDK after the binaries are built and before the installer \
+                        is compiled. The installer is not signed. Use the 'cargo \
+                        wix sign' subcommand to sign the installer.")
+                    .long("sign-binaries"))
+                .arg(Arg::with_name("sign-retries")
+                    .help("The number of times to retry signing a binary")
+                    .long_help("Retries signing a binary with the '--sign-binaries' \
+                        flag when the timestamp server fails. The wait before each \
+                        retry doubles, starting at one second. The default is \
+                        three (3) retries.")
+                    .long("sign-retries")
+                    .takes_value(true)
+                    .validator(|r| {
+                        r.parse::<u32>()
+                            .map(|_| ())
+                            .map_err(|_| String::from("The value must be a non-negative integer"))
+                    }))
+                .arg(Arg::with_name("strict-version")
+                    .help("Fails if the version exceeds the Windows Installer limits")
+                    .long_help("The Windows Installer limits the major and minor \
+                        parts of the product version to 255 and the build part to \
+                        65535. By default, an error occurs if the version \
+                        exceeds these limits, so this flag is only kept for \
+                        compatibility.")
+                    .long("strict-version"))
+                .arg(Arg::with_name("no-strict-version")
+                    .help("Warns instead of failing if the version exceeds the Windows Installer limits")
+                    .long_help("Displays a warning and still creates the installer \
+                        if the version exceeds the 255.255.65535 limits of the \
+                        Windows Installer for the product version.")
+                    .long("no-strict-version")
+                    .conflicts_with("strict-version"))
+                .arg(Arg::with_name("suppress-warning")
+                    .help("A WiX Toolset warning number to suppress")
+                    .long_help("Passes the warning number as the '-sw<N>' option \
+                        to both the compiler (candle) and the linker (light). Use \
+                        this option repeatedly to suppress multiple warnings. The \
+                        default is to use the 'suppress-warnings' field of the \
+                        [package.metadata.wix] section of the package's manifest \
+                        (Cargo.toml).")
+                    .long("suppress-warning")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .validator(|w| {
+                        w.parse::<u32>()
+                            .map(|_| ())
+                            .map_err(|_| String::from("The value must be a non-negative integer"))
+                    }))
+                .arg(Arg::with_name("target-dir")
+                    .help("A path to the directory for all generated artifacts")
+                    .long_help("Forwarded to the builder (cargo) as the \
+                        '--target-dir' option. The binaries are read from, and the \
+                        installer is written to, this directory. This takes \
+                        precedence over the CARGO_TARGET_DIR environment variable. \
+                        The default is to use the CARGO_TARGET_DIR environment \
+                        variable if it is set; otherwise, the 'target' folder is \
+                        used.")
+                    .long("target-dir")
+                    .takes_value(true))
+                .arg(timeout.clone())
+                .arg(Arg::with_name("warnings-as-errors")
+                    .help("Treats WiX Toolset warnings as errors")
+                    .long_help("Passes the '-wx' flag to both the compiler \
+                        (candle) and the linker (light), so creating the installer \
+                        fails if a warning occurs. Suppressed warnings are still \
+                        ignored. The default is to use the 'warnings-as-errors' \
+                        field of the [package.metadata.wix] section of the \
+                        package's manifest (Cargo.toml).")
+                    .long("warnings-as-errors"))
                 .subcommand(SubCommand::with_name("print")
                     .version(crate_version!())
                     .about("Prints a template")
@@ -1438,12 +2826,107 @@ fn main() {
                         (RTF) and for a WiX Source file (wxs), the output is in XML. \
                         New GUIDs are generated for the 'UpgradeCode' and Path \
                         Component each time the 'WXS' template is printed. [values: \
-                        Apache-2.0, GPL-3.0, MIT, WXS]")
+                        Apache-2.0, BSD-2-Clause, BSD-3-Clause, Bundle, GPL-3.0, ISC, \
+                        MIT, MPL-2.0, WXI, WXS]")
+                    .arg(Arg::with_name("allow-downgrades")
+                        .help("Allows an older version to replace a newer version")
+                        .long_help("Sets the 'AllowDowngrades' attribute of the \
+                            'MajorUpgrade' element when printing the 'WXS' template, \
+                            so installing an older version removes a newer version \
+                            instead of failing. This flag is ignored for all other \
+                            templates.")
+                        .long("allow-downgrades"))
                     .arg(banner)
                     .arg(binaries)
+                    .arg(Arg::with_name("binary-display-name")
+                        .help("Sets a display name for a binary")
+                        .long_help("Sets a human-friendly name for a binary when \
+                            printing the 'WXS' template. The name of the binary and \
+                            the display name are required, i.e. \
+                            '--binary-display-name tool \"Example Tool\"'. The \
+                            display name is available to the template as the \
+                            'binary-display-name' variable. Use this option \
+                            repeatedly for multiple binaries. The default is to use \
+                            the name of the binary. This option is ignored for all \
+                            other templates.")
+                        .long("binary-display-name")
+                        .multiple(true)
+                        .number_of_values(2)
+                        .value_names(&["NAME", "DISPLAY_NAME"]))
+                    .arg(Arg::with_name("comments")
+                        .help("Comments for the installer's summary information")
+                        .long_help("Sets the 'Comments' field of the installer's \
+                            summary information when printing the 'WXS' template. \
+                            The default is to use the description. This option is \
+                            ignored for all other templates.")
+                        .long("comments")
+                        .takes_value(true))
+                    .arg(Arg::with_name("company-dir")
+                        .help("A name of a folder for the installation folder")
+                        .long_help("Sets the name of a company folder within the \
+                            Program Files folder that contains the installation \
+                            folder when printing the 'WXS' template. The name cannot \
+                            contain path separators. The default is to install \
+                            directly within the Program Files folder. This option is \
+                            ignored for all other templates.")
+                        .long("company-dir")
+                        .takes_value(true))
+                    .arg(Arg::with_name("compression")
+                        .help("The compression level of the embedded cabinet")
+                        .long_help("Sets the compression level of the cabinet that \
+                            is embedded in the installer when printing the 'WXS' \
+                            template. The default is 'High'. This option is ignored \
+                            for all other templates. [values: None, Low, Medium, \
+                            High, Mszip]")
+                        .hide_possible_values(true)
+                        .possible_values(&print::wxs::Compression::possible_values())
+                        .long("compression")
+                        .takes_value(true))
                     .arg(description)
                     .arg(dialog)
+                    .arg(Arg::with_name("downgrade-message")
+                        .help("The message displayed when a newer version is installed")
+                        .long_help("Sets the 'DowngradeErrorMessage' attribute of the \
+                            'MajorUpgrade' element when printing the 'WXS' template. \
+                            The message can contain Windows Installer properties, \
+                            i.e. '[ProductName]'. This option is ignored with the \
+                            '--allow-downgrades' flag and for all other templates.")
+                        .long("downgrade-message")
+                        .takes_value(true))
                     .arg(eula)
+                    .arg(Arg::with_name("eula-from-markdown")
+                        .help("A path to a Markdown file (.md) for the installer's license agreement dialog")
+                        .long_help("Converts a Markdown file to a Rich Text Format \
+                            (RTF) file and uses it as the End User License Agreement \
+                            (EULA) when printing the 'WXS' template. The RTF file is \
+                            written next to the printed WXS file. Headings, \
+                            paragraphs, emphasis, inline code, code blocks, links, \
+                            and lists are converted, and everything else is kept as \
+                            plain text. The '-e,--eula' option takes precedence. This \
+                            option is ignored for all other templates.")
+                        .long("eula-from-markdown")
+                        .takes_value(true))
+                    .arg(Arg::with_name("file-assoc")
+                        .help("Associates a file extension with the binary")
+                        .long_help("Adds a file association for the first binary \
+                            when printing the 'WXS' template. The extension, \
+                            programmatic identifier (ProgId), and description of the \
+                            file type are required, i.e. '--file-assoc ext \
+                            Example.Document \"Example Document\"'. Opening a file \
+                            with the extension runs the binary with the path to the \
+                            file as its argument. This option is ignored for all \
+                            other templates.")
+                        .long("file-assoc")
+                        .number_of_values(3)
+                        .value_names(&["EXTENSION", "PROGID", "DESCRIPTION"]))
+                    .arg(Arg::with_name("include-pdb")
+                        .help("Installs the debug symbols (pdb) for each binary")
+                        .long_help("Adds the program database (pdb) file of each \
+                            binary next to the binary when printing the 'WXS' \
+                            template, so crash dumps can be symbolicated. The \
+                            program database must exist when the installer is \
+                            created. This flag is ignored for all other templates.")
+                        .long("include-pdb"))
                     .arg(Arg::with_name("INPUT")
                         .help("A path to a package's manifest (Cargo.toml)")
                         .long_help("The selected template will be printed to \
@@ -1452,8 +2935,84 @@ fn main() {
                             current working directory (cwd). An error occurs if a \
                             manifest is not found.")
                         .index(2))
+                    .arg(Arg::with_name("install-dir")
+                        .help("A name of the installation folder")
+                        .long_help("Sets the name of the installation folder when \
+                            printing the 'WXS' template. The name cannot contain path \
+                            separators. The default is to use the product name. This \
+                            option is ignored for all other templates.")
+                        .long("install-dir")
+                        .takes_value(true))
+                    .arg(Arg::with_name("install-readme")
+                        .help("A path to a readme file to install")
+                        .long_help("Adds a component that installs the readme \
+                            file in the installation folder when printing the 'WXS' \
+                            template. A relative path is relative to the package's \
+                            root. This option is ignored for all other templates.")
+                        .long("install-readme")
+                        .takes_value(true))
+                    .arg(Arg::with_name("installer")
+                        .help("A path to the product's installer (msi) for a bundle")
+                        .long_help("Sets the path to the product's installer (msi) \
+                            that is chained after all of the prerequisites when \
+                            printing the 'Bundle' template. The default is the \
+                            installer created by the cargo-wix subcommand in the \
+                            'target\\wix' folder. This option is ignored for all \
+                            other templates.")
+                        .long("installer")
+                        .takes_value(true))
+                    .arg(Arg::with_name("keywords")
+                        .help("A keyword for the installer's summary information")
+                        .long_help("Adds a keyword to the 'Keywords' field of the \
+                            installer's summary information when printing the 'WXS' \
+                            template. Use this option repeatedly to add multiple \
+                            keywords. The default is to use the 'keywords' field of \
+                            the [package.metadata.wix] section, then the 'keywords' \
+                            field of the [package] section, of the package's manifest \
+                            (Cargo.toml), or the 'Installer' keyword if neither field \
+                            exists. This option is ignored for all other templates.")
+                        .long("keyword")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1))
+                    .arg(Arg::with_name("language")
+                        .help("A language code identifier (LCID) for the product")
+                        .long_help("Sets the 'Language' attribute of the 'Product' \
+                            tag and the 'Languages' attribute of the 'Package' tag \
+                            when printing the 'WXS' template. The value is a \
+                            Windows language code identifier (LCID), such as 1033 \
+                            for English (United States). The language of the \
+                            product can be different than the culture of the \
+                            installer's user interface, but a warning is printed. \
+                            The default is to use the LCID of the culture in the \
+                            'package.metadata.wix.culture' field of the package's \
+                            manifest (Cargo.toml), or 1033 if the field does not \
+                            exist. This option is ignored for all other templates.")
+                        .long("language")
+                        .takes_value(true)
+                        .validator(|l| {
+                            l.parse::<u16>()
+                                .map(|_| ())
+                                .map_err(|_| String::from("The value must be a language code identifier (LCID), such as 1033"))
+                        }))
                     .arg(license)
                     .arg(manufacturer)
+                    .arg(Arg::with_name("manifest-path")
+                        .help("A path to a package's manifest (Cargo.toml)")
+                        .long_help("An alternative to the <INPUT> argument, which \
+                            is similar to the '--manifest-path' option for cargo. \
+                            The path can be to the manifest or to a folder \
+                            containing the manifest.")
+                        .long("manifest-path")
+                        .takes_value(true)
+                        .conflicts_with("INPUT"))
+                    .arg(Arg::with_name("no-install-license")
+                        .help("Excludes the license file from the installation folder")
+                        .long_help("Omits the component that installs the license \
+                            sidecar file when printing the 'WXS' template. \
+                            The license is still used for the license agreement \
+                            dialog. This flag is ignored for all other templates.")
+                        .long("no-install-license"))
                     .arg(Arg::with_name("output")
                         .help("A path to a folder for generated files")
                         .long_help("Sets the destination for printing the \
@@ -1463,20 +3022,198 @@ fn main() {
                         .long("output")
                         .short("o")
                         .takes_value(true))
+                    .arg(Arg::with_name("output-dir")
+                        .help("A path to a folder for all generated files")
+                        .long_help("Writes the WiX Source (wxs) file to the \
+                            'wix\\main.wxs' file within this folder when printing \
+                            the 'WXS' template. A generated license is referenced \
+                            from the same 'wix' folder. The folders are created if \
+                            they do not exist. The '-o,--output' option takes \
+                            precedence. This option is ignored for all other \
+                            templates.")
+                        .long("output-dir")
+                        .takes_value(true))
                     .arg(owner)
+                    .arg(Arg::with_name("path-guid")
+                        .help("A GUID for the component that modifies the PATH")
+                        .long_help("Sets the GUID for the component that adds the \
+                            installation folder to the PATH system environment \
+                            variable when printing the 'WXS' template. The default \
+                            is to generate a new, random GUID.")
+                        .long("path-guid")
+                        .takes_value(true))
+                    .arg(Arg::with_name("per-binary-features")
+                        .help("Places each binary in its own feature")
+                        .long_help("Places the component for each binary in a \
+                            separate feature when printing the 'WXS' template, so \
+                            the binaries can be deselected during installation with \
+                            the 'FeatureTree' or 'Mondo' dialog sets. This flag is \
+                            ignored for all other templates.")
+                        .long("per-binary-features"))
+                    .arg(Arg::with_name("platform")
+                        .help("The platform of the installer (x86 or x64)")
+                        .long_help("Sets the platform of the installer when printing \
+                            the 'WXS' template. The installation folder and \
+                            components are fixed for the platform in the printed \
+                            file. Both 'x86' and 'x64', or 'i686' and 'x86_64', are \
+                            accepted. The default is to determine the installation \
+                            folder when the installer is created. This option is \
+                            ignored for all other templates.")
+                        .long("platform")
+                        .possible_values(&["x86", "x64", "i686", "x86_64"])
+                        .takes_value(true))
+                    .arg(Arg::with_name("prerequisite")
+                        .help("A path to a prerequisite package (.exe or .msi) for a bundle")
+                        .long_help("Adds a package that is installed before the \
+                            product's installer (msi) when printing the 'Bundle' \
+                            template. A path with the '.msi' extension is added as an \
+                            installer package, while all other paths are added as \
+                            executable packages. Use this option repeatedly to add \
+                            multiple prerequisites. Prerequisites are installed in the \
+                            order they are specified. This option is ignored for all \
+                            other templates.")
+                        .long("prerequisite")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .takes_value(true))
                     .arg(product_icon)
                     .arg(product_name.clone())
+                    .arg(Arg::with_name("profile-var")
+                        .help("The name of the preprocessor variable for the build profile")
+                        .long_help("Uses the name for the preprocessor variable of \
+                            the build profile in the source paths of the binaries \
+                            when printing the 'WXS' or 'WXI' template, i.e. \
+                            '$(var.<name>)'. The same name must be used with the \
+                            '--profile-var' option when creating the installer. \
+                            This option is ignored for all other templates. The \
+                            default is 'Profile'.")
+                        .long("profile-var")
+                        .takes_value(true))
+                    .arg(Arg::with_name("property")
+                        .help("Adds a property to the installer")
+                        .long_help("Adds a 'Property' element with the identifier \
+                            and value when printing the 'WXS' template, i.e. \
+                            '--property INSTALLMODE per-machine'. The identifier \
+                            must be uppercase letters, digits, underscores, and \
+                            periods, starting with a letter or an underscore, and \
+                            it cannot be defined by the template already. Use \
+                            this option repeatedly to add multiple properties. This \
+                            option is ignored for all other templates.")
+                        .long("property")
+                        .multiple(true)
+                        .number_of_values(2)
+                        .value_names(&["ID", "VALUE"]))
+                    .arg(Arg::with_name("registry")
+                        .help("Writes a value to the Windows registry")
+                        .long_help("Adds a value that is written to the Windows \
+                            registry during installation and removed during \
+                            uninstallation when printing the 'WXS' template. The \
+                            root (HKLM, HKCU, HKMU, HKCR, or HKU), key, name, type \
+                            (string, integer, expandable, multiString, or binary), \
+                            and value are required, i.e. '--registry HKLM \
+                            Software\\Example Version string 1.0.0'. Use an empty \
+                            name to set the default value of the key. Use this \
+                            option repeatedly to add multiple values. This option \
+                            is ignored for all other templates.")
+                        .long("registry")
+                        .multiple(true)
+                        .number_of_values(5)
+                        .value_names(&["ROOT", "KEY", "NAME", "TYPE", "VALUE"]))
+                    .arg(Arg::with_name("skip-license-file")
+                        .help("Skips writing the generated license file")
+                        .long_help("When printing the 'WXS' template to a file, a \
+                            'License.rtf' file is written next to the WiX Source \
+                            (wxs) file if the 'license' field of the package's \
+                            manifest (Cargo.toml) is a supported license. An \
+                            existing license file is not overwritten. This flag \
+                            skips writing the license file. This flag is ignored \
+                            for all other templates.")
+                        .long("skip-license-file"))
+                    .arg(Arg::with_name("stamp")
+                        .help("Adds the build's time and revision to the comments")
+                        .long_help("Appends the current time in UTC and the \
+                            abbreviated revision of the git 'HEAD' commit to the \
+                            'Comments' field of the installer's summary information \
+                            when printing the 'WXS' template. Only the time is used \
+                            if the revision cannot be determined with git. This \
+                            flag is ignored for all other templates.")
+                        .long("stamp"))
+                    .arg(Arg::with_name("strict-metadata")
+                        .help("Fails if a description, help URL, license, or EULA is missing")
+                        .long_help("Changes the warnings for a missing description, \
+                            help URL, license file, or EULA to errors when printing \
+                            the 'WXS' template, which is useful for catching \
+                            incomplete installer metadata in continuous integration \
+                            (CI). This flag is ignored for all other templates.")
+                        .long("strict-metadata"))
                     .arg(Arg::with_name("TEMPLATE")
                         .help("A name of a template")
                         .long_help("This is required and values are case \
-                            insensitive. [values: Apache-2.0, GPL-3.0, MIT, WXS]")
+                            insensitive. Use 'cultures' to list the supported \
+                            culture codes instead of printing a template. [values: \
+                            Apache-2.0, BSD-2-Clause, BSD-3-Clause, Bundle, GPL-3.0, \
+                            ISC, MIT, MPL-2.0, WXI, WXS]")
                         .hide_possible_values(true)
                         .possible_values(&Template::possible_values()
                             .iter()
                             .map(|s| s.as_ref())
+                            .chain(vec![CULTURES_TEMPLATE, "Cultures"])
                             .collect::<Vec<&str>>())
                         .required(true)
                         .index(1))
+                    .arg(Arg::with_name("template-file")
+                        .help("A path to a mustache template to use instead of the embedded template")
+                        .long_help("Renders the mustache template in this file \
+                            instead of the embedded template when printing the \
+                            'WXS' template. The same values are available to the \
+                            template in the file as the embedded template, which is \
+                            useful for maintaining a customized template that is \
+                            shared across projects. An error occurs if the file \
+                            cannot be read or is not a valid mustache template. \
+                            This option is ignored for all other templates.")
+                        .long("template-file")
+                        .takes_value(true))
+                    .arg(Arg::with_name("ui")
+                        .help("The set of dialogs for the installer")
+                        .long_help("Sets the set of dialogs from the WixUI extension \
+                            for the installer's graphical user interface (GUI) when \
+                            printing the 'WXS' template. The license agreement \
+                            dialog is skipped if an EULA is not available, except \
+                            for the 'Minimal' dialog set, which always displays a \
+                            license agreement. Use 'None' for an installer without \
+                            dialogs. The default is 'FeatureTree'. This option is \
+                            ignored for all other templates. [values: FeatureTree, \
+                            InstallDir, Minimal, Mondo, None]")
+                        .hide_possible_values(true)
+                        .possible_values(&print::wxs::UiSet::possible_values())
+                        .long("ui")
+                        .takes_value(true))
+                    .arg(Arg::with_name("ui-license")
+                        .help("A path to a RTF file displayed in the license agreement dialog")
+                        .long_help("Sets the RTF file for the 'WixUILicenseRtf' \
+                            variable when printing the 'WXS' template, which takes \
+                            precedence over the '--eula', '--eula-from-markdown', and \
+                            '--license' options for the dialog. The installed \
+                            license is not changed. This option is ignored for all \
+                            other templates.")
+                        .long("ui-license")
+                        .takes_value(true))
+                    .arg(Arg::with_name("uninstall-shortcut")
+                        .help("Adds an uninstall shortcut to the Start Menu")
+                        .long_help("Adds a folder with the product name to the Start \
+                            Menu with a shortcut that uninstalls the product when \
+                            printing the 'WXS' template. The folder and shortcut \
+                            are removed when the product is uninstalled. This flag \
+                            is ignored for all other templates.")
+                        .long("uninstall-shortcut"))
+                    .arg(Arg::with_name("upgrade-guid")
+                        .help("A GUID for the upgrade code")
+                        .long_help("Sets the upgrade code, a GUID that identifies \
+                            the product across versions, when printing the 'WXS' \
+                            or 'WXI' templates. The default is to generate a new, \
+                            random GUID.")
+                        .long("upgrade-guid")
+                        .takes_value(true))
                     .arg(url)
                     .arg(year)
                     .arg(verbose.clone()))
@@ -1538,6 +3275,21 @@ fn main() {
                             show the output.")
                         .long("nocapture"))
                     .arg(product_name)
+                    .arg(Arg::with_name("retries")
+                        .help("The number of times to retry signing")
+                        .long_help("Retries signing when the timestamp server \
+                            fails, such as the server could not be reached. The \
+                            wait before each retry doubles, starting at one \
+                            second. Other failures, such as a missing certificate, \
+                            are not retried. The default is three (3) retries.")
+                        .long("retries")
+                        .takes_value(true)
+                        .validator(|r| {
+                            r.parse::<u32>()
+                                .map(|_| ())
+                                .map_err(|_| String::from("The value must be a non-negative integer"))
+                        }))
+                    .arg(timeout)
                     .arg(Arg::with_name("timestamp")
                         .help("An alias or URL to a timestamp server")
                         .long_help("Either an alias or URL can be used. Aliases \
@@ -1550,6 +3302,7 @@ fn main() {
         ).get_matches();
     let matches = matches.subcommand_matches(SUBCOMMAND_NAME).unwrap();
     let verbosity = match matches.subcommand() {
+        ("check", Some(m)) => m,
         ("clean", Some(m)) => m,
         ("init", Some(m)) => m,
         ("print", Some(m)) => m,
@@ -1605,6 +3358,19 @@ fn main() {
         )
         .init();
     let result = match matches.subcommand() {
+        ("check", Some(m)) => {
+            let mut check = check::Builder::new();
+            check.banner(m.value_of("banner"));
+            check.bin_path(m.value_of("bin-path"));
+            check.compiler_name(m.value_of("compiler-name"));
+            check.eula(m.value_of("eula"));
+            check.includes(m.values_of("include").map(|a| a.collect()));
+            check.input(m.value_of("INPUT"));
+            check.linker_name(m.value_of("linker-name"));
+            check.locale(m.value_of("locale"));
+            check.product_icon(m.value_of("product-icon"));
+            check.build().run()
+        }
         ("clean", Some(m)) => {
             let mut clean = clean::Builder::new();
             clean.input(m.value_of("INPUT"));
@@ -1629,30 +3395,106 @@ fn main() {
             init.product_name(m.value_of("product-name"));
             init.build().run()
         }
+        ("print", Some(m))
+            if m.value_of("TEMPLATE").map(str::to_lowercase).as_deref()
+                == Some(CULTURES_TEMPLATE) =>
+        {
+            for culture in Cultures::all() {
+                println!("{}", culture);
+            }
+            Ok(())
+        }
         ("print", Some(m)) => {
             let template = value_t!(m, "TEMPLATE", Template).unwrap();
+            let input = m.value_of("INPUT").or_else(|| m.value_of("manifest-path"));
             match template {
                 Template::Wxs => {
                     let mut print = print::wxs::Builder::new();
+                    print.allow_downgrades(m.is_present("allow-downgrades"));
                     print.banner(m.value_of("banner"));
                     print.binaries(m.values_of("binaries").map(|v| v.collect()));
+                    print.binary_display_names(m.values_of("binary-display-name").map(|v| {
+                        let v: Vec<&str> = v.collect();
+                        v.chunks(2).map(|c| (c[0], c[1])).collect()
+                    }));
+                    print.comments(m.value_of("comments"));
+                    print.company_dir(m.value_of("company-dir"));
+                    print.compression(
+                        value_t!(m, "compression", print::wxs::Compression).unwrap_or_default(),
+                    );
                     print.description(m.value_of("description"));
                     print.dialog(m.value_of("dialog"));
+                    print.downgrade_message(m.value_of("downgrade-message"));
                     print.eula(m.value_of("eula"));
+                    print.eula_markdown(m.value_of("eula-from-markdown"));
+                    print.file_association(m.values_of("file-assoc").map(|v| {
+                        let v: Vec<&str> = v.collect();
+                        print::wxs::FileAssoc::new(v[0], v[1], v[2])
+                    }));
                     print.help_url(m.value_of("url"));
-                    print.input(m.value_of("INPUT"));
+                    print.include_symbols(m.is_present("include-pdb"));
+                    print.input(input);
+                    print.install_dir(m.value_of("install-dir"));
+                    print.install_license(!m.is_present("no-install-license"));
+                    print.install_readme(m.value_of("install-readme"));
+                    print.keywords(m.values_of("keywords").map(|k| k.collect()));
+                    print.language(value_t!(m, "language", u16).ok());
                     print.license(m.value_of("license"));
                     print.manufacturer(m.value_of("manufacturer"));
                     print.output(m.value_of("output"));
+                    print.output_dir(m.value_of("output-dir"));
+                    print.path_component_guid(m.value_of("path-guid"));
+                    print.per_binary_features(m.is_present("per-binary-features"));
+                    print.platform(m.value_of("platform"));
                     print.product_icon(m.value_of("product-icon"));
                     print.product_name(m.value_of("product-name"));
+                    print.profile_var(m.value_of("profile-var"));
+                    print.properties(m.values_of("property").map_or_else(Vec::new, |v| {
+                        let v: Vec<&str> = v.collect();
+                        v.chunks(2).map(|c| (c[0], c[1])).collect()
+                    }));
+                    print.registry(m.values_of("registry").map_or_else(Vec::new, |v| {
+                        let v: Vec<&str> = v.collect();
+                        v.chunks(5)
+                            .map(|c| print::wxs::RegEntry::new(c[0], c[1], c[2], c[3], c[4]))
+                            .collect()
+                    }));
+                    print.skip_license_file(m.is_present("skip-license-file"));
+                    print.stamp(m.is_present("stamp"));
+                    print.strict_metadata(m.is_present("strict-metadata"));
+                    print.template_file(m.value_of("template-file"));
+                    print.ui(value_t!(m, "ui", print::wxs::UiSet).unwrap_or_default());
+                    print.ui_license(m.value_of("ui-license"));
+                    print.uninstall_shortcut(m.is_present("uninstall-shortcut"));
+                    print.upgrade_code(m.value_of("upgrade-guid"));
+                    print.build().run()
+                }
+                Template::Wxi => {
+                    let mut print = print::wxi::Builder::new();
+                    print.binaries(m.values_of("binaries").map(|v| v.collect()));
+                    print.input(input);
+                    print.manufacturer(m.value_of("manufacturer"));
+                    print.output(m.value_of("output"));
+                    print.product_name(m.value_of("product-name"));
+                    print.profile_var(m.value_of("profile-var"));
+                    print.upgrade_code(m.value_of("upgrade-guid"));
+                    print.build().run()
+                }
+                Template::Bundle => {
+                    let mut print = print::bundle::Builder::new();
+                    print.input(input);
+                    print.installer(m.value_of("installer"));
+                    print.manufacturer(m.value_of("manufacturer"));
+                    print.output(m.value_of("output"));
+                    print.prerequisites(m.values_of("prerequisite").map(|v| v.collect()));
+                    print.product_name(m.value_of("product-name"));
                     print.build().run()
                 }
                 t => {
                     let mut print = print::license::Builder::new();
                     print.copyright_holder(m.value_of("owner"));
                     print.copyright_year(m.value_of("year"));
-                    print.input(m.value_of("INPUT"));
+                    print.input(input);
                     print.output(m.value_of("output"));
                     print.build().run(t)
                 }
@@ -1671,25 +3513,93 @@ fn main() {
             sign.homepage(m.value_of("homepage"));
             sign.input(m.value_of("INPUT"));
             sign.product_name(m.value_of("product-name"));
+            if let Some(r) = m.value_of("retries").and_then(|r| r.parse().ok()) {
+                sign.retries(r);
+            }
+            sign.timeout(
+                m.value_of("timeout")
+                    .and_then(|t| t.parse().ok())
+                    .map(Duration::from_secs),
+            );
             sign.timestamp(m.value_of("timestamp"));
             sign.build().run()
         }
         _ => {
             let mut create = create::Builder::new();
+            create.all_features(matches.is_present("all-features"));
             create.bin_path(matches.value_of("bin-path"));
+            create.candle_arch(matches.value_of("candle-arch"));
             create.capture_output(!matches.is_present("no-capture"));
+            create.checksum(value_t!(matches, "checksum", create::ChecksumKind).ok());
             create.compiler_args(matches.values_of("compiler-arg").map(|a| a.collect()));
+            create.compiler_name(matches.value_of("compiler-name"));
             create.culture(matches.value_of("culture"));
             create.debug_build(matches.is_present("debug-build"));
             create.debug_name(matches.is_present("debug-name"));
+            create.dry_run(matches.is_present("dry-run"));
+            create.extensions(matches.values_of("extension").map(|a| a.collect()));
+            create.features(matches.values_of("features").map(|a| {
+                a.flat_map(|f| f.split(|c: char| c == ',' || c.is_whitespace()))
+                    .filter(|f| !f.is_empty())
+                    .collect()
+            }));
+            create.force(matches.is_present("force"));
+            create.frozen(matches.is_present("frozen"));
+            create.hash_in_name(matches.is_present("hash-in-name"));
             create.includes(matches.values_of("include").map(|a| a.collect()));
-            create.input(matches.value_of("INPUT"));
+            create.input(
+                matches
+                    .value_of("INPUT")
+                    .or_else(|| matches.value_of("manifest-path")),
+            );
+            create.jobs(matches.value_of("jobs").and_then(|j| j.parse().ok()));
+            create.keep_pdb(matches.is_present("keep-pdb"));
+            create.libraries(matches.values_of("library").map(|a| a.collect()));
             create.linker_args(matches.values_of("linker-arg").map(|a| a.collect()));
+            create.linker_name(matches.value_of("linker-name"));
             create.locale(matches.value_of("locale"));
+            create.locked(matches.is_present("locked"));
+            create.merge_args(matches.is_present("merge-args"));
+            create.message_format(value_t!(matches, "message-format", create::MessageFormat).ok());
+            create.min_wix_version(matches.value_of("min-wix-version"));
             create.name(matches.value_of("name"));
+            create.name_suffix(matches.value_of("name-suffix"));
             create.no_build(matches.is_present("no-build"));
+            create.no_default_features(matches.is_present("no-default-features"));
+            create.no_ui(matches.is_present("no-ui"));
+            create.no_util_extension(matches.is_present("no-util-extension"));
+            create.offline(matches.is_present("offline"));
             create.output(matches.value_of("output"));
+            create.pdb_output(matches.value_of("pdb-output"));
+            create.post_build(matches.value_of("post-build"));
+            create.pre_build(matches.value_of("pre-build"));
+            create.profile_var(matches.value_of("profile-var"));
+            create.quiet(matches.is_present("quiet"));
+            create.require_clean_git(matches.is_present("require-clean-git"));
+            create.show_logo(matches.is_present("show-logo"));
+            create.sign_binaries(matches.is_present("sign-binaries"));
+            if let Some(r) = matches
+                .value_of("sign-retries")
+                .and_then(|r| r.parse().ok())
+            {
+                create.sign_retries(r);
+            }
+            create.sort_sources(!matches.is_present("no-sort-sources"));
+            create.strict_version(!matches.is_present("no-strict-version"));
+            create.suppress_warnings(
+                matches
+                    .values_of("suppress-warning")
+                    .map_or_else(Vec::new, |v| v.filter_map(|w| w.parse().ok()).collect()),
+            );
+            create.target_dir(matches.value_of("target-dir"));
+            create.timeout(
+                matches
+                    .value_of("timeout")
+                    .and_then(|t| t.parse().ok())
+                    .map(Duration::from_secs),
+            );
             create.version(matches.value_of("install-version"));
+            create.warnings_as_errors(matches.is_present("warnings-as-errors"));
             create.build().run()
         }
     };
@@ -1729,7 +3639,7 @@ fn main() {
                     .reset()
                     .expect("Revert color settings after printing the message");
             }
-            std::process::exit(e.code());
+            std::process::exit(e.exit_code());
         }
     }
 }
@@ -0,0 +1,345 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for printing a WiX Source (wxs) file for a bundle.
+
+use crate::manifest;
+use crate::product_name;
+use crate::Error;
+use crate::Platform;
+use crate::Result;
+use crate::Template;
+use crate::MSI_FILE_EXTENSION;
+use crate::TARGET_FOLDER_NAME;
+use crate::WIX;
+
+use mustache::{self, MapBuilder};
+
+use std::path::PathBuf;
+
+use toml::Value;
+
+use uuid::Uuid;
+
+/// A builder for creating an execution context to print a WiX Toolset source
+/// file (wxs) for a bundle.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    input: Option<&'a str>,
+    installer: Option<&'a str>,
+    manufacturer: Option<&'a str>,
+    output: Option<&'a str>,
+    prerequisites: Option<Vec<&'a str>>,
+    product_name: Option<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder {
+            input: None,
+            installer: None,
+            manufacturer: None,
+            output: None,
+            prerequisites: None,
+            product_name: None,
+        }
+    }
+
+    /// Sets the path to a package's manifest (Cargo.toml) to be used to
+    /// generate a WiX Source (wxs) file for a bundle from the embedded
+    /// template.
+    ///
+    /// The default is to use the package's manifest in the current working
+    /// directory.
+    pub fn input(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.input = i;
+        self
+    }
+
+    /// Sets the path to the product's installer (msi) that is chained after
+    /// all of the prerequisites.
+    ///
+    /// The default is the installer created by the `cargo wix` subcommand in
+    /// the `target\wix` folder, i.e.
+    /// `target\wix\<product-name>-<version>-<arch>.msi`, where the version is
+    /// the `version` field of the package's manifest (Cargo.toml) and the
+    /// architecture is the architecture of the current platform.
+    pub fn installer(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.installer = i;
+        self
+    }
+
+    /// Sets the manufacturer.
+    ///
+    /// The default is to use the `manufacturer` field of the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    /// If the field does not exist, then the first author in the `authors`
+    /// field is used. If there are no authors, then the domain of the
+    /// `homepage` field is used. An error occurs if none of these exist. This
+    /// overrides all of the defaults.
+    pub fn manufacturer(&mut self, m: Option<&'a str>) -> &mut Self {
+        self.manufacturer = m;
+        self
+    }
+
+    /// Sets the destination.
+    ///
+    /// The default is to print all output to `STDOUT`. This method can be used
+    /// to specify that the bundle be written, or "printed", to a file instead
+    /// of `STDOUT`.
+    pub fn output(&mut self, o: Option<&'a str>) -> &mut Self {
+        self.output = o;
+        self
+    }
+
+    /// Sets the paths to the prerequisite packages.
+    ///
+    /// The prerequisites are installed in the order they are listed before the
+    /// product's installer. A path with the `.msi` extension is chained as an
+    /// installer package, while all other paths are chained as executable
+    /// packages. The default is to have no prerequisites.
+    pub fn prerequisites(&mut self, p: Option<Vec<&'a str>>) -> &mut Self {
+        self.prerequisites = p;
+        self
+    }
+
+    /// Sets the product name.
+    ///
+    /// The default is to use the `name` field under the `package` section of
+    /// the package's manifest (Cargo.toml). This overrides that value. An error
+    /// occurs if the `name` field is not found in the manifest.
+    pub fn product_name(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.product_name = p;
+        self
+    }
+
+    /// Builds an execution context for printing a bundle.
+    pub fn build(&self) -> Execution {
+        Execution {
+            input: self.input.map(PathBuf::from),
+            installer: self.installer.map(PathBuf::from),
+            manufacturer: self.manufacturer.map(String::from),
+            output: self.output.map(PathBuf::from),
+            prerequisites: self
+                .prerequisites
+                .as_ref()
+                .map(|p| p.iter().map(PathBuf::from).collect()),
+            product_name: self.product_name.map(String::from),
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for printing a WiX Toolset source file (wxs) for a bundle.
+#[derive(Debug)]
+pub struct Execution {
+    input: Option<PathBuf>,
+    installer: Option<PathBuf>,
+    manufacturer: Option<String>,
+    output: Option<PathBuf>,
+    prerequisites: Option<Vec<PathBuf>>,
+    product_name: Option<String>,
+}
+
+impl Execution {
+    /// Prints a WiX Source (wxs) file for a bundle based on the built context.
+    pub fn run(self) -> Result<()> {
+        debug!("input = {:?}", self.input);
+        debug!("installer = {:?}", self.installer);
+        debug!("manufacturer = {:?}", self.manufacturer);
+        debug!("output = {:?}", self.output);
+        debug!("prerequisites = {:?}", self.prerequisites);
+        debug!("product_name = {:?}", self.product_name);
+        let manifest = manifest(self.input.as_ref())?;
+        let mut destination = super::destination(self.output.as_ref())?;
+        let template = mustache::compile_str(Template::Bundle.to_str())?;
+        let product_name = product_name(self.product_name.as_ref(), &manifest)?;
+        let prerequisites = self.prerequisites.clone().unwrap_or_default();
+        let mut map = MapBuilder::new()
+            .insert_str("product-name", &product_name)
+            .insert_str("manufacturer", self.manufacturer(&manifest)?)
+            .insert_str(
+                "upgrade-code-guid",
+                Uuid::new_v4().to_hyphenated().to_string().to_uppercase(),
+            )
+            .insert_str(
+                "installer-source",
+                self.installer_source(&product_name, &manifest)?,
+            );
+        if !prerequisites.is_empty() {
+            map = map.insert_vec("prerequisites", |mut builder| {
+                for (index, prerequisite) in prerequisites.iter().enumerate() {
+                    builder = builder.push_map(|builder| {
+                        builder
+                            .insert_str("prerequisite-index", index.to_string())
+                            .insert_str("prerequisite-source", prerequisite.display().to_string())
+                            .insert_bool(
+                                "prerequisite-msi",
+                                prerequisite.extension().and_then(|e| e.to_str())
+                                    == Some(MSI_FILE_EXTENSION),
+                            )
+                    });
+                }
+                builder
+            });
+        }
+        let data = map.build();
+        template
+            .render_data(&mut destination, &data)
+            .map_err(Error::from)
+    }
+
+    fn installer_source(&self, product_name: &str, manifest: &Value) -> Result<String> {
+        if let Some(ref installer) = self.installer {
+            Ok(installer.display().to_string())
+        } else {
+            let version = manifest
+                .get("package")
+                .and_then(|p| p.as_table())
+                .and_then(|t| t.get("version"))
+                .and_then(|v| v.as_str())
+                .ok_or(Error::Manifest("version"))?;
+            Ok(PathBuf::from(TARGET_FOLDER_NAME)
+                .join(WIX)
+                .join(format!(
+                    "{}-{}-{}.{}",
+                    product_name,
+                    version,
+                    Platform::default().arch(),
+                    MSI_FILE_EXTENSION
+                ))
+                .display()
+                .to_string())
+        }
+    }
+
+    fn manufacturer(&self, manifest: &Value) -> Result<String> {
+        if let Some(ref m) = self.manufacturer {
+            Ok(m.to_owned())
+        } else {
+            super::manufacturer(manifest)
+        }
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\example\\Cargo.toml";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn installer_works() {
+            const EXPECTED: &str = "C:\\example\\Example.msi";
+            let mut actual = Builder::new();
+            actual.installer(Some(EXPECTED));
+            assert_eq!(actual.installer, Some(EXPECTED));
+        }
+
+        #[test]
+        fn manufacturer_works() {
+            const EXPECTED: &str = "Example";
+            let mut actual = Builder::new();
+            actual.manufacturer(Some(EXPECTED));
+            assert_eq!(actual.manufacturer, Some(EXPECTED));
+        }
+
+        #[test]
+        fn output_works() {
+            const EXPECTED: &str = "C:\\example\\output";
+            let mut actual = Builder::new();
+            actual.output(Some(EXPECTED));
+            assert_eq!(actual.output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn prerequisites_works() {
+            const EXPECTED: &str = "C:\\example\\vc_redist.x64.exe";
+            let mut actual = Builder::new();
+            actual.prerequisites(Some(vec![EXPECTED]));
+            assert_eq!(actual.prerequisites, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn product_name_works() {
+            const EXPECTED: &str = "Example Product Name";
+            let mut actual = Builder::new();
+            actual.product_name(Some(EXPECTED));
+            assert_eq!(actual.product_name, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        use super::*;
+
+        const MIN_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+        "#;
+
+        #[test]
+        fn installer_source_with_defaults_works() {
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let expected = PathBuf::from(TARGET_FOLDER_NAME)
+                .join(WIX)
+                .join(format!("Example-0.1.0-{}.msi", Platform::default().arch()));
+            let actual = Execution::default()
+                .installer_source("Example", &manifest)
+                .unwrap();
+            assert_eq!(actual, expected.display().to_string());
+        }
+
+        #[test]
+        fn installer_source_with_override_works() {
+            const EXPECTED: &str = "C:\\example\\Example.msi";
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .installer(Some(EXPECTED))
+                .build()
+                .installer_source("Example", &manifest)
+                .unwrap();
+            assert_eq!(actual, String::from(EXPECTED));
+        }
+
+        #[test]
+        fn manufacturer_with_defaults_works() {
+            const EXPECTED: &str = "First Last";
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().manufacturer(&manifest).unwrap();
+            assert_eq!(actual, String::from(EXPECTED));
+        }
+    }
+}
@@ -130,7 +130,10 @@ impl Execution {
         let template = mustache::compile_str(template.to_str())?;
         let data = MapBuilder::new()
             .insert_str("copyright-year", self.copyright_year())
-            .insert_str("copyright-holder", self.copyright_holder(&manifest)?)
+            .insert_str(
+                "copyright-holder",
+                super::rtf::escape(&self.copyright_holder(&manifest)?),
+            )
             .build();
         template
             .render_data(&mut destination, &data)
@@ -242,5 +245,24 @@ mod tests {
                 .copyright_year();
             assert_eq!(actual, String::from(EXPECTED));
         }
+
+        #[test]
+        fn run_with_special_characters_in_copyright_holder_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            std::fs::write(&manifest, MIN_MANIFEST).unwrap();
+            let output = temp_dir.path().join("License.rtf");
+            Builder::new()
+                .copyright_holder(Some("A & B <Corp> {Caf\u{e9}}"))
+                .copyright_year(Some("2026"))
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .build()
+                .run(Template::Mit)
+                .unwrap();
+            let rtf = std::fs::read_to_string(&output).unwrap();
+            assert!(rtf.contains("Copyright (c) 2026 A & B <Corp> \\{Caf\\u233?\\}\\par"));
+            assert!(!rtf.contains("&amp;"));
+        }
     }
 }
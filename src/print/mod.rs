@@ -16,14 +16,16 @@
 //! printing various templates based on a package's manifest (Cargo.toml) or
 //! end-user input.
 
+pub mod bundle;
 pub mod license;
+mod rtf;
+pub mod wxi;
 pub mod wxs;
 
+use crate::create::meta_str;
 use crate::Error;
 use crate::Result;
 
-use regex::Regex;
-
 use std::fs::File;
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -50,17 +52,69 @@ fn first_author(manifest: &Value) -> Result<String> {
         .and_then(|p| p.as_table())
         .and_then(|t| t.get("authors"))
         .and_then(|a| a.as_array())
-        .and_then(|a| a.get(0))
+        .and_then(|a| a.first())
         .and_then(|f| f.as_str())
-        .and_then(|s| {
-            // Strip email if it exists.
-            let re = Regex::new(r"<(.*?)>").unwrap();
-            Some(re.replace_all(s, ""))
-        })
-        .map(|s| String::from(s.trim()))
+        .and_then(author_name)
         .ok_or(Error::Manifest("authors"))
 }
 
+/// Gets the name of an author from an entry in the `authors` field.
+///
+/// The email portion, i.e. everything from the first `<` to the last `>`, is
+/// removed. If no name remains, then the local part of the email is used.
+fn author_name(author: &str) -> Option<String> {
+    let (name, email) = match (author.find('<'), author.rfind('>')) {
+        (Some(start), Some(end)) if start < end => (
+            [author[..start].trim(), author[end + 1..].trim()]
+                .iter()
+                .filter(|p| !p.is_empty())
+                .copied()
+                .collect::<Vec<&str>>()
+                .join(" "),
+            Some(&author[start + 1..end]),
+        ),
+        _ => (String::from(author.trim()), None),
+    };
+    if name.is_empty() {
+        email
+            .map(|e| e.trim_matches(|c: char| c == '<' || c == '>' || c.is_whitespace()))
+            .and_then(|e| e.split('@').next())
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+    } else {
+        Some(name)
+    }
+}
+
+fn homepage_domain(manifest: &Value) -> Option<String> {
+    manifest
+        .get("package")
+        .and_then(|p| p.as_table())
+        .and_then(|t| t.get("homepage"))
+        .and_then(|h| h.as_str())
+        .map(|h| h.split("://").last().unwrap_or(h))
+        .and_then(|h| h.split(['/', ':']).next())
+        .map(|h| h.trim_start_matches("www."))
+        .filter(|h| !h.is_empty())
+        .map(String::from)
+}
+
+fn manufacturer(manifest: &Value) -> Result<String> {
+    if let Some(pkg_meta_wix_manufacturer) = meta_str(manifest, "manufacturer") {
+        trace!("Using the manufacturer in the package's metadata");
+        Ok(String::from(pkg_meta_wix_manufacturer))
+    } else if let Ok(author) = first_author(manifest) {
+        trace!("Using the first author for the manufacturer");
+        Ok(author)
+    } else if let Some(domain) = homepage_domain(manifest) {
+        trace!("Using the domain of the homepage for the manufacturer");
+        Ok(domain)
+    } else {
+        Err(Error::Manifest("authors"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +148,115 @@ mod tests {
         let actual = first_author(&manifest).unwrap();
         assert_eq!(actual, String::from("1 Author"));
     }
+
+    #[test]
+    fn first_author_with_inherited_authors_works() {
+        const WORKSPACE_MANIFEST: &str = r#"[workspace]
+            members = ["member"]
+
+            [workspace.package]
+            authors = ["First Last <first.last@example.com>"]
+        "#;
+        const MEMBER_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors.workspace = true
+        "#;
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("Cargo.toml"), WORKSPACE_MANIFEST).unwrap();
+        let member_dir = temp_dir.path().join("member");
+        std::fs::create_dir(&member_dir).unwrap();
+        let manifest_path = member_dir.join("Cargo.toml");
+        std::fs::write(&manifest_path, MEMBER_MANIFEST).unwrap();
+        let manifest = crate::manifest(Some(&manifest_path)).unwrap();
+        let actual = first_author(&manifest).unwrap();
+        assert_eq!(actual, String::from("First Last"));
+    }
+
+    #[test]
+    fn author_name_works() {
+        let authors = [
+            ("First Last <first.last@example.com>", Some("First Last")),
+            ("  First Last  ", Some("First Last")),
+            ("First Last", Some("First Last")),
+            ("<only@example.com>", Some("only")),
+            ("  <only@example.com>  ", Some("only")),
+            ("First Last <<first.last@example.com>>", Some("First Last")),
+            ("<<nested@example.com>>", Some("nested")),
+            (
+                "First Last <first.last@example.com> (Example)",
+                Some("First Last (Example)"),
+            ),
+            ("<>", None),
+            ("", None),
+        ];
+        for (author, expected) in authors.iter() {
+            assert_eq!(
+                author_name(author),
+                expected.map(String::from),
+                "author = {:?}",
+                author
+            );
+        }
+    }
+
+    #[test]
+    fn manufacturer_with_metadata_and_no_authors_works() {
+        const MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+
+            [package.metadata.wix]
+            manufacturer = "Example Organization"
+        "#;
+        let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+        let actual = manufacturer(&manifest).unwrap();
+        assert_eq!(actual, String::from("Example Organization"));
+    }
+
+    #[test]
+    fn manufacturer_with_metadata_and_authors_works() {
+        const MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+
+            [package.metadata.wix]
+            manufacturer = "Example Organization"
+        "#;
+        let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+        let actual = manufacturer(&manifest).unwrap();
+        assert_eq!(actual, String::from("Example Organization"));
+    }
+
+    #[test]
+    fn manufacturer_with_authors_works() {
+        let manifest = SINGLE_AUTHOR_MANIFEST
+            .parse::<Value>()
+            .expect("Parsing TOML");
+        let actual = manufacturer(&manifest).unwrap();
+        assert_eq!(actual, String::from("First Last"));
+    }
+
+    #[test]
+    fn manufacturer_with_homepage_and_no_authors_works() {
+        const MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            homepage = "https://www.example.com/product"
+        "#;
+        let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+        let actual = manufacturer(&manifest).unwrap();
+        assert_eq!(actual, String::from("example.com"));
+    }
+
+    #[test]
+    fn manufacturer_with_no_authors_fails() {
+        const MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+        "#;
+        let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+        assert!(manufacturer(&manifest).is_err());
+    }
 }
@@ -0,0 +1,308 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for converting Markdown to the Rich Text Format (RTF).
+//!
+//! Only the subset of Markdown that is commonly used for licenses is
+//! supported: headings, paragraphs, bold and italic text, inline code, fenced
+//! code blocks, links, and ordered and unordered lists. Everything else is
+//! written as plain text.
+
+const HEADER: &str = "{\\rtf1\\ansi\\deff0\\nouicompat{\\fonttbl{\\f0\\fnil\\fcharset0 Arial;}\
+                      {\\f1\\fnil\\fcharset0 Courier New;}}\n\\viewkind4\\uc1\n";
+const FOOTER: &str = "}\n";
+const PARAGRAPH: &str = "\\pard\\sa180\\fs24 ";
+const LIST_ITEM: &str = "\\pard\\fi-360\\li720\\sa60\\fs24 ";
+const CODE_BLOCK: &str = "\\pard\\sa180\\f1\\fs20 ";
+
+/// Converts Markdown to a Rich Text Format (RTF) document.
+pub fn from_markdown(markdown: &str) -> String {
+    let mut rtf = String::from(HEADER);
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code_block: Option<Vec<&str>> = None;
+    for line in markdown.lines() {
+        if let Some(ref mut code) = code_block {
+            if line.trim_start().starts_with("```") {
+                rtf.push_str(CODE_BLOCK);
+                let lines = code.iter().map(|l| escape(l)).collect::<Vec<String>>();
+                rtf.push_str(&lines.join("\\line\n"));
+                rtf.push_str("\\f0\\par\n");
+                code_block = None;
+            } else {
+                code.push(line);
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            push_paragraph(&mut rtf, &mut paragraph);
+            code_block = Some(Vec::new());
+        } else if trimmed.is_empty() {
+            push_paragraph(&mut rtf, &mut paragraph);
+        } else if let Some((level, text)) = heading(trimmed) {
+            push_paragraph(&mut rtf, &mut paragraph);
+            let size = match level {
+                1 => 32,
+                2 => 28,
+                _ => 24,
+            };
+            rtf.push_str(&format!(
+                "\\pard\\sa180\\b\\fs{} {}\\b0\\fs24\\par\n",
+                size,
+                inline(text)
+            ));
+        } else if let Some(text) = unordered_item(trimmed) {
+            push_paragraph(&mut rtf, &mut paragraph);
+            rtf.push_str(LIST_ITEM);
+            rtf.push_str(&format!("\\bullet\\tab {}\\par\n", inline(text)));
+        } else if let Some((number, text)) = ordered_item(trimmed) {
+            push_paragraph(&mut rtf, &mut paragraph);
+            rtf.push_str(LIST_ITEM);
+            rtf.push_str(&format!("{}.\\tab {}\\par\n", number, inline(text)));
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    if let Some(code) = code_block {
+        rtf.push_str(CODE_BLOCK);
+        let lines = code.iter().map(|l| escape(l)).collect::<Vec<String>>();
+        rtf.push_str(&lines.join("\\line\n"));
+        rtf.push_str("\\f0\\par\n");
+    }
+    push_paragraph(&mut rtf, &mut paragraph);
+    rtf.push_str(FOOTER);
+    rtf
+}
+
+fn push_paragraph(rtf: &mut String, paragraph: &mut Vec<&str>) {
+    if !paragraph.is_empty() {
+        rtf.push_str(PARAGRAPH);
+        rtf.push_str(&inline(&paragraph.join(" ")));
+        rtf.push_str("\\par\n");
+        paragraph.clear();
+    }
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) {
+        let rest = &line[level..];
+        if rest.is_empty() || rest.starts_with(' ') {
+            return Some((level, rest.trim().trim_end_matches('#').trim_end()));
+        }
+    }
+    None
+}
+
+fn unordered_item(line: &str) -> Option<&str> {
+    ["- ", "* ", "+ "]
+        .iter()
+        .find(|m| line.starts_with(*m))
+        .map(|m| line[m.len()..].trim())
+}
+
+fn ordered_item(line: &str) -> Option<(&str, &str)> {
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if rest.starts_with(". ") || rest.starts_with(") ") {
+            return Some((&line[..digits], rest[2..].trim()));
+        }
+    }
+    None
+}
+
+/// Converts the inline Markdown within a block to RTF control words.
+fn inline(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<char>>();
+    let mut rtf = String::new();
+    let mut bold = false;
+    let mut italic = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\\' if next.filter(char::is_ascii_punctuation).is_some() => {
+                push_escaped(&mut rtf, next.unwrap());
+                i += 2;
+                continue;
+            }
+            '`' => {
+                if let Some(end) = chars[i + 1..].iter().position(|c| *c == '`') {
+                    rtf.push_str("\\f1 ");
+                    for c in &chars[i + 1..i + 1 + end] {
+                        push_escaped(&mut rtf, *c);
+                    }
+                    rtf.push_str("\\f0 ");
+                    i += end + 2;
+                    continue;
+                }
+            }
+            '[' => {
+                if let Some((label, url, len)) = link(&chars[i..]) {
+                    rtf.push_str(&inline(&label));
+                    rtf.push_str(" (");
+                    for c in url.chars() {
+                        push_escaped(&mut rtf, c);
+                    }
+                    rtf.push(')');
+                    i += len;
+                    continue;
+                }
+            }
+            '*' | '_' if next == Some(c) => {
+                if bold {
+                    rtf.push_str("\\b0 ");
+                } else {
+                    rtf.push_str("\\b ");
+                }
+                bold = !bold;
+                i += 2;
+                continue;
+            }
+            '*' | '_' if is_emphasis(c, prev, next, italic) => {
+                if italic {
+                    rtf.push_str("\\i0 ");
+                } else {
+                    rtf.push_str("\\i ");
+                }
+                italic = !italic;
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        push_escaped(&mut rtf, c);
+        i += 1;
+    }
+    if bold {
+        rtf.push_str("\\b0 ");
+    }
+    if italic {
+        rtf.push_str("\\i0 ");
+    }
+    rtf
+}
+
+// An underscore within a word, such as `snake_case`, is not emphasis.
+fn is_emphasis(c: char, prev: Option<char>, next: Option<char>, open: bool) -> bool {
+    let word = |c: Option<char>| c.filter(|c| c.is_alphanumeric()).is_some();
+    let text = |c: Option<char>| c.filter(|c| !c.is_whitespace()).is_some();
+    if open {
+        text(prev) && (c == '*' || !word(next))
+    } else {
+        text(next) && (c == '*' || !word(prev))
+    }
+}
+
+fn link(chars: &[char]) -> Option<(String, String, usize)> {
+    let close = chars.iter().position(|c| *c == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = chars[close + 2..].iter().position(|c| *c == ')')?;
+    let label = chars[1..close].iter().collect();
+    let url = chars[close + 2..close + 2 + end].iter().collect();
+    Some((label, url, close + 3 + end))
+}
+
+/// Escapes the RTF special characters and all non-ASCII characters.
+pub(super) fn escape(text: &str) -> String {
+    let mut rtf = String::with_capacity(text.len());
+    for c in text.chars() {
+        push_escaped(&mut rtf, c);
+    }
+    rtf
+}
+
+fn push_escaped(rtf: &mut String, c: char) {
+    match c {
+        '\\' | '{' | '}' => {
+            rtf.push('\\');
+            rtf.push(c);
+        }
+        '\t' => rtf.push_str("\\tab "),
+        c if c.is_ascii() => rtf.push(c),
+        c => {
+            // RTF uses signed 16-bit values for Unicode characters, and
+            // characters outside of the Basic Multilingual Plane are written
+            // as a surrogate pair. The `?` is the replacement for readers that
+            // do not support Unicode.
+            let mut buffer = [0u16; 2];
+            for unit in c.encode_utf16(&mut buffer) {
+                rtf.push_str(&format!("\\u{}?", *unit as i16));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_markdown_works() {
+        const MARKDOWN: &str = "# License\n\
+            \n\
+            Copyright (c) 2020 First Last.\n\
+            All rights reserved.\n\
+            \n\
+            ## Conditions\n\
+            \n\
+            - Keep the **copyright** notice.\n\
+            - Do _not_ remove `NOTICE`.\n\
+            \n\
+            1. See [the website](https://www.example.com).\n";
+        let actual = from_markdown(MARKDOWN);
+        assert!(actual.starts_with("{\\rtf1\\ansi"));
+        assert!(actual.ends_with("}\n"));
+        assert!(actual.contains("\\pard\\sa180\\b\\fs32 License\\b0\\fs24\\par\n"));
+        assert!(actual.contains("\\pard\\sa180\\b\\fs28 Conditions\\b0\\fs24\\par\n"));
+        assert!(actual.contains(
+            "\\pard\\sa180\\fs24 Copyright (c) 2020 First Last. All rights reserved.\\par\n"
+        ));
+        assert!(actual.contains("\\bullet\\tab Keep the \\b copyright\\b0  notice.\\par\n"));
+        assert!(actual.contains("\\bullet\\tab Do \\i not\\i0  remove \\f1 NOTICE\\f0 .\\par\n"));
+        assert!(actual.contains("1.\\tab See the website (https://www.example.com).\\par\n"));
+    }
+
+    #[test]
+    fn from_markdown_with_code_block_works() {
+        let actual = from_markdown("```\nfn main() {}\nlet a = 1;\n```\n");
+        assert!(actual
+            .contains("\\pard\\sa180\\f1\\fs20 fn main() \\{\\}\\line\nlet a = 1;\\f0\\par\n"));
+    }
+
+    #[test]
+    fn from_markdown_with_snake_case_works() {
+        let actual = from_markdown("Use the snake_case_name value.");
+        assert!(actual.contains("Use the snake_case_name value."));
+        assert!(!actual.contains("\\i "));
+    }
+
+    #[test]
+    fn escape_works() {
+        assert_eq!(escape("a\\b{c}"), "a\\\\b\\{c\\}");
+    }
+
+    #[test]
+    fn escape_with_non_ascii_works() {
+        assert_eq!(escape("caf\u{e9}"), "caf\\u233?");
+        assert_eq!(escape("\u{2014}"), "\\u8212?");
+        assert_eq!(escape("\u{fffd}"), "\\u-3?");
+        assert_eq!(escape("\u{1f600}"), "\\u-10179?\\u-8704?");
+    }
+}
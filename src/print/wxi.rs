@@ -0,0 +1,528 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for printing a WiX Include (wxi) file of pre-processor
+//! variables.
+
+use crate::binaries;
+use crate::create;
+use crate::manifest;
+use crate::product_name;
+use crate::Error;
+use crate::Result;
+use crate::Template;
+use crate::PROFILE_VARIABLE;
+
+use mustache::{self, MapBuilder};
+
+use std::path::PathBuf;
+
+use toml::Value;
+
+use uuid::Uuid;
+
+use super::wxs;
+
+/// A builder for creating an execution context to print a WiX Include (wxi)
+/// file of pre-processor variables.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    binaries: Option<Vec<&'a str>>,
+    input: Option<&'a str>,
+    manufacturer: Option<&'a str>,
+    output: Option<&'a str>,
+    product_name: Option<&'a str>,
+    profile_var: Option<&'a str>,
+    upgrade_code: Option<&'a str>,
+    version: Option<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder {
+            binaries: None,
+            input: None,
+            manufacturer: None,
+            output: None,
+            product_name: None,
+            profile_var: None,
+            upgrade_code: None,
+            version: None,
+        }
+    }
+
+    /// Sets the path to one or more binaries to define variables for.
+    ///
+    /// The default is to use all of the binaries defined in the `bin`
+    /// sections of the package's manifest (Cargo.toml), or the package's name
+    /// if there are no `bin` sections, located in the `target` folder.
+    pub fn binaries(&mut self, b: Option<Vec<&'a str>>) -> &mut Self {
+        self.binaries = b;
+        self
+    }
+
+    /// Sets the path to a package's manifest (Cargo.toml) to be used to
+    /// generate a WiX Include (wxi) file from the embedded template.
+    ///
+    /// The default is to use the package's manifest in the current working
+    /// directory.
+    pub fn input(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.input = i;
+        self
+    }
+
+    /// Sets the manufacturer.
+    ///
+    /// The default is to use the `manufacturer` field of the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    /// If the field does not exist, then the first author in the `authors`
+    /// field is used. If there are no authors, then the domain of the
+    /// `homepage` field is used. An error occurs if none of these exist. This
+    /// overrides all of the defaults.
+    pub fn manufacturer(&mut self, m: Option<&'a str>) -> &mut Self {
+        self.manufacturer = m;
+        self
+    }
+
+    /// Sets the destination.
+    ///
+    /// The default is to print all output to `STDOUT`. This method can be used
+    /// to specify that the include file be written, or "printed", to a file
+    /// instead of `STDOUT`.
+    pub fn output(&mut self, o: Option<&'a str>) -> &mut Self {
+        self.output = o;
+        self
+    }
+
+    /// Sets the product name.
+    ///
+    /// The default is to use the `name` field under the `package` section of
+    /// the package's manifest (Cargo.toml). This overrides that value. An error
+    /// occurs if the `name` field is not found in the manifest.
+    pub fn product_name(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.product_name = p;
+        self
+    }
+
+    /// Sets the name of the preprocessor variable for the build profile.
+    ///
+    /// The default source path of a binary is
+    /// `$(var.CargoTargetDir)\$(var.Profile)\<name>.exe`. This changes the
+    /// `Profile` part, along with the variable that is defined as `release`
+    /// if the compiler (`candle`) is used directly, and it should match the
+    /// [`profile_var`] method of the `print::wxs::Builder`. The default is
+    /// `Profile`.
+    ///
+    /// [`profile_var`]: ../wxs/struct.Builder.html#method.profile_var
+    pub fn profile_var(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.profile_var = p;
+        self
+    }
+
+    /// Sets the GUID for the upgrade code.
+    ///
+    /// The default is to generate a new, random GUID each time the include
+    /// file is printed. An error occurs if the value is not a valid GUID.
+    pub fn upgrade_code(&mut self, u: Option<&'a str>) -> &mut Self {
+        self.upgrade_code = u;
+        self
+    }
+
+    /// Sets the version.
+    ///
+    /// The default is to use the `version` field of the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or the `version` field
+    /// of the `package` section if it does not exist, which is the same
+    /// version used when the installer is created. An error occurs if the
+    /// version is not a valid semantic version.
+    pub fn version(&mut self, v: Option<&'a str>) -> &mut Self {
+        self.version = v;
+        self
+    }
+
+    /// Builds an execution context for printing a WiX Include (wxi) file.
+    pub fn build(&self) -> Execution {
+        Execution {
+            binaries: self
+                .binaries
+                .as_ref()
+                .map(|b| b.iter().map(PathBuf::from).collect()),
+            input: self.input.map(PathBuf::from),
+            manufacturer: self.manufacturer.map(String::from),
+            output: self.output.map(PathBuf::from),
+            product_name: self.product_name.map(String::from),
+            profile_var: self.profile_var.map(String::from),
+            upgrade_code: self.upgrade_code.map(String::from),
+            version: self.version.map(String::from),
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for printing a WiX Include (wxi) file of pre-processor variables.
+#[derive(Debug)]
+pub struct Execution {
+    binaries: Option<Vec<PathBuf>>,
+    input: Option<PathBuf>,
+    manufacturer: Option<String>,
+    output: Option<PathBuf>,
+    product_name: Option<String>,
+    profile_var: Option<String>,
+    upgrade_code: Option<String>,
+    version: Option<String>,
+}
+
+impl Execution {
+    /// Prints a WiX Include (wxi) file based on the built context.
+    pub fn run(self) -> Result<()> {
+        debug!("binaries = {:?}", self.binaries);
+        debug!("input = {:?}", self.input);
+        debug!("manufacturer = {:?}", self.manufacturer);
+        debug!("output = {:?}", self.output);
+        debug!("product_name = {:?}", self.product_name);
+        debug!("profile_var = {:?}", self.profile_var);
+        debug!("upgrade_code = {:?}", self.upgrade_code);
+        debug!("version = {:?}", self.version);
+        let manifest = manifest(self.input.as_ref())?;
+        let profile_var = self.profile_var.as_deref().unwrap_or(PROFILE_VARIABLE);
+        let binaries = binaries(self.binaries.as_ref(), &manifest, |name| {
+            PathBuf::from(wxs::Execution::binary_path(name, profile_var))
+        })?;
+        let mut destination = super::destination(self.output.as_ref())?;
+        let template = mustache::compile_str(Template::Wxi.to_str())?;
+        for binary in &binaries {
+            define_value("binary name", &binary.name)?;
+            define_value("binary source", &binary.source.display().to_string())?;
+        }
+        let data = MapBuilder::new()
+            .insert_str(
+                "product-name",
+                define_value(
+                    "product name",
+                    product_name(self.product_name.as_ref(), &manifest)?,
+                )?,
+            )
+            .insert_str(
+                "manufacturer",
+                define_value("manufacturer", self.manufacturer(&manifest)?)?,
+            )
+            .insert_str("profile-var", profile_var)
+            .insert_str("upgrade-code-guid", self.upgrade_code()?)
+            .insert_str("version", self.version(&manifest)?)
+            .insert_vec("binaries", |mut builder| {
+                for (index, binary) in binaries.iter().enumerate() {
+                    builder = builder.push_map(|builder| {
+                        builder
+                            .insert_str("binary-index", index.to_string())
+                            .insert_str("binary-name", &binary.name)
+                            .insert_str("binary-source", binary.source.display().to_string())
+                    });
+                }
+                builder
+            })
+            .build();
+        template
+            .render_data(&mut destination, &data)
+            .map_err(Error::from)
+    }
+
+    fn manufacturer(&self, manifest: &Value) -> Result<String> {
+        if let Some(ref m) = self.manufacturer {
+            Ok(m.to_owned())
+        } else {
+            super::manufacturer(manifest)
+        }
+    }
+
+    fn upgrade_code(&self) -> Result<String> {
+        if let Some(ref u) = self.upgrade_code {
+            Uuid::parse_str(u.trim_start_matches('{').trim_end_matches('}'))
+                .map(|u| u.to_hyphenated().to_string().to_uppercase())
+                .map_err(|err| {
+                    Error::Generic(format!(
+                        "The '{}' upgrade code is not a valid GUID: {}",
+                        u, err
+                    ))
+                })
+        } else {
+            Ok(Uuid::new_v4().to_hyphenated().to_string().to_uppercase())
+        }
+    }
+
+    fn version(&self, manifest: &Value) -> Result<String> {
+        create::Builder::new()
+            .version(self.version.as_deref())
+            .build()
+            .version(manifest)
+            .map(|v| v.to_string())
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+/// Checks a value can be the value of a pre-processor variable.
+///
+/// The value of a `<?define ?>` processing instruction is not XML text, so
+/// the XML special characters cannot be escaped, and a quotation mark (`"`)
+/// would end the value early.
+fn define_value<S: AsRef<str>>(description: &str, value: S) -> Result<S> {
+    if value.as_ref().contains('"') {
+        Err(Error::Generic(format!(
+            "The '{}' {} contains a quotation mark (\"), which cannot be used in a \
+             WiX Include (wxi) file",
+            value.as_ref(),
+            description
+        )))
+    } else {
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn binaries_works() {
+            const EXPECTED: &str = "bin\\Example.exe";
+            let mut actual = Builder::new();
+            actual.binaries(Some(vec![EXPECTED]));
+            assert_eq!(actual.binaries, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\example\\Cargo.toml";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn manufacturer_works() {
+            const EXPECTED: &str = "Example";
+            let mut actual = Builder::new();
+            actual.manufacturer(Some(EXPECTED));
+            assert_eq!(actual.manufacturer, Some(EXPECTED));
+        }
+
+        #[test]
+        fn output_works() {
+            const EXPECTED: &str = "C:\\example\\output";
+            let mut actual = Builder::new();
+            actual.output(Some(EXPECTED));
+            assert_eq!(actual.output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn product_name_works() {
+            const EXPECTED: &str = "Example Product Name";
+            let mut actual = Builder::new();
+            actual.product_name(Some(EXPECTED));
+            assert_eq!(actual.product_name, Some(EXPECTED));
+        }
+
+        #[test]
+        fn profile_var_works() {
+            const EXPECTED: &str = "BuildConfig";
+            let mut actual = Builder::new();
+            actual.profile_var(Some(EXPECTED));
+            assert_eq!(actual.profile_var, Some(EXPECTED));
+        }
+
+        #[test]
+        fn upgrade_code_works() {
+            const EXPECTED: &str = "0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C";
+            let mut actual = Builder::new();
+            actual.upgrade_code(Some(EXPECTED));
+            assert_eq!(actual.upgrade_code, Some(EXPECTED));
+        }
+
+        #[test]
+        fn version_works() {
+            const EXPECTED: &str = "1.2.3";
+            let mut actual = Builder::new();
+            actual.version(Some(EXPECTED));
+            assert_eq!(actual.version, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        extern crate assert_fs;
+
+        use super::*;
+        use std::fs;
+
+        const MIN_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+        "#;
+
+        const MULTIPLE_BIN_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+
+            [[bin]]
+            name = "binary0"
+            path = "src/binary0/main.rs"
+
+            [[bin]]
+            name = "binary1"
+            path = "src/binary1/main.rs"
+        "#;
+
+        fn render<F>(manifest: &str, configure: F) -> String
+        where
+            F: FnOnce(&mut Builder),
+        {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let input = temp_dir.path().join("Cargo.toml");
+            fs::write(&input, manifest).unwrap();
+            let output = temp_dir.path().join("variables.wxi");
+            let mut builder = Builder::default();
+            configure(&mut builder);
+            builder
+                .input(input.to_str())
+                .output(output.to_str())
+                .build()
+                .run()
+                .unwrap();
+            fs::read_to_string(&output).unwrap()
+        }
+
+        #[test]
+        fn run_with_defaults_works() {
+            let wxi = render(MIN_MANIFEST, |_| {});
+            assert!(wxi.contains("<?define Version = \"0.1.0\" ?>"));
+            assert!(wxi.contains("<?define ProductName = \"Example\" ?>"));
+            assert!(wxi.contains("<?define Manufacturer = \"First Last\" ?>"));
+            assert!(wxi.contains("<?define UpgradeCode = \""));
+            assert!(wxi.contains("<?define Binary0Name = \"Example\" ?>"));
+            assert!(wxi.contains(&format!(
+                "<?define Binary0Source = \"{}\" ?>",
+                wxs::Execution::default_binary_path("Example")
+            )));
+            assert!(wxi.contains("<?ifndef Profile ?>"));
+            assert!(wxi.contains("<?define Profile = \"release\" ?>"));
+            sxd_document::parser::parse(&wxi).expect("Well-formed XML");
+        }
+
+        #[test]
+        fn run_with_profile_var_works() {
+            let wxi = render(MIN_MANIFEST, |b| {
+                b.profile_var(Some("BuildConfig"));
+            });
+            assert!(wxi.contains("<?ifndef BuildConfig ?>"));
+            assert!(wxi.contains("<?define BuildConfig = \"release\" ?>"));
+            assert!(wxi.contains("$(var.BuildConfig)"));
+            assert!(!wxi.contains("$(var.Profile)"));
+        }
+
+        #[test]
+        fn run_with_overrides_works() {
+            let wxi = render(MIN_MANIFEST, |b| {
+                b.manufacturer(Some("Example & Co"))
+                    .product_name(Some("Example Product"))
+                    .upgrade_code(Some("{0e6c6c4f-8d4a-4b3a-9c3e-5d1e0f2a7b6c}"))
+                    .version(Some("1.2.3"));
+            });
+            assert!(wxi.contains("<?define Version = \"1.2.3\" ?>"));
+            assert!(wxi.contains("<?define ProductName = \"Example Product\" ?>"));
+            assert!(wxi.contains("<?define Manufacturer = \"Example & Co\" ?>"));
+            assert!(
+                wxi.contains("<?define UpgradeCode = \"0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C\" ?>")
+            );
+        }
+
+        #[test]
+        fn run_with_metadata_version_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                version = "2.0.0"
+            "#;
+            let wxi = render(MANIFEST, |_| {});
+            assert!(wxi.contains("<?define Version = \"2.0.0\" ?>"));
+        }
+
+        #[test]
+        fn run_with_multiple_bin_sections_works() {
+            let wxi = render(MULTIPLE_BIN_MANIFEST, |_| {});
+            for index in 0..2 {
+                let name = format!("binary{}", index);
+                assert!(wxi.contains(&format!("<?define Binary{}Name = \"{}\" ?>", index, name)));
+                assert!(wxi.contains(&format!(
+                    "<?define Binary{}Source = \"{}\" ?>",
+                    index,
+                    wxs::Execution::default_binary_path(&name)
+                )));
+            }
+            assert!(!wxi.contains("Binary2Name"));
+        }
+
+        #[test]
+        fn run_with_quotation_mark_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let input = temp_dir.path().join("Cargo.toml");
+            fs::write(&input, MIN_MANIFEST).unwrap();
+            let output = temp_dir.path().join("variables.wxi");
+            let result = Builder::default()
+                .input(input.to_str())
+                .manufacturer(Some("Example \"Quoted\" Co"))
+                .output(output.to_str())
+                .build()
+                .run();
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains("manufacturer")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn define_value_works() {
+            assert_eq!(
+                define_value("name", "Example & Co").unwrap(),
+                "Example & Co"
+            );
+            assert!(define_value("name", "Example \"Co\"").is_err());
+        }
+
+        #[test]
+        fn upgrade_code_with_invalid_guid_fails() {
+            let result = Builder::default()
+                .upgrade_code(Some("not-a-guid"))
+                .build()
+                .upgrade_code();
+            assert!(result.is_err());
+        }
+    }
+}
@@ -14,62 +14,263 @@
 
 //! The implementation for printing a WiX Source (wxs) file.
 
+use crate::binaries;
+use crate::create::{meta_array, meta_path, meta_str};
 use crate::description;
 use crate::eula::Eula;
+use crate::init_logging;
 use crate::manifest;
+use crate::manifest_input;
+use crate::package_root;
 use crate::product_name;
+use crate::Cultures;
 use crate::Error;
+use crate::Platform;
 use crate::Result;
 use crate::Template;
 use crate::EXE_FILE_EXTENSION;
+use crate::GIT;
 use crate::LICENSE_FILE_NAME;
+use crate::PROFILE_VARIABLE;
 use crate::RTF_FILE_EXTENSION;
+use crate::WIX;
+use crate::WIX_SOURCE_FILE_EXTENSION;
+use crate::WIX_SOURCE_FILE_NAME;
+
+use chrono::{DateTime, Utc};
+
+use log::LevelFilter;
 
 use mustache::{self, MapBuilder};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
 
 use toml::Value;
 
 use uuid::Uuid;
 
+const ICO_FILE_EXTENSION: &str = "ico";
+const PDB_FILE_EXTENSION: &str = "pdb";
+const ICO_MAGIC: [u8; 4] = [0x00, 0x00, 0x01, 0x00];
+const ICO_HEADER_LENGTH: usize = 6;
+const ICO_ENTRY_LENGTH: usize = 16;
+const EULA_FILE_NAME: &str = "Eula";
+const SUMMARY_TEXT_MAX_LENGTH: usize = 255;
+const SUMMARY_TEXT_ELLIPSIS: &str = "...";
+const TEMPLATE_PROPERTIES: [&str; 4] = [
+    "ARPHELPLINK",
+    "ARPINSTALLLOCATION",
+    "ARPPRODUCTICON",
+    "WIXUI_INSTALLDIR",
+];
+const DEFAULT_DOWNGRADE_MESSAGE: &str =
+    "A newer version of [ProductName] is already installed. Setup will now exit.";
+const REGISTRY_ROOTS: [&str; 5] = ["HKLM", "HKCU", "HKMU", "HKCR", "HKU"];
+const REGISTRY_TYPES: [&str; 5] = ["string", "integer", "expandable", "multiString", "binary"];
+const TEMPLATE_VARIABLES: [&str; 55] = [
+    "allow-downgrades",
+    "banner",
+    "binaries",
+    "binary-display-name",
+    "binary-index",
+    "binary-name",
+    "binary-source",
+    "binary-symbols-name",
+    "binary-symbols-source",
+    "build-stamp",
+    "comments",
+    "company-dir",
+    "compression",
+    "description",
+    "dialog",
+    "downgrade-message",
+    "eula",
+    "file-association",
+    "file-association-description",
+    "file-association-extension",
+    "file-association-guid",
+    "file-association-prog-id",
+    "fixed-platform",
+    "help-url",
+    "install-dir",
+    "keywords",
+    "language",
+    "license-name",
+    "license-source",
+    "manufacturer",
+    "path-component-guid",
+    "per-binary-features",
+    "product-icon",
+    "product-name",
+    "properties",
+    "property-id",
+    "property-value",
+    "readme-name",
+    "readme-source",
+    "registry",
+    "registry-guid",
+    "registry-key",
+    "registry-key-path",
+    "registry-name",
+    "registry-root",
+    "registry-type",
+    "registry-value",
+    "registry-values",
+    "ui",
+    "ui-install-dir",
+    "ui-next-dialog",
+    "uninstall-shortcut",
+    "uninstall-shortcut-guid",
+    "upgrade-code-guid",
+    "win64",
+];
+
+/// Gets the names of all of the variables available to the WiX Source (wxs)
+/// template.
+///
+/// This is the complete set of values that can be used in the embedded
+/// template or in a template file, i.e. `{{product-name}}`. Optional values,
+/// such as `banner` and `help-url`, are only defined if they are specified or
+/// found in the package's manifest (Cargo.toml), so they should be used within
+/// a section, i.e. `{{#banner}}...{{/banner}}`. The `binary-*` variables are
+/// only defined within the `binaries` section, and the `file-association-*`
+/// variables are only defined within the `file-association` section, the
+/// `property-*` variables are only defined within the `properties` section,
+/// and the `registry-*` variables, other than `registry-guid` and
+/// `registry-values`, are only defined within the `registry-values` section. The
+/// `uninstall-shortcut-guid` variable is only defined within the
+/// `uninstall-shortcut` section. The
+/// `fixed-platform` and `win64` variables are only defined if a platform is
+/// specified.
+///
+/// # Examples
+///
+/// ```
+/// use wix::print::wxs;
+///
+/// assert!(wxs::wxs_template_variables().contains(&"product-name"));
+/// assert!(wxs::wxs_template_variables().contains(&"upgrade-code-guid"));
+/// ```
+pub fn wxs_template_variables() -> &'static [&'static str] {
+    &TEMPLATE_VARIABLES
+}
+
 /// A builder for creating an execution context to print a WiX Toolset source file (wxs).
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
+    allow_downgrades: bool,
     banner: Option<&'a str>,
     binaries: Option<Vec<&'a str>>,
+    binary_display_names: Option<HashMap<&'a str, &'a str>>,
+    comments: Option<&'a str>,
+    company_dir: Option<&'a str>,
+    compression: Compression,
     description: Option<&'a str>,
     dialog: Option<&'a str>,
+    downgrade_message: Option<&'a str>,
     eula: Option<&'a str>,
+    eula_markdown: Option<&'a str>,
+    file_association: Option<FileAssoc>,
     help_url: Option<&'a str>,
+    include_symbols: bool,
     input: Option<&'a str>,
+    install_dir: Option<&'a str>,
+    install_license: bool,
+    install_readme: Option<&'a str>,
+    keywords: Option<Vec<&'a str>>,
+    language: Option<u16>,
     license: Option<&'a str>,
+    log_level: Option<LevelFilter>,
     manufacturer: Option<&'a str>,
     output: Option<&'a str>,
+    output_dir: Option<&'a str>,
+    path_component_guid: Option<&'a str>,
+    per_binary_features: bool,
+    platform: Option<&'a str>,
     product_icon: Option<&'a str>,
     product_name: Option<&'a str>,
+    profile_var: Option<&'a str>,
+    properties: Vec<(&'a str, &'a str)>,
+    registry: Vec<RegEntry>,
+    skip_license_file: bool,
+    stamp: bool,
+    strict_metadata: bool,
+    template_file: Option<&'a str>,
+    ui: UiSet,
+    ui_license: Option<&'a str>,
+    uninstall_shortcut: bool,
+    upgrade_code: Option<&'a str>,
 }
 
 impl<'a> Builder<'a> {
     /// Creates a new `Builder` instance.
     pub fn new() -> Self {
         Builder {
+            allow_downgrades: false,
             banner: None,
             binaries: None,
+            binary_display_names: None,
+            comments: None,
+            company_dir: None,
+            compression: Compression::default(),
             description: None,
             dialog: None,
+            downgrade_message: None,
             eula: None,
+            eula_markdown: None,
+            file_association: None,
             help_url: None,
+            include_symbols: false,
             input: None,
+            install_dir: None,
+            install_license: true,
+            install_readme: None,
+            keywords: None,
+            language: None,
             license: None,
+            log_level: None,
             manufacturer: None,
             output: None,
+            output_dir: None,
+            path_component_guid: None,
+            per_binary_features: false,
+            platform: None,
             product_icon: None,
             product_name: None,
+            profile_var: None,
+            properties: Vec::new(),
+            registry: Vec::new(),
+            skip_license_file: false,
+            stamp: false,
+            strict_metadata: false,
+            template_file: None,
+            ui: UiSet::default(),
+            ui_license: None,
+            uninstall_shortcut: false,
+            upgrade_code: None,
         }
     }
 
+    /// Allows a newer version of the product to be replaced with an older
+    /// version.
+    ///
+    /// If `true`, the `AllowDowngrades` attribute of the `MajorUpgrade`
+    /// element is set, so installing an older version removes the newer version
+    /// instead of failing with the [`downgrade_message`]. The default is
+    /// `false`, i.e. downgrades are blocked.
+    ///
+    /// [`downgrade_message`]: #method.downgrade_message
+    pub fn allow_downgrades(&mut self, a: bool) -> &mut Self {
+        self.allow_downgrades = a;
+        self
+    }
+
     /// Sets the path to a bitmap (BMP) file to be used as a banner image across
     /// the top of each dialog in the installer.
     ///
@@ -96,12 +297,43 @@ impl<'a> Builder<'a> {
     /// the path will be used for the source. The binary names will _not_ appear
     /// in the Add/Remove Programs control panel. Use the `product_name` method
     /// to change the name that appears in the Add/Remove Programs control
-    /// panel.
+    /// panel. An error occurs if two binaries have the same name.
+    ///
+    /// A path can also be a glob pattern, such as `target/release/*.exe`,
+    /// which is expanded to all of the matching files when the WXS file is
+    /// printed. Each matching file is included in the installer as a separate
+    /// binary. An error occurs if a pattern does not match any files.
+    ///
+    /// Without this, the path to each binary is
+    /// `$(var.CargoTargetDir)\$(var.Profile)\<name>.exe`, which is relative
+    /// to the folder containing the package's manifest (Cargo.toml) if it is
+    /// set with the [`input`] method.
+    ///
+    /// [`input`]: #method.input
     pub fn binaries(&mut self, b: Option<Vec<&'a str>>) -> &mut Self {
         self.binaries = b;
         self
     }
 
+    /// Sets a human-friendly name for one or more binaries.
+    ///
+    /// Each key is the name of a binary, i.e. the `name` field of a `[[bin]]`
+    /// section or the file stem of a path set with the [`binaries`] method,
+    /// and the value is the name to display for the binary. The display name
+    /// is available as the `binary-display-name` variable in the template,
+    /// which is useful for labels such as Start Menu shortcuts, while the
+    /// `binary-name` variable remains the file name of the installed binary.
+    /// In the default template, the display name is the title of the binary's
+    /// feature if the [`per_binary_features`] method is enabled.
+    /// The default is to use the binary name as the display name.
+    ///
+    /// [`binaries`]: #method.binaries
+    /// [`per_binary_features`]: #method.per_binary_features
+    pub fn binary_display_names(&mut self, b: Option<HashMap<&'a str, &'a str>>) -> &mut Self {
+        self.binary_display_names = b;
+        self
+    }
+
     /// Sets the description.
     ///
     /// This overrides the description determined from the `description` field
@@ -124,10 +356,27 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the message displayed when a newer version of the product is
+    /// already installed.
+    ///
+    /// This is the `DowngradeErrorMessage` attribute of the `MajorUpgrade`
+    /// element, and it can contain Windows Installer properties, i.e.
+    /// `[ProductName]`. The message is ignored if downgrades are allowed with
+    /// the [`allow_downgrades`] method. The default is "A newer version of
+    /// [ProductName] is already installed. Setup will now exit."
+    ///
+    /// [`allow_downgrades`]: #method.allow_downgrades
+    pub fn downgrade_message(&mut self, d: Option<&'a str>) -> &mut Self {
+        self.downgrade_message = d;
+        self
+    }
+
     /// Sets the path to a custom EULA.
     ///
-    /// The default is to generate an EULA from an embedded template as a RTF
-    /// file based on the name of the license specified in the `license` field
+    /// The default is to use the path in the `eula` field of the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    /// If the field does not exist, then an EULA is generated from an
+    /// embedded template as a RTF file based on the name of the license specified in the `license` field
     /// of the package's manifest (Cargo.toml). If the `license` field is not
     /// specified or a template for the license does not exist but the
     /// `license-file` field does specify a path to a file with the RTF
@@ -142,11 +391,74 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the comments of the installer.
+    ///
+    /// The comments are stored in the `Comments` field of the installer's
+    /// summary information, which is displayed in the properties of the
+    /// installer (msi) file. The default is to use the description.
+    pub fn comments(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.comments = c;
+        self
+    }
+
+    /// Sets the name of the company folder for the installation.
+    ///
+    /// If set, the installation folder is created within a folder with this
+    /// name in the `Program Files` folder, i.e. `C:\Program
+    /// Files\<company-dir>\<install-dir>`. The name must be a single folder
+    /// name, so an error occurs if it contains path separators or characters
+    /// that are not allowed in file names. The default is to install directly
+    /// within the `Program Files` folder.
+    pub fn company_dir(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.company_dir = c;
+        self
+    }
+
+    /// Sets the compression level of the cabinet embedded in the installer.
+    ///
+    /// Higher levels produce a smaller installer but take longer to create.
+    /// The default is `Compression::High`.
+    pub fn compression(&mut self, c: Compression) -> &mut Self {
+        self.compression = c;
+        self
+    }
+
+    /// Sets the path to a Markdown file to be used as the EULA.
+    ///
+    /// The Markdown file is converted to a Rich Text Format (RTF) file, which
+    /// is written next to the WiX Source (wxs) file and used for the license
+    /// agreement dialog in the installer. Headings, paragraphs, bold and
+    /// italic text, inline code, code blocks, links, and lists are converted,
+    /// while all other Markdown is written as plain text. The RTF file is not
+    /// written when printing to stdout. The [`eula`] method takes precedence,
+    /// but this overrides the EULA determined from the package's manifest
+    /// (Cargo.toml).
+    ///
+    /// [`eula`]: #method.eula
+    pub fn eula_markdown(&mut self, e: Option<&'a str>) -> &mut Self {
+        self.eula_markdown = e;
+        self
+    }
+
+    /// Sets a file association for the installed binary.
+    ///
+    /// If set, a component is added to the installer that registers the
+    /// extension and program identifier (ProgId) so that opening a file with
+    /// the extension runs the first binary with the path to the file as its
+    /// argument. The default is to not associate any file types with the
+    /// binary.
+    pub fn file_association(&mut self, f: Option<FileAssoc>) -> &mut Self {
+        self.file_association = f;
+        self
+    }
+
     /// Sets the help URL.
     ///
-    /// The default is to obtain a URL from one of the following fields in the
-    /// package's manifest (Cargo.toml): `documentation`, `homepage`, or
-    /// `respository`. If none of these are specified, then the default is to
+    /// The default is to use the `help-url` field of the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    /// If the field does not exist, then a URL is obtained from one of the
+    /// following fields in the package's manifest: `documentation`,
+    /// `homepage`, or `respository`. If none of these are specified, then the default is to
     /// exclude a help URL from the installer. This will override the default
     /// behavior and provide a help URL for the installer if none of the fields
     /// exist.
@@ -155,17 +467,106 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Enables or disables installing the debug symbols for each binary.
+    ///
+    /// If `true`, the program database (pdb) file for each binary is installed
+    /// next to the binary, so crash dumps can be symbolicated. The program
+    /// database is expected in the same folder as the binary with the name of
+    /// the binary, where hyphens are replaced with underscores, and the `.pdb`
+    /// extension, which is where Cargo writes it for the `*-pc-windows-msvc`
+    /// targets. The file is only checked when the installer is created, so an
+    /// error occurs at that time if it does not exist. The default is to not
+    /// install the debug symbols.
+    pub fn include_symbols(&mut self, i: bool) -> &mut Self {
+        self.include_symbols = i;
+        self
+    }
+
     /// Sets the path to a package's manifest (Cargo.toml) to be used to
     /// generate a WiX Source (wxs) file from the embedded template.
     ///
     /// A `wix` and `wix\main.wxs` file will be created in the same directory as
     /// the package's manifest. The default is to use the package's manifest in
-    /// the current working directory.
+    /// the current working directory. The path can also be to a folder
+    /// containing the package's manifest.
     pub fn input(&mut self, i: Option<&'a str>) -> &mut Self {
         self.input = i;
         self
     }
 
+    /// Sets the name of the installation folder.
+    ///
+    /// This is the name of the folder created within the `Program Files`
+    /// folder, or within the company folder if one is set, for the installation.
+    /// The name must be a single folder name, so an error occurs if it contains
+    /// path separators or characters that are not allowed in file names. The
+    /// default is to use the product name.
+    pub fn install_dir(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.install_dir = i;
+        self
+    }
+
+    /// Enables or disables installing the license sidecar file.
+    ///
+    /// If `true`, the license file determined by the [`license`] method, or
+    /// from the `license` and `license-file` fields of the package's manifest
+    /// (Cargo.toml), is installed in the installation folder next to the
+    /// binaries. If `false`, the component for the license file is omitted and
+    /// only the placeholder component for manually adding a license file is
+    /// printed in the WiX Source (wxs) file, but the license is still used for
+    /// the license agreement dialog. The default is to install the license
+    /// file.
+    ///
+    /// [`license`]: #method.license
+    pub fn install_license(&mut self, i: bool) -> &mut Self {
+        self.install_license = i;
+        self
+    }
+
+    /// Sets the path to a readme file to install.
+    ///
+    /// If set, a component is added to the installer that installs the file,
+    /// such as a `README.txt` file, in the installation folder next to the
+    /// binaries with the same file name. A relative path is relative to the
+    /// package's root when the installer is created. The default is to not
+    /// install a readme file.
+    pub fn install_readme(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.install_readme = i;
+        self
+    }
+
+    /// Sets the keywords of the installer.
+    ///
+    /// The keywords are stored in the `Keywords` field of the installer's
+    /// summary information, which is used by searches in the Windows Explorer.
+    /// The default is to use the `keywords` field of the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml). If the field does not exist, then the `keywords` field
+    /// of the `package` section is used. If neither field exists, then the
+    /// `Installer` keyword is used.
+    pub fn keywords(&mut self, k: Option<Vec<&'a str>>) -> &mut Self {
+        self.keywords = k;
+        self
+    }
+
+    /// Sets the language of the product as a Windows language code identifier
+    /// (LCID).
+    ///
+    /// The language is used for the `Language` attribute of the `Product` tag
+    /// and the `Languages` attribute of the `Package` tag, which are stored in
+    /// the installer's summary information. This is separate from the culture
+    /// used for the user interface of the installer, so an installer with a
+    /// French user interface can be created for an English product, or vice
+    /// versa. A warning is logged if the language differs from the culture.
+    /// The default is to use the LCID of the culture in the
+    /// `package.metadata.wix.culture` field of the package's manifest
+    /// (Cargo.toml). If the field does not exist, then the LCID for `en-US`,
+    /// i.e. `1033`, is used.
+    pub fn language(&mut self, l: Option<u16>) -> &mut Self {
+        self.language = l;
+        self
+    }
+
     /// Sets the path to a file to be used as the license [sidecar] file.
     ///
     /// The default is to use the value specified in the `license-file` field of
@@ -184,10 +585,26 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the maximum level for the logging statements.
+    ///
+    /// A logger that writes to `STDERR` is installed if the application has not
+    /// already installed one, so the logging statements are available without
+    /// any additional setup. This can be called any number of times. If a
+    /// level is not set, then a logger is not installed and the maximum level
+    /// is not changed, so the application is free to install its own logger.
+    pub fn log_level(&mut self, l: LevelFilter) -> &mut Self {
+        self.log_level = Some(l);
+        self
+    }
+
     /// Sets the manufacturer.
     ///
-    /// Default is to use the first author in the `authors` field of the
-    /// package's manifest (Cargo.toml). This would override the default value.
+    /// The default is to use the `manufacturer` field of the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    /// If the field does not exist, then the first author in the `authors`
+    /// field is used. If there are no authors, then the domain of the
+    /// `homepage` field is used. An error occurs if none of these exist. This
+    /// overrides all of the defaults.
     pub fn manufacturer(&mut self, m: Option<&'a str>) -> &mut Self {
         self.manufacturer = m;
         self
@@ -202,12 +619,72 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the directory for all of the generated files.
+    ///
+    /// If set, the WiX Source (wxs) file is written to `<dir>\wix\main.wxs`
+    /// and a generated license is referenced from the same `wix` folder. The
+    /// folders are created if they do not exist, and an error occurs if they
+    /// cannot be created. The [`output`] method takes precedence if both are
+    /// used. The default is to print all output to `STDOUT`.
+    ///
+    /// [`output`]: #output
+    pub fn output_dir(&mut self, o: Option<&'a str>) -> &mut Self {
+        self.output_dir = o;
+        self
+    }
+
+    /// Sets the GUID for the component that adds the installation folder to
+    /// the `PATH` system environment variable.
+    ///
+    /// The GUID can be in the hyphenated form with or without braces, and it
+    /// is printed in the uppercase, hyphenated form. An error occurs if the
+    /// value is not a valid GUID. The default is to generate a new, random
+    /// GUID each time the template is printed.
+    pub fn path_component_guid(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.path_component_guid = p;
+        self
+    }
+
+    /// Places each binary in its own feature.
+    ///
+    /// If `true`, the component for each binary is referenced by a separate
+    /// feature within the main feature, titled with the binary's display name,
+    /// so an end-user can choose which binaries to install. The features can
+    /// only be deselected with a dialog set that includes feature selection,
+    /// i.e. `UiSet::FeatureTree` or `UiSet::Mondo`. The default is `false`,
+    /// i.e. all binaries are installed with the main feature.
+    pub fn per_binary_features(&mut self, p: bool) -> &mut Self {
+        self.per_binary_features = p;
+        self
+    }
+
+    /// Sets the platform, or architecture, of the installer.
+    ///
+    /// Both the WiX Toolset values, `x86` and `x64`, and the Rust toolchain
+    /// architectures, `i686` and `x86_64`, are accepted. If set, the
+    /// components and installation folder for the platform are fixed in the
+    /// WiX Source (wxs) file, i.e. a `x64` platform always installs to the
+    /// `ProgramFiles64Folder` folder with 64-bit components. An error occurs
+    /// if the platform is unknown. The default is to determine the
+    /// installation folder and components when the installer is created from
+    /// the `Platform` variable passed to the compiler by the `cargo wix`
+    /// subcommand, so the same WiX Source (wxs) file can be used for both
+    /// platforms.
+    pub fn platform(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.platform = p;
+        self
+    }
+
     /// Sets the path to an image file to be used for product icon.
     ///
     /// The product icon is the icon that appears for an installed application
     /// in the Add/Remove Programs (ARP) control panel. If a product icon is
     /// _not_ defined for an application within the installer, then the Windows
     /// OS assigns a generic one.
+    ///
+    /// The file must be an ICO file with the `.ico` extension. An error
+    /// occurs when the installer source is printed if the file is not a valid
+    /// ICO file.
     pub fn product_icon(&mut self, p: Option<&'a str>) -> &mut Self {
         self.product_icon = p;
         self
@@ -231,24 +708,235 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the name of the preprocessor variable for the build profile in
+    /// the source paths of the binaries.
+    ///
+    /// The default source path of a binary is
+    /// `$(var.CargoTargetDir)\$(var.Profile)\<name>.exe`. This changes the
+    /// `Profile` part, i.e. `BuildConfig` results in `$(var.BuildConfig)`,
+    /// which is useful for WiX Source (wxs) files that are built with other
+    /// defines. The same name must be used with the [`profile_var`] method of
+    /// the `create::Builder`, so the compiler (`candle`) is passed the
+    /// matching define. Paths set with the [`binaries`] method are not
+    /// changed. The default is `Profile`.
+    ///
+    /// [`binaries`]: #method.binaries
+    /// [`profile_var`]: ../../create/struct.Builder.html#method.profile_var
+    pub fn profile_var(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.profile_var = p;
+        self
+    }
+
+    /// Sets additional properties for the installer.
+    ///
+    /// Each property is a pair of an identifier and a value, which is added to
+    /// the installer as a `Property` element, i.e. `<Property Id='KEY'
+    /// Value='VALUE'/>`, so custom actions and conditions can use values that
+    /// are set when the WiX Source (wxs) file is created. The identifier must
+    /// be a public property, i.e. only uppercase letters, digits, underscores
+    /// (`_`), and periods (`.`), starting with a letter or an underscore, so it
+    /// can also be set at the command line when the installer runs. An error
+    /// occurs if the identifier is already defined by the template, such as
+    /// `ARPPRODUCTICON`. The default is to not add any properties.
+    pub fn properties(&mut self, p: Vec<(&'a str, &'a str)>) -> &mut Self {
+        self.properties = p;
+        self
+    }
+
+    /// Sets the values to write to the Windows registry.
+    ///
+    /// If any values are set, a component is added to the installer that
+    /// writes all of the values when the package is installed and removes them
+    /// when it is uninstalled. The default is to not write any values to the
+    /// registry.
+    pub fn registry(&mut self, r: Vec<RegEntry>) -> &mut Self {
+        self.registry = r;
+        self
+    }
+
+    /// Skips writing a license file generated from the `license` field of the
+    /// package's manifest (Cargo.toml).
+    ///
+    /// If the `license` field is a recognized SPDX license, then the WiX
+    /// Source (wxs) file references a `License.rtf` file, which is rendered
+    /// from the embedded license template and written next to the WiX Source
+    /// (wxs) file. Nothing is written if the WiX Source (wxs) file is printed
+    /// to `STDOUT`, and an existing license file is not overwritten, so any
+    /// edits to it are kept. If `true`, the license file is referenced but not
+    /// written, which is useful when the license file is managed separately.
+    /// The default is to write the license file.
+    pub fn skip_license_file(&mut self, s: bool) -> &mut Self {
+        self.skip_license_file = s;
+        self
+    }
+
+    /// Enables or disables stamping the installer with the build's time and
+    /// revision.
+    ///
+    /// If `true`, the current time in UTC and the abbreviated revision of the
+    /// `HEAD` commit from `git rev-parse --short HEAD`, run in the package's
+    /// root folder, are appended to the `Comments` field of the installer's
+    /// summary information for traceability, i.e. `Built 2024-01-31T12:00:00Z
+    /// from 1a2b3c4`. The stamp is also available to a template as the
+    /// `build-stamp` variable. Only the time is used if git is not installed
+    /// or the package is not in a git repository. The default is to not stamp
+    /// the installer.
+    pub fn stamp(&mut self, s: bool) -> &mut Self {
+        self.stamp = s;
+        self
+    }
+
+    /// Enables or disables failing when installer metadata is missing.
+    ///
+    /// If `true`, an error occurs instead of a warning if a description, help
+    /// URL, license file, or End User License Agreement (EULA) cannot be
+    /// resolved from the builder or the package's manifest (Cargo.toml), which
+    /// is useful for catching incomplete metadata for a release in continuous
+    /// integration (CI). A license file that is not installed because of the
+    /// [`install_license`] method is not an error. The default is `false`.
+    ///
+    /// [`install_license`]: #method.install_license
+    pub fn strict_metadata(&mut self, s: bool) -> &mut Self {
+        self.strict_metadata = s;
+        self
+    }
+
+    /// Sets the path to a file to use as the template instead of the embedded
+    /// WiX Source (wxs) template.
+    ///
+    /// The file must be a [mustache] template, and it is rendered with the
+    /// same values as the embedded template. This is useful for maintaining a
+    /// customized template that is shared across projects. An error occurs if
+    /// the file cannot be read or is not a valid mustache template. A warning
+    /// is logged for each variable in the template that is not one of the
+    /// [`wxs_template_variables`]. The default is to use the embedded template.
+    ///
+    /// [mustache]: https://mustache.github.io/
+    /// [`wxs_template_variables`]: fn.wxs_template_variables.html
+    pub fn template_file(&mut self, t: Option<&'a str>) -> &mut Self {
+        self.template_file = t;
+        self
+    }
+
+    /// Sets the set of dialogs for the installer's user interface (UI).
+    ///
+    /// The dialog set is selected independently of the End User License
+    /// Agreement (EULA). If an EULA is not available, then the license
+    /// agreement dialog is skipped for the `FeatureTree`, `InstallDir`, and
+    /// `Mondo` dialog sets. The `Minimal` dialog set always displays a license
+    /// agreement on its only dialog. The `InstallDir` dialog set lets the user
+    /// change the installation folder, which is wired to the same folder as the
+    /// binaries through the `WIXUI_INSTALLDIR` property. If `UiSet::None`, then
+    /// the installer does not have a UI and only the progress of the
+    /// installation is displayed. The default is `UiSet::FeatureTree`.
+    pub fn ui(&mut self, u: UiSet) -> &mut Self {
+        self.ui = u;
+        self
+    }
+
+    /// Sets the path to a Rich Text Format (RTF) file displayed in the license
+    /// agreement dialog.
+    ///
+    /// This only changes the license displayed in the user interface, i.e. the
+    /// `WixUILicenseRtf` variable, and it takes precedence over the [`eula`]
+    /// and [`eula_markdown`] methods and a RTF license from the [`license`]
+    /// method. The license installed with the binaries is not changed, so a
+    /// custom EULA can be displayed while a separate license is installed. The
+    /// default is to determine the license agreement from the EULA and license
+    /// sources.
+    ///
+    /// [`eula`]: #method.eula
+    /// [`eula_markdown`]: #method.eula_markdown
+    /// [`license`]: #method.license
+    pub fn ui_license(&mut self, u: Option<&'a str>) -> &mut Self {
+        self.ui_license = u;
+        self
+    }
+
+    /// Adds an "Uninstall" shortcut to the Start Menu.
+    ///
+    /// If `true`, a folder with the product name is created in the Start
+    /// Menu's programs folder with a shortcut that uninstalls the product,
+    /// i.e. `msiexec /x [ProductCode]`. The folder and shortcut are removed
+    /// when the product is uninstalled. The default is `false`.
+    pub fn uninstall_shortcut(&mut self, u: bool) -> &mut Self {
+        self.uninstall_shortcut = u;
+        self
+    }
+
+    /// Sets the upgrade code, which is a GUID that identifies the product
+    /// across versions.
+    ///
+    /// The upgrade code must stay the same for all versions of a product so
+    /// that a newer installer can upgrade an older installation. The GUID can
+    /// be in the hyphenated form with or without braces, and it is printed in
+    /// the uppercase, hyphenated form. An error occurs if the value is not a
+    /// valid GUID. The default is to generate a new, random GUID each time the
+    /// template is printed.
+    pub fn upgrade_code(&mut self, u: Option<&'a str>) -> &mut Self {
+        self.upgrade_code = u;
+        self
+    }
+
     /// Builds an execution context for printing a template.
     pub fn build(&self) -> Execution {
         Execution {
+            allow_downgrades: self.allow_downgrades,
             banner: self.banner.map(PathBuf::from),
             binaries: self
                 .binaries
                 .as_ref()
                 .map(|b| b.iter().map(PathBuf::from).collect()),
+            binary_display_names: self.binary_display_names.as_ref().map(|b| {
+                b.iter()
+                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                    .collect()
+            }),
+            comments: self.comments.map(String::from),
+            company_dir: self.company_dir.map(String::from),
+            compression: self.compression,
             description: self.description.map(String::from),
             dialog: self.dialog.map(PathBuf::from),
+            downgrade_message: self.downgrade_message.map(String::from),
             eula: self.eula.map(PathBuf::from),
+            eula_markdown: self.eula_markdown.map(PathBuf::from),
+            file_association: self.file_association.clone(),
             help_url: self.help_url.map(String::from),
-            input: self.input.map(PathBuf::from),
+            include_symbols: self.include_symbols,
+            input: self.input.map(manifest_input),
+            install_dir: self.install_dir.map(String::from),
+            install_license: self.install_license,
+            install_readme: self.install_readme.map(PathBuf::from),
+            keywords: self
+                .keywords
+                .as_ref()
+                .map(|k| k.iter().map(|s| (*s).to_string()).collect()),
+            language: self.language,
             license: self.license.map(PathBuf::from),
+            log_level: self.log_level,
             manufacturer: self.manufacturer.map(String::from),
             output: self.output.map(PathBuf::from),
+            output_dir: self.output_dir.map(PathBuf::from),
+            path_component_guid: self.path_component_guid.map(String::from),
+            per_binary_features: self.per_binary_features,
+            platform: self.platform.map(String::from),
             product_icon: self.product_icon.map(PathBuf::from),
             product_name: self.product_name.map(String::from),
+            profile_var: self.profile_var.map(String::from),
+            properties: self
+                .properties
+                .iter()
+                .map(|(id, value)| (String::from(*id), String::from(*value)))
+                .collect(),
+            registry: self.registry.clone(),
+            skip_license_file: self.skip_license_file,
+            stamp: self.stamp,
+            strict_metadata: self.strict_metadata,
+            template_file: self.template_file.map(PathBuf::from),
+            ui: self.ui,
+            ui_license: self.ui_license.map(PathBuf::from),
+            uninstall_shortcut: self.uninstall_shortcut,
+            upgrade_code: self.upgrade_code.map(String::from),
         }
     }
 }
@@ -262,92 +950,328 @@ impl<'a> Default for Builder<'a> {
 /// A context for printing a WiX Toolset source file (wxs).
 #[derive(Debug)]
 pub struct Execution {
+    allow_downgrades: bool,
     banner: Option<PathBuf>,
     binaries: Option<Vec<PathBuf>>,
+    binary_display_names: Option<HashMap<String, String>>,
+    comments: Option<String>,
+    company_dir: Option<String>,
+    compression: Compression,
     description: Option<String>,
     dialog: Option<PathBuf>,
+    downgrade_message: Option<String>,
     eula: Option<PathBuf>,
+    eula_markdown: Option<PathBuf>,
+    file_association: Option<FileAssoc>,
     help_url: Option<String>,
+    include_symbols: bool,
     input: Option<PathBuf>,
+    install_dir: Option<String>,
+    install_license: bool,
+    install_readme: Option<PathBuf>,
+    keywords: Option<Vec<String>>,
+    language: Option<u16>,
     license: Option<PathBuf>,
+    log_level: Option<LevelFilter>,
     manufacturer: Option<String>,
     output: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    path_component_guid: Option<String>,
+    per_binary_features: bool,
+    platform: Option<String>,
     product_icon: Option<PathBuf>,
     product_name: Option<String>,
+    profile_var: Option<String>,
+    properties: Vec<(String, String)>,
+    registry: Vec<RegEntry>,
+    skip_license_file: bool,
+    stamp: bool,
+    strict_metadata: bool,
+    template_file: Option<PathBuf>,
+    ui: UiSet,
+    ui_license: Option<PathBuf>,
+    uninstall_shortcut: bool,
+    upgrade_code: Option<String>,
 }
 
 impl Execution {
     /// Prints a WiX Source (wxs) file based on the built context.
     pub fn run(self) -> Result<()> {
+        if let Some(level) = self.log_level {
+            init_logging(level);
+        }
+        debug!("allow_downgrades = {:?}", self.allow_downgrades);
         debug!("banner = {:?}", self.banner);
         debug!("binaries = {:?}", self.binaries);
+        debug!("binary_display_names = {:?}", self.binary_display_names);
+        debug!("comments = {:?}", self.comments);
+        debug!("company_dir = {:?}", self.company_dir);
+        debug!("compression = {:?}", self.compression);
         debug!("description = {:?}", self.description);
         debug!("dialog = {:?}", self.description);
+        debug!("downgrade_message = {:?}", self.downgrade_message);
         debug!("eula = {:?}", self.eula);
+        debug!("eula_markdown = {:?}", self.eula_markdown);
+        debug!("file_association = {:?}", self.file_association);
         debug!("help_url = {:?}", self.help_url);
+        debug!("include_symbols = {:?}", self.include_symbols);
         debug!("input = {:?}", self.input);
+        debug!("install_dir = {:?}", self.install_dir);
+        debug!("install_license = {:?}", self.install_license);
+        debug!("install_readme = {:?}", self.install_readme);
+        debug!("keywords = {:?}", self.keywords);
+        debug!("language = {:?}", self.language);
         debug!("license = {:?}", self.license);
+        debug!("log_level = {:?}", self.log_level);
         debug!("manufacturer = {:?}", self.manufacturer);
         debug!("output = {:?}", self.output);
+        debug!("output_dir = {:?}", self.output_dir);
+        debug!("path_component_guid = {:?}", self.path_component_guid);
+        debug!("per_binary_features = {:?}", self.per_binary_features);
+        debug!("platform = {:?}", self.platform);
         debug!("product_icon = {:?}", self.product_icon);
         debug!("product_name = {:?}", self.product_name);
+        debug!("profile_var = {:?}", self.profile_var);
+        debug!("properties = {:?}", self.properties);
+        debug!("registry = {:?}", self.registry);
+        debug!("skip_license_file = {:?}", self.skip_license_file);
+        debug!("stamp = {:?}", self.stamp);
+        debug!("strict_metadata = {:?}", self.strict_metadata);
+        debug!("template_file = {:?}", self.template_file);
+        debug!("ui = {:?}", self.ui);
+        debug!("ui_license = {:?}", self.ui_license);
+        debug!("uninstall_shortcut = {:?}", self.uninstall_shortcut);
+        debug!("upgrade_code = {:?}", self.upgrade_code);
         let manifest = manifest(self.input.as_ref())?;
-        let mut destination = super::destination(self.output.as_ref())?;
-        let template = mustache::compile_str(Template::Wxs.to_str())?;
+        let output = self.output_path()?;
+        debug!("output = {:?}", output);
+        let mut destination = super::destination(output.as_ref())?;
+        let template = self.template()?;
         let binaries = self.binaries(&manifest)?;
+        if let Some(ref display_names) = self.binary_display_names {
+            for name in display_names.keys() {
+                if !binaries.iter().any(|b| &b["binary-name"] == name) {
+                    warn!(
+                        "The '{}' binary for the display name does not exist and is ignored",
+                        name
+                    );
+                }
+            }
+        }
+        let manufacturer = self.manufacturer(&manifest)?;
+        let product_name = product_name(self.product_name.as_ref(), &manifest)?;
+        let install_dir = if let Some(ref i) = self.install_dir {
+            Self::validate_directory_name(i, "installation")?;
+            i.to_owned()
+        } else {
+            product_name.clone()
+        };
+        debug!("install_dir = {:?}", install_dir);
+        // The values are inserted verbatim because the `{{name}}` tags in the
+        // template escape the XML special characters, i.e. `&` and `<`.
         let mut map = MapBuilder::new()
             .insert_vec("binaries", |mut builder| {
                 for binary in &binaries {
                     builder = builder.push_map(|builder| {
-                        builder
+                        let builder = builder
                             .insert_str("binary-index", binary.get("binary-index").unwrap())
                             .insert_str("binary-name", binary.get("binary-name").unwrap())
-                            .insert_str("binary-source", binary.get("binary-source").unwrap())
+                            .insert_str(
+                                "binary-display-name",
+                                self.binary_display_name(binary.get("binary-name").unwrap()),
+                            )
+                            .insert_str("binary-source", binary.get("binary-source").unwrap());
+                        if self.include_symbols {
+                            let (name, source) = Self::symbols(
+                                binary.get("binary-name").unwrap(),
+                                binary.get("binary-source").unwrap(),
+                            );
+                            builder
+                                .insert_str("binary-symbols-name", name)
+                                .insert_str("binary-symbols-source", source)
+                        } else {
+                            builder
+                        }
                     });
                 }
                 builder
             })
-            .insert_str(
-                "product-name",
-                product_name(self.product_name.as_ref(), &manifest)?,
-            )
-            .insert_str("manufacturer", self.manufacturer(&manifest)?)
+            .insert_str("product-name", product_name)
+            .insert_str("install-dir", install_dir)
+            .insert_str("manufacturer", &manufacturer)
             .insert_str(
                 "upgrade-code-guid",
-                Uuid::new_v4().to_hyphenated().to_string().to_uppercase(),
+                Self::guid(self.upgrade_code.as_ref(), "upgrade code")?,
             )
             .insert_str(
                 "path-component-guid",
-                Uuid::new_v4().to_hyphenated().to_string().to_uppercase(),
+                Self::guid(self.path_component_guid.as_ref(), "path component GUID")?,
             );
         if let Some(ref banner) = self.banner {
             map = map.insert_str("banner", banner.display().to_string());
         }
-        if let Some(description) = description(self.description.clone(), &manifest) {
+        if let Some(ref company_dir) = self.company_dir {
+            Self::validate_directory_name(company_dir, "company")?;
+            map = map.insert_str("company-dir", company_dir);
+        }
+        map = map.insert_str("compression", self.compression.to_string());
+        if self.allow_downgrades {
+            if self.downgrade_message.is_some() {
+                warn!("The downgrade message is ignored because downgrades are allowed");
+            }
+            map = map.insert_bool("allow-downgrades", true);
+        }
+        map = map.insert_str(
+            "downgrade-message",
+            self.downgrade_message
+                .as_deref()
+                .unwrap_or(DEFAULT_DOWNGRADE_MESSAGE),
+        );
+        if let Some(ref file_association) = self.file_association {
+            file_association.validate()?;
+            let guid = Self::guid(None, "file association GUID")?;
+            map = map.insert_map("file-association", |builder| {
+                builder
+                    .insert_str("file-association-guid", &guid)
+                    .insert_str("file-association-extension", file_association.extension())
+                    .insert_str("file-association-prog-id", file_association.prog_id())
+                    .insert_str(
+                        "file-association-description",
+                        file_association.description(),
+                    )
+            });
+        }
+        if !self.properties.is_empty() {
+            Self::validate_properties(&self.properties)?;
+            let properties = &self.properties;
+            map = map.insert_vec("properties", |mut builder| {
+                for (id, value) in properties {
+                    builder = builder.push_map(|builder| {
+                        builder
+                            .insert_str("property-id", id)
+                            .insert_str("property-value", value)
+                    });
+                }
+                builder
+            });
+        }
+        if !self.registry.is_empty() {
+            for entry in &self.registry {
+                entry.validate()?;
+            }
+            let guid = Self::guid(None, "registry GUID")?;
+            let registry = &self.registry;
+            map = map.insert_map("registry", |builder| {
+                builder.insert_str("registry-guid", &guid).insert_vec(
+                    "registry-values",
+                    |mut builder| {
+                        for (index, entry) in registry.iter().enumerate() {
+                            builder = builder.push_map(|builder| {
+                                let builder = builder
+                                    .insert_str("registry-root", entry.root())
+                                    .insert_str("registry-key", entry.key())
+                                    .insert_str("registry-type", entry.kind())
+                                    .insert_str("registry-value", entry.value())
+                                    .insert_str(
+                                        "registry-key-path",
+                                        if index == 0 { "yes" } else { "no" },
+                                    );
+                                if entry.name().is_empty() {
+                                    builder
+                                } else {
+                                    builder.insert_str("registry-name", entry.name())
+                                }
+                            });
+                        }
+                        builder
+                    },
+                )
+            });
+        }
+        if self.per_binary_features {
+            if self.ui != UiSet::FeatureTree && self.ui != UiSet::Mondo {
+                warn!(
+                    "The '{}' dialog set does not include feature selection, so the feature \
+                     for each binary cannot be deselected during installation. Use the \
+                     'FeatureTree' or 'Mondo' dialog set instead.",
+                    self.ui
+                );
+            }
+            map = map.insert_bool("per-binary-features", true);
+        }
+        if self.ui != UiSet::None {
+            map = map.insert_str("ui", self.ui.to_string());
+            if self.ui == UiSet::InstallDir {
+                map = map.insert_bool("ui-install-dir", true);
+            }
+            if let Some(next_dialog) = self.ui.next_dialog() {
+                map = map.insert_str("ui-next-dialog", next_dialog);
+            }
+        }
+        if self.uninstall_shortcut {
+            let guid = Self::guid(None, "uninstall shortcut GUID")?;
+            map = map.insert_map("uninstall-shortcut", |builder| {
+                builder.insert_str("uninstall-shortcut-guid", &guid)
+            });
+        }
+        if let Some(platform) = self.platform()? {
+            map = map
+                .insert_bool("fixed-platform", true)
+                .insert_bool("win64", platform == Platform::X64);
+        }
+        let description = description(self.description.clone(), &manifest)
+            .map(|d| Self::summary_text(&d, "description"));
+        let comments = self
+            .comments
+            .as_ref()
+            .map(|c| Self::summary_text(c, "comments"))
+            .or_else(|| description.clone());
+        let comments = if self.stamp {
+            let stamp = Self::build_stamp(self.revision().as_deref(), Utc::now());
+            debug!("stamp = {:?}", stamp);
+            map = map.insert_str("build-stamp", &stamp);
+            Some(match comments {
+                Some(c) => Self::summary_text(&format!("{} ({})", c, stamp), "comments"),
+                None => stamp,
+            })
+        } else {
+            comments
+        };
+        if let Some(comments) = comments {
+            map = map.insert_str("comments", comments);
+        }
+        map = map
+            .insert_str("keywords", self.keywords(&manifest))
+            .insert_str("language", self.language(&manifest)?.to_string());
+        if let Some(description) = description {
             map = map.insert_str("description", description);
         } else {
-            warn!(
+            self.missing_metadata(
                 "A description was not specified at the command line or in the package's manifest \
                  (Cargo.toml). The description can be added manually to the generated WiX \
-                 Source (wxs) file using a text editor."
-            );
+                 Source (wxs) file using a text editor.",
+            )?;
         }
         if let Some(ref dialog) = self.dialog {
             map = map.insert_str("dialog", dialog.display().to_string());
         }
-        match self.eula(&manifest)? {
+        let eula = self.eula(&manifest)?;
+        match eula {
             Eula::Disabled => {
-                warn!(
+                self.missing_metadata(
                     "An EULA was not specified at the command line, a RTF \
                      license file was not specified in the package manifest's \
                      (Cargo.toml) 'license-file' field, or the license ID from the \
                      package manifest's 'license' field is not recognized. The \
                      license agreement dialog will be excluded from the installer. An \
                      EULA can be added manually to the generated WiX Source (wxs) \
-                     file using a text editor."
-                );
+                     file using a text editor.",
+                )?;
+            }
+            Eula::Generate(..) => {
+                map = map.insert_str("eula", self.generated_license_path().display().to_string())
             }
-            e => map = map.insert_str("eula", e.to_string()),
+            ref e => map = map.insert_str("eula", e.to_string()),
         }
         if let Some(url) = self
             .help_url
@@ -356,145 +1280,351 @@ impl Execution {
         {
             map = map.insert_str("help-url", url);
         } else {
-            warn!(
+            self.missing_metadata(
                 "A help URL could not be found and it will be excluded from the installer. \
                  A help URL can be added manually to the generated WiX Source (wxs) file \
-                 using a text editor."
-            );
+                 using a text editor.",
+            )?;
         }
         if let Some(name) = self.license_name(&manifest) {
             map = map.insert_str("license-name", name);
         }
-        if let Some(source) = self.license_source(&manifest)? {
+        if !self.install_license {
+            trace!("The license file is not installed");
+        } else if let Some(source) = self.license_source(&manifest)? {
             map = map.insert_str("license-source", source);
         } else {
-            warn!(
+            self.missing_metadata(
                 "A license file could not be found and it will be excluded from the \
                  installer. A license file can be added manually to the generated WiX Source \
-                 (wxs) file using a text editor."
-            );
+                 (wxs) file using a text editor.",
+            )?;
         }
-        if let Some(icon) = self.product_icon {
+        if let Some(ref readme) = self.install_readme {
+            let name = readme.file_name().ok_or_else(|| {
+                Error::Generic(format!(
+                    "The '{}' path for the readme file does not have a file name",
+                    readme.display()
+                ))
+            })?;
+            map = map
+                .insert_str("readme-name", name.to_string_lossy())
+                .insert_str("readme-source", readme.display().to_string());
+        }
+        if let Some(ref icon) = self.product_icon {
+            Self::validate_product_icon(icon)?;
             map = map.insert_str("product-icon", icon.display().to_string());
         }
+        if let Some(ref markdown) = self.eula_markdown {
+            if self.eula.is_none() && self.ui_license.is_none() {
+                let rtf = Self::markdown_eula(markdown)?;
+                let eula_path = self.generated_eula_path();
+                if output.is_none() {
+                    trace!("The EULA file is not written when printing to STDOUT");
+                } else {
+                    info!("Creating the '{}' file", eula_path.display());
+                    fs::write(&eula_path, rtf)?;
+                }
+            }
+        }
+        if let Some(license_template) = self.generated_license(&eula, &manifest) {
+            let license_path = self.generated_license_path();
+            if self.skip_license_file {
+                trace!(
+                    "Skipped writing the '{}' license file",
+                    license_path.display()
+                );
+            } else if output.is_none() {
+                trace!("The license file is not written when printing to STDOUT");
+            } else if license_path.exists() {
+                warn!(
+                    "The '{}' license file already exists and is not overwritten. Remove \
+                     the file to render it again from the license template.",
+                    license_path.display()
+                );
+            } else {
+                info!("Creating the '{}' file", license_path.display());
+                let mut license_printer = super::license::Builder::new();
+                license_printer.copyright_holder(Some(&manufacturer));
+                license_printer.input(self.input.as_deref().and_then(Path::to_str));
+                license_printer.output(license_path.to_str());
+                license_printer.build().run(license_template)?;
+            }
+        }
         let data = map.build();
         template
             .render_data(&mut destination, &data)
             .map_err(Error::from)
     }
 
-    fn binaries(&self, manifest: &Value) -> Result<Vec<HashMap<&'static str, String>>> {
-        let mut binaries = Vec::new();
-        if let Some(binary_paths) = &self.binaries {
-            let mut map = HashMap::with_capacity(3);
-            for (index, binary) in binary_paths.iter().enumerate() {
-                let binary_file_stem = binary.file_stem().ok_or_else(|| {
-                    Error::Generic(format!(
-                        "The '{}' binary path does not have a file name",
-                        binary.display()
-                    ))
-                })?;
-                map.insert("binary-index", index.to_string());
-                map.insert(
-                    "binary-name",
-                    binary_file_stem.to_string_lossy().into_owned(),
-                );
-                map.insert("binary-source", binary.to_string_lossy().into_owned());
+    /// Gets the binaries that would be installed by the WiX Source (wxs) file
+    /// without rendering the template.
+    ///
+    /// The binaries are determined in the same way as printing the template,
+    /// i.e. from the binaries set with the [`binaries`] method of the
+    /// `Builder`, or from the `bin` sections of the package's manifest
+    /// (Cargo.toml), so they can be listed or validated before printing.
+    ///
+    /// [`binaries`]: struct.Builder.html#method.binaries
+    pub fn discovered_binaries(&self) -> Result<Vec<BinaryInfo>> {
+        let manifest = manifest(self.input.as_ref())?;
+        Ok(self
+            .binaries(&manifest)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut binary)| BinaryInfo {
+                index,
+                name: binary.remove("binary-name").unwrap_or_default(),
+                source: binary.remove("binary-source").unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Gets the abbreviated revision of the `HEAD` commit of the git
+    /// repository containing the package.
+    ///
+    /// `None` is returned if git is not installed or the package is not in a
+    /// git repository.
+    fn revision(&self) -> Option<String> {
+        let folder = package_root(self.input.as_ref()).unwrap_or_else(|_| PathBuf::from("."));
+        let output = Command::new(GIT)
+            .args(["rev-parse", "--short", "HEAD"])
+            .current_dir(folder)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+        match output {
+            Ok(o) if o.status.success() => {
+                let revision = String::from_utf8_lossy(&o.stdout).trim().to_owned();
+                Some(revision).filter(|r| !r.is_empty())
             }
-            binaries.push(map);
-        } else if let Some(array) = manifest.get("bin").and_then(|b| b.as_array()) {
-            for (index, binary) in array.iter().enumerate() {
-                let mut map = HashMap::with_capacity(3);
-                let table = binary
-                    .as_table()
-                    .expect("The [[bin]] section to be a table");
-                map.insert("binary-index", index.to_string());
-                if let Some(name) = table.get("name").and_then(|n| n.as_str()) {
-                    map.insert("binary-name", name.to_owned());
-                } else {
-                    return Err(Error::Generic(String::from(
-                            "Missing the 'name' field for the binary in the project's manifest file (Cargo.toml)",
-                        )));
-                }
-                map.insert(
-                    "binary-source",
-                    Self::default_binary_path(table.get("name").and_then(|n| n.as_str()).unwrap()),
+            _ => {
+                warn!(
+                    "The revision could not be determined with git, so only the time is used \
+                     for the build stamp"
                 );
-                binaries.push(map);
+                None
             }
+        }
+    }
+
+    fn build_stamp(revision: Option<&str>, time: DateTime<Utc>) -> String {
+        let time = time.format("%Y-%m-%dT%H:%M:%SZ");
+        if let Some(revision) = revision {
+            format!("Built {} from {}", time, revision)
         } else {
-            let mut map = HashMap::with_capacity(3);
-            let name = product_name(None, manifest)?;
-            map.insert("binary-index", 0.to_string());
-            map.insert("binary-source", Self::default_binary_path(&name));
-            map.insert("binary-name", name);
-            binaries.push(map);
+            format!("Built {}", time)
         }
-        Ok(binaries)
     }
 
-    fn default_binary_path(name: &str) -> String {
-        let mut path = PathBuf::from("target").join("$(var.Profile)").join(name);
-        path.set_extension(EXE_FILE_EXTENSION);
-        path.to_str()
-            .map(String::from)
-            .expect("Path to string conversion")
+    /// Warns about missing installer metadata, or fails if the metadata is
+    /// strict.
+    fn missing_metadata(&self, message: &str) -> Result<()> {
+        if self.strict_metadata {
+            Err(Error::Generic(format!(
+                "{} This is an error because strict metadata is enabled.",
+                message
+            )))
+        } else {
+            warn!("{}", message);
+            Ok(())
+        }
     }
 
-    fn help_url(manifest: &Value) -> Option<String> {
-        manifest
-            .get("package")
-            .and_then(|p| p.as_table())
-            .and_then(|t| {
-                t.get("documentation")
-                    .or_else(|| t.get("homepage"))
-                    .or_else(|| t.get("repository"))
-            })
-            .and_then(|h| h.as_str())
-            .map(|s| {
-                trace!("Using '{}' for the help URL", s);
-                String::from(s)
-            })
+    fn summary_text(text: &str, name: &str) -> String {
+        let collapsed = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if collapsed.chars().count() > SUMMARY_TEXT_MAX_LENGTH {
+            warn!(
+                "The {} is longer than {} characters and it has been truncated for the \
+                 installer's summary information.",
+                name, SUMMARY_TEXT_MAX_LENGTH
+            );
+            let truncated = collapsed
+                .chars()
+                .take(SUMMARY_TEXT_MAX_LENGTH - SUMMARY_TEXT_ELLIPSIS.len())
+                .collect::<String>();
+            format!("{}{}", truncated.trim_end(), SUMMARY_TEXT_ELLIPSIS)
+        } else {
+            collapsed
+        }
     }
 
-    fn eula(&self, manifest: &Value) -> Result<Eula> {
-        if let Some(ref path) = self.eula.clone().map(PathBuf::from) {
-            Eula::new(Some(path), manifest)
+    fn keywords(&self, manifest: &Value) -> String {
+        if let Some(ref keywords) = self.keywords {
+            keywords.join(", ")
+        } else if let Some(pkg_keywords) = meta_array(manifest, "keywords")
+            .or_else(|| {
+                manifest
+                    .get("package")
+                    .and_then(|p| p.as_table())
+                    .and_then(|t| t.get("keywords"))
+                    .and_then(|k| k.as_array())
+            })
+            .map(|a| a.iter().filter_map(|k| k.as_str()).collect::<Vec<&str>>())
+            .filter(|k| !k.is_empty())
+        {
+            trace!("Using the keywords from the package's manifest");
+            pkg_keywords.join(", ")
         } else {
-            Eula::new(
-                self.license
-                    .clone()
-                    .map(PathBuf::from)
-                    .filter(|p| p.extension().and_then(|p| p.to_str()) == Some(RTF_FILE_EXTENSION))
-                    .as_ref(),
-                manifest,
-            )
+            String::from("Installer")
         }
     }
 
-    fn license_name(&self, manifest: &Value) -> Option<String> {
-        if let Some(ref l) = self.license.clone().map(PathBuf::from) {
-            l.file_name().and_then(|f| f.to_str()).map(String::from)
+    fn language(&self, manifest: &Value) -> Result<u16> {
+        let culture = if let Some(pkg_meta_wix_culture) = meta_str(manifest, "culture") {
+            Cultures::from_str(pkg_meta_wix_culture)?
         } else {
-            manifest
-                .get("package")
-                .and_then(|p| p.as_table())
-                .and_then(|t| {
-                    t.get("license")
-                        .filter(|l| {
-                            if let Some(s) = l.as_str() {
-                                Template::license_ids().contains(&s.to_owned())
-                            } else {
-                                false
-                            }
-                        })
-                        .map(|_| String::from(LICENSE_FILE_NAME))
-                        .or_else(|| {
-                            t.get("license-file")
-                                .and_then(|l| l.as_str())
-                                .and_then(|l| {
-                                    Path::new(l)
-                                        .file_name()
+            Cultures::EnUs
+        };
+        if let Some(language) = self.language {
+            if language != culture.lcid() {
+                warn!(
+                    "The '{}' product language does not match the '{}' LCID of the '{}' \
+                     culture. The user interface of the installer will use a different \
+                     language than the product.",
+                    language,
+                    culture.lcid(),
+                    culture
+                );
+            }
+            Ok(language)
+        } else {
+            Ok(culture.lcid())
+        }
+    }
+
+    fn binaries(&self, manifest: &Value) -> Result<Vec<HashMap<&'static str, String>>> {
+        // The default path is resolved from the package's root, which is the
+        // base path used by the linker when creating the installer, if the
+        // path to a package's manifest (Cargo.toml) is specified as the input.
+        let package_root = match self.input {
+            Some(_) => Some(package_root(self.input.as_ref())?),
+            None => None,
+        };
+        let binaries = binaries(self.binaries.as_ref(), manifest, |name| {
+            let path = PathBuf::from(Self::binary_path(name, self.profile_var()));
+            match &package_root {
+                Some(root) => root.join(path),
+                None => path,
+            }
+        })?;
+        Ok(binaries
+            .into_iter()
+            .enumerate()
+            .map(|(index, binary)| {
+                let mut map = HashMap::with_capacity(3);
+                map.insert("binary-index", index.to_string());
+                map.insert("binary-name", binary.name);
+                map.insert(
+                    "binary-source",
+                    binary.source.to_string_lossy().into_owned(),
+                );
+                map
+            })
+            .collect())
+    }
+
+    fn binary_display_name<'b>(&'b self, binary_name: &'b str) -> &'b str {
+        self.binary_display_names
+            .as_ref()
+            .and_then(|d| d.get(binary_name))
+            .map(String::as_str)
+            .unwrap_or(binary_name)
+    }
+
+    fn symbols(binary_name: &str, binary_source: &str) -> (String, String) {
+        let mut name = PathBuf::from(binary_name.replace('-', "_"));
+        name.set_extension(PDB_FILE_EXTENSION);
+        let name = name.to_string_lossy().into_owned();
+        // The source is a path for the WiX Toolset, which always uses the
+        // Windows path separator, so the folder is not obtained with the
+        // platform-dependent `Path::parent` method.
+        let source = match binary_source.rfind(['\\', '/']) {
+            Some(index) => format!("{}{}", &binary_source[..=index], name),
+            None => name.clone(),
+        };
+        (name, source)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn default_binary_path(name: &str) -> String {
+        Self::binary_path(name, PROFILE_VARIABLE)
+    }
+
+    pub(crate) fn binary_path(name: &str, profile_var: &str) -> String {
+        let mut path = PathBuf::from("$(var.CargoTargetDir)")
+            .join(format!("$(var.{})", profile_var))
+            .join(name);
+        path.set_extension(EXE_FILE_EXTENSION);
+        path.to_str()
+            .map(String::from)
+            .expect("Path to string conversion")
+    }
+
+    fn profile_var(&self) -> &str {
+        self.profile_var.as_deref().unwrap_or(PROFILE_VARIABLE)
+    }
+
+    fn help_url(manifest: &Value) -> Option<String> {
+        meta_str(manifest, "help-url")
+            .or_else(|| {
+                manifest
+                    .get("package")
+                    .and_then(|p| p.as_table())
+                    .and_then(|t| {
+                        t.get("documentation")
+                            .or_else(|| t.get("homepage"))
+                            .or_else(|| t.get("repository"))
+                    })
+                    .and_then(|h| h.as_str())
+            })
+            .map(|s| {
+                trace!("Using '{}' for the help URL", s);
+                String::from(s)
+            })
+    }
+
+    fn eula(&self, manifest: &Value) -> Result<Eula> {
+        if let Some(ref path) = self.ui_license {
+            trace!("Using the license for the user interface as the EULA");
+            Ok(Eula::CommandLine(path.to_owned()))
+        } else if let Some(ref path) = self.eula.clone() {
+            Eula::new(Some(path), manifest)
+        } else if self.eula_markdown.is_some() {
+            trace!("Using the RTF file converted from the Markdown EULA");
+            Ok(Eula::CommandLine(self.generated_eula_path()))
+        } else if let Some(ref path) = self
+            .license
+            .clone()
+            .filter(|p| p.extension().and_then(|p| p.to_str()) == Some(RTF_FILE_EXTENSION))
+        {
+            Eula::new(Some(path), manifest)
+        } else if let Some(pkg_meta_wix_eula) = meta_path(manifest, "eula")? {
+            trace!("Using the EULA in the package's metadata");
+            Eula::from_metadata(pkg_meta_wix_eula)
+        } else {
+            Eula::from_manifest(manifest)
+        }
+    }
+
+    fn license_name(&self, manifest: &Value) -> Option<String> {
+        if let Some(ref l) = self.license {
+            l.file_name().and_then(|f| f.to_str()).map(String::from)
+        } else {
+            manifest
+                .get("package")
+                .and_then(|p| p.as_table())
+                .and_then(|t| {
+                    t.get("license")
+                        .and_then(|l| l.as_str())
+                        .and_then(Template::from_license_expression)
+                        .map(|_| String::from(LICENSE_FILE_NAME))
+                        .or_else(|| {
+                            t.get("license-file")
+                                .and_then(|l| l.as_str())
+                                .and_then(|l| {
+                                    Path::new(l)
+                                        .file_name()
                                         .and_then(|f| f.to_str())
                                         .map(String::from)
                                 })
@@ -504,21 +1634,16 @@ impl Execution {
     }
 
     fn license_source(&self, manifest: &Value) -> Result<Option<String>> {
-        if let Some(ref path) = self.license.clone().map(PathBuf::from) {
+        if let Some(ref path) = self.license {
             Ok(path.to_str().map(String::from))
         } else {
             Ok(manifest.get("package")
                 .and_then(|p| p.as_table())
                .and_then(|t| {
                    t.get("license")
-                       .filter(|l| {
-                           if let Some(s) = l.as_str() {
-                               Template::license_ids().contains(&s.to_string())
-                           } else {
-                               false
-                           }
-                       })
-                       .map(|_| LICENSE_FILE_NAME.to_owned() + "." + RTF_FILE_EXTENSION)
+                       .and_then(|l| l.as_str())
+                       .and_then(Template::from_license_expression)
+                       .map(|_| self.generated_license_path().display().to_string())
                        .or_else(|| {
                             t.get("license-file")
                             .and_then(|l| l.as_str())
@@ -538,11 +1663,258 @@ impl Execution {
         }
     }
 
+    fn validate_directory_name(name: &str, kind: &str) -> Result<()> {
+        const ILLEGAL_CHARACTERS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+        if name.trim().is_empty() {
+            Err(Error::Generic(format!(
+                "The {} folder name cannot be empty.",
+                kind
+            )))
+        } else if name
+            .chars()
+            .any(|c| ILLEGAL_CHARACTERS.contains(&c) || c.is_control())
+        {
+            Err(Error::Generic(format!(
+                "The '{}' {} folder name contains a path separator or a character that is not \
+                 allowed in a folder name. Please use a single folder name without any of the \
+                 following characters: < > : \" / \\ | ? *",
+                name, kind
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validates that each property has a unique identifier for a public
+    /// property, i.e. `INSTALLMODE`, that is not already defined by the
+    /// template.
+    fn validate_properties(properties: &[(String, String)]) -> Result<()> {
+        let mut ids = HashSet::new();
+        for (id, _) in properties {
+            let mut chars = id.chars();
+            let valid = matches!(chars.next(), Some(c) if c.is_ascii_uppercase() || c == '_')
+                && chars
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '.');
+            if !valid {
+                return Err(Error::InvalidProperty {
+                    id: id.to_owned(),
+                    reason: "identifier is not valid. Please use only uppercase letters, \
+                             digits, underscores (_), and periods (.), starting with a letter \
+                             or an underscore.",
+                });
+            }
+            if TEMPLATE_PROPERTIES.contains(&id.as_str()) {
+                return Err(Error::InvalidProperty {
+                    id: id.to_owned(),
+                    reason: "is already defined by the WXS template.",
+                });
+            }
+            if !ids.insert(id.as_str()) {
+                return Err(Error::InvalidProperty {
+                    id: id.to_owned(),
+                    reason: "is set more than once.",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn platform(&self) -> Result<Option<Platform>> {
+        self.platform
+            .as_ref()
+            .map(|p| Platform::from_str(p))
+            .transpose()
+    }
+
+    fn guid(guid: Option<&String>, name: &str) -> Result<String> {
+        if let Some(guid) = guid {
+            Uuid::parse_str(guid.trim_start_matches('{').trim_end_matches('}'))
+                .map(|u| u.to_hyphenated().to_string().to_uppercase())
+                .map_err(|err| {
+                    Error::Generic(format!(
+                        "The '{}' {} is not a valid GUID: {}",
+                        guid, name, err
+                    ))
+                })
+        } else {
+            Ok(Uuid::new_v4().to_hyphenated().to_string().to_uppercase())
+        }
+    }
+
+    fn generated_license(&self, eula: &Eula, manifest: &Value) -> Option<Template> {
+        match *eula {
+            Eula::Generate(template) => Some(template),
+            _ if self.license.is_none() => manifest
+                .get("package")
+                .and_then(|p| p.as_table())
+                .and_then(|t| t.get("license"))
+                .and_then(|l| l.as_str())
+                .and_then(Template::from_license_expression),
+            _ => None,
+        }
+    }
+
+    fn generated_license_path(&self) -> PathBuf {
+        self.generated_rtf_path(LICENSE_FILE_NAME)
+    }
+
+    fn generated_eula_path(&self) -> PathBuf {
+        self.generated_rtf_path(EULA_FILE_NAME)
+    }
+
+    fn generated_rtf_path(&self, name: &str) -> PathBuf {
+        let mut path = match (&self.output, &self.output_dir) {
+            (Some(output), _) => output
+                .parent()
+                .map(|p| p.join(name))
+                .unwrap_or_else(|| PathBuf::from(name)),
+            (None, Some(output_dir)) => output_dir.join(WIX).join(name),
+            (None, None) => PathBuf::from(name),
+        };
+        path.set_extension(RTF_FILE_EXTENSION);
+        path
+    }
+
+    fn markdown_eula(markdown: &Path) -> Result<String> {
+        fs::read_to_string(markdown)
+            .map(|m| super::rtf::from_markdown(&m))
+            .map_err(|err| {
+                Error::Generic(format!(
+                    "The '{}' Markdown file for the EULA could not be read: {}",
+                    markdown.display(),
+                    err
+                ))
+            })
+    }
+
+    fn template(&self) -> Result<mustache::Template> {
+        if let Some(ref template_file) = self.template_file {
+            trace!("Using the template file");
+            let source = fs::read_to_string(template_file).map_err(|err| {
+                Error::Generic(format!(
+                    "The '{}' template file could not be read: {}",
+                    template_file.display(),
+                    err
+                ))
+            })?;
+            for variable in Self::unknown_template_variables(&source) {
+                warn!(
+                    "The '{}' variable in the '{}' template file is not a known template \
+                     variable and it will be rendered as blank text. Use one of the \
+                     following variables: {}.",
+                    variable,
+                    template_file.display(),
+                    TEMPLATE_VARIABLES.join(", ")
+                );
+            }
+            mustache::compile_str(&source).map_err(|err| {
+                Error::Generic(format!(
+                    "The '{}' template file is not a valid mustache template: {}",
+                    template_file.display(),
+                    err
+                ))
+            })
+        } else {
+            trace!("Using the embedded template");
+            mustache::compile_str(Template::Wxs.to_str()).map_err(Error::from)
+        }
+    }
+
+    // Comments (`{{!...}}`), partials (`{{>...}}`), delimiter changes
+    // (`{{=...=}}`), and the implicit iterator (`{{.}}`) are not variables.
+    fn unknown_template_variables(source: &str) -> Vec<String> {
+        let mut unknown = Vec::new();
+        let mut rest = source;
+        while let Some(start) = rest.find("{{") {
+            rest = &rest[start + 2..];
+            let end = match rest.find("}}") {
+                Some(e) => e,
+                None => break,
+            };
+            let tag = rest[..end].trim_start_matches('{').trim();
+            rest = &rest[end + 2..];
+            if tag.starts_with(['!', '>', '=']) {
+                continue;
+            }
+            let name = tag.trim_start_matches(['#', '^', '/', '&']).trim();
+            if name != "."
+                && !TEMPLATE_VARIABLES.contains(&name)
+                && !unknown.iter().any(|u| u == name)
+            {
+                unknown.push(name.to_owned());
+            }
+        }
+        unknown
+    }
+
+    fn output_path(&self) -> Result<Option<PathBuf>> {
+        if let Some(ref output) = self.output {
+            trace!("Using the explicitly specified output path");
+            Ok(Some(output.to_owned()))
+        } else if let Some(ref output_dir) = self.output_dir {
+            trace!("Using the output directory for the output path");
+            let wix_dir = output_dir.join(WIX);
+            if !wix_dir.exists() {
+                info!("Creating the '{}' directory", wix_dir.display());
+                fs::create_dir_all(&wix_dir).map_err(|err| {
+                    Error::Generic(format!(
+                        "The '{}' directory could not be created: {}",
+                        wix_dir.display(),
+                        err
+                    ))
+                })?;
+            }
+            let mut path = wix_dir.join(WIX_SOURCE_FILE_NAME);
+            path.set_extension(WIX_SOURCE_FILE_EXTENSION);
+            Ok(Some(path))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn validate_product_icon(icon: &Path) -> Result<()> {
+        if !matches!(
+            icon.extension().and_then(|e| e.to_str()),
+            Some(e) if e.eq_ignore_ascii_case(ICO_FILE_EXTENSION)
+        ) {
+            return Err(Error::Generic(format!(
+                "The '{}' product icon does not have the '.{}' file extension",
+                icon.display(),
+                ICO_FILE_EXTENSION
+            )));
+        }
+        let mut header = [0; ICO_HEADER_LENGTH + ICO_ENTRY_LENGTH];
+        let header_length = File::open(icon)?.read(&mut header)?;
+        if header_length < ICO_HEADER_LENGTH || header[..ICO_MAGIC.len()] != ICO_MAGIC {
+            return Err(Error::Generic(format!(
+                "The '{}' product icon is not a valid ICO file",
+                icon.display()
+            )));
+        }
+        let image_count = u16::from_le_bytes([header[4], header[5]]);
+        if image_count == 1 && header_length == header.len() {
+            // A zero (0) width or height in the directory entry means 256 pixels.
+            let width = header[ICO_HEADER_LENGTH];
+            let height = header[ICO_HEADER_LENGTH + 1];
+            if width != 0 && height != 0 && (width < 32 || height < 32) {
+                warn!(
+                    "The '{}' product icon only contains a single {}x{} image. The icon \
+                     may appear blurry in the Add/Remove Programs (ARP) control panel. \
+                     An icon with a 32x32 or larger image is recommended.",
+                    icon.display(),
+                    width,
+                    height
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn manufacturer(&self, manifest: &Value) -> Result<String> {
         if let Some(ref m) = self.manufacturer {
             Ok(m.to_owned())
         } else {
-            super::first_author(&manifest)
+            super::manufacturer(manifest)
         }
     }
 }
@@ -553,6 +1925,313 @@ impl Default for Execution {
     }
 }
 
+/// A binary that is installed by the WiX Source (wxs) file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryInfo {
+    index: usize,
+    name: String,
+    source: String,
+}
+
+impl BinaryInfo {
+    /// Gets the zero-based position of the binary in the installer.
+    ///
+    /// This is the value of the `binary-index` template variable.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets the name of the binary without the file extension.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the source path of the binary used by the WiX Toolset compiler.
+    ///
+    /// The path may contain WiX preprocessor variables, i.e.
+    /// `$(var.CargoTargetDir)\$(var.Profile)\example.exe`.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// A file association for the installed binary.
+///
+/// Opening a file with the extension from the Windows Explorer runs the first
+/// binary in the installer with the path to the file as its only argument.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileAssoc {
+    extension: String,
+    prog_id: String,
+    description: String,
+}
+
+impl FileAssoc {
+    /// Creates a new `FileAssoc` instance.
+    ///
+    /// The `extension` is the file extension with or without the leading dot,
+    /// i.e. `ext` or `.ext`. The `prog_id` is the programmatic identifier
+    /// (ProgId) for the file type, i.e. `Example.Document`. The `description`
+    /// is the name of the file type displayed in the Windows Explorer.
+    pub fn new(extension: &str, prog_id: &str, description: &str) -> Self {
+        FileAssoc {
+            extension: extension.to_owned(),
+            prog_id: prog_id.to_owned(),
+            description: description.to_owned(),
+        }
+    }
+
+    /// Gets the file extension without the leading dot.
+    pub fn extension(&self) -> &str {
+        self.extension.trim_start_matches('.')
+    }
+
+    /// Gets the programmatic identifier (ProgId).
+    pub fn prog_id(&self) -> &str {
+        &self.prog_id
+    }
+
+    /// Gets the description of the file type.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.extension().is_empty()
+            || !self
+                .extension()
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            Err(Error::Generic(format!(
+                "The '{}' file association extension is not valid. Please use an extension \
+                 with only letters, numbers, underscores, or hyphens.",
+                self.extension
+            )))
+        } else if self.prog_id.trim().is_empty() || self.prog_id.chars().any(char::is_whitespace) {
+            Err(Error::Generic(format!(
+                "The '{}' file association ProgId is not valid. Please use a ProgId without \
+                 any whitespace, such as 'Example.Document'.",
+                self.prog_id
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A value written to the Windows registry by the installer.
+///
+/// All of the values are added to a single component, so they are written
+/// when the package is installed and removed when it is uninstalled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegEntry {
+    root: String,
+    key: String,
+    name: String,
+    kind: String,
+    value: String,
+}
+
+impl RegEntry {
+    /// Creates a new `RegEntry` instance.
+    ///
+    /// The `root` is the abbreviated name of the registry hive, i.e. `HKLM`,
+    /// `HKCU`, `HKMU`, `HKCR`, or `HKU`. The `key` is the path to the
+    /// registry key within the hive, i.e. `Software\Example`. An empty `name`
+    /// sets the default value of the key. The `kind` is the WiX Toolset type
+    /// of the value, i.e. `string`, `integer`, `expandable`, `multiString`, or
+    /// `binary`.
+    pub fn new(root: &str, key: &str, name: &str, kind: &str, value: &str) -> Self {
+        RegEntry {
+            root: root.to_owned(),
+            key: key.to_owned(),
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    /// Gets the abbreviated name of the registry hive.
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Gets the path to the registry key within the hive.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Gets the name of the value, which is empty for the default value.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the WiX Toolset type of the value.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Gets the data of the value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !REGISTRY_ROOTS.contains(&self.root.as_str()) {
+            Err(Error::Generic(format!(
+                "The '{}' registry root is not valid. Please use one of the following: {}.",
+                self.root,
+                REGISTRY_ROOTS.join(", ")
+            )))
+        } else if self.key.trim().is_empty() {
+            Err(Error::Generic(String::from(
+                "The registry key is empty. Please use a path to a key, such as \
+                 'Software\\Example'.",
+            )))
+        } else if !REGISTRY_TYPES.contains(&self.kind.as_str()) {
+            Err(Error::Generic(format!(
+                "The '{}' registry value type is not valid. Please use one of the \
+                 following: {}.",
+                self.kind,
+                REGISTRY_TYPES.join(", ")
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The compression levels of the cabinet embedded in the installer.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Compression {
+    /// No compression, which is the fastest but produces the largest
+    /// installer.
+    None,
+    /// Low compression.
+    Low,
+    /// Medium compression.
+    Medium,
+    /// High compression, which is the slowest but produces the smallest
+    /// installer.
+    #[default]
+    High,
+    /// The Microsoft ZIP (MSZIP) compression algorithm.
+    Mszip,
+}
+
+impl Compression {
+    /// Gets the possible string representations of each variant.
+    pub fn possible_values() -> Vec<&'static str> {
+        vec![
+            "none", "None", "low", "Low", "medium", "Medium", "high", "High", "mszip", "Mszip",
+        ]
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Compression::None => write!(f, "none"),
+            Compression::Low => write!(f, "low"),
+            Compression::Medium => write!(f, "medium"),
+            Compression::High => write!(f, "high"),
+            Compression::Mszip => write!(f, "mszip"),
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(Compression::None),
+            "low" => Ok(Compression::Low),
+            "medium" => Ok(Compression::Medium),
+            "high" => Ok(Compression::High),
+            "mszip" => Ok(Compression::Mszip),
+            c => Err(Error::Generic(format!("Unknown '{}' compression level", c))),
+        }
+    }
+}
+
+/// The dialog sets of the WixUI extension for the installer's user interface.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum UiSet {
+    /// The `WixUI_FeatureTree` dialog set, which includes a dialog for
+    /// selecting the features to install.
+    #[default]
+    FeatureTree,
+    /// The `WixUI_InstallDir` dialog set, which includes a dialog for
+    /// changing the installation folder.
+    InstallDir,
+    /// The `WixUI_Minimal` dialog set, which is a single dialog with a license
+    /// agreement.
+    Minimal,
+    /// The `WixUI_Mondo` dialog set, which includes a dialog for selecting a
+    /// typical, custom, or complete installation.
+    Mondo,
+    /// No dialogs, i.e. only the progress of the installation is displayed.
+    None,
+}
+
+impl UiSet {
+    /// Gets the possible string representations of each variant.
+    pub fn possible_values() -> Vec<&'static str> {
+        vec![
+            "featuretree",
+            "FeatureTree",
+            "installdir",
+            "InstallDir",
+            "minimal",
+            "Minimal",
+            "mondo",
+            "Mondo",
+            "none",
+            "None",
+        ]
+    }
+
+    // The dialog that follows the license agreement dialog, which is
+    // displayed after the welcome dialog instead if there is not an EULA.
+    fn next_dialog(self) -> Option<&'static str> {
+        match self {
+            UiSet::FeatureTree => Some("CustomizeDlg"),
+            UiSet::InstallDir => Some("InstallDirDlg"),
+            UiSet::Mondo => Some("SetupTypeDlg"),
+            UiSet::Minimal | UiSet::None => None,
+        }
+    }
+}
+
+impl fmt::Display for UiSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UiSet::FeatureTree => write!(f, "WixUI_FeatureTree"),
+            UiSet::InstallDir => write!(f, "WixUI_InstallDir"),
+            UiSet::Minimal => write!(f, "WixUI_Minimal"),
+            UiSet::Mondo => write!(f, "WixUI_Mondo"),
+            UiSet::None => write!(f, "None"),
+        }
+    }
+}
+
+impl FromStr for UiSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().trim_start_matches("wixui_") {
+            "featuretree" => Ok(UiSet::FeatureTree),
+            "installdir" => Ok(UiSet::InstallDir),
+            "minimal" => Ok(UiSet::Minimal),
+            "mondo" => Ok(UiSet::Mondo),
+            "none" => Ok(UiSet::None),
+            u => Err(Error::Generic(format!("Unknown '{}' dialog set", u))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -568,6 +2247,21 @@ mod tests {
             assert_eq!(actual.banner, Some(EXPECTED));
         }
 
+        #[test]
+        fn allow_downgrades_works() {
+            let mut actual = Builder::new();
+            actual.allow_downgrades(true);
+            assert!(actual.allow_downgrades);
+        }
+
+        #[test]
+        fn downgrade_message_works() {
+            const EXPECTED: &str = "Please uninstall [ProductName] first.";
+            let mut actual = Builder::new();
+            actual.downgrade_message(Some(EXPECTED));
+            assert_eq!(actual.downgrade_message, Some(EXPECTED));
+        }
+
         #[test]
         fn binaries_name_works() {
             const EXPECTED: &str = "bin\\Example.exe";
@@ -576,6 +2270,14 @@ mod tests {
             assert_eq!(actual.binaries, Some(vec![EXPECTED]));
         }
 
+        #[test]
+        fn binary_display_names_works() {
+            let expected = hashmap! { "tool" => "Example Tool" };
+            let mut actual = Builder::new();
+            actual.binary_display_names(Some(expected.clone()));
+            assert_eq!(actual.binary_display_names, Some(expected));
+        }
+
         #[test]
         fn description_works() {
             const EXPECTED: &str = "This is a description.";
@@ -601,69 +2303,281 @@ mod tests {
         }
 
         #[test]
-        fn help_url_works() {
-            const EXPECTED: &str = "http://www.example.com";
+        fn comments_works() {
+            const EXPECTED: &str = "Example comments";
             let mut actual = Builder::new();
-            actual.help_url(Some(EXPECTED));
-            assert_eq!(actual.help_url, Some(EXPECTED));
+            actual.comments(Some(EXPECTED));
+            assert_eq!(actual.comments, Some(EXPECTED));
         }
 
         #[test]
-        fn input_works() {
-            const EXPECTED: &str = "C:\\example\\Cargo.toml";
+        fn keywords_works() {
+            let expected = vec!["cli", "tool"];
             let mut actual = Builder::new();
-            actual.input(Some(EXPECTED));
-            assert_eq!(actual.input, Some(EXPECTED));
+            actual.keywords(Some(expected.clone()));
+            assert_eq!(actual.keywords, Some(expected));
         }
 
         #[test]
-        fn license_works() {
-            const EXPECTED: &str = "C:\\example\\Example License.rtf";
+        fn language_works() {
+            const EXPECTED: u16 = 1036;
             let mut actual = Builder::new();
-            actual.license(Some(EXPECTED));
-            assert_eq!(actual.license, Some(EXPECTED));
+            actual.language(Some(EXPECTED));
+            assert_eq!(actual.language, Some(EXPECTED));
         }
 
         #[test]
-        fn manufacturer_works() {
-            const EXPECTED: &str = "Example";
+        fn company_dir_works() {
+            const EXPECTED: &str = "Example Company";
             let mut actual = Builder::new();
-            actual.manufacturer(Some(EXPECTED));
-            assert_eq!(actual.manufacturer, Some(EXPECTED));
+            actual.company_dir(Some(EXPECTED));
+            assert_eq!(actual.company_dir, Some(EXPECTED));
         }
 
         #[test]
-        fn output_works() {
-            const EXPECTED: &str = "C:\\example\\output";
+        fn install_dir_works() {
+            const EXPECTED: &str = "Example Application";
             let mut actual = Builder::new();
-            actual.output(Some(EXPECTED));
-            assert_eq!(actual.output, Some(EXPECTED));
+            actual.install_dir(Some(EXPECTED));
+            assert_eq!(actual.install_dir, Some(EXPECTED));
         }
 
         #[test]
-        fn product_icon_works() {
-            const EXPECTED: &str = "img\\Product.ico";
+        fn install_license_works() {
             let mut actual = Builder::new();
-            actual.product_icon(Some(EXPECTED));
-            assert_eq!(actual.product_icon, Some(EXPECTED));
+            actual.install_license(false);
+            assert!(!actual.install_license);
         }
 
         #[test]
-        fn product_name_works() {
-            const EXPECTED: &str = "Example Product Name";
+        fn install_readme_works() {
+            const EXPECTED: &str = "README.txt";
             let mut actual = Builder::new();
-            actual.product_name(Some(EXPECTED));
-            assert_eq!(actual.product_name, Some(EXPECTED));
+            actual.install_readme(Some(EXPECTED));
+            assert_eq!(actual.install_readme, Some(EXPECTED));
         }
-    }
 
-    mod execution {
-        extern crate assert_fs;
-
-        use super::*;
-        use std::fs::File;
+        #[test]
+        fn eula_markdown_works() {
+            const EXPECTED: &str = "LICENSE.md";
+            let mut actual = Builder::new();
+            actual.eula_markdown(Some(EXPECTED));
+            assert_eq!(actual.eula_markdown, Some(EXPECTED));
+        }
 
-        const MIN_MANIFEST: &str = r#"[package]
+        #[test]
+        fn file_association_works() {
+            let expected = FileAssoc::new("ext", "Example.Document", "Example Document");
+            let mut actual = Builder::new();
+            actual.file_association(Some(expected.clone()));
+            assert_eq!(actual.file_association, Some(expected));
+        }
+
+        #[test]
+        fn include_symbols_works() {
+            let mut actual = Builder::new();
+            actual.include_symbols(true);
+            assert!(actual.include_symbols);
+        }
+
+        #[test]
+        fn help_url_works() {
+            const EXPECTED: &str = "http://www.example.com";
+            let mut actual = Builder::new();
+            actual.help_url(Some(EXPECTED));
+            assert_eq!(actual.help_url, Some(EXPECTED));
+        }
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\example\\Cargo.toml";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn log_level_works() {
+            let mut actual = Builder::new();
+            actual.log_level(LevelFilter::Info);
+            assert_eq!(actual.log_level, Some(LevelFilter::Info));
+        }
+
+        #[test]
+        fn license_works() {
+            const EXPECTED: &str = "C:\\example\\Example License.rtf";
+            let mut actual = Builder::new();
+            actual.license(Some(EXPECTED));
+            assert_eq!(actual.license, Some(EXPECTED));
+        }
+
+        #[test]
+        fn manufacturer_works() {
+            const EXPECTED: &str = "Example";
+            let mut actual = Builder::new();
+            actual.manufacturer(Some(EXPECTED));
+            assert_eq!(actual.manufacturer, Some(EXPECTED));
+        }
+
+        #[test]
+        fn output_works() {
+            const EXPECTED: &str = "C:\\example\\output";
+            let mut actual = Builder::new();
+            actual.output(Some(EXPECTED));
+            assert_eq!(actual.output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn path_component_guid_works() {
+            const EXPECTED: &str = "0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C";
+            let mut actual = Builder::new();
+            actual.path_component_guid(Some(EXPECTED));
+            assert_eq!(actual.path_component_guid, Some(EXPECTED));
+        }
+
+        #[test]
+        fn ui_license_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\wix\\Eula.rtf";
+            let mut actual = Builder::new();
+            actual.ui_license(Some(EXPECTED));
+            assert_eq!(actual.ui_license, Some(EXPECTED));
+        }
+
+        #[test]
+        fn uninstall_shortcut_works() {
+            let mut actual = Builder::new();
+            actual.uninstall_shortcut(true);
+            assert!(actual.uninstall_shortcut);
+        }
+
+        #[test]
+        fn per_binary_features_works() {
+            let mut actual = Builder::new();
+            actual.per_binary_features(true);
+            assert!(actual.per_binary_features);
+        }
+
+        #[test]
+        fn platform_works() {
+            const EXPECTED: &str = "x64";
+            let mut actual = Builder::new();
+            actual.platform(Some(EXPECTED));
+            assert_eq!(actual.platform, Some(EXPECTED));
+        }
+
+        #[test]
+        fn upgrade_code_works() {
+            const EXPECTED: &str = "0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C";
+            let mut actual = Builder::new();
+            actual.upgrade_code(Some(EXPECTED));
+            assert_eq!(actual.upgrade_code, Some(EXPECTED));
+        }
+
+        #[test]
+        fn template_file_works() {
+            const EXPECTED: &str = "wix\\main.wxs.mustache";
+            let mut actual = Builder::new();
+            actual.template_file(Some(EXPECTED));
+            assert_eq!(actual.template_file, Some(EXPECTED));
+        }
+
+        #[test]
+        fn compression_works() {
+            let mut actual = Builder::new();
+            actual.compression(Compression::Mszip);
+            assert_eq!(actual.compression, Compression::Mszip);
+        }
+
+        #[test]
+        fn ui_works() {
+            let mut actual = Builder::new();
+            actual.ui(UiSet::InstallDir);
+            assert_eq!(actual.ui, UiSet::InstallDir);
+        }
+
+        #[test]
+        fn properties_works() {
+            let mut actual = Builder::new();
+            actual.properties(vec![("INSTALLMODE", "per-machine")]);
+            assert_eq!(actual.properties, vec![("INSTALLMODE", "per-machine")]);
+        }
+
+        #[test]
+        fn registry_works() {
+            let expected = vec![RegEntry::new(
+                "HKLM",
+                "Software\\Example",
+                "Version",
+                "string",
+                "1.0.0",
+            )];
+            let mut actual = Builder::new();
+            actual.registry(expected.clone());
+            assert_eq!(actual.registry, expected);
+        }
+
+        #[test]
+        fn skip_license_file_works() {
+            let mut actual = Builder::new();
+            actual.skip_license_file(true);
+            assert!(actual.skip_license_file);
+        }
+
+        #[test]
+        fn stamp_works() {
+            let mut actual = Builder::new();
+            actual.stamp(true);
+            assert!(actual.stamp);
+        }
+
+        #[test]
+        fn strict_metadata_works() {
+            let mut actual = Builder::new();
+            actual.strict_metadata(true);
+            assert!(actual.strict_metadata);
+        }
+
+        #[test]
+        fn output_dir_works() {
+            const EXPECTED: &str = "C:\\example\\output";
+            let mut actual = Builder::new();
+            actual.output_dir(Some(EXPECTED));
+            assert_eq!(actual.output_dir, Some(EXPECTED));
+        }
+
+        #[test]
+        fn product_icon_works() {
+            const EXPECTED: &str = "img\\Product.ico";
+            let mut actual = Builder::new();
+            actual.product_icon(Some(EXPECTED));
+            assert_eq!(actual.product_icon, Some(EXPECTED));
+        }
+
+        #[test]
+        fn product_name_works() {
+            const EXPECTED: &str = "Example Product Name";
+            let mut actual = Builder::new();
+            actual.product_name(Some(EXPECTED));
+            assert_eq!(actual.product_name, Some(EXPECTED));
+        }
+
+        #[test]
+        fn profile_var_works() {
+            const EXPECTED: &str = "BuildConfig";
+            let mut actual = Builder::new();
+            actual.profile_var(Some(EXPECTED));
+            assert_eq!(actual.profile_var, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        extern crate assert_fs;
+
+        use super::*;
+        use chrono::TimeZone;
+        use std::io::Write;
+
+        const MIN_MANIFEST: &str = r#"[package]
             name = "Example"
             version = "0.1.0"
             authors = ["First Last <first.last@example.com>"]
@@ -690,6 +2604,62 @@ mod tests {
             license = "Apache-2.0"
         "#;
 
+        const BSD2_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "BSD-2-Clause"
+        "#;
+
+        const BSD3_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "BSD-3-Clause"
+        "#;
+
+        const ISC_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "ISC"
+        "#;
+
+        const MPL2_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "MPL-2.0"
+        "#;
+
+        const MIT_OR_APACHE2_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "MIT OR Apache-2.0"
+        "#;
+
+        const APACHE2_WITH_EXCEPTION_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "Apache-2.0 WITH LLVM-exception"
+        "#;
+
+        const GPL3_OR_LATER_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "GPL-3.0-or-later"
+        "#;
+
+        const GPL3_ONLY_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "GPL-3.0-only"
+        "#;
+
         const UNKNOWN_MANIFEST: &str = r#"[package]
             name = "Example"
             version = "0.1.0"
@@ -726,6 +2696,21 @@ mod tests {
             path = "src/binary2/main.rs"
         "#;
 
+        const DUPLICATE_BIN_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "MIT"
+
+            [[bin]]
+            name = "tool"
+            path = "src/tool/main.rs"
+
+            [[bin]]
+            name = "tool"
+            path = "src/bin/tool.rs"
+        "#;
+
         const DOCUMENTATION_MANIFEST: &str = r#"[package]
             name = "Example"
             version = "0.1.0"
@@ -777,6 +2762,46 @@ mod tests {
             assert_eq!(actual, String::from(LICENSE_FILE_NAME));
         }
 
+        #[test]
+        fn license_name_with_mpl2_license_field_works() {
+            let manifest = MPL2_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default()
+                .license_name(&manifest)
+                .expect("License name");
+            assert_eq!(actual, String::from(LICENSE_FILE_NAME));
+        }
+
+        #[test]
+        fn license_name_with_mit_or_apache2_license_field_works() {
+            let manifest = MIT_OR_APACHE2_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default()
+                .license_name(&manifest)
+                .expect("License name");
+            assert_eq!(actual, String::from(LICENSE_FILE_NAME));
+        }
+
+        #[test]
+        fn license_name_with_gpl3_only_license_field_works() {
+            let manifest = GPL3_ONLY_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default()
+                .license_name(&manifest)
+                .expect("License name");
+            assert_eq!(actual, String::from(LICENSE_FILE_NAME));
+        }
+
+        #[test]
+        fn license_name_with_gpl3_or_later_license_field_works() {
+            let manifest = GPL3_OR_LATER_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default()
+                .license_name(&manifest)
+                .expect("License name");
+            assert_eq!(actual, String::from(LICENSE_FILE_NAME));
+        }
+
         #[test]
         fn license_name_with_unknown_license_field_works() {
             let manifest = UNKNOWN_MANIFEST.parse::<Value>().expect("Parsing TOML");
@@ -821,38 +2846,1362 @@ mod tests {
         }
 
         #[test]
-        fn license_source_with_unknown_license_field_works() {
-            let manifest = UNKNOWN_MANIFEST.parse::<Value>().expect("Parsing TOML");
-            let actual = Execution::default().license_source(&manifest).unwrap();
-            assert!(actual.is_none());
+        fn license_source_with_mit_or_apache2_license_field_works() {
+            let manifest = MIT_OR_APACHE2_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default()
+                .license_source(&manifest)
+                .expect("License source");
+            assert_eq!(
+                actual,
+                Some(LICENSE_FILE_NAME.to_owned() + "." + RTF_FILE_EXTENSION)
+            );
+        }
+
+        #[test]
+        fn license_source_with_apache2_with_exception_license_field_works() {
+            let manifest = APACHE2_WITH_EXCEPTION_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default()
+                .license_source(&manifest)
+                .expect("License source");
+            assert_eq!(
+                actual,
+                Some(LICENSE_FILE_NAME.to_owned() + "." + RTF_FILE_EXTENSION)
+            );
+        }
+
+        #[test]
+        fn license_source_with_gpl3_or_later_license_field_works() {
+            let manifest = GPL3_OR_LATER_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default()
+                .license_source(&manifest)
+                .expect("License source");
+            assert_eq!(
+                actual,
+                Some(LICENSE_FILE_NAME.to_owned() + "." + RTF_FILE_EXTENSION)
+            );
+        }
+
+        #[test]
+        fn license_source_with_output_dir_works() {
+            let manifest = MIT_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .output_dir(Some("output"))
+                .build()
+                .license_source(&manifest)
+                .expect("License source");
+            let expected = PathBuf::from("output")
+                .join(WIX)
+                .join(LICENSE_FILE_NAME.to_owned() + "." + RTF_FILE_EXTENSION);
+            assert_eq!(actual, Some(expected.display().to_string()));
+        }
+
+        #[test]
+        fn license_source_with_output_and_output_dir_works() {
+            let manifest = MIT_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .output(Some("main.wxs"))
+                .output_dir(Some("output"))
+                .build()
+                .license_source(&manifest)
+                .expect("License source");
+            assert_eq!(
+                actual,
+                Some(LICENSE_FILE_NAME.to_owned() + "." + RTF_FILE_EXTENSION)
+            );
+        }
+
+        #[test]
+        fn guid_with_defaults_works() {
+            let actual = Execution::guid(None, "upgrade code").unwrap();
+            assert!(Uuid::parse_str(&actual).is_ok());
+            assert_eq!(actual, actual.to_uppercase());
+        }
+
+        #[test]
+        fn guid_with_lowercase_works() {
+            let guid = String::from("0e6c6c4f-8d4a-4b3a-9c3e-5d1e0f2a7b6c");
+            assert_eq!(
+                Execution::guid(Some(&guid), "upgrade code").unwrap(),
+                "0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C"
+            );
+        }
+
+        #[test]
+        fn guid_with_braces_works() {
+            let guid = String::from("{0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C}");
+            assert_eq!(
+                Execution::guid(Some(&guid), "upgrade code").unwrap(),
+                "0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C"
+            );
+        }
+
+        #[test]
+        fn guid_with_invalid_value_fails() {
+            let guid = String::from("not-a-guid");
+            match Execution::guid(Some(&guid), "upgrade code") {
+                Err(Error::Generic(..)) => {}
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_upgrade_code_and_path_component_guid_works() {
+            let wxs = render(|b| {
+                b.upgrade_code(Some("0e6c6c4f-8d4a-4b3a-9c3e-5d1e0f2a7b6c"))
+                    .path_component_guid(Some("{1F7D7D50-9E5B-4C4B-AD4F-6E2F103B8C7D}"))
+            })
+            .unwrap();
+            assert!(wxs.contains("UpgradeCode='0E6C6C4F-8D4A-4B3A-9C3E-5D1E0F2A7B6C'"));
+            assert!(wxs.contains("Guid='1F7D7D50-9E5B-4C4B-AD4F-6E2F103B8C7D'"));
+        }
+
+        /// Prints the WiX Source (wxs) file for the manifest to a file after
+        /// the builder is configured and returns the contents of the file.
+        fn render_manifest<F>(manifest: &str, configure: F) -> Result<String>
+        where
+            F: for<'b, 'c> FnOnce(&'b mut Builder<'c>) -> &'b mut Builder<'c>,
+        {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest_path = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest_path, manifest).unwrap();
+            let output = temp_dir.path().join("main.wxs");
+            let mut builder = Builder::default();
+            builder
+                .input(manifest_path.to_str())
+                .output(output.to_str());
+            configure(&mut builder).build().run()?;
+            Ok(fs::read_to_string(&output).unwrap())
+        }
+
+        /// Prints the WiX Source (wxs) file for the minimal manifest after
+        /// the builder is configured and returns the contents of the file.
+        fn render<F>(configure: F) -> Result<String>
+        where
+            F: for<'b, 'c> FnOnce(&'b mut Builder<'c>) -> &'b mut Builder<'c>,
+        {
+            render_manifest(MIN_MANIFEST, configure)
+        }
+
+        #[test]
+        fn run_with_default_ui_works() {
+            let wxs = render(|b| b.ui(UiSet::default())).unwrap();
+            assert!(wxs.contains("<UIRef Id='WixUI_FeatureTree'/>"));
+            assert!(wxs.contains(
+                "<Publish Dialog='WelcomeDlg' Control='Next' Event='NewDialog' \
+                 Value='CustomizeDlg' Order='99'>1</Publish>"
+            ));
+        }
+
+        #[test]
+        fn run_with_install_dir_ui_works() {
+            let wxs = render(|b| b.ui(UiSet::InstallDir)).unwrap();
+            assert!(wxs.contains("<UIRef Id='WixUI_InstallDir'/>"));
+            assert!(wxs.contains("<Property Id='WIXUI_INSTALLDIR' Value='APPLICATIONFOLDER'/>"));
+            assert!(wxs.contains(
+                "<Publish Dialog='InstallDirDlg' Control='Back' Event='NewDialog' \
+                 Value='WelcomeDlg' Order='99'>1</Publish>"
+            ));
+        }
+
+        #[test]
+        fn run_with_install_dir_ui_wires_install_dir_property() {
+            let wxs = render(|b| b.ui(UiSet::InstallDir)).unwrap();
+            let install_dir =
+                evaluate(&wxs, "//wix:UI/wix:Property[@Id='WIXUI_INSTALLDIR']/@Value");
+            assert_eq!(install_dir, "APPLICATIONFOLDER");
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    &format!("count(//wix:Directory[@Id='{}'])", install_dir)
+                ),
+                "1"
+            );
+            assert_eq!(
+                evaluate(&wxs, "//wix:Feature[@Id='Binaries']/@ConfigurableDirectory"),
+                install_dir
+            );
+        }
+
+        #[test]
+        fn run_with_feature_tree_ui_does_not_wire_install_dir_property() {
+            let wxs = render(|b| b.ui(UiSet::FeatureTree)).unwrap();
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:Property[@Id='WIXUI_INSTALLDIR'])"),
+                "0"
+            );
+        }
+
+        #[test]
+        fn run_with_minimal_ui_works() {
+            let wxs = render(|b| b.ui(UiSet::Minimal)).unwrap();
+            assert!(wxs.contains("<UIRef Id='WixUI_Minimal'/>"));
+            assert!(!wxs.contains("<Publish "));
+            assert!(!wxs.contains("WIXUI_INSTALLDIR"));
+        }
+
+        #[test]
+        fn run_with_mondo_ui_works() {
+            let wxs = render(|b| b.ui(UiSet::Mondo)).unwrap();
+            assert!(wxs.contains("<UIRef Id='WixUI_Mondo'/>"));
+            assert!(wxs.contains(
+                "<Publish Dialog='WelcomeDlg' Control='Next' Event='NewDialog' \
+                 Value='SetupTypeDlg' Order='99'>1</Publish>"
+            ));
+        }
+
+        #[test]
+        fn run_with_no_ui_works() {
+            let wxs = render(|b| b.ui(UiSet::None)).unwrap();
+            assert!(!wxs.lines().any(|l| l.trim() == "<UI>"));
+            assert!(!wxs.lines().any(|l| l.trim().starts_with("<UIRef ")));
+        }
+
+        #[test]
+        fn ui_set_from_str_works() {
+            assert_eq!(UiSet::from_str("InstallDir").unwrap(), UiSet::InstallDir);
+            assert_eq!(UiSet::from_str("mondo").unwrap(), UiSet::Mondo);
+            assert_eq!(UiSet::from_str("WixUI_Minimal").unwrap(), UiSet::Minimal);
+            assert_eq!(UiSet::from_str("none").unwrap(), UiSet::None);
+            assert!(UiSet::from_str("Advanced").is_err());
+        }
+
+        #[test]
+        fn run_with_compression_works() {
+            for (compression, expected) in &[
+                (Compression::None, "none"),
+                (Compression::Low, "low"),
+                (Compression::Medium, "medium"),
+                (Compression::High, "high"),
+                (Compression::Mszip, "mszip"),
+            ] {
+                let wxs = render(|b| b.compression(*compression)).unwrap();
+                assert!(wxs.contains(&format!(
+                    "<Media Id='1' Cabinet='media1.cab' EmbedCab='yes' CompressionLevel='{}' \
+                     DiskPrompt='CD-ROM #1'/>",
+                    expected
+                )));
+            }
+        }
+
+        #[test]
+        fn run_with_default_compression_works() {
+            let wxs = render(|b| b).unwrap();
+            assert!(wxs.contains("CompressionLevel='high'"));
+        }
+
+        #[test]
+        fn compression_from_str_works() {
+            assert_eq!(Compression::from_str("MSZIP").unwrap(), Compression::Mszip);
+            assert_eq!(Compression::from_str("none").unwrap(), Compression::None);
+            assert_eq!(
+                Compression::from_str("Medium").unwrap(),
+                Compression::Medium
+            );
+            assert!(Compression::from_str("ultra").is_err());
+        }
+
+        #[test]
+        fn run_with_x64_platform_works() {
+            let wxs = render(|b| b.platform(Some("x64"))).unwrap();
+            assert!(wxs.contains("<?define Win64 = \"yes\" ?>"));
+            assert!(
+                wxs.contains("<?define PlatformProgramFilesFolder = \"ProgramFiles64Folder\" ?>")
+            );
+            assert!(!wxs.contains("ProgramFilesFolder\""));
+            assert!(!wxs.contains("<?if $(var.Platform) = x64 ?>"));
+        }
+
+        #[test]
+        fn run_with_x86_platform_works() {
+            let wxs = render(|b| b.platform(Some("i686"))).unwrap();
+            assert!(wxs.contains("<?define Win64 = \"no\" ?>"));
+            assert!(wxs.contains("<?define PlatformProgramFilesFolder = \"ProgramFilesFolder\" ?>"));
+            assert!(!wxs.contains("ProgramFiles64Folder"));
+            assert!(!wxs.contains("<?if $(var.Platform) = x64 ?>"));
+        }
+
+        #[test]
+        fn run_with_default_platform_works() {
+            let wxs = render(|b| b.platform(None)).unwrap();
+            assert!(wxs.contains("<?if $(var.Platform) = x64 ?>"));
+            assert_ne!(
+                render(|b| b.platform(Some("x64"))).unwrap(),
+                render(|b| b.platform(Some("x86"))).unwrap()
+            );
+        }
+
+        const KEYWORDS_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            description = "An example package"
+            keywords = ["cli", "example", "tool"]
+        "#;
+
+        #[test]
+        fn summary_text_works() {
+            assert_eq!(
+                Execution::summary_text("An example package", "description"),
+                String::from("An example package")
+            );
+        }
+
+        #[test]
+        fn summary_text_with_whitespace_works() {
+            assert_eq!(
+                Execution::summary_text(
+                    "  An example\n  package\r\n\tfor   testing. ",
+                    "description"
+                ),
+                String::from("An example package for testing.")
+            );
+        }
+
+        #[test]
+        fn summary_text_with_long_text_works() {
+            let text = "word ".repeat(100);
+            let actual = Execution::summary_text(&text, "description");
+            assert_eq!(actual.chars().count(), SUMMARY_TEXT_MAX_LENGTH);
+            assert!(actual.ends_with("word wo..."));
+        }
+
+        #[test]
+        fn summary_text_with_multibyte_characters_works() {
+            let text = "\u{e9}".repeat(300);
+            let actual = Execution::summary_text(&text, "description");
+            assert_eq!(actual.chars().count(), SUMMARY_TEXT_MAX_LENGTH);
+            assert!(actual.ends_with("\u{e9}..."));
+        }
+
+        #[test]
+        fn summary_text_with_max_length_works() {
+            let text = "a".repeat(SUMMARY_TEXT_MAX_LENGTH);
+            assert_eq!(Execution::summary_text(&text, "description"), text);
+        }
+
+        #[test]
+        fn run_with_multiline_description_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                description = """
+                An example
+                package.
+                """
+            "#;
+            let wxs = render_manifest(MANIFEST, |b| b).unwrap();
+            assert!(wxs.contains("Description='An example package.'"));
+            assert!(wxs.contains("Comments='An example package.'"));
+        }
+
+        #[test]
+        fn run_with_profile_var_works() {
+            let wxs = render(|b| b.profile_var(Some("BuildConfig"))).unwrap();
+            assert!(wxs.contains("$(var.BuildConfig)"));
+            assert!(!wxs.contains("$(var.Profile)"));
+        }
+
+        #[test]
+        fn run_with_strict_metadata_and_min_manifest_fails() {
+            match render(|b| b.strict_metadata(true)) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("description")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_strict_metadata_and_missing_help_url_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                description = "An example package."
+                license = "MIT"
+            "#;
+            match render_manifest(MANIFEST, |b| b.strict_metadata(true)) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("help URL")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_strict_metadata_and_complete_manifest_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                description = "An example package."
+                homepage = "http://www.example.com"
+                license = "MIT"
+            "#;
+            let wxs = render_manifest(MANIFEST, |b| b.strict_metadata(true)).unwrap();
+            assert!(wxs.contains("Description='An example package.'"));
+        }
+
+        #[test]
+        fn run_with_defaults_blocks_downgrades() {
+            let wxs = render(|b| b).unwrap();
+            assert_eq!(evaluate(&wxs, "//wix:MajorUpgrade/@AllowDowngrades"), "");
+            assert_eq!(
+                evaluate(&wxs, "//wix:MajorUpgrade/@DowngradeErrorMessage"),
+                DEFAULT_DOWNGRADE_MESSAGE
+            );
+            assert_eq!(
+                evaluate(&wxs, "//wix:MajorUpgrade/@Schedule"),
+                "afterInstallInitialize"
+            );
+        }
+
+        #[test]
+        fn run_with_allow_downgrades_works() {
+            let wxs = render(|b| b.allow_downgrades(true)).unwrap();
+            assert_eq!(evaluate(&wxs, "//wix:MajorUpgrade/@AllowDowngrades"), "yes");
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:MajorUpgrade/@DowngradeErrorMessage)"),
+                "0"
+            );
+        }
+
+        #[test]
+        fn run_with_downgrade_message_works() {
+            const EXPECTED: &str = "Please uninstall [ProductName] first.";
+            let wxs = render(|b| b.downgrade_message(Some(EXPECTED))).unwrap();
+            assert_eq!(
+                evaluate(&wxs, "//wix:MajorUpgrade/@DowngradeErrorMessage"),
+                EXPECTED
+            );
+        }
+
+        #[test]
+        fn symbols_works() {
+            let (name, source) = Execution::symbols(
+                "example-bin",
+                "$(var.CargoTargetDir)\\$(var.Profile)\\example-bin.exe",
+            );
+            assert_eq!(name, String::from("example_bin.pdb"));
+            assert_eq!(
+                source,
+                String::from("$(var.CargoTargetDir)\\$(var.Profile)\\example_bin.pdb")
+            );
+        }
+
+        #[test]
+        fn run_with_include_symbols_works() {
+            let wxs = render(|b| b.include_symbols(true)).unwrap();
+            assert!(wxs.contains("Id='pdb0'"));
+            assert!(wxs.contains("Name='Example.pdb'"));
+            let (_, source) =
+                Execution::symbols("Example", &Execution::default_binary_path("Example"));
+            assert!(source.starts_with("$(var.CargoTargetDir)"));
+            // The default binary path is relative to the package's root.
+            assert!(wxs.contains(&format!("{}'", source)));
+            sxd_document::parser::parse(&wxs).expect("Well-formed XML");
+        }
+
+        #[test]
+        fn run_without_include_symbols_works() {
+            let wxs = render(|b| b.include_symbols(false)).unwrap();
+            assert!(!wxs.contains(".pdb"));
+        }
+
+        #[test]
+        fn keywords_with_defaults_works() {
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().keywords(&manifest);
+            assert_eq!(actual, String::from("Installer"));
+        }
+
+        #[test]
+        fn keywords_with_manifest_works() {
+            let manifest = KEYWORDS_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().keywords(&manifest);
+            assert_eq!(actual, String::from("cli, example, tool"));
+        }
+
+        #[test]
+        fn keywords_with_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                keywords = ["cli", "example", "tool"]
+
+                [package.metadata.wix]
+                keywords = ["installer", "setup"]
+            "#;
+            let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().keywords(&manifest);
+            assert_eq!(actual, String::from("installer, setup"));
+        }
+
+        #[test]
+        fn keywords_with_override_works() {
+            let manifest = KEYWORDS_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .keywords(Some(vec!["installer", "example"]))
+                .build()
+                .keywords(&manifest);
+            assert_eq!(actual, String::from("installer, example"));
+        }
+
+        #[test]
+        fn run_with_manifest_keywords_and_description_works() {
+            let wxs = render_manifest(KEYWORDS_MANIFEST, |b| b).unwrap();
+            assert!(wxs.contains("Keywords='cli, example, tool'"));
+            assert!(wxs.contains("Comments='An example package'"));
+        }
+
+        #[test]
+        fn run_with_comments_works() {
+            let wxs = render_manifest(KEYWORDS_MANIFEST, |b| b.comments(Some("Example comments")))
+                .unwrap();
+            assert!(wxs.contains("Comments='Example comments'"));
+            assert!(wxs.contains("Description='An example package'"));
+        }
+
+        #[test]
+        fn run_with_default_directories_works() {
+            let wxs = render(|b| b).unwrap();
+            assert!(wxs.contains("<Directory Id='APPLICATIONFOLDER' Name='Example'>"));
+            assert!(!wxs.contains("COMPANYFOLDER"));
+        }
+
+        #[test]
+        fn run_with_company_and_install_dirs_works() {
+            let wxs =
+                render(|b| b.company_dir(Some("MyCompany")).install_dir(Some("MyApp"))).unwrap();
+            let company = wxs
+                .find("<Directory Id='COMPANYFOLDER' Name='MyCompany'>")
+                .expect("Company folder");
+            let application = wxs
+                .find("<Directory Id='APPLICATIONFOLDER' Name='MyApp'>")
+                .expect("Application folder");
+            assert!(company < application);
+            sxd_document::parser::parse(&wxs).expect("Well-formed XML");
+        }
+
+        #[test]
+        fn run_with_install_dir_works() {
+            let wxs = render(|b| b.install_dir(Some("MyApp"))).unwrap();
+            assert!(wxs.contains("<Directory Id='APPLICATIONFOLDER' Name='MyApp'>"));
+            assert!(!wxs.contains("COMPANYFOLDER"));
+            sxd_document::parser::parse(&wxs).expect("Well-formed XML");
+        }
+
+        #[test]
+        fn run_with_invalid_install_dir_fails() {
+            assert!(render(|b| b.install_dir(Some("MyCompany\\MyApp"))).is_err());
+            assert!(render(|b| b.install_dir(Some("MyCompany/MyApp"))).is_err());
+            assert!(render(|b| b.install_dir(Some("My:App"))).is_err());
+            assert!(render(|b| b.install_dir(Some(" "))).is_err());
+        }
+
+        #[test]
+        fn run_with_invalid_company_dir_fails() {
+            assert!(render(|b| b.company_dir(Some("My*Company"))).is_err());
+        }
+
+        /// Evaluates an XPath expression against a rendered WiX Source (wxs)
+        /// file, where the `wix` prefix is for the WiX namespace.
+        fn evaluate(wxs: &str, expression: &str) -> String {
+            let package = sxd_document::parser::parse(wxs).expect("Well-formed XML");
+            let document = package.as_document();
+            let mut context = sxd_xpath::Context::new();
+            context.set_namespace("wix", "http://schemas.microsoft.com/wix/2006/wi");
+            sxd_xpath::Factory::new()
+                .build(expression)
+                .unwrap()
+                .unwrap()
+                .evaluate(&context, document.root())
+                .unwrap()
+                .string()
+        }
+
+        #[test]
+        fn run_with_directory_input_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            fs::write(temp_dir.path().join("Cargo.toml"), MIN_MANIFEST).unwrap();
+            let output = temp_dir.path().join("main.wxs");
+            Builder::default()
+                .input(temp_dir.path().to_str())
+                .output(output.to_str())
+                .build()
+                .run()
+                .unwrap();
+            let wxs = fs::read_to_string(&output).unwrap();
+            assert_eq!(evaluate(&wxs, "//wix:Product/@Name"), "Example");
+        }
+
+        #[test]
+        fn run_with_directory_input_without_manifest_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let result = Builder::default()
+                .input(temp_dir.path().to_str())
+                .build()
+                .run();
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains("does not contain")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_install_license_works() {
+            let wxs = render(|b| b.install_license(true).license(Some("License.rtf"))).unwrap();
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "//wix:Directory[@Id='APPLICATIONFOLDER']\
+                     /wix:Component[wix:File/@Source='License.rtf']/@Id"
+                ),
+                "License"
+            );
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:ComponentRef[@Id='License'])"),
+                "1"
+            );
+        }
+
+        #[test]
+        fn run_with_per_binary_features_works() {
+            let wxs =
+                render_manifest(MULTIPLE_BIN_MANIFEST, |b| b.per_binary_features(true)).unwrap();
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Feature[@Id='Binaries']/wix:Feature[starts-with(@Id, 'BinaryFeature')])"
+                ),
+                "3"
+            );
+            for index in 0..3 {
+                let feature = format!("//wix:Feature[@Id='BinaryFeature{}']", index);
+                assert_eq!(
+                    evaluate(&wxs, &format!("{}/@Title", feature)),
+                    format!("binary{}", index)
+                );
+                assert_eq!(
+                    evaluate(&wxs, &format!("{}/wix:ComponentRef/@Id", feature)),
+                    format!("binary{}", index)
+                );
+            }
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Feature[@Id='Binaries']/wix:ComponentRef[starts-with(@Id, 'binary')])"
+                ),
+                "0"
+            );
+        }
+
+        #[test]
+        fn run_with_uninstall_shortcut_works() {
+            let wxs = render(|b| b.uninstall_shortcut(true)).unwrap();
+            let shortcut = "//wix:Directory[@Id='ProgramMenuFolder']\
+                            /wix:Directory[@Id='ApplicationProgramsFolder']\
+                            /wix:Component[@Id='UninstallShortcut']/wix:Shortcut";
+            assert_eq!(
+                evaluate(&wxs, &format!("{}/@Target", shortcut)),
+                "[System64Folder]msiexec.exe"
+            );
+            assert_eq!(
+                evaluate(&wxs, &format!("{}/@Arguments", shortcut)),
+                "/x [ProductCode]"
+            );
+            assert_eq!(
+                evaluate(&wxs, &format!("{}/@Name", shortcut)),
+                "Uninstall Example"
+            );
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Feature[@Id='Binaries']/wix:ComponentRef[@Id='UninstallShortcut'])"
+                ),
+                "1"
+            );
+        }
+
+        #[test]
+        fn run_without_uninstall_shortcut_works() {
+            let wxs = render(|b| b.uninstall_shortcut(false)).unwrap();
+            assert_eq!(evaluate(&wxs, "count(//wix:Shortcut)"), "0");
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:Directory[@Id='ProgramMenuFolder'])"),
+                "0"
+            );
+        }
+
+        #[test]
+        fn run_without_per_binary_features_works() {
+            let wxs =
+                render_manifest(MULTIPLE_BIN_MANIFEST, |b| b.per_binary_features(false)).unwrap();
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Feature[starts-with(@Id, 'BinaryFeature')])"
+                ),
+                "0"
+            );
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Feature[@Id='Binaries']/wix:ComponentRef[starts-with(@Id, 'binary')])"
+                ),
+                "3"
+            );
+        }
+
+        #[test]
+        fn run_without_install_license_works() {
+            let wxs = render(|b| b.install_license(false).license(Some("License.rtf"))).unwrap();
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:File[@Source='License.rtf'])"),
+                "0"
+            );
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:ComponentRef[@Id='License'])"),
+                "0"
+            );
+        }
+
+        #[test]
+        fn run_with_install_readme_works() {
+            let wxs = render(|b| {
+                b.install_license(true)
+                    .install_readme(Some("docs/README.txt"))
+                    .license(Some("License.rtf"))
+            })
+            .unwrap();
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "//wix:Directory[@Id='APPLICATIONFOLDER']\
+                     /wix:Component[@Id='Readme']/wix:File/@Name"
+                ),
+                "README.txt"
+            );
+            assert_eq!(
+                evaluate(&wxs, "//wix:Component[@Id='Readme']/wix:File/@Source"),
+                "docs/README.txt"
+            );
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:ComponentRef[@Id='Readme'])"),
+                "1"
+            );
+        }
+
+        #[test]
+        fn run_without_install_readme_works() {
+            let wxs = render(|b| b.install_license(true).license(Some("License.rtf"))).unwrap();
+            assert_eq!(evaluate(&wxs, "count(//wix:Component[@Id='Readme'])"), "0");
+        }
+
+        #[test]
+        fn run_with_file_association_works() {
+            let wxs = render(|b| {
+                b.file_association(Some(FileAssoc::new(
+                    ".ext",
+                    "Example.Document",
+                    "Example Document",
+                )))
+            })
+            .unwrap();
+            assert!(wxs.contains("<Extension Id='ext'"));
+            assert!(wxs.contains("<ProgId Id='Example.Document' Description='Example Document'>"));
+            assert!(wxs.contains("TargetFile='exe0'"));
+            assert!(wxs.contains("<ComponentRef Id='FileAssociation'/>"));
+            sxd_document::parser::parse(&wxs).expect("Well-formed XML");
+        }
+
+        #[test]
+        fn run_without_file_association_works() {
+            let wxs = render(|b| b.file_association(None)).unwrap();
+            assert!(!wxs.contains("<Extension"));
+            assert!(!wxs.contains("<ProgId"));
+            assert!(!wxs.contains("FileAssociation"));
+        }
+
+        #[test]
+        fn run_with_invalid_file_association_fails() {
+            assert!(render(|b| b.file_association(Some(FileAssoc::new(
+                "e x t",
+                "Example.Document",
+                "Example Document"
+            ))))
+            .is_err());
+            assert!(render(|b| b.file_association(Some(FileAssoc::new(
+                "ext",
+                "Example Document",
+                "Example Document"
+            ))))
+            .is_err());
+        }
+
+        #[test]
+        fn run_with_properties_works() {
+            let wxs = render(|b| {
+                b.properties(vec![
+                    ("INSTALLMODE", "per-machine"),
+                    ("SERVER_URL", "https://example.com/?a=1&b=<2>'"),
+                ])
+            })
+            .unwrap();
+            assert!(wxs.contains("<Property Id='INSTALLMODE' Value='per-machine'/>"));
+            assert!(wxs.contains(
+                "<Property Id='SERVER_URL' \
+                 Value='https://example.com/?a=1&amp;b=&lt;2&gt;&#39;'/>"
+            ));
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Property[@Id='INSTALLMODE' or @Id='SERVER_URL'])"
+                ),
+                "2"
+            );
+            assert_eq!(
+                evaluate(&wxs, "//wix:Property[@Id='SERVER_URL']/@Value"),
+                "https://example.com/?a=1&b=<2>'"
+            );
+        }
+
+        #[test]
+        fn run_without_properties_works() {
+            let wxs = render(|b| b.properties(Vec::new())).unwrap();
+            assert_eq!(evaluate(&wxs, "count(//wix:Product/wix:Property)"), "1");
+        }
+
+        #[test]
+        fn run_with_invalid_property_id_fails() {
+            for id in &["installmode", "1MODE", "", "INSTALL-MODE", "Mode"] {
+                match render(|b| b.properties(vec![(id, "value")])) {
+                    Err(err @ Error::InvalidProperty { .. }) => {
+                        assert!(err.to_string().contains("not valid"))
+                    }
+                    r => panic!("Unexpected result for '{}': {:?}", id, r),
+                }
+            }
+        }
+
+        #[test]
+        fn run_with_duplicate_property_fails() {
+            match render(|b| b.properties(vec![("MODE", "a"), ("_PRIVATE.X", "b"), ("MODE", "c")]))
+            {
+                Err(err @ Error::InvalidProperty { .. }) => {
+                    assert!(err.to_string().contains("more than once"))
+                }
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn run_with_template_property_fails() {
+            for id in &TEMPLATE_PROPERTIES {
+                match render(|b| b.properties(vec![(id, "value")])) {
+                    Err(err @ Error::InvalidProperty { .. }) => {
+                        assert!(err.to_string().contains("already defined"))
+                    }
+                    r => panic!("Unexpected result for '{}': {:?}", id, r),
+                }
+            }
+        }
+
+        #[test]
+        fn template_properties_are_defined_by_template() {
+            let template = Template::Wxs.to_str();
+            for id in &TEMPLATE_PROPERTIES {
+                assert!(
+                    template.contains(&format!("Property Id='{}'", id)),
+                    "The '{}' property is not in the template",
+                    id
+                );
+            }
+        }
+
+        #[test]
+        fn run_with_registry_works() {
+            let wxs = render(|b| {
+                b.registry(vec![
+                    RegEntry::new("HKLM", "Software\\Example", "Version", "string", "1.0.0"),
+                    RegEntry::new("HKCU", "Software\\Example", "", "integer", "1"),
+                ])
+            })
+            .unwrap();
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:Component[@Id='Registry'])"),
+                "1"
+            );
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Component[@Id='Registry']/wix:RegistryValue)"
+                ),
+                "2"
+            );
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "//wix:Component[@Id='Registry']/wix:RegistryValue[1]/@Name"
+                ),
+                "Version"
+            );
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Component[@Id='Registry']/wix:RegistryValue[2]/@Name)"
+                ),
+                "0"
+            );
+            assert_eq!(
+                evaluate(
+                    &wxs,
+                    "count(//wix:Component[@Id='Registry']/wix:RegistryValue[@KeyPath='yes'])"
+                ),
+                "1"
+            );
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:ComponentRef[@Id='Registry'])"),
+                "1"
+            );
+        }
+
+        #[test]
+        fn run_without_registry_works() {
+            let wxs = render(|b| b.registry(Vec::new())).unwrap();
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:Component[@Id='Registry'])"),
+                "0"
+            );
+            assert_eq!(
+                evaluate(&wxs, "count(//wix:ComponentRef[@Id='Registry'])"),
+                "0"
+            );
+        }
+
+        #[test]
+        fn run_with_invalid_registry_fails() {
+            assert!(render(|b| b.registry(vec![RegEntry::new(
+                "HKEY_LOCAL_MACHINE",
+                "Software\\Example",
+                "Version",
+                "string",
+                "1.0.0"
+            )]))
+            .is_err());
+            assert!(render(|b| b.registry(vec![RegEntry::new(
+                "HKLM", "", "Version", "string", "1.0.0"
+            )]))
+            .is_err());
+            assert!(render(|b| b.registry(vec![RegEntry::new(
+                "HKLM",
+                "Software\\Example",
+                "Version",
+                "text",
+                "1.0.0"
+            )]))
+            .is_err());
+        }
+
+        #[test]
+        fn run_with_language_works() {
+            let wxs = render(|b| b.language(Some(1036))).unwrap();
+            assert_eq!(evaluate(&wxs, "string(//wix:Product/@Language)"), "1036");
+            assert_eq!(evaluate(&wxs, "string(//wix:Package/@Languages)"), "1036");
+        }
+
+        #[test]
+        fn run_without_language_works() {
+            let wxs = render(|b| b.language(None)).unwrap();
+            assert_eq!(evaluate(&wxs, "string(//wix:Product/@Language)"), "1033");
+            assert_eq!(evaluate(&wxs, "string(//wix:Package/@Languages)"), "1033");
+        }
+
+        #[test]
+        fn run_with_culture_in_manifest_works() {
+            const CULTURE_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+
+            [package.metadata.wix]
+            culture = "de-DE"
+            "#;
+            let wxs = render_manifest(CULTURE_MANIFEST, |b| b.language(None)).unwrap();
+            assert_eq!(evaluate(&wxs, "string(//wix:Product/@Language)"), "1031");
+            let wxs = render_manifest(CULTURE_MANIFEST, |b| b.language(Some(1033))).unwrap();
+            assert_eq!(evaluate(&wxs, "string(//wix:Product/@Language)"), "1033");
+        }
+
+        #[test]
+        fn platform_with_unknown_value_fails() {
+            let actual = Builder::default().platform(Some("arm")).build().platform();
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn generated_license_path_with_output_works() {
+            let actual = Builder::default()
+                .output(Some("wix/main.wxs"))
+                .build()
+                .generated_license_path();
+            assert_eq!(
+                actual,
+                PathBuf::from("wix").join(LICENSE_FILE_NAME.to_owned() + "." + RTF_FILE_EXTENSION)
+            );
+        }
+
+        #[test]
+        fn run_with_output_writes_license_file_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIT_MANIFEST).unwrap();
+            let output = temp_dir.path().join("main.wxs");
+            Builder::default()
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .build()
+                .run()
+                .unwrap();
+            let license = temp_dir.path().join("License.rtf");
+            assert!(license.exists());
+            let wxs = fs::read_to_string(&output).unwrap();
+            assert!(wxs.contains(&license.display().to_string()));
+        }
+
+        #[test]
+        fn run_with_existing_license_file_does_not_overwrite_it() {
+            const EXPECTED: &str = "An edited license";
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIT_MANIFEST).unwrap();
+            let license = temp_dir.path().join("License.rtf");
+            fs::write(&license, EXPECTED).unwrap();
+            let output = temp_dir.path().join("main.wxs");
+            Builder::default()
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .build()
+                .run()
+                .unwrap();
+            assert_eq!(fs::read_to_string(&license).unwrap(), EXPECTED);
+            let wxs = fs::read_to_string(&output).unwrap();
+            assert!(wxs.contains(&license.display().to_string()));
+        }
+
+        #[test]
+        fn run_with_skip_license_file_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIT_MANIFEST).unwrap();
+            let output = temp_dir.path().join("main.wxs");
+            Builder::default()
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .skip_license_file(true)
+                .build()
+                .run()
+                .unwrap();
+            assert!(output.exists());
+            assert!(!temp_dir.path().join("License.rtf").exists());
+        }
+
+        #[test]
+        fn build_stamp_with_revision_works() {
+            let time = Utc.with_ymd_and_hms(2024, 1, 31, 12, 30, 45).unwrap();
+            assert_eq!(
+                Execution::build_stamp(Some("1a2b3c4"), time),
+                "Built 2024-01-31T12:30:45Z from 1a2b3c4"
+            );
+        }
+
+        #[test]
+        fn build_stamp_without_revision_works() {
+            let time = Utc.with_ymd_and_hms(2024, 1, 31, 12, 30, 45).unwrap();
+            assert_eq!(
+                Execution::build_stamp(None, time),
+                "Built 2024-01-31T12:30:45Z"
+            );
+        }
+
+        #[test]
+        fn run_with_stamp_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIN_MANIFEST).unwrap();
+            let template = temp_dir.path().join("main.wxs.mustache");
+            fs::write(
+                &template,
+                "<Wix Comments='{{comments}}' Stamp='{{build-stamp}}'/>",
+            )
+            .unwrap();
+            let output = temp_dir.path().join("main.wxs");
+            Builder::default()
+                .comments(Some("Example comments"))
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .stamp(true)
+                .template_file(template.to_str())
+                .build()
+                .run()
+                .unwrap();
+            let wxs = fs::read_to_string(&output).unwrap();
+            let package = sxd_document::parser::parse(&wxs).unwrap();
+            let document = package.as_document();
+            let stamp = sxd_xpath::evaluate_xpath(&document, "string(/Wix/@Stamp)")
+                .unwrap()
+                .string();
+            assert!(stamp.starts_with("Built "));
+            assert_eq!(
+                sxd_xpath::evaluate_xpath(&document, "string(/Wix/@Comments)")
+                    .unwrap()
+                    .string(),
+                format!("Example comments ({})", stamp)
+            );
+        }
+
+        #[test]
+        fn output_path_with_defaults_works() {
+            assert!(Execution::default().output_path().unwrap().is_none());
+        }
+
+        #[test]
+        fn output_path_with_output_dir_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let output_dir = temp_dir.path().join("output");
+            let actual = Builder::default()
+                .output_dir(output_dir.to_str())
+                .build()
+                .output_path()
+                .unwrap();
+            assert_eq!(actual, Some(output_dir.join(WIX).join("main.wxs")));
+            assert!(output_dir.join(WIX).is_dir());
+        }
+
+        #[test]
+        fn output_path_with_output_and_output_dir_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let output_dir = temp_dir.path().join("output");
+            let actual = Builder::default()
+                .output(Some("Example.wxs"))
+                .output_dir(output_dir.to_str())
+                .build()
+                .output_path()
+                .unwrap();
+            assert_eq!(actual, Some(PathBuf::from("Example.wxs")));
+            assert!(!output_dir.exists());
+        }
+
+        #[test]
+        fn output_path_with_invalid_output_dir_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let file = temp_dir.path().join("file");
+            File::create(&file).unwrap();
+            let result = Builder::default()
+                .output_dir(file.to_str())
+                .build()
+                .output_path();
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn license_source_with_unknown_license_field_works() {
+            let manifest = UNKNOWN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().license_source(&manifest).unwrap();
+            assert!(actual.is_none());
+        }
+
+        #[test]
+        fn binaries_with_no_bin_section_works() {
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().binaries(&manifest).unwrap();
+            assert_eq!(
+                actual,
+                vec![hashmap! {
+                    "binary-index" => 0.to_string(),
+                    "binary-name" => String::from("Example"),
+                    "binary-source" => String::from("$(var.CargoTargetDir)\\$(var.Profile)\\Example.exe")
+                }]
+            )
+        }
+
+        #[test]
+        fn binaries_with_profile_var_works() {
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .profile_var(Some("BuildConfig"))
+                .build()
+                .binaries(&manifest)
+                .unwrap();
+            assert!(actual[0]["binary-source"].contains("$(var.BuildConfig)"));
+            assert_eq!(
+                actual[0]["binary-source"],
+                Execution::binary_path("Example", "BuildConfig")
+            );
+        }
+
+        #[test]
+        fn binaries_with_single_bin_section_works() {
+            let manifest = MIT_MANIFEST_BIN.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().binaries(&manifest).unwrap();
+            assert_eq!(
+                actual,
+                vec![hashmap! {
+                    "binary-index" => 0.to_string(),
+                    "binary-name" => String::from("Different"),
+                    "binary-source" => String::from("$(var.CargoTargetDir)\\$(var.Profile)\\Different.exe")
+                }]
+            )
+        }
+
+        #[test]
+        fn binaries_with_duplicate_bin_sections_fails() {
+            let manifest = DUPLICATE_BIN_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            match Execution::default().binaries(&manifest) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("'tool'")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn binaries_with_paths_works() {
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::new()
+                .binaries(Some(vec!["bin/one.exe", "bin/two.exe"]))
+                .build()
+                .binaries(&manifest)
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    hashmap! {
+                        "binary-index" => 0.to_string(),
+                        "binary-name" => String::from("one"),
+                        "binary-source" => String::from("bin/one.exe")
+                    },
+                    hashmap! {
+                        "binary-index" => 1.to_string(),
+                        "binary-name" => String::from("two"),
+                        "binary-source" => String::from("bin/two.exe")
+                    }
+                ]
+            )
+        }
+
+        #[test]
+        fn binaries_with_pattern_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            fs::write(temp_dir.path().join("one.exe"), "").unwrap();
+            fs::write(temp_dir.path().join("two.exe"), "").unwrap();
+            fs::write(temp_dir.path().join("two.pdb"), "").unwrap();
+            let pattern = temp_dir.path().join("*.exe");
+            let literal = temp_dir.path().join("bin").join("three.exe");
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::new()
+                .binaries(Some(vec![
+                    pattern.to_str().unwrap(),
+                    literal.to_str().unwrap(),
+                ]))
+                .build()
+                .binaries(&manifest)
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    hashmap! {
+                        "binary-index" => 0.to_string(),
+                        "binary-name" => String::from("one"),
+                        "binary-source" => temp_dir.path().join("one.exe").to_string_lossy().into_owned()
+                    },
+                    hashmap! {
+                        "binary-index" => 1.to_string(),
+                        "binary-name" => String::from("two"),
+                        "binary-source" => temp_dir.path().join("two.exe").to_string_lossy().into_owned()
+                    },
+                    hashmap! {
+                        "binary-index" => 2.to_string(),
+                        "binary-name" => String::from("three"),
+                        "binary-source" => literal.to_string_lossy().into_owned()
+                    }
+                ]
+            )
         }
 
         #[test]
-        fn binaries_with_no_bin_section_works() {
+        fn binaries_with_input_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let package_dir = temp_dir.path().join("sub");
+            fs::create_dir(&package_dir).unwrap();
+            let input = package_dir.join("Cargo.toml");
+            fs::write(&input, MIN_MANIFEST).unwrap();
             let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
-            let actual = Execution::default().binaries(&manifest).unwrap();
+            let actual = Builder::new()
+                .input(input.to_str())
+                .build()
+                .binaries(&manifest)
+                .unwrap();
             assert_eq!(
                 actual,
                 vec![hashmap! {
                     "binary-index" => 0.to_string(),
                     "binary-name" => String::from("Example"),
-                    "binary-source" => String::from("target\\$(var.Profile)\\Example.exe")
+                    "binary-source" => package_dir
+                        .join(Execution::default_binary_path("Example"))
+                        .to_string_lossy()
+                        .into_owned()
                 }]
             )
         }
 
         #[test]
-        fn binaries_with_single_bin_section_works() {
-            let manifest = MIT_MANIFEST_BIN.parse::<Value>().expect("Parsing TOML");
-            let actual = Execution::default().binaries(&manifest).unwrap();
-            assert_eq!(
-                actual,
-                vec![hashmap! {
-                    "binary-index" => 0.to_string(),
-                    "binary-name" => String::from("Different"),
-                    "binary-source" => String::from("target\\$(var.Profile)\\Different.exe")
-                }]
-            )
+        fn binaries_with_input_and_path_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let package_dir = temp_dir.path().join("sub");
+            fs::create_dir(&package_dir).unwrap();
+            let input = package_dir.join("Cargo.toml");
+            fs::write(&input, MIN_MANIFEST).unwrap();
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::new()
+                .binaries(Some(vec!["bin/tool.exe"]))
+                .input(input.to_str())
+                .build()
+                .binaries(&manifest)
+                .unwrap();
+            assert_eq!(actual[0]["binary-source"], "bin/tool.exe");
+        }
+
+        #[test]
+        fn binaries_with_unmatched_pattern_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let pattern = temp_dir.path().join("*.exe");
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let result = Builder::new()
+                .binaries(Some(vec![pattern.to_str().unwrap()]))
+                .build()
+                .binaries(&manifest);
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains("does not match any files")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn binaries_with_duplicate_file_stems_fails() {
+            let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let result = Builder::new()
+                .binaries(Some(vec!["one/tool.exe", "two/tool.exe"]))
+                .build()
+                .binaries(&manifest);
+            match result {
+                Err(Error::Generic(msg)) => assert!(msg.contains("'tool'")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
         }
 
         #[test]
@@ -867,22 +4216,46 @@ mod tests {
                     hashmap! {
                         "binary-index" => 0.to_string(),
                         "binary-name" => String::from("binary0"),
-                        "binary-source" => String::from("target\\$(var.Profile)\\binary0.exe")
+                        "binary-source" => String::from("$(var.CargoTargetDir)\\$(var.Profile)\\binary0.exe")
                     },
                     hashmap! {
                         "binary-index" => 1.to_string(),
                         "binary-name" => String::from("binary1"),
-                        "binary-source" => String::from("target\\$(var.Profile)\\binary1.exe")
+                        "binary-source" => String::from("$(var.CargoTargetDir)\\$(var.Profile)\\binary1.exe")
                     },
                     hashmap! {
                         "binary-index" => 2.to_string(),
                         "binary-name" => String::from("binary2"),
-                        "binary-source" => String::from("target\\$(var.Profile)\\binary2.exe")
+                        "binary-source" => String::from("$(var.CargoTargetDir)\\$(var.Profile)\\binary2.exe")
                     }
                 ]
             )
         }
 
+        #[test]
+        fn discovered_binaries_with_multiple_bin_sections_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MULTIPLE_BIN_MANIFEST).unwrap();
+            let actual = Builder::default()
+                .input(manifest.to_str())
+                .build()
+                .discovered_binaries()
+                .unwrap();
+            assert_eq!(actual.len(), 3);
+            for (index, binary) in actual.iter().enumerate() {
+                assert_eq!(binary.index(), index);
+                assert_eq!(binary.name(), format!("binary{}", index));
+                assert_eq!(
+                    binary.source(),
+                    temp_dir
+                        .path()
+                        .join(Execution::default_binary_path(&format!("binary{}", index)))
+                        .to_string_lossy()
+                );
+            }
+        }
+
         #[test]
         fn manufacturer_with_defaults_works() {
             const EXPECTED: &str = "First Last";
@@ -891,6 +4264,20 @@ mod tests {
             assert_eq!(actual, String::from(EXPECTED));
         }
 
+        #[test]
+        fn manufacturer_with_metadata_and_no_authors_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+
+                [package.metadata.wix]
+                manufacturer = "Example Organization"
+            "#;
+            let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().manufacturer(&manifest).unwrap();
+            assert_eq!(actual, String::from("Example Organization"));
+        }
+
         #[test]
         fn manufacturer_with_override_works() {
             const EXPECTED: &str = "Example";
@@ -936,6 +4323,323 @@ mod tests {
             assert_eq!(actual, Some(String::from(EXPECTED)));
         }
 
+        #[test]
+        fn help_url_with_metadata_works() {
+            const EXPECTED: &str = "http://www.example.com/installer";
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                documentation = "http://www.example.com"
+
+                [package.metadata.wix]
+                help-url = "http://www.example.com/installer"
+            "#;
+            let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::help_url(&manifest);
+            assert_eq!(actual, Some(String::from(EXPECTED)));
+        }
+
+        #[test]
+        fn eula_with_metadata_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let eula = temp_dir.path().join("Installer_Eula.rtf");
+            fs::write(&eula, "").unwrap();
+            let manifest = format!(
+                r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                license = "MIT"
+
+                [package.metadata.wix]
+                eula = '{}'
+            "#,
+                eula.display()
+            )
+            .parse::<Value>()
+            .expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Manifest(eula));
+        }
+
+        #[test]
+        fn eula_with_missing_metadata_file_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                eula = "Missing_Eula.rtf"
+            "#;
+            let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+            match Execution::default().eula(&manifest) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("does not exist")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn eula_with_non_rtf_metadata_file_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let eula = temp_dir.path().join("Installer_Eula.txt");
+            fs::write(&eula, "").unwrap();
+            let manifest = format!(
+                r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                eula = '{}'
+            "#,
+                eula.display()
+            )
+            .parse::<Value>()
+            .expect("Parsing TOML");
+            match Execution::default().eula(&manifest) {
+                Err(Error::Generic(msg)) => assert!(msg.contains("RTF file extension")),
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn eula_with_override_and_metadata_works() {
+            const EXPECTED: &str = "Example_Eula.rtf";
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                eula = "Installer_Eula.rtf"
+            "#;
+            let manifest = MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .eula(Some(EXPECTED))
+                .build()
+                .eula(&manifest)
+                .unwrap();
+            assert_eq!(actual, Eula::CommandLine(PathBuf::from(EXPECTED)));
+        }
+
+        #[test]
+        fn eula_with_ui_license_and_override_works() {
+            const EXPECTED: &str = "Ui_Eula.rtf";
+            let manifest = MIT_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .ui_license(Some(EXPECTED))
+                .eula(Some("Example_Eula.rtf"))
+                .license(Some("License.rtf"))
+                .build()
+                .eula(&manifest)
+                .unwrap();
+            assert_eq!(actual, Eula::CommandLine(PathBuf::from(EXPECTED)));
+        }
+
+        #[test]
+        fn run_with_special_characters_works() {
+            const NAME: &str = "A & B <Corp> \u{1f680}";
+            const MANUFACTURER: &str = "O'Neil & \"Sons\"";
+            const DESCRIPTION: &str = "x < y & y > z";
+            let wxs = render(|b| {
+                b.product_name(Some(NAME))
+                    .manufacturer(Some(MANUFACTURER))
+                    .description(Some(DESCRIPTION))
+            })
+            .unwrap();
+            assert!(wxs.contains("Name='A &amp; B &lt;Corp&gt; \u{1f680}'"));
+            assert_eq!(evaluate(&wxs, "//wix:Product/@Name"), NAME);
+            assert_eq!(evaluate(&wxs, "//wix:Product/@Manufacturer"), MANUFACTURER);
+            assert_eq!(evaluate(&wxs, "//wix:Package/@Description"), DESCRIPTION);
+        }
+
+        #[test]
+        fn run_with_ui_license_and_license_works() {
+            let wxs =
+                render(|b| b.license(Some("License.rtf")).ui_license(Some("Eula.rtf"))).unwrap();
+            assert_eq!(
+                evaluate(&wxs, "//wix:WixVariable[@Id='WixUILicenseRtf']/@Value"),
+                "Eula.rtf"
+            );
+            assert_eq!(
+                evaluate(&wxs, "//wix:Component[@Id='License']/wix:File/@Source"),
+                "License.rtf"
+            );
+        }
+
+        #[test]
+        fn run_with_ui_license_and_generated_license_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIT_MANIFEST).unwrap();
+            let wix_dir = temp_dir.path().join("wix");
+            fs::create_dir(&wix_dir).unwrap();
+            let output = wix_dir.join("main.wxs");
+            Builder::default()
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .ui_license(Some("Eula.rtf"))
+                .build()
+                .run()
+                .unwrap();
+            let wxs = fs::read_to_string(&output).unwrap();
+            let license = wix_dir.join("License.rtf");
+            assert_eq!(
+                evaluate(&wxs, "//wix:WixVariable[@Id='WixUILicenseRtf']/@Value"),
+                "Eula.rtf"
+            );
+            assert_eq!(
+                evaluate(&wxs, "//wix:Component[@Id='License']/wix:File/@Source"),
+                license.display().to_string()
+            );
+            assert!(license.exists());
+        }
+
+        #[test]
+        fn eula_with_markdown_works() {
+            let manifest = MIT_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Builder::default()
+                .eula_markdown(Some("LICENSE.md"))
+                .output(Some("wix/main.wxs"))
+                .build()
+                .eula(&manifest)
+                .unwrap();
+            assert_eq!(
+                actual,
+                Eula::CommandLine(PathBuf::from("wix").join("Eula.rtf"))
+            );
+        }
+
+        #[test]
+        fn run_with_eula_markdown_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIN_MANIFEST).unwrap();
+            let markdown = temp_dir.path().join("LICENSE.md");
+            fs::write(&markdown, "# License\n\nAll rights reserved.\n").unwrap();
+            let wix_dir = temp_dir.path().join("wix");
+            fs::create_dir(&wix_dir).unwrap();
+            let output = wix_dir.join("main.wxs");
+            Builder::default()
+                .eula_markdown(markdown.to_str())
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .build()
+                .run()
+                .unwrap();
+            let eula = wix_dir.join("Eula.rtf");
+            let rtf = fs::read_to_string(&eula).unwrap();
+            assert!(rtf.starts_with("{\\rtf1"));
+            assert!(rtf.contains("All rights reserved."));
+            let wxs = fs::read_to_string(&output).unwrap();
+            assert!(wxs.contains(&format!("Value='{}'", eula.display())));
+        }
+
+        #[test]
+        fn run_with_missing_eula_markdown_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIN_MANIFEST).unwrap();
+            let result = Builder::default()
+                .eula_markdown(temp_dir.path().join("LICENSE.md").to_str())
+                .input(manifest.to_str())
+                .output(temp_dir.path().join("main.wxs").to_str())
+                .build()
+                .run();
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn run_with_template_file_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIN_MANIFEST).unwrap();
+            let template_file = temp_dir.path().join("main.wxs.mustache");
+            fs::write(
+                &template_file,
+                "<Product Name='{{product-name}}' Manufacturer='{{manufacturer}}'/>",
+            )
+            .unwrap();
+            let output = temp_dir.path().join("main.wxs");
+            Builder::default()
+                .input(manifest.to_str())
+                .output(output.to_str())
+                .template_file(template_file.to_str())
+                .build()
+                .run()
+                .unwrap();
+            let actual = fs::read_to_string(&output).unwrap();
+            assert_eq!(
+                actual,
+                "<Product Name='Example' Manufacturer='First Last'/>"
+            );
+        }
+
+        #[test]
+        fn run_with_binary_display_names_works() {
+            let wxs = render(|b| {
+                b.binaries(Some(vec!["bin/tool.exe", "bin/other.exe"]))
+                    .binary_display_names(Some(hashmap! { "tool" => "Example -- Tool" }))
+                    .per_binary_features(true)
+            })
+            .unwrap();
+            assert_eq!(
+                evaluate(&wxs, "//wix:Feature[@Id='BinaryFeature0']/@Title"),
+                "Example -- Tool"
+            );
+            assert_eq!(evaluate(&wxs, "//wix:File[@Id='exe0']/@Name"), "tool.exe");
+            assert_eq!(
+                evaluate(&wxs, "//wix:Feature[@Id='BinaryFeature1']/@Title"),
+                "other"
+            );
+        }
+
+        #[test]
+        fn run_with_missing_template_file_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let manifest = temp_dir.path().join("Cargo.toml");
+            fs::write(&manifest, MIN_MANIFEST).unwrap();
+            let result = Builder::default()
+                .input(manifest.to_str())
+                .output(temp_dir.path().join("main.wxs").to_str())
+                .template_file(temp_dir.path().join("main.wxs.mustache").to_str())
+                .build()
+                .run();
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn template_with_invalid_template_file_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let template_file = temp_dir.path().join("main.wxs.mustache");
+            fs::write(&template_file, "<Product Name='{{#product-name}}'/>").unwrap();
+            let result = Builder::default()
+                .template_file(template_file.to_str())
+                .build()
+                .template();
+            match result {
+                Err(Error::Generic(..)) => {}
+                _ => panic!("Expected a generic error"),
+            }
+        }
+
+        #[test]
+        fn unknown_template_variables_with_embedded_template_works() {
+            let actual = Execution::unknown_template_variables(Template::Wxs.to_str());
+            assert!(actual.is_empty());
+        }
+
+        #[test]
+        fn unknown_template_variables_works() {
+            const SOURCE: &str = "{{! A comment }}<Product Name='{{product-nmae}}' \
+                {{#banner}}Banner='{{banner}}'{{/banner}} {{{ manufacturer }}} \
+                {{&product-version}} {{^win65}}{{/win65}} {{product-nmae}}/>";
+            let actual = Execution::unknown_template_variables(SOURCE);
+            assert_eq!(actual, vec!["product-nmae", "product-version", "win65"]);
+        }
+
         #[test]
         fn eula_with_defaults_works() {
             let manifest = MIN_MANIFEST.parse::<Value>().expect("Parsing TOML");
@@ -964,6 +4668,68 @@ mod tests {
             assert_eq!(actual, Eula::Generate(Template::Gpl3));
         }
 
+        #[test]
+        fn eula_with_bsd2_license_field_works() {
+            let manifest = BSD2_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Bsd2));
+        }
+
+        #[test]
+        fn eula_with_bsd3_license_field_works() {
+            let manifest = BSD3_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Bsd3));
+        }
+
+        #[test]
+        fn eula_with_isc_license_field_works() {
+            let manifest = ISC_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Isc));
+        }
+
+        #[test]
+        fn eula_with_mpl2_license_field_works() {
+            let manifest = MPL2_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Mpl2));
+        }
+
+        #[test]
+        fn eula_with_mit_or_apache2_license_field_works() {
+            let manifest = MIT_OR_APACHE2_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Mit));
+        }
+
+        #[test]
+        fn eula_with_apache2_with_exception_license_field_works() {
+            let manifest = APACHE2_WITH_EXCEPTION_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Apache2));
+        }
+
+        #[test]
+        fn eula_with_gpl3_or_later_license_field_works() {
+            let manifest = GPL3_OR_LATER_MANIFEST
+                .parse::<Value>()
+                .expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Gpl3));
+        }
+
+        #[test]
+        fn eula_with_gpl3_only_license_field_works() {
+            let manifest = GPL3_ONLY_MANIFEST.parse::<Value>().expect("Parsing TOML");
+            let actual = Execution::default().eula(&manifest).unwrap();
+            assert_eq!(actual, Eula::Generate(Template::Gpl3));
+        }
+
         #[test]
         fn eula_with_unknown_license_field_works() {
             let manifest = UNKNOWN_MANIFEST.parse::<Value>().expect("Parsing TOML");
@@ -1038,5 +4804,55 @@ mod tests {
                 .unwrap();
             assert_eq!(actual, Eula::CommandLine(license_file_path));
         }
+
+        #[test]
+        fn validate_product_icon_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let icon_path = temp_dir.path().join("Product.ico");
+            let mut icon_handle = File::create(&icon_path).expect("Create file");
+            icon_handle
+                .write_all(&[
+                    0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x20, 0x20, 0x00, 0x00, 0x01, 0x00, 0x20,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00,
+                ])
+                .expect("Write file");
+            assert!(Execution::validate_product_icon(&icon_path).is_ok());
+        }
+
+        #[test]
+        fn validate_product_icon_with_uppercase_extension_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let icon_path = temp_dir.path().join("Product.ICO");
+            let mut icon_handle = File::create(&icon_path).expect("Create file");
+            icon_handle
+                .write_all(&[
+                    0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x20, 0x20, 0x00, 0x00, 0x01, 0x00, 0x20,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00,
+                ])
+                .expect("Write file");
+            assert!(Execution::validate_product_icon(&icon_path).is_ok());
+        }
+
+        #[test]
+        fn validate_product_icon_with_wrong_extension_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let icon_path = temp_dir.path().join("Product.png");
+            let mut icon_handle = File::create(&icon_path).expect("Create file");
+            icon_handle
+                .write_all(&[0x00, 0x00, 0x01, 0x00, 0x01, 0x00])
+                .expect("Write file");
+            assert!(Execution::validate_product_icon(&icon_path).is_err());
+        }
+
+        #[test]
+        fn validate_product_icon_with_wrong_magic_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let icon_path = temp_dir.path().join("Product.ico");
+            let mut icon_handle = File::create(&icon_path).expect("Create file");
+            icon_handle
+                .write_all(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A])
+                .expect("Write file");
+            assert!(Execution::validate_product_icon(&icon_path).is_err());
+        }
     }
 }
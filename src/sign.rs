@@ -15,6 +15,8 @@
 //! The implementation for the `sign` command. This command focuses on signing
 //! installers using the Windows SDK `signtool` application.
 
+use crate::create::{meta_path, meta_str, meta_value};
+use crate::wait_with_output;
 use crate::Error;
 use crate::Result;
 use crate::TimestampServer;
@@ -27,15 +29,36 @@ use crate::TARGET_FOLDER_NAME;
 use crate::WIX;
 
 use std::env;
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
 use std::fs;
-use std::io::ErrorKind;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
 use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
 
 use toml::Value;
 
+/// The default digest algorithm for signing.
+const DEFAULT_DIGEST: &str = "sha256";
+
+/// The digest algorithms supported by the signer.
+const DIGESTS: [&str; 4] = ["sha1", "sha256", "sha384", "sha512"];
+
+/// The default number of times to retry signing when the timestamp server fails.
+const DEFAULT_RETRIES: u32 = 3;
+
+/// The wait before the first retry when the timestamp server fails, which
+/// doubles for each subsequent retry.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The signer argument for the password of a PFX file.
+const PASSWORD_ARG: &str = "/p";
+
+/// The text displayed in place of a password.
+const REDACTED: &str = "********";
+
 /// A builder for creating an execution context to sign an installer.
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
@@ -45,6 +68,8 @@ pub struct Builder<'a> {
     homepage: Option<&'a str>,
     input: Option<&'a str>,
     product_name: Option<&'a str>,
+    retries: u32,
+    timeout: Option<Duration>,
     timestamp: Option<&'a str>,
 }
 
@@ -58,6 +83,8 @@ impl<'a> Builder<'a> {
             homepage: None,
             input: None,
             product_name: None,
+            retries: DEFAULT_RETRIES,
+            timeout: None,
             timestamp: None,
         }
     }
@@ -118,6 +145,28 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the number of times to retry signing when the timestamp server
+    /// fails.
+    ///
+    /// Public timestamp servers can be unreliable, so if the signer fails
+    /// because the timestamp server could not be reached or returned an invalid
+    /// response, then signing is retried after waiting, where the wait doubles
+    /// after each attempt. Any other failure, such as a missing or invalid
+    /// certificate, is not retried. The default is three (3) retries.
+    pub fn retries(&mut self, r: u32) -> &mut Self {
+        self.retries = r;
+        self
+    }
+
+    /// Sets the maximum time to wait for each attempt of the signer.
+    ///
+    /// If the signer does not exit within the timeout, then it is stopped and
+    /// an error occurs. The default is to wait without a timeout.
+    pub fn timeout(&mut self, t: Option<Duration>) -> &mut Self {
+        self.timeout = t;
+        self
+    }
+
     /// Sets the URL for the timestamp server used when signing an installer.
     ///
     /// The default is to _not_ use a timestamp server, even though it is highly
@@ -136,6 +185,8 @@ impl<'a> Builder<'a> {
             homepage: self.homepage.map(String::from),
             input: self.input.map(PathBuf::from),
             product_name: self.product_name.map(String::from),
+            retries: self.retries,
+            timeout: self.timeout,
             timestamp: self.timestamp.map(String::from),
         }
     }
@@ -156,6 +207,8 @@ pub struct Execution {
     homepage: Option<String>,
     input: Option<PathBuf>,
     product_name: Option<String>,
+    retries: u32,
+    timeout: Option<Duration>,
     timestamp: Option<String>,
 }
 
@@ -169,10 +222,29 @@ impl Execution {
         debug!("homepage = {:?}", self.homepage);
         debug!("input = {:?}", self.input);
         debug!("product_name = {:?}", self.product_name);
+        debug!("retries = {:?}", self.retries);
+        debug!("timeout = {:?}", self.timeout);
         debug!("timestamp = {:?}", self.timestamp);
         let manifest = super::manifest(self.input.as_ref())?;
-        let product_name = super::product_name(self.product_name.as_ref(), &manifest)?;
-        let description = if let Some(d) = super::description(self.description.clone(), &manifest) {
+        let msi = self.msi()?;
+        let signer = self.command(&manifest, &super::package_root(self.input.as_ref())?, &msi)?;
+        self.sign(signer)
+    }
+
+    /// Creates the signer command to sign a file, such as an installer or a
+    /// binary.
+    ///
+    /// The description of the signed content is the product name followed by
+    /// the description, if one is available, and the homepage is used for the
+    /// expanded description.
+    pub(crate) fn command(
+        &self,
+        manifest: &Value,
+        manifest_dir: &Path,
+        file: &Path,
+    ) -> Result<Command> {
+        let product_name = super::product_name(self.product_name.as_ref(), manifest)?;
+        let description = if let Some(d) = super::description(self.description.clone(), manifest) {
             trace!("A description was provided either at the command line or in the package's manifest (Cargo.toml).");
             format!("{} - {}", product_name, d)
         } else {
@@ -180,49 +252,90 @@ impl Execution {
             product_name
         };
         debug!("description = {:?}", description);
-        let msi = self.msi()?;
         let mut signer = self.signer()?;
-        debug!("signer = {:?}", signer);
-        if self.capture_output {
-            trace!("Capturing the {} output", SIGNTOOL);
-            signer.stdout(Stdio::null());
-            signer.stderr(Stdio::null());
-        }
-        signer.arg("sign").arg("/a").arg("/d").arg(description);
-        if let Some(h) = self.homepage(&manifest) {
+        signer.arg("sign");
+        if let Some(mut settings) = Settings::from_manifest(manifest, manifest_dir)? {
+            trace!("Using the signing settings from the package's manifest (Cargo.toml)");
+            debug!("settings = {:?}", settings);
+            if self.timestamp.is_some() && settings.timestamp_url.is_some() {
+                trace!("The timestamp server overrides the timestamp URL from the manifest");
+                settings.timestamp_url = None;
+            }
+            signer.args(settings.args(settings.password()?.as_deref()));
+        } else {
+            signer.arg("/a");
+        }
+        signer.arg("/d").arg(description);
+        if let Some(h) = self.homepage(manifest) {
             trace!("Using the '{}' URL for the expanded description", h);
             signer.arg("/du").arg(h);
         }
-        if let Some(t) = self.timestamp {
-            let server = TimestampServer::from_str(&t)?;
-            trace!(
-                "Using the '{}' timestamp server to sign the installer",
-                server
-            );
+        if let Some(t) = &self.timestamp {
+            let server = TimestampServer::from_str(t)?;
+            trace!("Using the '{}' timestamp server to sign the file", server);
             signer.arg("/t");
             signer.arg(server.url());
         }
-        let status = signer.arg(&msi).status().map_err(|err| {
-            if err.kind() == ErrorKind::NotFound {
-                Error::Generic(format!(
-                    "The {0} application could not be found. Please check the Windows 10 SDK \
-                     (https://developer.microsoft.com/en-us/windows/downloads/windows-10-sdk) is \
-                     installed and you are using the x64 or x86 Native Build Tools prompt so the \
-                     {0} application is available.",
-                    SIGNTOOL
-                ))
+        signer.arg(file);
+        debug!("command = {:?}", redact(&signer));
+        Ok(signer)
+    }
+
+    /// Runs a signer command created with the [`command`] method.
+    ///
+    /// If the signer fails because of the timestamp server, then the signer is
+    /// run again up to the number of retries with an exponential backoff.
+    ///
+    /// [`command`]: #method.command
+    pub(crate) fn sign(&self, signer: Command) -> Result<()> {
+        self.sign_with_backoff(signer, RETRY_DELAY)
+    }
+
+    fn sign_with_backoff(&self, mut signer: Command, delay: Duration) -> Result<()> {
+        // The output is always captured to determine if the timestamp server
+        // failed, but it is displayed after each attempt unless it should be
+        // hidden.
+        signer.stdout(Stdio::piped());
+        signer.stderr(Stdio::piped());
+        let mut attempt = 0;
+        loop {
+            let child = signer.spawn().map_err(|err| {
+                if err.kind() == ErrorKind::NotFound {
+                    Error::Generic(format!(
+                        "The {0} application could not be found. Please check the Windows 10 SDK \
+                         (https://developer.microsoft.com/en-us/windows/downloads/windows-10-sdk) is \
+                         installed and you are using the x64 or x86 Native Build Tools prompt so the \
+                         {0} application is available.",
+                        SIGNTOOL
+                    ))
+                } else {
+                    err.into()
+                }
+            })?;
+            let output = wait_with_output(child, SIGNTOOL, self.timeout)?;
+            if !self.capture_output {
+                io::stdout().write_all(&output.stdout)?;
+                io::stderr().write_all(&output.stderr)?;
+            }
+            if output.status.success() {
+                return Ok(());
+            }
+            if attempt < self.retries && is_timestamp_failure(&output) {
+                let wait = delay.saturating_mul(2u32.saturating_pow(attempt));
+                attempt += 1;
+                warn!(
+                    "The timestamp server failed while signing. Retrying in {:?} ({} of {})",
+                    wait, attempt, self.retries
+                );
+                thread::sleep(wait);
             } else {
-                err.into()
+                return Err(Error::Command(
+                    SIGNTOOL.into(),
+                    output.status.code().unwrap_or(100),
+                    self.capture_output,
+                ));
             }
-        })?;
-        if !status.success() {
-            return Err(Error::Command(
-                SIGNTOOL,
-                status.code().unwrap_or(100),
-                self.capture_output,
-            ));
         }
-        Ok(())
     }
 
     fn homepage(&self, manifest: &Value) -> Option<String> {
@@ -322,12 +435,170 @@ impl Execution {
     }
 }
 
+/// Determines if the signer failed because of the timestamp server from the
+/// signer's output.
+///
+/// The signer reports that the timestamp server "could not be reached or
+/// returned an invalid response" for both the `/t` and `/tr` options, while
+/// certificate problems are reported without mentioning the timestamp server.
+fn is_timestamp_failure(output: &Output) -> bool {
+    [&output.stdout, &output.stderr].iter().any(|o| {
+        String::from_utf8_lossy(o)
+            .to_lowercase()
+            .contains("timestamp server")
+    })
+}
+
 impl Default for Execution {
     fn default() -> Self {
         Builder::new().build()
     }
 }
 
+/// The signing settings from the `[package.metadata.wix.sign]` section of the
+/// package's manifest (Cargo.toml).
+///
+/// The password for a PFX file is never stored in the manifest. Instead, the
+/// `password-env` field is the name of an environment variable that contains
+/// the password, which is read when the signer command is created.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Settings {
+    digest: String,
+    password_env: Option<String>,
+    pfx: Option<PathBuf>,
+    thumbprint: Option<String>,
+    timestamp_url: Option<String>,
+}
+
+impl Settings {
+    /// Gets the signing settings from the package's manifest (Cargo.toml).
+    ///
+    /// `None` is returned if the manifest does not have a
+    /// `[package.metadata.wix.sign]` section. References to environment
+    /// variables in the `pfx` field are expanded, and a relative path is
+    /// resolved against the folder of the package's manifest.
+    pub(crate) fn from_manifest(manifest: &Value, manifest_dir: &Path) -> Result<Option<Self>> {
+        match meta_value(manifest, "sign") {
+            Some(sign) if !sign.is_table() => {
+                return Err(Error::InvalidManifestField("package.metadata.wix.sign"))
+            }
+            Some(_) => {}
+            None => return Ok(None),
+        }
+        let field = |key: &str, name: &'static str| -> Result<Option<String>> {
+            match (meta_value(manifest, key), meta_str(manifest, key)) {
+                (Some(_), None) => Err(Error::InvalidManifestField(name)),
+                (_, value) => Ok(value.map(String::from)),
+            }
+        };
+        let digest = field("sign.digest", "package.metadata.wix.sign.digest")?
+            .map(|d| d.to_lowercase())
+            .unwrap_or_else(|| String::from(DEFAULT_DIGEST));
+        if !DIGESTS.contains(&digest.as_str()) {
+            return Err(Error::InvalidManifestField(
+                "package.metadata.wix.sign.digest",
+            ));
+        }
+        field("sign.pfx", "package.metadata.wix.sign.pfx")?;
+        let settings = Settings {
+            digest,
+            password_env: field(
+                "sign.password-env",
+                "package.metadata.wix.sign.password-env",
+            )?,
+            pfx: meta_path(manifest, "sign.pfx")?.map(|p| manifest_dir.join(p)),
+            thumbprint: field("sign.thumbprint", "package.metadata.wix.sign.thumbprint")?,
+            timestamp_url: field(
+                "sign.timestamp-url",
+                "package.metadata.wix.sign.timestamp-url",
+            )?,
+        };
+        if settings.password_env.is_some() && settings.pfx.is_none() {
+            warn!("The 'password-env' signing setting is ignored without the 'pfx' setting");
+        }
+        Ok(Some(settings))
+    }
+
+    /// Reads the password for the PFX file from the environment variable named
+    /// by the `password-env` field.
+    ///
+    /// `None` is returned if a PFX file or environment variable is not used.
+    pub(crate) fn password(&self) -> Result<Option<String>> {
+        match (&self.pfx, &self.password_env) {
+            (Some(pfx), Some(key)) => env::var(key).map(Some).map_err(|_| {
+                Error::Generic(format!(
+                    "The '{}' environment variable for the password of the '{}' PFX \
+                     file is not set",
+                    key,
+                    pfx.display()
+                ))
+            }),
+            _ => Ok(None),
+        }
+    }
+
+    /// Creates the arguments for the signer to select the certificate, the
+    /// digest algorithm, and the timestamp server.
+    ///
+    /// The certificate is selected by the thumbprint, the PFX file, or both.
+    /// Otherwise, the signer automatically selects the best certificate.
+    pub(crate) fn args(&self, password: Option<&str>) -> Vec<OsString> {
+        let mut args: Vec<OsString> = Vec::new();
+        if let Some(thumbprint) = &self.thumbprint {
+            args.push("/sha1".into());
+            args.push(thumbprint.into());
+        }
+        if let Some(pfx) = &self.pfx {
+            args.push("/f".into());
+            args.push(pfx.into());
+            if let Some(password) = password {
+                args.push(PASSWORD_ARG.into());
+                args.push(password.into());
+            }
+        }
+        if self.thumbprint.is_none() && self.pfx.is_none() {
+            args.push("/a".into());
+        }
+        args.push("/fd".into());
+        args.push((&self.digest).into());
+        if let Some(url) = &self.timestamp_url {
+            args.push("/tr".into());
+            args.push(url.into());
+            args.push("/td".into());
+            args.push((&self.digest).into());
+        }
+        args
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            digest: String::from(DEFAULT_DIGEST),
+            password_env: None,
+            pfx: None,
+            thumbprint: None,
+            timestamp_url: None,
+        }
+    }
+}
+
+/// Creates a copy of a signer command with any password redacted.
+///
+/// The copy is only for logging and displaying the command, as only the program
+/// and arguments are copied.
+pub(crate) fn redact(command: &Command) -> Command {
+    let mut redacted = Command::new(command.get_program());
+    let mut args = command.get_args();
+    while let Some(arg) = args.next() {
+        redacted.arg(arg);
+        if arg == PASSWORD_ARG && args.next().is_some() {
+            redacted.arg(REDACTED);
+        }
+    }
+    redacted
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,6 +653,22 @@ mod tests {
             assert_eq!(actual.product_name, Some(EXPECTED));
         }
 
+        #[test]
+        fn retries_works() {
+            const EXPECTED: u32 = 5;
+            let mut actual = Builder::new();
+            actual.retries(EXPECTED);
+            assert_eq!(actual.retries, EXPECTED);
+        }
+
+        #[test]
+        fn timeout_works() {
+            const EXPECTED: Duration = Duration::from_secs(60);
+            let mut actual = Builder::new();
+            actual.timeout(Some(EXPECTED));
+            assert_eq!(actual.timeout, Some(EXPECTED));
+        }
+
         #[test]
         fn timestamp_works() {
             const EXPECTED: &str = "http://www.example.com";
@@ -477,5 +764,264 @@ mod tests {
             env::remove_var(SIGNTOOL_PATH_KEY);
             assert!(result.is_err());
         }
+
+        const SIGN_MANIFEST: &str = r#"[package]
+            name = "Example"
+
+            [package.metadata.wix.sign]
+            digest = "SHA384"
+            password-env = "CARGO_WIX_TEST_PFX_PASSWORD"
+            pfx = "C:\\Certificates\\Example.pfx"
+            thumbprint = "0123456789ABCDEF"
+            timestamp-url = "http://timestamp.example.com"
+        "#;
+
+        #[test]
+        fn settings_without_sign_section_works() {
+            let manifest = MIN_MANIFEST.parse::<Value>().unwrap();
+            assert_eq!(
+                Settings::from_manifest(&manifest, Path::new("")).unwrap(),
+                None
+            );
+        }
+
+        #[test]
+        fn settings_with_empty_sign_section_works() {
+            let manifest = r#"[package.metadata.wix.sign]"#.parse::<Value>().unwrap();
+            assert_eq!(
+                Settings::from_manifest(&manifest, Path::new("")).unwrap(),
+                Some(Settings::default())
+            );
+        }
+
+        #[test]
+        fn settings_with_all_fields_works() {
+            let manifest = SIGN_MANIFEST.parse::<Value>().unwrap();
+            assert_eq!(
+                Settings::from_manifest(&manifest, Path::new("")).unwrap(),
+                Some(Settings {
+                    digest: String::from("sha384"),
+                    password_env: Some(String::from("CARGO_WIX_TEST_PFX_PASSWORD")),
+                    pfx: Some(PathBuf::from("C:\\Certificates\\Example.pfx")),
+                    thumbprint: Some(String::from("0123456789ABCDEF")),
+                    timestamp_url: Some(String::from("http://timestamp.example.com")),
+                })
+            );
+        }
+
+        #[test]
+        fn settings_with_unsupported_digest_fails() {
+            let manifest = r#"[package.metadata.wix.sign]
+                digest = "md5"
+            "#
+            .parse::<Value>()
+            .unwrap();
+            match Settings::from_manifest(&manifest, Path::new("")) {
+                Err(Error::InvalidManifestField(field)) => {
+                    assert_eq!(field, "package.metadata.wix.sign.digest")
+                }
+                e => panic!("Unexpected result: {:?}", e),
+            }
+        }
+
+        #[test]
+        fn settings_with_non_string_field_fails() {
+            let manifest = r#"[package.metadata.wix.sign]
+                thumbprint = 1234
+            "#
+            .parse::<Value>()
+            .unwrap();
+            match Settings::from_manifest(&manifest, Path::new("")) {
+                Err(Error::InvalidManifestField(field)) => {
+                    assert_eq!(field, "package.metadata.wix.sign.thumbprint")
+                }
+                e => panic!("Unexpected result: {:?}", e),
+            }
+        }
+
+        #[test]
+        fn settings_with_relative_pfx_works() {
+            let manifest = r#"[package.metadata.wix.sign]
+                pfx = "$CARGO_WIX_TEST_PFX_FOLDER/Example.pfx"
+            "#
+            .parse::<Value>()
+            .unwrap();
+            env::set_var("CARGO_WIX_TEST_PFX_FOLDER", "certificates");
+            let settings = Settings::from_manifest(&manifest, Path::new("package"));
+            env::remove_var("CARGO_WIX_TEST_PFX_FOLDER");
+            assert_eq!(
+                settings.unwrap().unwrap().pfx,
+                Some(Path::new("package").join("certificates/Example.pfx"))
+            );
+        }
+
+        #[test]
+        fn settings_args_with_defaults_works() {
+            assert_eq!(Settings::default().args(None), vec!["/a", "/fd", "sha256"]);
+        }
+
+        #[test]
+        fn settings_args_with_thumbprint_works() {
+            let settings = Settings {
+                thumbprint: Some(String::from("0123456789ABCDEF")),
+                ..Default::default()
+            };
+            assert_eq!(
+                settings.args(None),
+                vec!["/sha1", "0123456789ABCDEF", "/fd", "sha256"]
+            );
+        }
+
+        #[test]
+        fn settings_args_with_all_fields_redacts_password() {
+            let manifest = SIGN_MANIFEST.parse::<Value>().unwrap();
+            let settings = Settings::from_manifest(&manifest, Path::new(""))
+                .unwrap()
+                .unwrap();
+            let mut command = Command::new(SIGNTOOL);
+            command.args(settings.args(Some("secret")));
+            let redacted = redact(&command);
+            assert_eq!(
+                redacted.get_args().collect::<Vec<&OsStr>>(),
+                vec![
+                    "/sha1",
+                    "0123456789ABCDEF",
+                    "/f",
+                    "C:\\Certificates\\Example.pfx",
+                    "/p",
+                    REDACTED,
+                    "/fd",
+                    "sha384",
+                    "/tr",
+                    "http://timestamp.example.com",
+                    "/td",
+                    "sha384"
+                ]
+            );
+            assert!(command.get_args().any(|a| a == "secret"));
+        }
+
+        #[test]
+        fn settings_password_without_environment_variable_fails() {
+            let settings = Settings {
+                password_env: Some(String::from("CARGO_WIX_TEST_MISSING_PASSWORD")),
+                pfx: Some(PathBuf::from("Example.pfx")),
+                ..Default::default()
+            };
+            assert!(settings.password().is_err());
+        }
+
+        #[test]
+        fn settings_password_without_pfx_works() {
+            let settings = Settings {
+                password_env: Some(String::from("CARGO_WIX_TEST_MISSING_PASSWORD")),
+                ..Default::default()
+            };
+            assert_eq!(settings.password().unwrap(), None);
+        }
+
+        #[test]
+        fn command_with_settings_works() {
+            env::set_var("CARGO_WIX_TEST_PFX_PASSWORD", "secret");
+            let manifest = SIGN_MANIFEST.parse::<Value>().unwrap();
+            let command = Execution::default()
+                .command(&manifest, Path::new(""), Path::new("Example.exe"))
+                .unwrap();
+            env::remove_var("CARGO_WIX_TEST_PFX_PASSWORD");
+            let args = redact(&command)
+                .get_args()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect::<Vec<String>>();
+            assert_eq!(&args[..2], ["sign", "/sha1"]);
+            assert!(args.contains(&String::from(REDACTED)));
+            assert!(!args.contains(&String::from("secret")));
+            assert_eq!(args.last().map(String::as_str), Some("Example.exe"));
+        }
+
+        #[test]
+        fn command_with_timestamp_overrides_timestamp_url() {
+            let manifest = r#"[package]
+                name = "Example"
+
+                [package.metadata.wix.sign]
+                timestamp-url = "http://timestamp.example.com"
+            "#
+            .parse::<Value>()
+            .unwrap();
+            let command = Builder::new()
+                .timestamp(Some("verisign"))
+                .build()
+                .command(&manifest, Path::new(""), Path::new("Example.exe"))
+                .unwrap();
+            assert!(!command.get_args().any(|a| a == "/tr"));
+            assert!(command.get_args().any(|a| a == "/t"));
+        }
+
+        const TIMESTAMP_ERROR: &str = "SignTool Error: The specified timestamp server either \
+                                       could not be reached or returned an invalid response";
+        const CERTIFICATE_ERROR: &str =
+            "SignTool Error: No certificates were found that met all the given criteria";
+
+        /// Creates a fake signer that fails twice with the message before
+        /// succeeding, using files in the folder to track the attempts.
+        fn fake_signer(folder: &Path, message: &str) -> Command {
+            let mut signer = if cfg!(windows) {
+                let mut signer = Command::new("cmd");
+                signer.arg("/C").arg(format!(
+                    "if exist second (exit 0) else if exist first (type nul > second & \
+                     echo {0} 1>&2 & exit 1) else (type nul > first & echo {0} 1>&2 & exit 1)",
+                    message
+                ));
+                signer
+            } else {
+                let mut signer = Command::new("sh");
+                signer
+                    .arg("-c")
+                    .arg(
+                        "if [ -e second ]; then exit 0; \
+                         elif [ -e first ]; then touch second; echo \"$0\" >&2; exit 1; \
+                         else touch first; echo \"$0\" >&2; exit 1; fi",
+                    )
+                    .arg(message);
+                signer
+            };
+            signer.current_dir(folder);
+            signer
+        }
+
+        #[test]
+        fn sign_with_timestamp_failures_retries() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let result = Execution::default().sign_with_backoff(
+                fake_signer(temp_dir.path(), TIMESTAMP_ERROR),
+                Duration::from_millis(1),
+            );
+            assert!(result.is_ok());
+            assert!(temp_dir.path().join("second").exists());
+        }
+
+        #[test]
+        fn sign_with_too_many_timestamp_failures_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let result = Builder::new().retries(1).build().sign_with_backoff(
+                fake_signer(temp_dir.path(), TIMESTAMP_ERROR),
+                Duration::from_millis(1),
+            );
+            match result {
+                Err(Error::Command(ref name, 1, true)) if name == SIGNTOOL => {}
+                r => panic!("Unexpected result: {:?}", r),
+            }
+        }
+
+        #[test]
+        fn sign_with_certificate_failure_does_not_retry() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let result = Execution::default().sign_with_backoff(
+                fake_signer(temp_dir.path(), CERTIFICATE_ERROR),
+                Duration::from_millis(1),
+            );
+            assert!(result.is_err());
+            assert!(!temp_dir.path().join("second").exists());
+        }
     }
 }
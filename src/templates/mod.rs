@@ -19,15 +19,33 @@ use std::str::FromStr;
 /// The WiX Source (wxs) template.
 static WIX_SOURCE_TEMPLATE: &str = include_str!("main.wxs.mustache");
 
+/// The WiX Source (wxs) template for a bundle.
+static WIX_BUNDLE_TEMPLATE: &str = include_str!("bundle.wxs.mustache");
+
+/// The WiX Include (wxi) template of pre-processor variables.
+static WIX_INCLUDE_TEMPLATE: &str = include_str!("variables.wxi.mustache");
+
 /// The Apache-2.0 Rich Text Format (RTF) license template.
 static APACHE2_LICENSE_TEMPLATE: &str = include_str!("Apache-2.0.rtf.mustache");
 
+/// The BSD-2-Clause Rich Text Format (RTF) license template.
+static BSD2_LICENSE_TEMPLATE: &str = include_str!("BSD-2-Clause.rtf.mustache");
+
+/// The BSD-3-Clause Rich Text Format (RTF) license template.
+static BSD3_LICENSE_TEMPLATE: &str = include_str!("BSD-3-Clause.rtf.mustache");
+
 /// The GPL-3.0 Rich Text Format (RTF) license template.
 static GPL3_LICENSE_TEMPLATE: &str = include_str!("GPL-3.0.rtf.mustache");
 
+/// The ISC Rich Text Format (RTF) license template.
+static ISC_LICENSE_TEMPLATE: &str = include_str!("ISC.rtf.mustache");
+
 /// The MIT Rich Text Format (RTF) license template.
 static MIT_LICENSE_TEMPLATE: &str = include_str!("MIT.rtf.mustache");
 
+/// The MPL-2.0 Rich Text Format (RTF) license template.
+static MPL2_LICENSE_TEMPLATE: &str = include_str!("MPL-2.0.rtf.mustache");
+
 /// The different templates that can be printed or written to a file.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Template {
@@ -35,14 +53,46 @@ pub enum Template {
     ///
     /// [Apache-2.0]: https://opensource.org/licenses/Apache-2.0
     Apache2,
+    /// The [BSD-2-Clause] license.
+    ///
+    /// [BSD-2-Clause]: https://opensource.org/licenses/BSD-2-Clause
+    Bsd2,
+    /// The [BSD-3-Clause] license.
+    ///
+    /// [BSD-3-Clause]: https://opensource.org/licenses/BSD-3-Clause
+    Bsd3,
+    /// A [WiX Source (wxs)] file for a [bundle].
+    ///
+    /// A bundle chains, or combines, prerequisite packages with the product's
+    /// installer (msi) into a single executable (exe) installer.
+    ///
+    /// [Wix Source (wxs)]: http://wixtoolset.org/documentation/manual/v3/overview/files.html
+    /// [bundle]: http://wixtoolset.org/documentation/manual/v3/bundle/
+    Bundle,
     /// The [GPL-3.0] license.
     ///
     /// [GPL-3.0]: https://opensource.org/licenses/gpl-3.0.html
     Gpl3,
+    /// The [ISC] license.
+    ///
+    /// [ISC]: https://opensource.org/licenses/ISC
+    Isc,
     /// The [MIT] license.
     ///
     /// [MIT]: https://opensource.org/licenses/MIT
     Mit,
+    /// The [MPL-2.0] license.
+    ///
+    /// [MPL-2.0]: https://opensource.org/licenses/MPL-2.0
+    Mpl2,
+    /// A [WiX Include (wxi)] file of pre-processor variables.
+    ///
+    /// The variables, such as the product name and version, are determined
+    /// from the package's manifest (Cargo.toml) for use in a hand-authored
+    /// WiX Source (wxs) file.
+    ///
+    /// [Wix Include (wxi)]: http://wixtoolset.org/documentation/manual/v3/overview/preprocessor.html
+    Wxi,
     /// A [WiX Source (wxs)] file.
     ///
     /// [Wix Source (wxs)]: http://wixtoolset.org/documentation/manual/v3/overview/files.html
@@ -62,8 +112,14 @@ impl Template {
     /// use wix::Template;
     ///
     /// assert_eq!(Template::Apache2.id(), "Apache-2.0");
+    /// assert_eq!(Template::Bsd2.id(), "BSD-2-Clause");
+    /// assert_eq!(Template::Bsd3.id(), "BSD-3-Clause");
+    /// assert_eq!(Template::Bundle.id(), "Bundle");
     /// assert_eq!(Template::Gpl3.id(), "GPL-3.0");
+    /// assert_eq!(Template::Isc.id(), "ISC");
     /// assert_eq!(Template::Mit.id(), "MIT");
+    /// assert_eq!(Template::Mpl2.id(), "MPL-2.0");
+    /// assert_eq!(Template::Wxi.id(), "WXI");
     /// assert_eq!(Template::Wxs.id(), "WXS");
     /// ```
     ///
@@ -71,8 +127,14 @@ impl Template {
     pub fn id(&self) -> &str {
         match *self {
             Template::Apache2 => "Apache-2.0",
+            Template::Bsd2 => "BSD-2-Clause",
+            Template::Bsd3 => "BSD-3-Clause",
+            Template::Bundle => "Bundle",
             Template::Gpl3 => "GPL-3.0",
+            Template::Isc => "ISC",
             Template::Mit => "MIT",
+            Template::Mpl2 => "MPL-2.0",
+            Template::Wxi => "WXI",
             Template::Wxs => "WXS",
         }
     }
@@ -92,10 +154,22 @@ impl Template {
     ///     vec![
     ///         "Apache-2.0",
     ///         "apache-2.0",
+    ///         "BSD-2-Clause",
+    ///         "bsd-2-clause",
+    ///         "BSD-3-Clause",
+    ///         "bsd-3-clause",
+    ///         "Bundle",
+    ///         "bundle",
     ///         "GPL-3.0",
     ///         "gpl-3.0",
+    ///         "ISC",
+    ///         "isc",
     ///         "MIT",
     ///         "mit",
+    ///         "MPL-2.0",
+    ///         "mpl-2.0",
+    ///         "WXI",
+    ///         "wxi",
     ///         "WXS",
     ///         "wxs"
     ///     ]
@@ -105,10 +179,22 @@ impl Template {
         vec![
             Template::Apache2.id().to_owned(),
             Template::Apache2.id().to_lowercase(),
+            Template::Bsd2.id().to_owned(),
+            Template::Bsd2.id().to_lowercase(),
+            Template::Bsd3.id().to_owned(),
+            Template::Bsd3.id().to_lowercase(),
+            Template::Bundle.id().to_owned(),
+            Template::Bundle.id().to_lowercase(),
             Template::Gpl3.id().to_owned(),
             Template::Gpl3.id().to_lowercase(),
+            Template::Isc.id().to_owned(),
+            Template::Isc.id().to_lowercase(),
             Template::Mit.id().to_owned(),
             Template::Mit.id().to_lowercase(),
+            Template::Mpl2.id().to_owned(),
+            Template::Mpl2.id().to_lowercase(),
+            Template::Wxi.id().to_owned(),
+            Template::Wxi.id().to_lowercase(),
             Template::Wxs.id().to_owned(),
             Template::Wxs.id().to_lowercase(),
         ]
@@ -125,30 +211,123 @@ impl Template {
     ///     Template::license_ids(),
     ///     vec![
     ///         "Apache-2.0",
+    ///         "BSD-2-Clause",
+    ///         "BSD-3-Clause",
     ///         "GPL-3.0",
+    ///         "ISC",
     ///         "MIT",
+    ///         "MPL-2.0",
     ///     ]
     /// );
     /// ```
     pub fn license_ids() -> Vec<String> {
         vec![
             Template::Apache2.id().to_owned(),
+            Template::Bsd2.id().to_owned(),
+            Template::Bsd3.id().to_owned(),
             Template::Gpl3.id().to_owned(),
+            Template::Isc.id().to_owned(),
             Template::Mit.id().to_owned(),
+            Template::Mpl2.id().to_owned(),
         ]
     }
 
+    /// Gets the license template for a [SPDX license expression].
+    ///
+    /// The `license` field in a package's manifest (Cargo.toml) can be a
+    /// compound expression, such as `MIT OR Apache-2.0`. The first license in
+    /// the expression with an embedded template is used, regardless of the
+    /// `OR` and `AND` operators. Exceptions added with the `WITH` operator are
+    /// ignored, and the "or later" `+` operator is removed before matching.
+    /// Variant and deprecated license IDs are normalized to the ID of the
+    /// embedded template, such as `GPL-3.0-only` and `GPL-3.0-or-later` to
+    /// `GPL-3.0`. License IDs are case sensitive.
+    ///
+    /// `None` is returned if no license in the expression has an embedded
+    /// template.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::Template;
+    ///
+    /// assert_eq!(Template::from_license_expression("MIT"), Some(Template::Mit));
+    /// assert_eq!(
+    ///     Template::from_license_expression("MIT OR Apache-2.0"),
+    ///     Some(Template::Mit)
+    /// );
+    /// assert_eq!(
+    ///     Template::from_license_expression("(LGPL-2.1 OR Apache-2.0) AND MIT"),
+    ///     Some(Template::Apache2)
+    /// );
+    /// assert_eq!(
+    ///     Template::from_license_expression("GPL-3.0-only"),
+    ///     Some(Template::Gpl3)
+    /// );
+    /// assert_eq!(Template::from_license_expression("Unlicense"), None);
+    /// ```
+    ///
+    /// [SPDX license expression]: https://spdx.github.io/spdx-spec/SPDX-license-expressions/
+    pub fn from_license_expression(expression: &str) -> Option<Template> {
+        let license_ids = Template::license_ids();
+        // Some non-canonical spellings contain whitespace, so they must be
+        // matched against the whole expression before it is split into IDs.
+        let whole = normalize_license_id(expression.trim());
+        if license_ids.iter().any(|l| l == whole) {
+            return Template::from_str(whole).ok();
+        }
+        let mut exception = false;
+        expression
+            .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+            .filter(|t| !t.is_empty())
+            .filter(|t| {
+                if exception {
+                    exception = false;
+                    false
+                } else if *t == "WITH" || *t == "with" {
+                    exception = true;
+                    false
+                } else {
+                    !["AND", "and", "OR", "or"].contains(t)
+                }
+            })
+            .map(|t| normalize_license_id(t.trim_end_matches('+')))
+            .find(|id| license_ids.iter().any(|l| l == id))
+            .and_then(|id| Template::from_str(id).ok())
+    }
+
     /// Gets the embedded contents of the template as a string.
     pub fn to_str(&self) -> &str {
         match *self {
             Template::Apache2 => APACHE2_LICENSE_TEMPLATE,
+            Template::Bsd2 => BSD2_LICENSE_TEMPLATE,
+            Template::Bsd3 => BSD3_LICENSE_TEMPLATE,
+            Template::Bundle => WIX_BUNDLE_TEMPLATE,
             Template::Gpl3 => GPL3_LICENSE_TEMPLATE,
+            Template::Isc => ISC_LICENSE_TEMPLATE,
             Template::Mit => MIT_LICENSE_TEMPLATE,
+            Template::Mpl2 => MPL2_LICENSE_TEMPLATE,
+            Template::Wxi => WIX_INCLUDE_TEMPLATE,
             Template::Wxs => WIX_SOURCE_TEMPLATE,
         }
     }
 }
 
+/// Maps variant and deprecated [SPDX license IDs] onto the ID of an embedded
+/// license template.
+///
+/// IDs without a known alias are returned unchanged.
+///
+/// [SPDX license IDs]: https://spdx.org/licenses/
+fn normalize_license_id(id: &str) -> &str {
+    match id {
+        "Apache 2.0" | "Apache 2" | "Apache-2" | "Apache2" => "Apache-2.0",
+        "BSD-2-Clause-NetBSD" => "BSD-2-Clause",
+        "GPL-3.0-only" | "GPL-3.0-or-later" => "GPL-3.0",
+        _ => id,
+    }
+}
+
 impl fmt::Display for Template {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.id())
@@ -161,8 +340,14 @@ impl FromStr for Template {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s.to_lowercase().trim() {
             "apache-2.0" => Ok(Template::Apache2),
+            "bsd-2-clause" => Ok(Template::Bsd2),
+            "bsd-3-clause" => Ok(Template::Bsd3),
+            "bundle" => Ok(Template::Bundle),
             "gpl-3.0" => Ok(Template::Gpl3),
+            "isc" => Ok(Template::Isc),
             "mit" => Ok(Template::Mit),
+            "mpl-2.0" => Ok(Template::Mpl2),
+            "wxi" => Ok(Template::Wxi),
             "wxs" => Ok(Template::Wxs),
             _ => Err(Error::Generic(format!(
                 "Cannot convert from '{}' to a Template variant",
@@ -171,3 +356,65 @@ impl FromStr for Template {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_license_id_with_gpl3_only_works() {
+        assert_eq!(normalize_license_id("GPL-3.0-only"), "GPL-3.0");
+    }
+
+    #[test]
+    fn normalize_license_id_with_gpl3_or_later_works() {
+        assert_eq!(normalize_license_id("GPL-3.0-or-later"), "GPL-3.0");
+    }
+
+    #[test]
+    fn normalize_license_id_with_deprecated_bsd2_works() {
+        assert_eq!(normalize_license_id("BSD-2-Clause-NetBSD"), "BSD-2-Clause");
+    }
+
+    #[test]
+    fn normalize_license_id_with_bsd2_freebsd_is_unchanged() {
+        assert_eq!(
+            normalize_license_id("BSD-2-Clause-FreeBSD"),
+            "BSD-2-Clause-FreeBSD"
+        );
+    }
+
+    #[test]
+    fn normalize_license_id_with_apache2_spellings_works() {
+        assert_eq!(normalize_license_id("Apache 2.0"), "Apache-2.0");
+        assert_eq!(normalize_license_id("Apache 2"), "Apache-2.0");
+        assert_eq!(normalize_license_id("Apache-2"), "Apache-2.0");
+        assert_eq!(normalize_license_id("Apache2"), "Apache-2.0");
+    }
+
+    #[test]
+    fn from_license_expression_with_apache2_spelling_works() {
+        assert_eq!(
+            Template::from_license_expression("Apache 2.0"),
+            Some(Template::Apache2)
+        );
+    }
+
+    #[test]
+    fn from_license_expression_with_bsd2_freebsd_is_none() {
+        assert_eq!(
+            Template::from_license_expression("BSD-2-Clause-FreeBSD"),
+            None
+        );
+    }
+
+    #[test]
+    fn normalize_license_id_is_case_sensitive() {
+        assert_eq!(normalize_license_id("gpl-3.0-only"), "gpl-3.0-only");
+    }
+
+    #[test]
+    fn normalize_license_id_with_unknown_id_works() {
+        assert_eq!(normalize_license_id("MIT"), "MIT");
+    }
+}